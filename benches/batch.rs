@@ -0,0 +1,77 @@
+//! Throughput of the batch hashing of `hash_into` compared to calling `hash`
+//! for every key, hashing 2^20 pseudo random keys into a buffer.
+//!
+//! Run with `cargo bench --bench batch`. Run with `cargo bench --bench batch --features simd`
+//! to measure the AVX2 kernel of `Tab32Simple::hash_into` and the AVX-512 kernel of
+//! `Tab64Twisted::hash_into`, or with `RUSTFLAGS="-C target-cpu=native" cargo +nightly bench
+//! --bench batch --features portable-simd` for the kernel using `core::simd`, which is also
+//! used on aarch64 and wasm32 with `simd128`. With these
+//! features, `hash_into` is also measured with `force_scalar`, i.e. with the scalar batch loop.
+use std::hint::black_box;
+use std::time::Instant;
+use tab_hash::{Tab32Simple, Tab32Twisted, Tab64Simple, Tab64Twisted};
+
+const KEYS: usize = 1 << 20;
+const ROUNDS: usize = 50;
+
+/// Hash the keys into a buffer `ROUNDS` times and print the throughput
+fn bench<K: Copy, H: Copy + Default, F: Fn(&[K], &mut [H])>(name: &str, keys: &[K], hash_keys: F) {
+    let mut out = vec![H::default(); keys.len()];
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        hash_keys(black_box(keys), black_box(&mut out));
+    }
+    let seconds = start.elapsed().as_secs_f64();
+    let total = (ROUNDS * keys.len()) as f64;
+    println!(
+        "{:<37} {:>8.2} ns/key {:>10.1} Mkeys/s",
+        name,
+        seconds * 1e9 / total,
+        total / seconds / 1e6
+    );
+}
+
+/// Compare `hash` in a loop to `hash_into` for one hash function
+macro_rules! compare {
+    ($ty:ident, $keys:expr) => {
+        let hash_function = $ty::new_with_seed(42);
+        bench(concat!(stringify!($ty), "::hash"), $keys, |keys, out| {
+            for (h, key) in out.iter_mut().zip(keys) {
+                *h = hash_function.hash(*key);
+            }
+        });
+        bench(
+            concat!(stringify!($ty), "::hash_into"),
+            $keys,
+            |keys, out| hash_function.hash_into(keys, out),
+        );
+        #[cfg(any(feature = "simd", feature = "portable-simd"))]
+        {
+            tab_hash::force_scalar(true);
+            bench(
+                concat!(stringify!($ty), "::hash_into (scalar)"),
+                $keys,
+                |keys, out| hash_function.hash_into(keys, out),
+            );
+            tab_hash::force_scalar(false);
+        }
+    };
+}
+
+fn main() {
+    #[cfg(any(feature = "simd", feature = "portable-simd"))]
+    println!("batch kernel: {:?}", tab_hash::batch_kernel());
+    let mut key: u64 = 0x9e37_79b9_7f4a_7c15;
+    let keys64: Vec<u64> = (0..KEYS)
+        .map(|_| {
+            key = key.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+            key
+        })
+        .collect();
+    let keys32: Vec<u32> = keys64.iter().map(|k| (*k >> 32) as u32).collect();
+
+    compare!(Tab32Simple, &keys32);
+    compare!(Tab32Twisted, &keys32);
+    compare!(Tab64Simple, &keys64);
+    compare!(Tab64Twisted, &keys64);
+}
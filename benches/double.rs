@@ -0,0 +1,36 @@
+//! Throughput of double tabulation compared to simple and twisted tabulation.
+//!
+//! Run with `cargo bench --bench double`.
+use std::hint::black_box;
+use std::time::Instant;
+use tab_hash::{Tab64Double, Tab64Simple, Tab64Twisted};
+
+const KEYS: u64 = 10_000_000;
+
+/// Hash `KEYS` pseudo random keys and print the throughput
+fn bench<F: Fn(u64) -> u64>(name: &str, hash: F) {
+    let start = Instant::now();
+    let mut acc = 0;
+    let mut key: u64 = 0x9e37_79b9_7f4a_7c15;
+    for _ in 0..KEYS {
+        key = key.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+        acc ^= hash(black_box(key));
+    }
+    black_box(acc);
+    let seconds = start.elapsed().as_secs_f64();
+    println!(
+        "{:<14} {:>8.2} ns/key {:>10.1} Mkeys/s",
+        name,
+        seconds * 1e9 / KEYS as f64,
+        KEYS as f64 / seconds / 1e6
+    );
+}
+
+fn main() {
+    let simple = Tab64Simple::new_with_seed(42);
+    let twisted = Tab64Twisted::new_with_seed(42);
+    let double = Tab64Double::new_with_seed(42);
+    bench("Tab64Simple", |x| simple.hash(x));
+    bench("Tab64Twisted", |x| twisted.hash(x));
+    bench("Tab64Double", |x| double.hash(x));
+}
@@ -0,0 +1,67 @@
+//! Scaling of `par_hash_into_with_threads` with the number of threads compared to the
+//! serial `hash_into`, hashing 2^23 pseudo random keys into a buffer.
+//!
+//! Thread counts above the number of available cores cannot speed up hashing.
+//!
+//! Run with `cargo bench --bench parallel --features parallel`.
+use std::hint::black_box;
+use std::time::Instant;
+use tab_hash::{Tab32Simple, Tab64Twisted};
+
+const KEYS: usize = 1 << 23;
+const ROUNDS: usize = 10;
+const THREADS: [usize; 4] = [1, 2, 4, 8];
+
+/// Hash the keys into a buffer `ROUNDS` times and print the throughput
+fn bench<K: Copy, F: Fn(&[K], &mut [K])>(name: &str, keys: &[K], hash_keys: F) {
+    let mut out = keys.to_vec();
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        hash_keys(black_box(keys), black_box(&mut out));
+    }
+    let seconds = start.elapsed().as_secs_f64();
+    let total = (ROUNDS * keys.len()) as f64;
+    println!(
+        "{:<40} {:>8.2} ns/key {:>10.1} Mkeys/s",
+        name,
+        seconds * 1e9 / total,
+        total / seconds / 1e6
+    );
+}
+
+/// Compare `hash_into` to `par_hash_into_with_threads` for every thread count
+macro_rules! scaling {
+    ($ty:ident, $keys:expr) => {
+        let hash_function = $ty::new_with_seed(42);
+        bench(
+            concat!(stringify!($ty), "::hash_into"),
+            $keys,
+            |keys, out| hash_function.hash_into(keys, out),
+        );
+        for threads in THREADS.iter() {
+            bench(
+                &format!("{}::par_hash_into ({} threads)", stringify!($ty), threads),
+                $keys,
+                |keys, out| hash_function.par_hash_into_with_threads(keys, out, *threads),
+            );
+        }
+    };
+}
+
+fn main() {
+    println!(
+        "available parallelism: {}",
+        std::thread::available_parallelism().map_or(1, |n| n.get())
+    );
+    let mut key: u64 = 0x9e37_79b9_7f4a_7c15;
+    let keys64: Vec<u64> = (0..KEYS)
+        .map(|_| {
+            key = key.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+            key
+        })
+        .collect();
+    let keys32: Vec<u32> = keys64.iter().map(|k| (*k >> 32) as u32).collect();
+
+    scaling!(Tab32Simple, &keys32);
+    scaling!(Tab64Twisted, &keys64);
+}
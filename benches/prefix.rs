@@ -0,0 +1,55 @@
+//! Throughput of prefix caching compared to plain simple tabulation,
+//! hashing a sequential range of 2^20 keys into a buffer.
+//!
+//! Run with `cargo bench --bench prefix`.
+use std::hint::black_box;
+use std::time::Instant;
+use tab_hash::Tab32Simple;
+
+const START: u32 = 0x2a00_0000;
+const KEYS: usize = 1 << 20;
+const ROUNDS: usize = 50;
+
+/// Fill a buffer with the hash values of `START..START + KEYS` `ROUNDS` times
+/// and print the throughput
+fn bench<F: Fn(&mut [u32])>(name: &str, hash_range: F) {
+    let mut out = vec![0_u32; KEYS];
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        hash_range(black_box(&mut out));
+    }
+    let seconds = start.elapsed().as_secs_f64();
+    let total = (ROUNDS * KEYS) as f64;
+    println!(
+        "{:<22} {:>8.2} ns/key {:>10.1} Mkeys/s",
+        name,
+        seconds * 1e9 / total,
+        total / seconds / 1e6
+    );
+}
+
+/// Fill a buffer using one prefix of `prefix_bytes` bytes per run of keys
+fn bench_prefix(simple: &Tab32Simple, prefix_bytes: u8) {
+    let run = 1 << (32 - 8 * u32::from(prefix_bytes));
+    bench(&format!("with_prefix(_, {})", prefix_bytes), |out| {
+        for (i, chunk) in out.chunks_mut(run).enumerate() {
+            let hasher = simple.with_prefix(START + (i * run) as u32, prefix_bytes);
+            for (h, low) in chunk.iter_mut().zip(hasher.hash_suffixes(0..run as u32)) {
+                *h = low;
+            }
+        }
+    });
+}
+
+fn main() {
+    let simple = Tab32Simple::new_with_seed(42);
+    bench("Tab32Simple::hash", |out| {
+        for (h, key) in out.iter_mut().zip(START..) {
+            *h = simple.hash(key);
+        }
+    });
+    // two lookups per key
+    bench_prefix(&simple, 2);
+    // one lookup per key
+    bench_prefix(&simple, 3);
+}
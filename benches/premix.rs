@@ -0,0 +1,32 @@
+//! Throughput of premixed simple tabulation compared to plain simple tabulation.
+//!
+//! Run with `cargo bench --bench premix`.
+use std::hint::black_box;
+use std::time::Instant;
+use tab_hash::Tab64Premixed;
+
+const KEYS: u64 = 10_000_000;
+
+/// Hash the sequential keys `0..KEYS` and print the throughput
+fn bench<F: Fn(u64) -> u64>(name: &str, hash: F) {
+    let start = Instant::now();
+    let mut acc = 0;
+    for key in 0..KEYS {
+        acc ^= hash(black_box(key));
+    }
+    black_box(acc);
+    let seconds = start.elapsed().as_secs_f64();
+    println!(
+        "{:<14} {:>8.2} ns/key {:>10.1} Mkeys/s",
+        name,
+        seconds * 1e9 / KEYS as f64,
+        KEYS as f64 / seconds / 1e6
+    );
+}
+
+fn main() {
+    let premixed = Tab64Premixed::new_with_seed(42);
+    let simple = premixed.hash_function().clone();
+    bench("Tab64Simple", |x| simple.hash(x));
+    bench("Tab64Premixed", |x| premixed.hash(x));
+}
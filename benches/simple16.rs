@@ -0,0 +1,76 @@
+//! Throughput of simple tabulation with 16-bit characters compared to 8-bit characters,
+//! hashing a batch of keys in a hot loop.
+//!
+//! The 64-bit variant is measured on random keys and on sorted, clustered keys,
+//! since its 2 MiB table only stays in the cache for the latter.
+//! The wide tables are derived from the 8-bit tables with `derive_wide`,
+//! so both functions compute the same hash values.
+//!
+//! Run with `cargo bench --bench simple16`.
+use std::hint::black_box;
+use std::time::Instant;
+use tab_hash::{Tab32Simple, Tab64Simple};
+
+const BATCH: usize = 1 << 16;
+const ROUNDS: usize = 200;
+
+/// Hash a batch of keys `ROUNDS` times into an output buffer and print the throughput
+fn bench<K: Copy, V: Default + Clone, F: Fn(K) -> V>(name: &str, keys: &[K], hash: F) {
+    let mut out = vec![V::default(); keys.len()];
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        for (h, key) in out.iter_mut().zip(black_box(keys).iter()) {
+            *h = hash(*key);
+        }
+        black_box(&mut out);
+    }
+    let seconds = start.elapsed().as_secs_f64();
+    let total = (ROUNDS * keys.len()) as f64;
+    println!(
+        "{:<24} {:>8.2} ns/key {:>10.1} Mkeys/s",
+        name,
+        seconds * 1e9 / total,
+        total / seconds / 1e6
+    );
+}
+
+fn main() {
+    let mut key: u64 = 0x9e37_79b9_7f4a_7c15;
+    let keys: Vec<u64> = (0..BATCH)
+        .map(|_| {
+            key = key
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            key
+        })
+        .collect();
+    let keys32: Vec<u32> = keys.iter().map(|k| (k >> 32) as u32).collect();
+    // consecutive ids with gaps, like the rows of a sorted table
+    let sorted: Vec<u64> = keys
+        .iter()
+        .enumerate()
+        .map(|(i, k)| 0x0123_4567_0000_0000 + 16 * i as u64 + (k >> 60))
+        .collect();
+
+    let simple = Tab32Simple::new_with_seed(42);
+    let start = Instant::now();
+    let simple16 = simple.derive_wide();
+    println!(
+        "Tab32Simple::derive_wide   {:>8.2} ms",
+        start.elapsed().as_secs_f64() * 1e3
+    );
+    bench("Tab32Simple", &keys32, |x| simple.hash(x));
+    bench("Tab32Simple16", &keys32, |x| simple16.hash(x));
+
+    let simple = Tab64Simple::new_with_seed(42);
+    let start = Instant::now();
+    let simple16 = simple.derive_wide();
+    println!(
+        "Tab64Simple::derive_wide   {:>8.2} ms",
+        start.elapsed().as_secs_f64() * 1e3
+    );
+    bench("Tab64Simple (random)", &keys, |x| simple.hash(x));
+    bench("Tab64Simple16 (random)", &keys, |x| simple16.hash(x));
+    bench("Tab64Simple (sorted)", &sorted, |x| simple.hash(x));
+    bench("Tab64Simple16 (sorted)", &sorted, |x| simple16.hash(x));
+}
@@ -0,0 +1,51 @@
+//! Throughput of tabulation-1-permutation compared to twisted tabulation and
+//! tabulation-permutation.
+//!
+//! Run with `cargo bench --bench tabperm`.
+use std::hint::black_box;
+use std::time::Instant;
+use tab_hash::{
+    Tab32Simple, Tab32Tab1Perm, Tab32TabPerm, Tab32Twisted, Tab64Simple, Tab64Tab1Perm,
+    Tab64TabPerm, Tab64Twisted,
+};
+
+const KEYS: u64 = 10_000_000;
+
+/// Hash `KEYS` pseudo random keys and print the throughput
+fn bench<F: Fn(u64) -> u64>(name: &str, hash: F) {
+    let start = Instant::now();
+    let mut acc = 0;
+    let mut key: u64 = 0x9e37_79b9_7f4a_7c15;
+    for _ in 0..KEYS {
+        key = key.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+        acc ^= hash(black_box(key));
+    }
+    black_box(acc);
+    let seconds = start.elapsed().as_secs_f64();
+    println!(
+        "{:<14} {:>8.2} ns/key {:>10.1} Mkeys/s",
+        name,
+        seconds * 1e9 / KEYS as f64,
+        KEYS as f64 / seconds / 1e6
+    );
+}
+
+fn main() {
+    let simple = Tab32Simple::new_with_seed(42);
+    let twisted = Tab32Twisted::new_with_seed(42);
+    let tab1perm = Tab32Tab1Perm::new_with_seed(42);
+    let tabperm = Tab32TabPerm::new_with_seed(42);
+    bench("Tab32Simple", |x| u64::from(simple.hash(x as u32)));
+    bench("Tab32Twisted", |x| u64::from(twisted.hash(x as u32)));
+    bench("Tab32Tab1Perm", |x| u64::from(tab1perm.hash(x as u32)));
+    bench("Tab32TabPerm", |x| u64::from(tabperm.hash(x as u32)));
+
+    let simple = Tab64Simple::new_with_seed(42);
+    let twisted = Tab64Twisted::new_with_seed(42);
+    let tab1perm = Tab64Tab1Perm::new_with_seed(42);
+    let tabperm = Tab64TabPerm::new_with_seed(42);
+    bench("Tab64Simple", |x| simple.hash(x));
+    bench("Tab64Twisted", |x| twisted.hash(x));
+    bench("Tab64Tab1Perm", |x| tab1perm.hash(x));
+    bench("Tab64TabPerm", |x| tabperm.hash(x));
+}
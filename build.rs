@@ -1,7 +1,11 @@
-use cc;
+use std::env;
 
 fn main() {
-    cc::Build::new()
-        .file("tests/reference_implementation.c")
-        .compile("tab-hash-reference");
+    // The reference implementation is only needed for tests,
+    // which are not run when cross compiling.
+    if env::var("TARGET") == env::var("HOST") {
+        cc::Build::new()
+            .file("tests/reference_implementation.c")
+            .compile("tab-hash-reference");
+    }
 }
@@ -0,0 +1,101 @@
+//! Checks that `tab-hash` can be used in `no_std` environments.
+//!
+//! This crate depends on `tab-hash` without default features.
+//! Build it for a bare metal target to make sure no part of the
+//! standard library is required, e.g.
+//! `cargo build -p no-std-check --target thumbv7em-none-eabihf`.
+#![cfg_attr(not(test), no_std)]
+use tab_hash::{EntropySource, Tab32Simple, Tab64Twisted};
+
+/// Generate a table at compile time using a linear congruential generator.
+const fn static_table() -> [[u32; 256]; 4] {
+    let mut table = [[0; 256]; 4];
+    let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+    let mut i = 0;
+    while i < 4 {
+        let mut j = 0;
+        while j < 256 {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            table[i][j] = (state >> 32) as u32;
+            j += 1;
+        }
+        i += 1;
+    }
+    table
+}
+
+/// A table shipped with the firmware.
+pub const TABLE: [[u32; 256]; 4] = static_table();
+
+/// A hash function shipped with the firmware.
+pub static SIMPLE: Tab32Simple = Tab32Simple::with_table(TABLE);
+
+/// Hash a key with the statically provided table.
+pub fn hash_with_static_table(key: u32) -> u32 {
+    SIMPLE.hash(key)
+}
+
+/// Error of the (never failing) toy random number generator.
+#[derive(Debug)]
+pub struct TrngError;
+
+/// A stand-in for a hardware random number generator.
+pub struct ToyTrng {
+    state: u32,
+}
+
+impl ToyTrng {
+    pub fn new(state: u32) -> Self {
+        ToyTrng { state }
+    }
+}
+
+impl EntropySource for ToyTrng {
+    type Error = TrngError;
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Self::Error> {
+        for byte in dest.iter_mut() {
+            // xorshift32
+            self.state ^= self.state << 13;
+            self.state ^= self.state >> 17;
+            self.state ^= self.state << 5;
+            *byte = self.state as u8;
+        }
+        Ok(())
+    }
+}
+
+/// Hash a key with a random table seeded from the toy TRNG.
+pub fn hash_with_trng_table(trng: &mut ToyTrng, key: u64) -> Result<u64, TrngError> {
+    let twisted = Tab64Twisted::new_from_entropy(trng)?;
+    Ok(twisted.hash(key))
+}
+
+/// Hash a key with a hash function derived from a fixed seed.
+pub fn hash_with_seeded_table(key: u64) -> u64 {
+    Tab64Twisted::from_seed_bytes([7; 32]).hash(key)
+}
+
+#[test]
+fn static_table_hashing() {
+    let key = 0x0403_0201;
+    let expected = TABLE[0][1] ^ TABLE[1][2] ^ TABLE[2][3] ^ TABLE[3][4];
+    assert_eq!(hash_with_static_table(key), expected);
+}
+
+#[test]
+fn trng_hashing() {
+    let h1 = hash_with_trng_table(&mut ToyTrng::new(42), 1337).unwrap();
+    let h2 = hash_with_trng_table(&mut ToyTrng::new(42), 1337).unwrap();
+    let h3 = hash_with_trng_table(&mut ToyTrng::new(43), 1337).unwrap();
+    assert_eq!(h1, h2);
+    assert_ne!(h1, h3);
+}
+
+#[test]
+fn seeded_hashing() {
+    assert_eq!(hash_with_seeded_table(1337), hash_with_seeded_table(1337));
+    assert_ne!(hash_with_seeded_table(1337), hash_with_seeded_table(1338));
+}
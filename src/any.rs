@@ -0,0 +1,154 @@
+//! Hash functions whose family is chosen at runtime.
+use crate::{Hash32Fn, Hash64Fn, Tab32Simple, Tab32Twisted, Tab64Simple, Tab64Twisted};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Family of tabulation hash functions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Kind {
+    /// Simple tabulation hashing
+    Simple,
+    /// Twisted tabulation hashing
+    Twisted,
+}
+
+/// A simple or twisted tabulation hash function for 32-bit keys.
+///
+/// When serialized, the family is recorded alongside the table,
+/// so deserialization restores a hash function of the same family.
+// tables are stored inline to avoid an indirection when hashing
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AnyTab32 {
+    /// Simple tabulation hash function
+    Simple(Tab32Simple),
+    /// Twisted tabulation hash function
+    Twisted(Tab32Twisted),
+}
+
+impl AnyTab32 {
+    /// Create a new hash function of the given family with a random table.
+    #[cfg(feature = "getrandom")]
+    pub fn new(kind: Kind) -> Self {
+        match kind {
+            Kind::Simple => AnyTab32::Simple(Tab32Simple::new()),
+            Kind::Twisted => AnyTab32::Twisted(Tab32Twisted::new()),
+        }
+    }
+
+    /// Create a new hash function of the given family with a table derived from a seed.
+    ///
+    /// This is identical to `new_with_seed` of the respective hash function.
+    pub fn new_with_seed(kind: Kind, seed: u64) -> Self {
+        match kind {
+            Kind::Simple => AnyTab32::Simple(Tab32Simple::new_with_seed(seed)),
+            Kind::Twisted => AnyTab32::Twisted(Tab32Twisted::new_with_seed(seed)),
+        }
+    }
+
+    /// Get the family of this hash function.
+    pub fn kind(&self) -> Kind {
+        match self {
+            AnyTab32::Simple(_) => Kind::Simple,
+            AnyTab32::Twisted(_) => Kind::Twisted,
+        }
+    }
+
+    /// Compute the hash value of a 32bit integer number.
+    pub fn hash(&self, x: u32) -> u32 {
+        match self {
+            AnyTab32::Simple(h) => h.hash(x),
+            AnyTab32::Twisted(h) => h.hash(x),
+        }
+    }
+}
+
+impl From<Tab32Simple> for AnyTab32 {
+    fn from(h: Tab32Simple) -> Self {
+        AnyTab32::Simple(h)
+    }
+}
+
+impl From<Tab32Twisted> for AnyTab32 {
+    fn from(h: Tab32Twisted) -> Self {
+        AnyTab32::Twisted(h)
+    }
+}
+
+impl Hash32Fn for AnyTab32 {
+    fn hash32(&self, x: u32) -> u32 {
+        self.hash(x)
+    }
+}
+
+/// A simple or twisted tabulation hash function for 64-bit keys.
+///
+/// When serialized, the family is recorded alongside the table,
+/// so deserialization restores a hash function of the same family.
+// tables are stored inline to avoid an indirection when hashing
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AnyTab64 {
+    /// Simple tabulation hash function
+    Simple(Tab64Simple),
+    /// Twisted tabulation hash function
+    Twisted(Tab64Twisted),
+}
+
+impl AnyTab64 {
+    /// Create a new hash function of the given family with a random table.
+    #[cfg(feature = "getrandom")]
+    pub fn new(kind: Kind) -> Self {
+        match kind {
+            Kind::Simple => AnyTab64::Simple(Tab64Simple::new()),
+            Kind::Twisted => AnyTab64::Twisted(Tab64Twisted::new()),
+        }
+    }
+
+    /// Create a new hash function of the given family with a table derived from a seed.
+    ///
+    /// This is identical to `new_with_seed` of the respective hash function.
+    pub fn new_with_seed(kind: Kind, seed: u64) -> Self {
+        match kind {
+            Kind::Simple => AnyTab64::Simple(Tab64Simple::new_with_seed(seed)),
+            Kind::Twisted => AnyTab64::Twisted(Tab64Twisted::new_with_seed(seed)),
+        }
+    }
+
+    /// Get the family of this hash function.
+    pub fn kind(&self) -> Kind {
+        match self {
+            AnyTab64::Simple(_) => Kind::Simple,
+            AnyTab64::Twisted(_) => Kind::Twisted,
+        }
+    }
+
+    /// Compute the hash value of a 64bit integer number.
+    pub fn hash(&self, x: u64) -> u64 {
+        match self {
+            AnyTab64::Simple(h) => h.hash(x),
+            AnyTab64::Twisted(h) => h.hash(x),
+        }
+    }
+}
+
+impl From<Tab64Simple> for AnyTab64 {
+    fn from(h: Tab64Simple) -> Self {
+        AnyTab64::Simple(h)
+    }
+}
+
+impl From<Tab64Twisted> for AnyTab64 {
+    fn from(h: Tab64Twisted) -> Self {
+        AnyTab64::Twisted(h)
+    }
+}
+
+impl Hash64Fn for AnyTab64 {
+    fn hash64(&self, x: u64) -> u64 {
+        self.hash(x)
+    }
+}
@@ -0,0 +1,91 @@
+//! Runtime selection of the SIMD kernels of the batch methods.
+use core::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+/// A family of SIMD kernels used by the batch methods `hash_into`, `hash_many` and
+/// `hash_in_place`.
+///
+/// The kernel is detected once per process and cached, see `batch_kernel`. Each hash
+/// function uses the best of its own kernels that is supported:
+///
+/// - `Tab32Simple` uses its AVX2 kernel for `Avx2` and `Avx512`, and its `core::simd`
+///   kernel for `Portable`.
+/// - `Tab64Twisted` uses its AVX-512 kernel for `Avx512`.
+/// - All other cases use the scalar batch loop.
+///
+/// There is no NEON kernel, since NEON has no gather instructions, so aarch64 uses the
+/// `core::simd` kernel with the `portable-simd` feature, and the scalar batch loop without it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BatchKernel {
+    /// The scalar batch loop, hashing four keys at a time.
+    Scalar,
+    /// The kernels of the `portable-simd` feature, for x86-64 targets with gather
+    /// instructions, aarch64 and wasm32 with `simd128`.
+    Portable,
+    /// The AVX2 kernels of the `simd` feature.
+    Avx2,
+    /// The AVX-512 kernels of the `simd` feature, including the AVX2 kernels.
+    Avx512,
+}
+
+impl BatchKernel {
+    /// Detect the best kernel supported by the CPU and the enabled features,
+    /// ignoring `force_scalar`.
+    pub fn detect() -> BatchKernel {
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("avx512f") {
+                return BatchKernel::Avx512;
+            }
+            if is_x86_feature_detected!("avx2") {
+                return BatchKernel::Avx2;
+            }
+        }
+        #[cfg(feature = "portable-simd")]
+        {
+            if crate::portable_simd::TARGET_SUPPORTED {
+                return BatchKernel::Portable;
+            }
+        }
+        BatchKernel::Scalar
+    }
+}
+
+/// Marker of `DETECTED` before the first detection
+const UNDETECTED: u8 = u8::MAX;
+
+/// Cached result of `BatchKernel::detect`, as the index of the variant
+static DETECTED: AtomicU8 = AtomicU8::new(UNDETECTED);
+
+/// Whether `force_scalar` is active
+static FORCE_SCALAR: AtomicBool = AtomicBool::new(false);
+
+/// Get the kernel used by the batch methods.
+///
+/// This is the result of `BatchKernel::detect`, which is cached after the first call,
+/// or `BatchKernel::Scalar` while `force_scalar` is active. The cost of a call is two
+/// atomic loads, which is negligible for batches of more than a few keys.
+pub fn batch_kernel() -> BatchKernel {
+    if FORCE_SCALAR.load(Ordering::Relaxed) {
+        return BatchKernel::Scalar;
+    }
+    match DETECTED.load(Ordering::Relaxed) {
+        UNDETECTED => {
+            let kernel = BatchKernel::detect();
+            DETECTED.store(kernel as u8, Ordering::Relaxed);
+            kernel
+        }
+        0 => BatchKernel::Scalar,
+        1 => BatchKernel::Portable,
+        2 => BatchKernel::Avx2,
+        3 => BatchKernel::Avx512,
+        _ => BatchKernel::Scalar,
+    }
+}
+
+/// Make all batch methods of this process use the scalar batch loop, or undo this.
+///
+/// The hash values do not depend on the kernel, so this is meant for debugging the
+/// kernels and comparing their throughput. It affects all threads.
+pub fn force_scalar(force: bool) {
+    FORCE_SCALAR.store(force, Ordering::Relaxed);
+}
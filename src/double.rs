@@ -0,0 +1,517 @@
+//! Double tabulation hashing.
+//!
+//! Double tabulation composes two simple tabulation hash functions, see Mikkel Thorup,
+//! [Simple Tabulation, Fast Expanders, Double Tabulation, and High Independence](https://doi.org/10.1109/FOCS.2013.18)
+//! (FOCS 2013). A key of `c` characters is first mapped to `d` derived characters by simple
+//! tabulation, which are then hashed by a second, independent simple tabulation function.
+//! Thorup shows that for `d >= 6c`, the first function is an expander with high
+//! probability, and then the composition is highly independent.
+//!
+//! Both types use bytes as characters and `d = 6c` derived bytes. The independence
+//! Thorup's bound guarantees grows with the size of the characters, so for bytes it is
+//! modest, but tables stay small enough for the cache:
+//!
+//! | type | `c` | `d` | first table | second table | total |
+//! |------|-----|-----|-------------|--------------|-------|
+//! | `Tab32Double` | 4 | 24 | 4 x 256 x 24 bytes | 24 x 256 x 4 bytes | 48 KiB |
+//! | `Tab64Double` | 8 | 48 | 8 x 256 x 48 bytes | 48 x 256 x 8 bytes | 192 KiB |
+//!
+//! For comparison, `Tab64Simple` uses 16 KiB and `Tab64Twisted` 32 KiB. A `Tab64Double`
+//! hash value costs 56 lookups instead of 8, and the tables of a few hash functions
+//! exceed typical L1 and L2 caches, so it is about ten times slower than `Tab64Simple`
+//! (see `cargo bench --bench double`).
+//! Use it where the extra independence matters, and keep `Tab64Simple` or
+//! `Tab64Twisted` otherwise.
+#[cfg(feature = "getrandom")]
+use crate::entropy::OsEntropy;
+use crate::splitmix::SplitMix64;
+use crate::xoshiro::Xoshiro256StarStar;
+use crate::{byte_chunks_32, EntropySource, Hash32Fn, TableEntry, TableWords};
+#[cfg(feature = "alloc")]
+use crate::{byte_chunks_64, Hash64Fn};
+#[cfg(feature = "getrandom")]
+use crate::{os_seeded_rng, EntropyError};
+#[cfg(feature = "serde")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, vec};
+#[cfg(feature = "alloc")]
+use core::convert::TryFrom;
+#[cfg(feature = "rand")]
+use rand::distributions::{Distribution, Standard};
+#[cfg(feature = "rand")]
+use rand::Rng;
+use rand_chacha::ChaCha20Rng;
+use rand_core::{RngCore, SeedableRng};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize, Serializer};
+
+/// Number of derived characters (bytes) of the intermediate value of `Tab32Double`
+const DERIVED_32: usize = 24;
+
+/// Number of derived characters (bytes) of the intermediate value of `Tab64Double`
+#[cfg(feature = "alloc")]
+const DERIVED_64: usize = 48;
+
+/// A hash function for 32-bit integers using double tabulation.
+///
+/// The first table maps the 4 bytes of a key to a 192-bit intermediate value,
+/// stored as three 64-bit words (lowest first), by simple tabulation. The 24 bytes of the
+/// intermediate value (little endian, lowest word first) are then looked up in the
+/// 24 columns of the second table, whose XOR is the hash value.
+/// See the module documentation for the choice of parameters.
+///
+/// Tables are drawn from random number generators like for the other hash functions:
+/// first the first table, each entry as three 64-bit words starting with the lowest,
+/// then the second table, each entry from one `next_u32` call.
+/// The id is the digest of the words of the first table followed by the second table.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::Tab32Double;
+///
+/// let keys = vec![0, 8, 15, 47, 11];
+/// let double = Tab32Double::new_with_seed(42);
+/// for k in keys {
+///     println!("{}", double.hash(k));
+/// }
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawTab32Double"))]
+pub struct Tab32Double {
+    first: [[[u64; 3]; 256]; 4],
+    second: [[u32; 256]; DERIVED_32],
+}
+
+impl Tab32Double {
+    /// Create a new double tabulation hash function with random tables.
+    #[cfg(feature = "getrandom")]
+    pub fn new() -> Self {
+        Tab32Double::from_rng(&mut os_seeded_rng())
+    }
+
+    /// Create a new double tabulation hash function seeded from an entropy source.
+    ///
+    /// A 256-bit seed is read from `source` and expanded into the tables
+    /// using a xoshiro256** generator, just like `new` does with the
+    /// entropy source of the operating system.
+    pub fn new_from_entropy<E: EntropySource + ?Sized>(source: &mut E) -> Result<Self, E::Error> {
+        let mut rng = Xoshiro256StarStar::from_entropy(source)?;
+        Ok(Tab32Double::from_rng(&mut rng))
+    }
+
+    /// Create a new double tabulation hash function seeded from OS entropy.
+    ///
+    /// This is `new_from_entropy` with the entropy source of the operating system,
+    /// i.e. like `new`, but returns an error instead of panicking if there is no entropy.
+    #[cfg(feature = "getrandom")]
+    pub fn new_from_os_entropy() -> Result<Self, EntropyError> {
+        Tab32Double::new_from_entropy(&mut OsEntropy)
+    }
+
+    /// Create a new double tabulation hash function with tables derived from a seed.
+    ///
+    /// The seed is expanded with SplitMix64, see the crate documentation.
+    pub fn new_with_seed(seed: u64) -> Self {
+        Tab32Double::from_rng(&mut SplitMix64::new(seed))
+    }
+
+    /// Create a new double tabulation hash function from a 32-byte seed.
+    ///
+    /// The seed is used as ChaCha20 key, see the crate documentation.
+    pub fn from_seed_bytes(seed: [u8; 32]) -> Self {
+        Tab32Double::from_rng(&mut ChaCha20Rng::from_seed(seed))
+    }
+
+    /// Create a new double tabulation hash function with tables drawn from `rng`.
+    #[cfg(feature = "rand")]
+    pub fn new_with_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        Tab32Double::from_rng(rng)
+    }
+
+    /// Draw the first and then the second table from `rng`, column by column
+    fn from_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        let mut double = Tab32Double {
+            first: [[[0; 3]; 256]; 4],
+            second: [[0; 256]; DERIVED_32],
+        };
+        for entry in double.first.iter_mut().flatten() {
+            *entry = TableEntry::from_rng(rng);
+        }
+        for entry in double.second.iter_mut().flatten() {
+            *entry = TableEntry::from_rng(rng);
+        }
+        double
+    }
+
+    /// Create a new double tabulation hash function with the given tables.
+    ///
+    /// This is a `const fn`, like `with_table` of the simple tabulation types.
+    pub const fn with_tables(first: [[[u64; 3]; 256]; 4], second: [[u32; 256]; 24]) -> Self {
+        Tab32Double { first, second }
+    }
+
+    /// Get the tables used by this hash function, the first and the second one.
+    pub const fn get_tables(&self) -> ([[[u64; 3]; 256]; 4], [[u32; 256]; 24]) {
+        (self.first, self.second)
+    }
+
+    /// Get the id of this hash function, a 64-bit digest of its tables.
+    pub fn id(&self) -> u64 {
+        let mut words = TableWords::default();
+        for entry in self.first.iter().flatten() {
+            entry.for_each_word(|w| words.push(w));
+        }
+        for entry in self.second.iter().flatten() {
+            entry.for_each_word(|w| words.push(w));
+        }
+        words.digest()
+    }
+
+    /// Compute the intermediate value of a key with the first table.
+    pub fn derive(&self, x: u32) -> [u64; 3] {
+        let mut derived = [0; 3];
+        for (column, c) in self.first.iter().zip(byte_chunks_32(x).iter()) {
+            for (word, entry) in derived.iter_mut().zip(column[*c as usize].iter()) {
+                *word ^= entry;
+            }
+        }
+        derived
+    }
+
+    /// Compute the double tabulation hash value of a 32bit integer number.
+    pub fn hash(&self, x: u32) -> u32 {
+        let mut h = 0;
+        let mut columns = self.second.iter();
+        for word in self.derive(x).iter() {
+            for (c, column) in word.to_le_bytes().iter().zip(columns.by_ref()) {
+                h ^= column[*c as usize];
+            }
+        }
+        h
+    }
+}
+
+/// Unvalidated serialized form of a `Tab32Double`
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct RawTab32Double {
+    first: Vec<Vec<[u64; 3]>>,
+    second: Vec<Vec<u32>>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RawTab32Double> for Tab32Double {
+    type Error = &'static str;
+
+    fn try_from(raw: RawTab32Double) -> Result<Self, Self::Error> {
+        if raw.first.len() != 4 || raw.first.iter().any(|column| column.len() != 256) {
+            return Err("the first table needs 4 columns of 256 entries");
+        }
+        if raw.second.len() != DERIVED_32 || raw.second.iter().any(|column| column.len() != 256) {
+            return Err("the second table needs 24 columns of 256 entries");
+        }
+        let mut double = Tab32Double {
+            first: [[[0; 3]; 256]; 4],
+            second: [[0; 256]; DERIVED_32],
+        };
+        for (column, values) in double.first.iter_mut().zip(raw.first.iter()) {
+            column.copy_from_slice(values);
+        }
+        for (column, values) in double.second.iter_mut().zip(raw.second.iter()) {
+            column.copy_from_slice(values);
+        }
+        Ok(double)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct _VecTab32Double {
+    first: Vec<Vec<[u64; 3]>>,
+    second: Vec<Vec<u32>>,
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Tab32Double {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        _VecTab32Double {
+            first: self.first.iter().map(|column| column.to_vec()).collect(),
+            second: self.second.iter().map(|column| column.to_vec()).collect(),
+        }
+        .serialize(s)
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Distribution<Tab32Double> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Tab32Double {
+        Tab32Double::from_rng(rng)
+    }
+}
+
+#[cfg(feature = "getrandom")]
+impl Default for Tab32Double {
+    /// Create a new hash function with random tables, just like `new`.
+    fn default() -> Self {
+        Tab32Double::new()
+    }
+}
+
+impl core::fmt::Debug for Tab32Double {
+    /// Show the id of the hash function as fingerprint instead of all entries.
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let fingerprint = self.id();
+        f.debug_struct("Tab32Double")
+            .field("fingerprint", &format_args!("{:#018x}", fingerprint))
+            .finish()
+    }
+}
+
+impl Hash32Fn for Tab32Double {
+    fn hash32(&self, x: u32) -> u32 {
+        self.hash(x)
+    }
+}
+
+/// A hash function for 64-bit integers using double tabulation.
+///
+/// The first table maps the 8 bytes of a key to a 384-bit intermediate value,
+/// stored as six 64-bit words (lowest first), by simple tabulation. The 48 bytes of the
+/// intermediate value (little endian, lowest word first) are then looked up in the
+/// 48 columns of the second table, whose XOR is the hash value.
+/// See the module documentation for the choice of parameters.
+/// Since the tables take up 192 KiB, they are stored on the heap, like for `Tab128Twisted`.
+///
+/// Tables are drawn from random number generators like for the other hash functions:
+/// first the first table, each entry as six 64-bit words starting with the lowest,
+/// then the second table, each entry from one `next_u64` call.
+/// The id is the digest of the words of the first table followed by the second table.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::Tab64Double;
+///
+/// let keys = vec![0, 8, 15, 47, 11];
+/// let double = Tab64Double::new();
+/// for k in keys {
+///     println!("{}", double.hash(k));
+/// }
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawTab64Double"))]
+#[cfg(feature = "alloc")]
+pub struct Tab64Double {
+    first: Box<[[[u64; 6]; 256]; 8]>,
+    second: Box<[[u64; 256]; DERIVED_64]>,
+}
+
+#[cfg(feature = "alloc")]
+impl Tab64Double {
+    /// Create a new double tabulation hash function with random tables.
+    #[cfg(feature = "getrandom")]
+    pub fn new() -> Self {
+        Tab64Double::from_rng(&mut os_seeded_rng())
+    }
+
+    /// Create a new double tabulation hash function seeded from an entropy source.
+    ///
+    /// A 256-bit seed is read from `source` and expanded into the tables
+    /// using a xoshiro256** generator, just like `new` does with the
+    /// entropy source of the operating system.
+    pub fn new_from_entropy<E: EntropySource + ?Sized>(source: &mut E) -> Result<Self, E::Error> {
+        let mut rng = Xoshiro256StarStar::from_entropy(source)?;
+        Ok(Tab64Double::from_rng(&mut rng))
+    }
+
+    /// Create a new double tabulation hash function seeded from OS entropy.
+    ///
+    /// This is `new_from_entropy` with the entropy source of the operating system,
+    /// i.e. like `new`, but returns an error instead of panicking if there is no entropy.
+    #[cfg(feature = "getrandom")]
+    pub fn new_from_os_entropy() -> Result<Self, EntropyError> {
+        Tab64Double::new_from_entropy(&mut OsEntropy)
+    }
+
+    /// Create a new double tabulation hash function with tables derived from a seed.
+    ///
+    /// The seed is expanded with SplitMix64, see the crate documentation.
+    pub fn new_with_seed(seed: u64) -> Self {
+        Tab64Double::from_rng(&mut SplitMix64::new(seed))
+    }
+
+    /// Create a new double tabulation hash function from a 32-byte seed.
+    ///
+    /// The seed is used as ChaCha20 key, see the crate documentation.
+    pub fn from_seed_bytes(seed: [u8; 32]) -> Self {
+        Tab64Double::from_rng(&mut ChaCha20Rng::from_seed(seed))
+    }
+
+    /// Create a new double tabulation hash function with tables drawn from `rng`.
+    #[cfg(feature = "rand")]
+    pub fn new_with_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        Tab64Double::from_rng(rng)
+    }
+
+    /// Draw the first and then the second table from `rng`, column by column
+    fn from_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        let mut double = Tab64Double::zeroed();
+        for entry in double.first.iter_mut().flatten() {
+            *entry = TableEntry::from_rng(rng);
+        }
+        for entry in double.second.iter_mut().flatten() {
+            *entry = TableEntry::from_rng(rng);
+        }
+        double
+    }
+
+    /// Allocate tables filled with zeros directly on the heap
+    fn zeroed() -> Self {
+        let first = vec![[[0; 6]; 256]; 8].into_boxed_slice();
+        let second = vec![[0; 256]; DERIVED_64].into_boxed_slice();
+        match (TryFrom::try_from(first), TryFrom::try_from(second)) {
+            (Ok(first), Ok(second)) => Tab64Double { first, second },
+            _ => unreachable!("tables have exactly 8 and 48 columns"),
+        }
+    }
+
+    /// Create a new double tabulation hash function with the given tables.
+    ///
+    /// Unlike for `Tab32Double`, this is not a `const fn`, since the tables are
+    /// allocated on the heap.
+    pub fn with_tables(first: [[[u64; 6]; 256]; 8], second: [[u64; 256]; 48]) -> Self {
+        Tab64Double {
+            first: Box::new(first),
+            second: Box::new(second),
+        }
+    }
+
+    /// Get the tables used by this hash function, the first and the second one.
+    pub fn get_tables(&self) -> ([[[u64; 6]; 256]; 8], [[u64; 256]; 48]) {
+        (*self.first, *self.second)
+    }
+
+    /// Get the id of this hash function, a 64-bit digest of its tables.
+    pub fn id(&self) -> u64 {
+        let mut words = TableWords::default();
+        for entry in self.first.iter().flatten() {
+            entry.for_each_word(|w| words.push(w));
+        }
+        for entry in self.second.iter().flatten() {
+            entry.for_each_word(|w| words.push(w));
+        }
+        words.digest()
+    }
+
+    /// Compute the intermediate value of a key with the first table.
+    pub fn derive(&self, x: u64) -> [u64; 6] {
+        let mut derived = [0; 6];
+        for (column, c) in self.first.iter().zip(byte_chunks_64(x).iter()) {
+            for (word, entry) in derived.iter_mut().zip(column[*c as usize].iter()) {
+                *word ^= entry;
+            }
+        }
+        derived
+    }
+
+    /// Compute the double tabulation hash value of a 64bit integer number.
+    pub fn hash(&self, x: u64) -> u64 {
+        let mut h = 0;
+        let mut columns = self.second.iter();
+        for word in self.derive(x).iter() {
+            for (c, column) in word.to_le_bytes().iter().zip(columns.by_ref()) {
+                h ^= column[*c as usize];
+            }
+        }
+        h
+    }
+}
+
+/// Unvalidated serialized form of a `Tab64Double`
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct RawTab64Double {
+    first: Vec<Vec<[u64; 6]>>,
+    second: Vec<Vec<u64>>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RawTab64Double> for Tab64Double {
+    type Error = &'static str;
+
+    fn try_from(raw: RawTab64Double) -> Result<Self, Self::Error> {
+        if raw.first.len() != 8 || raw.first.iter().any(|column| column.len() != 256) {
+            return Err("the first table needs 8 columns of 256 entries");
+        }
+        if raw.second.len() != DERIVED_64 || raw.second.iter().any(|column| column.len() != 256) {
+            return Err("the second table needs 48 columns of 256 entries");
+        }
+        let mut double = Tab64Double::zeroed();
+        for (column, values) in double.first.iter_mut().zip(raw.first.iter()) {
+            column.copy_from_slice(values);
+        }
+        for (column, values) in double.second.iter_mut().zip(raw.second.iter()) {
+            column.copy_from_slice(values);
+        }
+        Ok(double)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct _VecTab64Double {
+    first: Vec<Vec<[u64; 6]>>,
+    second: Vec<Vec<u64>>,
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Tab64Double {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        _VecTab64Double {
+            first: self.first.iter().map(|column| column.to_vec()).collect(),
+            second: self.second.iter().map(|column| column.to_vec()).collect(),
+        }
+        .serialize(s)
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "rand"))]
+impl Distribution<Tab64Double> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Tab64Double {
+        Tab64Double::from_rng(rng)
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "getrandom"))]
+impl Default for Tab64Double {
+    /// Create a new hash function with random tables, just like `new`.
+    fn default() -> Self {
+        Tab64Double::new()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::fmt::Debug for Tab64Double {
+    /// Show the id of the hash function as fingerprint instead of all entries.
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let fingerprint = self.id();
+        f.debug_struct("Tab64Double")
+            .field("fingerprint", &format_args!("{:#018x}", fingerprint))
+            .finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Hash64Fn for Tab64Double {
+    fn hash64(&self, x: u64) -> u64 {
+        self.hash(x)
+    }
+}
@@ -0,0 +1,67 @@
+//! Sources of entropy used to create random tables.
+#[cfg(feature = "getrandom")]
+use crate::TableEntry;
+
+/// A source of entropy used to create random tables.
+///
+/// Implement this for hardware random number generators or other platform
+/// specific sources to create random hash functions on targets without
+/// operating system support, using the `new_from_entropy` constructors.
+pub trait EntropySource {
+    /// Error returned if the source fails to provide entropy.
+    type Error;
+
+    /// Fill `dest` entirely with random bytes.
+    fn fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Self::Error>;
+}
+
+/// Error returned if the operating system fails to provide entropy.
+#[cfg(feature = "getrandom")]
+pub use getrandom::Error as EntropyError;
+
+/// The entropy source of the operating system (using `getrandom`).
+#[cfg(feature = "getrandom")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OsEntropy;
+
+#[cfg(feature = "getrandom")]
+impl EntropySource for OsEntropy {
+    type Error = EntropyError;
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Self::Error> {
+        getrandom::getrandom(dest)
+    }
+}
+
+/// Buffered reader for table entries taken directly from an entropy source
+#[cfg(feature = "getrandom")]
+pub(crate) struct EntropyReader<'a, E: ?Sized> {
+    source: &'a mut E,
+    buffer: [u8; 256],
+    position: usize,
+}
+
+#[cfg(feature = "getrandom")]
+impl<'a, E: EntropySource + ?Sized> EntropyReader<'a, E> {
+    pub(crate) fn new(source: &'a mut E) -> Self {
+        EntropyReader {
+            source,
+            buffer: [0; 256],
+            position: 256,
+        }
+    }
+
+    /// Read the next entry as a little-endian integer.
+    ///
+    /// The buffer size is a multiple of all entry sizes,
+    /// so entries never span two refills.
+    pub(crate) fn read_entry<T: TableEntry>(&mut self) -> Result<T, E::Error> {
+        if self.position + T::BYTES > self.buffer.len() {
+            self.source.fill_bytes(&mut self.buffer)?;
+            self.position = 0;
+        }
+        let entry = T::from_le_slice(&self.buffer[self.position..self.position + T::BYTES]);
+        self.position += T::BYTES;
+        Ok(entry)
+    }
+}
@@ -0,0 +1,183 @@
+//! Table entries and how they are drawn, digested and read from bytes.
+use rand_core::RngCore;
+
+/// Table entries that can be drawn from a random number generator.
+///
+/// Entries of at most 32 bits consume one `next_u32` call and keep its low bits.
+/// Wider entries consume one `next_u64` call per 64 bits, starting with the lowest.
+///
+/// For digests, entries are split into 64-bit words in the same order.
+/// Entries of at most 64 bits are zero-extended to a single word.
+///
+/// From raw bytes, entries are read as little-endian integers of `BYTES` bytes.
+///
+/// The lowest byte of an entry is the twist of twisted tabulation.
+pub trait TableEntry: Sized {
+    #[cfg(feature = "getrandom")]
+    const BYTES: usize;
+
+    fn from_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self;
+
+    #[cfg(feature = "getrandom")]
+    fn from_le_slice(bytes: &[u8]) -> Self;
+
+    fn for_each_word<F: FnMut(u64)>(&self, f: F);
+
+    fn low_byte(&self) -> u8;
+}
+
+impl TableEntry for u16 {
+    #[cfg(feature = "getrandom")]
+    const BYTES: usize = 2;
+
+    fn from_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        rng.next_u32() as u16
+    }
+
+    fn for_each_word<F: FnMut(u64)>(&self, mut f: F) {
+        f(*self as u64)
+    }
+
+    #[cfg(feature = "getrandom")]
+    fn from_le_slice(bytes: &[u8]) -> Self {
+        u16::from_le_bytes([bytes[0], bytes[1]])
+    }
+
+    fn low_byte(&self) -> u8 {
+        *self as u8
+    }
+}
+
+impl TableEntry for u32 {
+    #[cfg(feature = "getrandom")]
+    const BYTES: usize = 4;
+
+    fn from_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        rng.next_u32()
+    }
+
+    fn for_each_word<F: FnMut(u64)>(&self, mut f: F) {
+        f(*self as u64)
+    }
+
+    #[cfg(feature = "getrandom")]
+    fn from_le_slice(bytes: &[u8]) -> Self {
+        let mut buf = [0; 4];
+        buf.copy_from_slice(bytes);
+        u32::from_le_bytes(buf)
+    }
+
+    fn low_byte(&self) -> u8 {
+        *self as u8
+    }
+}
+
+impl TableEntry for u64 {
+    #[cfg(feature = "getrandom")]
+    const BYTES: usize = 8;
+
+    fn from_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        rng.next_u64()
+    }
+
+    fn for_each_word<F: FnMut(u64)>(&self, mut f: F) {
+        f(*self)
+    }
+
+    #[cfg(feature = "getrandom")]
+    fn from_le_slice(bytes: &[u8]) -> Self {
+        let mut buf = [0; 8];
+        buf.copy_from_slice(bytes);
+        u64::from_le_bytes(buf)
+    }
+
+    fn low_byte(&self) -> u8 {
+        *self as u8
+    }
+}
+
+impl TableEntry for u128 {
+    #[cfg(feature = "getrandom")]
+    const BYTES: usize = 16;
+
+    fn from_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        let low = rng.next_u64() as u128;
+        let high = rng.next_u64() as u128;
+        (high << 64) | low
+    }
+
+    fn for_each_word<F: FnMut(u64)>(&self, mut f: F) {
+        f(*self as u64);
+        f((*self >> 64) as u64);
+    }
+
+    #[cfg(feature = "getrandom")]
+    fn from_le_slice(bytes: &[u8]) -> Self {
+        let mut buf = [0; 16];
+        buf.copy_from_slice(bytes);
+        u128::from_le_bytes(buf)
+    }
+
+    fn low_byte(&self) -> u8 {
+        *self as u8
+    }
+}
+
+impl TableEntry for [u128; 2] {
+    #[cfg(feature = "getrandom")]
+    const BYTES: usize = 32;
+
+    fn from_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        let low = u128::from_rng(rng);
+        let high = u128::from_rng(rng);
+        [low, high]
+    }
+
+    fn for_each_word<F: FnMut(u64)>(&self, mut f: F) {
+        self[0].for_each_word(&mut f);
+        self[1].for_each_word(f);
+    }
+
+    #[cfg(feature = "getrandom")]
+    fn from_le_slice(bytes: &[u8]) -> Self {
+        [
+            u128::from_le_slice(&bytes[..16]),
+            u128::from_le_slice(&bytes[16..]),
+        ]
+    }
+
+    fn low_byte(&self) -> u8 {
+        self[0] as u8
+    }
+}
+
+impl<const N: usize> TableEntry for [u64; N] {
+    #[cfg(feature = "getrandom")]
+    const BYTES: usize = 8 * N;
+
+    /// Draw the words in order, starting with the lowest
+    fn from_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        let mut words = [0; N];
+        for word in words.iter_mut() {
+            *word = rng.next_u64();
+        }
+        words
+    }
+
+    fn for_each_word<F: FnMut(u64)>(&self, mut f: F) {
+        self.iter().for_each(|w| f(*w));
+    }
+
+    #[cfg(feature = "getrandom")]
+    fn from_le_slice(bytes: &[u8]) -> Self {
+        let mut words = [0; N];
+        for (word, chunk) in words.iter_mut().zip(bytes.chunks(8)) {
+            *word = u64::from_le_slice(chunk);
+        }
+        words
+    }
+
+    fn low_byte(&self) -> u8 {
+        self[0] as u8
+    }
+}
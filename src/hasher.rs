@@ -0,0 +1,276 @@
+//! Adapters to use tabulation hash functions with `HashMap`s and `HashSet`s.
+//!
+//! Tabulation hash functions hash integers of a fixed width, not arbitrary byte streams.
+//! The `Hash` implementations of the integer types feed their value to the hasher
+//! with a single `write_u32` (or `write_u64`) call, which is all these adapters accept:
+//!
+//! - `Tab32Hasher` accepts exactly one `write_u32` (or `write_i32`) call,
+//!   `Tab64Hasher` accepts exactly one `write_u64` (or `write_i64`) call.
+//! - Any other write, or a second write, panics.
+//!   This means the adapters can only be used for maps with keys of the respective integer type.
+//! - `finish` returns the tabulation hash value. 32-bit hash values are widened
+//!   by repeating them in both halves of the result, since hash maps use the high
+//!   bits of a hash value as well. `finish` panics if no key was written.
+//!
+//! `TabRandomState` works like `Tab64BuildHasher`, but shares ownership of its
+//! hash function, so it can be cloned cheaply and used without lifetimes.
+use crate::{Tab32Simple, Tab32Twisted, Tab64Simple, Tab64Twisted};
+use core::hash::{BuildHasher, Hasher};
+#[cfg(feature = "std")]
+use std::sync::{Arc, OnceLock};
+
+/// Reference to a hash function for 32-bit keys
+#[derive(Clone, Copy)]
+enum Function32<'a> {
+    Simple(&'a Tab32Simple),
+    Twisted(&'a Tab32Twisted),
+}
+
+/// Reference to a hash function for 64-bit keys
+#[derive(Clone, Copy)]
+enum Function64<'a> {
+    Simple(&'a Tab64Simple),
+    Twisted(&'a Tab64Twisted),
+}
+
+/// A `Hasher` for `u32` keys using a borrowed simple or twisted tabulation hash function.
+///
+/// See the module documentation for the accepted writes.
+#[derive(Clone, Copy)]
+pub struct Tab32Hasher<'a> {
+    function: Function32<'a>,
+    hash: Option<u32>,
+}
+
+impl<'a> Hasher for Tab32Hasher<'a> {
+    fn write(&mut self, _bytes: &[u8]) {
+        panic!("Tab32Hasher can only hash a single u32 key");
+    }
+
+    fn write_u32(&mut self, key: u32) {
+        assert!(
+            self.hash.is_none(),
+            "Tab32Hasher can only hash a single u32 key"
+        );
+        self.hash = Some(match self.function {
+            Function32::Simple(h) => h.hash(key),
+            Function32::Twisted(h) => h.hash(key),
+        });
+    }
+
+    fn finish(&self) -> u64 {
+        match self.hash {
+            Some(h) => (u64::from(h) << 32) | u64::from(h),
+            None => panic!("Tab32Hasher finished without a key"),
+        }
+    }
+}
+
+/// A `Hasher` for `u64` keys using a borrowed simple or twisted tabulation hash function.
+///
+/// See the module documentation for the accepted writes.
+#[derive(Clone, Copy)]
+pub struct Tab64Hasher<'a> {
+    function: Function64<'a>,
+    hash: Option<u64>,
+}
+
+impl<'a> Hasher for Tab64Hasher<'a> {
+    fn write(&mut self, _bytes: &[u8]) {
+        panic!("Tab64Hasher can only hash a single u64 key");
+    }
+
+    fn write_u64(&mut self, key: u64) {
+        assert!(
+            self.hash.is_none(),
+            "Tab64Hasher can only hash a single u64 key"
+        );
+        self.hash = Some(match self.function {
+            Function64::Simple(h) => h.hash(key),
+            Function64::Twisted(h) => h.hash(key),
+        });
+    }
+
+    fn finish(&self) -> u64 {
+        match self.hash {
+            Some(h) => h,
+            None => panic!("Tab64Hasher finished without a key"),
+        }
+    }
+}
+
+/// A `BuildHasher` creating `Tab32Hasher`s for a borrowed hash function.
+///
+/// Create it from a reference to a `Tab32Simple` or `Tab32Twisted`,
+/// e.g. `HashMap::with_hasher(Tab32BuildHasher::from(&simple))`.
+#[derive(Clone, Copy)]
+pub struct Tab32BuildHasher<'a> {
+    function: Function32<'a>,
+}
+
+impl<'a> From<&'a Tab32Simple> for Tab32BuildHasher<'a> {
+    fn from(function: &'a Tab32Simple) -> Self {
+        Tab32BuildHasher {
+            function: Function32::Simple(function),
+        }
+    }
+}
+
+impl<'a> From<&'a Tab32Twisted> for Tab32BuildHasher<'a> {
+    fn from(function: &'a Tab32Twisted) -> Self {
+        Tab32BuildHasher {
+            function: Function32::Twisted(function),
+        }
+    }
+}
+
+impl<'a> BuildHasher for Tab32BuildHasher<'a> {
+    type Hasher = Tab32Hasher<'a>;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Tab32Hasher {
+            function: self.function,
+            hash: None,
+        }
+    }
+}
+
+/// A `BuildHasher` creating `Tab64Hasher`s for a borrowed hash function.
+///
+/// Create it from a reference to a `Tab64Simple` or `Tab64Twisted`,
+/// e.g. `HashMap::with_hasher(Tab64BuildHasher::from(&simple))`.
+#[derive(Clone, Copy)]
+pub struct Tab64BuildHasher<'a> {
+    function: Function64<'a>,
+}
+
+impl<'a> From<&'a Tab64Simple> for Tab64BuildHasher<'a> {
+    fn from(function: &'a Tab64Simple) -> Self {
+        Tab64BuildHasher {
+            function: Function64::Simple(function),
+        }
+    }
+}
+
+impl<'a> From<&'a Tab64Twisted> for Tab64BuildHasher<'a> {
+    fn from(function: &'a Tab64Twisted) -> Self {
+        Tab64BuildHasher {
+            function: Function64::Twisted(function),
+        }
+    }
+}
+
+impl<'a> BuildHasher for Tab64BuildHasher<'a> {
+    type Hasher = Tab64Hasher<'a>;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Tab64Hasher {
+            function: self.function,
+            hash: None,
+        }
+    }
+}
+
+/// The hash function shared by all `TabRandomState`s created with `new`
+#[cfg(feature = "std")]
+static GLOBAL_FUNCTION: OnceLock<Arc<Tab64Simple>> = OnceLock::new();
+
+/// A `BuildHasher` for `u64` keys sharing one simple tabulation hash function.
+///
+/// Like `std::collections::hash_map::RandomState`, but cloning only bumps
+/// the reference count of the shared hash function instead of copying its table.
+/// It can be sent to and shared between threads.
+///
+/// Keys are hashed by `hash_one`, which borrows the hash function, so hashing does not
+/// touch the reference count shared by all threads. `HashMap` and `HashSet` hash all keys
+/// this way. Only the hashers returned by `build_hasher` own a reference to the function.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct TabRandomState {
+    function: Arc<Tab64Simple>,
+}
+
+#[cfg(feature = "std")]
+impl TabRandomState {
+    /// Create a new state using the process-global hash function.
+    ///
+    /// The global hash function has a random table, which is created
+    /// on first use and shared by all states created this way.
+    pub fn new() -> Self {
+        TabRandomState {
+            function: GLOBAL_FUNCTION
+                .get_or_init(|| Arc::new(Tab64Simple::new()))
+                .clone(),
+        }
+    }
+
+    /// Create a new state sharing the given hash function.
+    ///
+    /// Use this with a persisted table to reproduce hash map layouts.
+    pub fn from_function(function: Arc<Tab64Simple>) -> Self {
+        TabRandomState { function }
+    }
+
+    /// Get the hash function used by this state, e.g. to persist its table.
+    pub fn function(&self) -> &Arc<Tab64Simple> {
+        &self.function
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for TabRandomState {
+    /// Create a new state using the process-global hash function, see `new`.
+    fn default() -> Self {
+        TabRandomState::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl BuildHasher for TabRandomState {
+    type Hasher = TabRandomStateHasher;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        TabRandomStateHasher {
+            function: self.function.clone(),
+            hash: None,
+        }
+    }
+
+    /// Hash a key with a `Tab64Hasher` borrowing the shared hash function.
+    fn hash_one<T: core::hash::Hash>(&self, x: T) -> u64 {
+        let mut hasher = Tab64BuildHasher::from(&*self.function).build_hasher();
+        core::hash::Hash::hash(&x, &mut hasher);
+        hasher.finish()
+    }
+}
+
+/// The `Hasher` created by `TabRandomState`.
+///
+/// Accepts the same writes as `Tab64Hasher`, see the module documentation.
+#[cfg(feature = "std")]
+pub struct TabRandomStateHasher {
+    function: Arc<Tab64Simple>,
+    hash: Option<u64>,
+}
+
+#[cfg(feature = "std")]
+impl Hasher for TabRandomStateHasher {
+    fn write(&mut self, _bytes: &[u8]) {
+        panic!("TabRandomStateHasher can only hash a single u64 key");
+    }
+
+    fn write_u64(&mut self, key: u64) {
+        assert!(
+            self.hash.is_none(),
+            "TabRandomStateHasher can only hash a single u64 key"
+        );
+        self.hash = Some(self.function.hash(key));
+    }
+
+    fn finish(&self) -> u64 {
+        match self.hash {
+            Some(h) => h,
+            None => panic!("TabRandomStateHasher finished without a key"),
+        }
+    }
+}
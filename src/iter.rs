@@ -0,0 +1,159 @@
+//! Iterator adapters hashing streams of keys.
+use crate::TabHash;
+use core::borrow::Borrow;
+use core::fmt;
+use core::iter::FusedIterator;
+
+/// Extension trait adding hashing adapters to iterators of keys.
+///
+/// The adapters work with every hash function implementing `TabHash`, e.g. `Tab32Simple`
+/// for iterators of `u32` and `Tab64Twisted` for iterators of `u64`. Keys can be owned or
+/// borrowed, so both `keys.into_iter()` and `keys.iter()` can be hashed.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::{Tab32Simple, TabHashIterExt};
+///
+/// let simple = Tab32Simple::new_with_seed(42);
+/// let keys = vec![1, 2, 3];
+/// let hashes: Vec<u32> = keys.iter().hashed(&simple).collect();
+/// assert_eq!(hashes[0], simple.hash(1));
+///
+/// for (key, hash) in keys.iter().with_hashes(&simple) {
+///     assert_eq!(hash, simple.hash(*key));
+/// }
+/// ```
+pub trait TabHashIterExt: Iterator + Sized {
+    /// Lazily map every key to its hash value.
+    fn hashed<H: TabHash>(self, hash_function: &H) -> Hashed<'_, Self, H>
+    where
+        Self::Item: Borrow<H::Key>,
+    {
+        Hashed {
+            iter: self,
+            hash_function,
+        }
+    }
+
+    /// Lazily map every key to a pair of the key and its hash value.
+    fn with_hashes<H: TabHash>(self, hash_function: &H) -> WithHashes<'_, Self, H>
+    where
+        Self::Item: Borrow<H::Key>,
+    {
+        WithHashes {
+            iter: self,
+            hash_function,
+        }
+    }
+}
+
+impl<I: Iterator> TabHashIterExt for I {}
+
+/// Iterator over the hash values of the keys of another iterator, see `TabHashIterExt::hashed`.
+pub struct Hashed<'a, I, H> {
+    iter: I,
+    hash_function: &'a H,
+}
+
+/// Iterator over pairs of the keys of another iterator and their hash values,
+/// see `TabHashIterExt::with_hashes`.
+pub struct WithHashes<'a, I, H> {
+    iter: I,
+    hash_function: &'a H,
+}
+
+/// Implement the iterator traits of an adapter with the items of its `hash_item` method,
+/// forwarding everything else to the inner iterator
+macro_rules! adapter_traits {
+    ($adapter:ident, $name:expr, $item:ty) => {
+        impl<'a, I, H> Iterator for $adapter<'a, I, H>
+        where
+            I: Iterator,
+            I::Item: Borrow<H::Key>,
+            H: TabHash,
+        {
+            type Item = $item;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                let key = self.iter.next()?;
+                Some(self.hash_item(key))
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.iter.size_hint()
+            }
+        }
+
+        impl<'a, I, H> DoubleEndedIterator for $adapter<'a, I, H>
+        where
+            I: DoubleEndedIterator,
+            I::Item: Borrow<H::Key>,
+            H: TabHash,
+        {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                let key = self.iter.next_back()?;
+                Some(self.hash_item(key))
+            }
+        }
+
+        impl<'a, I, H> ExactSizeIterator for $adapter<'a, I, H>
+        where
+            I: ExactSizeIterator,
+            I::Item: Borrow<H::Key>,
+            H: TabHash,
+        {
+        }
+
+        impl<'a, I, H> FusedIterator for $adapter<'a, I, H>
+        where
+            I: FusedIterator,
+            I::Item: Borrow<H::Key>,
+            H: TabHash,
+        {
+        }
+
+        impl<'a, I: Clone, H> Clone for $adapter<'a, I, H> {
+            fn clone(&self) -> Self {
+                $adapter {
+                    iter: self.iter.clone(),
+                    hash_function: self.hash_function,
+                }
+            }
+        }
+
+        impl<'a, I: fmt::Debug, H> fmt::Debug for $adapter<'a, I, H> {
+            /// Show the inner iterator, but not the table of the hash function
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct($name).field("iter", &self.iter).finish()
+            }
+        }
+    };
+}
+
+adapter_traits!(Hashed, "Hashed", H::Key);
+adapter_traits!(WithHashes, "WithHashes", (I::Item, H::Key));
+
+impl<'a, I, H> Hashed<'a, I, H>
+where
+    I: Iterator,
+    I::Item: Borrow<H::Key>,
+    H: TabHash,
+{
+    /// Turn a key into an item of the adapter
+    fn hash_item(&self, key: I::Item) -> H::Key {
+        self.hash_function.hash(*key.borrow())
+    }
+}
+
+impl<'a, I, H> WithHashes<'a, I, H>
+where
+    I: Iterator,
+    I::Item: Borrow<H::Key>,
+    H: TabHash,
+{
+    /// Turn a key into an item of the adapter
+    fn hash_item(&self, key: I::Item) -> (I::Item, H::Key) {
+        let hash = self.hash_function.hash(*key.borrow());
+        (key, hash)
+    }
+}
@@ -0,0 +1,115 @@
+//! Keys that can be split into byte chunks for tabulation hashing.
+use crate::{byte_chunks_128, byte_chunks_16, byte_chunks_32, byte_chunks_64};
+
+/// A key that can be hashed by the `hash_key` methods of the hash functions.
+///
+/// A key is split into byte chunks, chunk `i` is looked up in column `i` of the table.
+/// The number of chunks is encoded in the `Chunks` array type, so keys can only be
+/// hashed with hash functions of matching width, which is checked at compile time:
+///
+/// ```rust
+/// use tab_hash::{Tab32Simple, TabKey};
+///
+/// struct OrderId(u32);
+///
+/// impl TabKey for OrderId {
+///     type Chunks = [u8; 4];
+///
+///     fn chunks(&self) -> [u8; 4] {
+///         self.0.chunks()
+///     }
+/// }
+///
+/// let simple = Tab32Simple::new_with_seed(42);
+/// assert_eq!(simple.hash_key(OrderId(1337)), simple.hash(1337));
+/// ```
+///
+/// ```compile_fail
+/// use tab_hash::Tab32Simple;
+///
+/// // a 64-bit key cannot be hashed with a 32-bit hash function
+/// Tab32Simple::new_with_seed(42).hash_key(1337_u64);
+/// ```
+pub trait TabKey {
+    /// Byte chunks of the key, an array `[u8; N]` for a hash function with N columns.
+    type Chunks;
+
+    /// Split the key into byte chunks.
+    ///
+    /// The integer types use little-endian chunks, see `byte_chunks_32`.
+    /// Signed integers are split like the unsigned integers with the same
+    /// two's complement bit pattern.
+    fn chunks(&self) -> Self::Chunks;
+}
+
+impl<K: TabKey + ?Sized> TabKey for &K {
+    type Chunks = K::Chunks;
+
+    fn chunks(&self) -> Self::Chunks {
+        (**self).chunks()
+    }
+}
+
+impl TabKey for u16 {
+    type Chunks = [u8; 2];
+
+    fn chunks(&self) -> [u8; 2] {
+        byte_chunks_16(*self)
+    }
+}
+
+impl TabKey for u32 {
+    type Chunks = [u8; 4];
+
+    fn chunks(&self) -> [u8; 4] {
+        byte_chunks_32(*self)
+    }
+}
+
+impl TabKey for u64 {
+    type Chunks = [u8; 8];
+
+    fn chunks(&self) -> [u8; 8] {
+        byte_chunks_64(*self)
+    }
+}
+
+impl TabKey for u128 {
+    type Chunks = [u8; 16];
+
+    fn chunks(&self) -> [u8; 16] {
+        byte_chunks_128(*self)
+    }
+}
+
+impl TabKey for i16 {
+    type Chunks = [u8; 2];
+
+    fn chunks(&self) -> [u8; 2] {
+        byte_chunks_16(*self as u16)
+    }
+}
+
+impl TabKey for i32 {
+    type Chunks = [u8; 4];
+
+    fn chunks(&self) -> [u8; 4] {
+        byte_chunks_32(*self as u32)
+    }
+}
+
+impl TabKey for i64 {
+    type Chunks = [u8; 8];
+
+    fn chunks(&self) -> [u8; 8] {
+        byte_chunks_64(*self as u64)
+    }
+}
+
+impl TabKey for i128 {
+    type Chunks = [u8; 16];
+
+    fn chunks(&self) -> [u8; 16] {
+        byte_chunks_128(*self as u128)
+    }
+}
@@ -0,0 +1,238 @@
+//! Helpers for hashing DNA k-mers in 2-bit encoding.
+//!
+//! Bases are encoded as `A = 0`, `C = 1`, `G = 2` and `T = 3` (upper or lower case).
+//! A k-mer of up to 32 bases is packed into a `u64` with the first base in the
+//! highest used bits, i.e. base `i` of a k-mer of length `k` occupies bits
+//! `2 * (k - 1 - i)` and `2 * (k - 1 - i) + 1`. Hence packed k-mers of the same
+//! length compare like the k-mers themselves in lexicographic order.
+//!
+//! ```rust
+//! use tab_hash::kmer::encode_kmer;
+//! use tab_hash::Tab64Simple;
+//!
+//! let packed = encode_kmer(b"GATTACA").unwrap();
+//! assert_eq!(packed, 0b10_00_11_11_00_01_00);
+//!
+//! let simple = Tab64Simple::new_with_seed(42);
+//! println!("{}", simple.hash_kmer(packed, 7));
+//!
+//! // the reverse complement of GATTACA
+//! let reverse = encode_kmer(b"TGTAATC").unwrap();
+//! assert_eq!(simple.hash_canonical(packed, 7), simple.hash_canonical(reverse, 7));
+//! ```
+#[cfg(feature = "alloc")]
+use alloc::{collections::VecDeque, vec::Vec};
+use core::fmt;
+
+/// The maximum length of a k-mer packed into a `u64`.
+pub const MAX_K: usize = 32;
+
+/// A k-mer that cannot be packed into a `u64`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KmerError {
+    /// The k-mer contains a base other than `A`, `C`, `G` or `T`, e.g. an ambiguous `N`
+    InvalidBase {
+        /// Position of the base in the k-mer
+        position: usize,
+        /// The invalid base
+        base: u8,
+    },
+    /// The k-mer is longer than `MAX_K` bases
+    TooLong {
+        /// Length of the k-mer
+        k: usize,
+    },
+}
+
+impl fmt::Display for KmerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            KmerError::InvalidBase { position, base } => write!(
+                f,
+                "invalid base {:?} at position {} of k-mer",
+                char::from(*base),
+                position
+            ),
+            KmerError::TooLong { k } => write!(
+                f,
+                "k-mer of length {} exceeds the maximum length of {}",
+                k, MAX_K
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for KmerError {}
+
+/// Pack a k-mer of up to 32 bases into a `u64` using 2 bits per base.
+///
+/// See the module documentation for the encoding.
+pub fn encode_kmer(bases: &[u8]) -> Result<u64, KmerError> {
+    if bases.len() > MAX_K {
+        return Err(KmerError::TooLong { k: bases.len() });
+    }
+    let mut packed = 0;
+    for (position, base) in bases.iter().enumerate() {
+        let code = match base {
+            b'A' | b'a' => 0,
+            b'C' | b'c' => 1,
+            b'G' | b'g' => 2,
+            b'T' | b't' => 3,
+            _ => {
+                return Err(KmerError::InvalidBase {
+                    position,
+                    base: *base,
+                })
+            }
+        };
+        packed = (packed << 2) | code;
+    }
+    Ok(packed)
+}
+
+/// Unpack the k-mer of length `k` from a `u64`, the inverse of `encode_kmer`.
+///
+/// Bases are returned in upper case. Bits above the lowest `2 * k` are ignored.
+///
+/// # Panics
+/// If `k` is larger than `MAX_K`.
+#[cfg(feature = "alloc")]
+pub fn decode_kmer(packed: u64, k: usize) -> Vec<u8> {
+    assert!(k <= MAX_K, "k-mers can have at most {} bases", MAX_K);
+    (0..k)
+        .rev()
+        .map(|i| b"ACGT"[((packed >> (2 * i)) & 0b11) as usize])
+        .collect()
+}
+
+/// Clear all bits of a packed k-mer of length `k` except for the lowest `2 * k`.
+///
+/// # Panics
+/// If `k` is larger than `MAX_K`.
+pub const fn mask_kmer(packed: u64, k: usize) -> u64 {
+    assert!(k <= MAX_K, "k-mers can have at most 32 bases");
+    if k == MAX_K {
+        packed
+    } else {
+        packed & ((1 << (2 * k)) - 1)
+    }
+}
+
+/// Compute the reverse complement of a packed k-mer of length `k`.
+///
+/// Bases are complemented (`A <-> T`, `C <-> G`, i.e. all bits are flipped)
+/// and reversed. Bits above the lowest `2 * k` are ignored and cleared in the result.
+///
+/// # Panics
+/// If `k` is larger than `MAX_K`.
+pub const fn reverse_complement(packed: u64, k: usize) -> u64 {
+    assert!(k <= MAX_K, "k-mers can have at most 32 bases");
+    if k == 0 {
+        return 0;
+    }
+    let mut x = !packed;
+    // reverse the order of the 2-bit bases, then shift the k-mer back to the low bits
+    x = ((x >> 2) & 0x3333_3333_3333_3333) | ((x & 0x3333_3333_3333_3333) << 2);
+    x = ((x >> 4) & 0x0F0F_0F0F_0F0F_0F0F) | ((x & 0x0F0F_0F0F_0F0F_0F0F) << 4);
+    x = x.swap_bytes();
+    x >> (2 * (MAX_K - k))
+}
+
+/// Get the canonical form of a packed k-mer of length `k`.
+///
+/// This is the lexicographically smaller one of the k-mer and its reverse complement,
+/// i.e. the smaller packed value, so a k-mer and its reverse complement
+/// have the same canonical form. Bits above the lowest `2 * k` are ignored.
+///
+/// # Panics
+/// If `k` is larger than `MAX_K`.
+pub const fn canonical_kmer(packed: u64, k: usize) -> u64 {
+    let forward = mask_kmer(packed, k);
+    let reverse = reverse_complement(packed, k);
+    if forward < reverse {
+        forward
+    } else {
+        reverse
+    }
+}
+
+/// Iterator over the minimizers of a sequence of k-mers, see `Tab64Simple::minimizers`.
+///
+/// A monotonic queue holds the candidates of the current window in increasing
+/// order of position and hash value, so each k-mer is hashed, pushed and popped once.
+#[cfg(feature = "alloc")]
+pub(crate) struct Minimizers<I, F> {
+    kmers: I,
+    hash: F,
+    w: usize,
+    /// Candidates of the current window as (position, hash value, k-mer)
+    queue: VecDeque<(usize, u64, u64)>,
+    /// Position of the next k-mer
+    position: usize,
+    /// Position of the last reported minimizer
+    last: Option<usize>,
+}
+
+#[cfg(feature = "alloc")]
+impl<I: Iterator<Item = u64>, F: Fn(u64) -> u64> Minimizers<I, F> {
+    /// Create an iterator over the minimizers of windows of `w` k-mers.
+    ///
+    /// # Panics
+    /// If `w` is 0.
+    pub(crate) fn new(kmers: I, w: usize, hash: F) -> Self {
+        assert!(
+            w > 0,
+            "minimizer windows have to contain at least one k-mer"
+        );
+        Minimizers {
+            kmers,
+            hash,
+            w,
+            queue: VecDeque::with_capacity(w),
+            position: 0,
+            last: None,
+        }
+    }
+
+    /// Report the minimum of the current window, unless it was reported before
+    fn report(&mut self) -> Option<(usize, u64)> {
+        let (position, _, kmer) = *self.queue.front()?;
+        if self.last == Some(position) {
+            return None;
+        }
+        self.last = Some(position);
+        Some((position, kmer))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I: Iterator<Item = u64>, F: Fn(u64) -> u64> Iterator for Minimizers<I, F> {
+    type Item = (usize, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(kmer) = self.kmers.next() {
+            let position = self.position;
+            self.position += 1;
+            let h = (self.hash)(kmer);
+            // candidates with an equal hash value stay in front, so the leftmost one wins
+            while matches!(self.queue.back(), Some(&(_, back, _)) if back > h) {
+                self.queue.pop_back();
+            }
+            self.queue.push_back((position, h, kmer));
+            while matches!(self.queue.front(), Some(&(front, _, _)) if front + self.w <= position) {
+                self.queue.pop_front();
+            }
+            if position + 1 >= self.w {
+                if let Some(minimizer) = self.report() {
+                    return Some(minimizer);
+                }
+            }
+        }
+        // a sequence shorter than a window forms a single window
+        if self.position < self.w {
+            return self.report();
+        }
+        None
+    }
+}
@@ -1,12 +1,20 @@
-//! This crate offers rust implementations of simple and twisted tabulation hashing for 32-bit integer values.
+//! This crate offers rust implementations of simple and twisted tabulation hashing for 32-bit, 64-bit and 128-bit integer values,
+//! as well as simple tabulation hashing for 16-bit integer values
+//! tabulation-permutation hashing for 32-bit and 64-bit integer values (see `Tab64TabPerm`),
+//! and double tabulation hashing for 32-bit and 64-bit integer values (see `Tab64Double`).
 //!
 //! Instatiating `Tab32Simple` or `Tab32Twisted` will initialize a table and
 //! create a random hash function from the respective hash family.
 //! The hash value of a 32-bit integer can be computed by calling its `hash` method.
+//! The simple and twisted tabulation hash functions for integers are aliases of the
+//! generic `TabSimple` and `TabTwisted` for their entry type and number of characters,
+//! except for `Tab128Twisted`, whose table is stored on the heap.
 //!
 //! # Example:
 //!
 //! ```rust
+//! # #[cfg(feature = "std")]
+//! # {
 //! use tab_hash::Tab32Simple;
 //!
 //! let keys = vec![0, 8, 15, 47, 11];
@@ -14,12 +22,15 @@
 //! for k in keys {
 //!     println!("{}", simple.hash(k));
 //! }
+//! # }
 //! ```
 //!
 //! To reprocude hashes, save the table used by the hash function and save it.
 //! The function can be recreated using the `with_table` constructor.
 //!
 //! ```rust
+//! # #[cfg(feature = "std")]
+//! # {
 //! use tab_hash::Tab32Twisted;
 //!
 //! let key = 42;
@@ -28,20 +39,538 @@
 //! let twisted_3 = Tab32Twisted::new();
 //! assert_eq!(twisted_1.hash(key), twisted_2.hash(key));
 //! assert_ne!(twisted_1.hash(key), twisted_3.hash(key));
+//! # }
 //! ```
 //!
+//! Since `with_table` and `from_le_bytes` are `const fn`s, fixed tables can
+//! be embedded into the binary at compile time, e.g. from a file written with `to_le_bytes`:
+//!
+//! ```rust,ignore
+//! use tab_hash::Tab32Simple;
+//!
+//! static SIMPLE: Tab32Simple = Tab32Simple::from_le_bytes(include_bytes!("table.bin"));
+//! ```
+//!
+//! Alternatively, a hash function can be created from a 64-bit seed using the
+//! `new_with_seed` constructor. The seed is expanded into the table using
+//! the [SplitMix64](https://prng.di.unimi.it/splitmix64.c) generator.
+//! Entries are filled column by column, each column from entry 0 to 255.
+//! Entries of at most 64 bits consume one output word and keep its low bits,
+//! wider entries consume one word per 64 bits, starting with the lowest.
+//! This expansion is part of the stable interface of this crate,
+//! i.e. a seed produces the same hash function across all versions.
+//!
+//! For seeds that fully describe a hash function, e.g. to exchange them between
+//! services, use the `from_seed_bytes` constructor with a 32-byte seed.
+//! The seed is used as key of the ChaCha20 stream cipher (20 rounds, 64-bit block counter
+//! and 64-bit nonce starting at zero, as implemented by `rand_chacha::ChaCha20Rng`)
+//! and the keystream is drawn into the table in the order described below.
+//! This expansion is stable across versions and platforms as well.
+//!
+//! Each hash function has a 64-bit id, a digest of its table returned by `id`.
+//! To compute it, the table is split into 64-bit words (in table order; entries
+//! of up to 64 bits are zero-extended, wider entries are split starting with the
+//! lowest word). Starting with a state of 0, each word is XORed into the state
+//! which is then mixed with the SplitMix64 finalizer; finally the number of words
+//! is XORed in and mixed once more. The id is stable across versions and platforms.
+//!
+//! Families of hash functions can be derived from a single parent function
+//! using `derive_child`. To derive the child with index `i`, the first four
+//! outputs of a SplitMix64 generator seeded with the id of the parent form the
+//! ChaCha20 key (little endian), and `i` is used as ChaCha20 stream.
+//! The child table is drawn from this generator.
+//! Children are hence stable across versions and only need the parent table
+//! to be persisted.
+//!
+//! To draw tables from your own random number generator, use `new_with_rng`
+//! (requires the `rand` feature, which is enabled by default).
+//! Entries are drawn in the same order as for seeded hash functions.
+//! Hash functions can also be sampled directly, e.g. `let h: Tab32Simple = rng.gen();`,
+//! which is equivalent to calling `new_with_rng`.
+//! Entries of up to 32 bits consume one `next_u32` call and keep its low bits,
+//! wider entries consume one `next_u64` call per 64 bits, starting with the lowest.
+//!
+//! ```rust
+//! use tab_hash::Tab64Simple;
+//!
+//! let simple_1 = Tab64Simple::new_with_seed(42);
+//! let simple_2 = Tab64Simple::new_with_seed(42);
+//! assert_eq!(simple_1.hash(1337), simple_2.hash(1337));
+//! ```
+//!
+//! The `reference` module publishes versioned reference hash functions
+//! together with the hash values of a set of reference keys. Use `self_check`
+//! to verify that this crate computes these hash values on the current platform.
+//!
+//! ```rust
+//! assert!(tab_hash::self_check().is_ok());
+//! ```
+//!
+//! # Features:
+//! - `std` (default): Use the standard library. Implies `alloc` and `getrandom`.
+//! - `alloc`: Use heap allocations, required for `to_vec`, `from_vec`, `hash_many`,
+//!   `Tab128Twisted`, `Tab64Double`, `Tab32Simple16`, `Tab64Simple16` and the sketches
+//!   `CountMinSketch`, `AmsSketch`, `HyperLogLog`, `MinHash`, `OnePermMinHash` and `BottomK`
+//!   as well as `ConsistentRing` and `TabPoly64`.
+//! - `getrandom`: Create random tables from the entropy source of the operating system.
+//!   Required for `new`, `reseed` and `new_from_os_entropy`.
+//! - `serde` (default): Serialization and deserialization of all hash functions. Implies `alloc`.
+//! - `rand` (default): Integration with the `rand` crate, i.e. the `new_with_rng`
+//!   and `reseed_with_rng` methods and sampling hash functions via `rng.gen()`.
+//! - `derive`: `#[derive(TabKey)]` for structs with integer fields.
+//! - `bytemuck`: Hashing `bytemuck::Pod` keys with `hash_pod`.
+//! - `net`: Hashing IP addresses and socket addresses with `hash_ipv4`, `hash_ipv6`
+//!   and `hash_socketaddr_v4`.
+//! - `uuid`: Hashing UUIDs with `hash_uuid` of `TabBytes<16>` and `TabBytesTwisted<16>`.
+//! - `parallel`: Hashing large slices of keys on multiple threads with `par_hash_many`
+//!   and `par_hash_into` of all integer tabulation hash functions. The threads are scoped
+//!   threads of `std` spawned per call, not a global thread pool, so this adds no
+//!   dependencies. Implies `std`.
+//! - `simd`: Batch hashing with `Tab32Simple` using AVX2 gathers and with `Tab64Twisted`
+//!   using AVX-512 gathers on x86-64 CPUs that support them, detected at runtime.
+//!   Implies `std`. Whether this is faster than the scalar batch loop depends on the
+//!   throughput of gathers on the CPU, so measure it with the `batch` benchmark.
+//! - `portable-simd`: Batch hashing with `Tab32Simple` using the gathers of `core::simd`.
+//!   Requires nightly Rust. The kernel compiles for any target, but it is only used on x86-64
+//!   builds with gather instructions enabled at compile time, e.g. with
+//!   `-C target-feature=+avx2`, and without the `simd` feature, whose kernels take precedence,
+//!   on aarch64, and on wasm32 with `simd128`. On other targets, the scalar batch loop is
+//!   used instead.
+//!
+//! With the `simd` or `portable-simd` features, the kernel of the batch methods is selected
+//! at runtime, see `BatchKernel`.
+//!
+//! Random tables are created by seeding a xoshiro256** generator from the entropy source
+//! of the operating system (using `getrandom`).
+//!
+//! Without default features, this crate supports `no_std` targets.
+//! Hash functions can then be created using `with_table`, the seeded constructors,
+//! or `new_from_entropy` with a custom `EntropySource`, e.g. a hardware random number generator.
+//!
 //! # Note:
 //! These hash functions do not implement the `std::hash::Hasher` trait,
 //! since they do not work on arbitrary length byte streams.
+//! For hash maps with `u32` or `u64` keys, use the `Tab32BuildHasher` and
+//! `Tab64BuildHasher` adapters instead:
+//!
+//! ```rust
+//! use std::collections::HashMap;
+//! use tab_hash::{Tab32BuildHasher, Tab32Simple};
+//!
+//! let simple = Tab32Simple::new_with_seed(42);
+//! let mut map = HashMap::with_hasher(Tab32BuildHasher::from(&simple));
+//! map.insert(42_u32, "answer");
+//! assert_eq!(map.get(&42), Some(&"answer"));
+//! ```
 //!
 //! # Literature:
 //! This implementation is based on the articles of Mihai Patrascu and Mikkel Thorup:
 //! - [Simple Tabulation Hashing](http://dx.doi.org/10.1145/1993636.1993638)
 //! - [Twisted Tabulation Hashing](https://doi.org/10.1137/1.9781611973105.16)
+//!
+//! Tabulation-permutation and tabulation-1-permutation hashing follow
+//! Anders Aamand, Jakob Bæk Tejs Knudsen, Mathias Bæk Tejs Knudsen, Jakob Bæk Tejs Houen and Mikkel Thorup:
+//! - [Fast Hashing with Strong Concentration Bounds](https://doi.org/10.1145/3357713.3384259)
+//! - [Understanding the Moments of Tabulation Hashing via Chaoses](https://doi.org/10.4230/LIPIcs.ICALP.2022.74)
+//!
+//! Double tabulation hashing follows Mikkel Thorup:
+//! - [Simple Tabulation, Fast Expanders, Double Tabulation, and High Independence](https://doi.org/10.1109/FOCS.2013.18)
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+#![cfg_attr(feature = "portable-simd", feature(portable_simd))]
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, vec, vec::Vec};
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-/// Split up a 32bit number into 8bit chunks
-fn byte_chunks_32(x: u32) -> [u8; 4] {
+mod any;
+#[cfg(any(feature = "simd", feature = "portable-simd"))]
+mod dispatch;
+mod double;
+mod entropy;
+mod entry;
+mod hasher;
+mod iter;
+mod key;
+pub mod kmer;
+mod multiset;
+mod nibble;
+#[cfg(feature = "parallel")]
+mod parallel;
+mod partition;
+mod perm;
+#[cfg(feature = "alloc")]
+mod poly;
+#[cfg(feature = "portable-simd")]
+mod portable_simd;
+mod prefix;
+mod premix;
+mod probe;
+pub mod reference;
+mod rendezvous;
+#[cfg(feature = "alloc")]
+mod ring;
+mod rng;
+mod sampler;
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+mod simd;
+#[cfg(feature = "alloc")]
+mod simple16;
+#[cfg(feature = "alloc")]
+mod sketch;
+mod splitmix;
+mod tabperm;
+mod xoshiro;
+
+pub use any::{AnyTab32, AnyTab64, Kind};
+#[cfg(any(feature = "simd", feature = "portable-simd"))]
+pub use dispatch::{batch_kernel, force_scalar, BatchKernel};
+pub use double::Tab32Double;
+#[cfg(feature = "alloc")]
+pub use double::Tab64Double;
+pub use entropy::EntropySource;
+#[cfg(feature = "getrandom")]
+pub use entropy::{EntropyError, OsEntropy};
+pub use hasher::{Tab32BuildHasher, Tab32Hasher, Tab64BuildHasher, Tab64Hasher};
+#[cfg(feature = "std")]
+pub use hasher::{TabRandomState, TabRandomStateHasher};
+pub use iter::{Hashed, TabHashIterExt, WithHashes};
+pub use key::TabKey;
+pub use multiset::MultisetFingerprint;
+pub use nibble::Tab32Nibble;
+pub use partition::Partitioner;
+pub use perm::{Perm32, PermDomain};
+#[cfg(feature = "alloc")]
+pub use poly::TabPoly64;
+pub use prefix::PrefixHasher;
+pub use premix::Tab64Premixed;
+pub use reference::{self_check, SelfCheckError};
+pub use rendezvous::NoNodesError;
+#[cfg(feature = "alloc")]
+pub use ring::{ConsistentRing, RingError};
+pub use rng::TabRng;
+pub use sampler::{RateError, StableSampler};
+#[cfg(feature = "alloc")]
+pub use simple16::{Tab32Simple16, Tab64Simple16};
+#[cfg(feature = "std")]
+pub use sketch::Icws;
+#[cfg(feature = "alloc")]
+pub use sketch::{
+    lsh_bands, lsh_candidates, AmsSketch, BottomK, CountMinSketch, HyperLogLog, MergeError,
+    MinHash, OnePermMinHash,
+};
+#[cfg(feature = "derive")]
+pub use tab_hash_derive::TabKey;
+pub use tabperm::{Tab32Tab1Perm, Tab32TabPerm, Tab64Tab1Perm, Tab64TabPerm};
+
+#[cfg(feature = "net")]
+use core::net::{Ipv4Addr, Ipv6Addr, SocketAddrV4};
+use core::num::{NonZeroU32, NonZeroU64};
+#[cfg(feature = "getrandom")]
+use entropy::EntropyReader;
+use entry::TableEntry;
+#[cfg(feature = "rand")]
+use rand::distributions::{Distribution, Standard};
+#[cfg(feature = "rand")]
+use rand::Rng;
+use rand_chacha::ChaCha20Rng;
+use rand_core::{RngCore, SeedableRng};
+use splitmix::{mix64, SplitMix64};
+use xoshiro::Xoshiro256StarStar;
+
+/// Common interface of all tabulation hash functions.
+///
+/// Use this trait to write code that works with any of the hash functions in this crate:
+///
+/// ```rust
+/// use tab_hash::{Tab64Simple, Tab64Twisted, TabHash};
+///
+/// fn hash_all<H: TabHash<Key = u64>>(h: &H, keys: &[u64]) -> Vec<u64> {
+///     keys.iter().map(|k| h.hash(*k)).collect()
+/// }
+///
+/// let keys = [1, 2, 3];
+/// assert_eq!(hash_all(&Tab64Simple::new_with_seed(42), &keys).len(), 3);
+/// assert_eq!(hash_all(&Tab64Twisted::new_with_seed(42), &keys).len(), 3);
+/// ```
+pub trait TabHash: Sized {
+    /// Type of the keys, which is also the type of the hash values.
+    type Key: Copy + Eq + core::hash::Hash + core::fmt::Debug;
+
+    /// Type of the table entries.
+    type Entry: Copy + PartialEq + core::fmt::Debug;
+
+    /// Compute the hash value of a key.
+    fn hash(&self, key: Self::Key) -> Self::Key;
+
+    /// Create a new hash function with a random table.
+    #[cfg(feature = "getrandom")]
+    fn random() -> Self;
+
+    /// Create a new hash function with a table derived from a seed.
+    fn new_with_seed(seed: u64) -> Self;
+
+    /// Get the id of this hash function, a 64-bit digest of its table.
+    fn id(&self) -> u64;
+
+    /// Export the table of this hash function as a nested vector of columns.
+    #[cfg(feature = "alloc")]
+    fn to_vec(&self) -> Vec<Vec<Self::Entry>>;
+
+    /// Create a new hash function from a nested vector of columns.
+    ///
+    /// # Panics
+    /// If the number of columns or the number of entries of a column is wrong.
+    #[cfg(feature = "alloc")]
+    fn from_vec(table_data: Vec<Vec<Self::Entry>>) -> Self;
+}
+
+/// Object safe interface of hash functions for 32-bit keys.
+///
+/// Use it to choose between simple and twisted tabulation at runtime,
+/// e.g. with a `Box<dyn Hash32Fn + Send + Sync>`.
+pub trait Hash32Fn {
+    /// Compute the hash value of a 32-bit key.
+    fn hash32(&self, x: u32) -> u32;
+}
+
+/// Object safe interface of hash functions for 64-bit keys.
+///
+/// Use it to choose between simple and twisted tabulation at runtime,
+/// e.g. with a `Box<dyn Hash64Fn + Send + Sync>`.
+pub trait Hash64Fn {
+    /// Compute the hash value of a 64-bit key.
+    fn hash64(&self, x: u64) -> u64;
+}
+
+/// A finalizer mixing the bits of a 32-bit state, e.g. the last step of a streaming hasher.
+///
+/// This replaces avalanche steps like the `fmix32` function of MurmurHash3 with
+/// a tabulation lookup. It is implemented by `Tab32Simple` and `Tab32Twisted`,
+/// and by references and boxes of mixers, so a `&Tab32Simple` can be passed
+/// wherever a mixer is expected.
+///
+/// Unlike `fmix32`, the mixers are not guaranteed to be bijective: random tables
+/// almost surely map some distinct states to the same value. Only special tables are
+/// bijections, e.g. tables in which column `i` only sets byte `i` and contains each
+/// byte value once. Use `Perm32` where a permutation is required.
+pub trait Mixer32 {
+    /// Mix the bits of a 32-bit state.
+    fn mix(&self, x: u32) -> u32;
+}
+
+/// A finalizer mixing the bits of a 64-bit state, e.g. the last step of a streaming hasher.
+///
+/// This replaces avalanche steps like the `fmix64` function of MurmurHash3 with
+/// a tabulation lookup. It is implemented by `Tab64Simple` and `Tab64Twisted`,
+/// and by references and boxes of mixers, so a `&Tab64Simple` can be passed
+/// wherever a mixer is expected.
+///
+/// Like for `Mixer32`, the mixers are not guaranteed to be bijective.
+pub trait Mixer64 {
+    /// Mix the bits of a 64-bit state.
+    fn mix(&self, x: u64) -> u64;
+}
+
+impl<M: Mixer32 + ?Sized> Mixer32 for &M {
+    fn mix(&self, x: u32) -> u32 {
+        (**self).mix(x)
+    }
+}
+
+impl<M: Mixer64 + ?Sized> Mixer64 for &M {
+    fn mix(&self, x: u64) -> u64 {
+        (**self).mix(x)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<M: Mixer32 + ?Sized> Mixer32 for Box<M> {
+    fn mix(&self, x: u32) -> u32 {
+        (**self).mix(x)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<M: Mixer64 + ?Sized> Mixer64 for Box<M> {
+    fn mix(&self, x: u64) -> u64 {
+        (**self).mix(x)
+    }
+}
+
+/// Create a generator for random tables, seeded from the entropy source of the operating system.
+///
+/// # Panics
+/// If the operating system fails to provide entropy.
+#[cfg(feature = "getrandom")]
+fn os_seeded_rng() -> Xoshiro256StarStar {
+    match Xoshiro256StarStar::from_entropy(&mut OsEntropy) {
+        Ok(rng) => rng,
+        Err(e) => panic!("could not obtain entropy for a random table: {}", e),
+    }
+}
+
+/// Compute a 64-bit digest of a table.
+///
+/// All entries are split into 64-bit words in table order. Starting from 0,
+/// each word is XORed into the state, which is then mixed with the SplitMix64
+/// finalizer. Finally, the number of words is XORed in and mixed once more.
+fn table_digest<T: TableEntry>(columns: &[[T; 256]]) -> u64 {
+    let mut words = TableWords::default();
+    for column in columns.iter() {
+        for entry in column.iter() {
+            entry.for_each_word(|w| words.push(w));
+        }
+    }
+    words.digest()
+}
+
+/// State of `table_digest`, for digests of tables with several parts
+#[derive(Default)]
+struct TableWords {
+    h: u64,
+    words: u64,
+}
+
+impl TableWords {
+    /// Mix the next 64-bit word into the state.
+    fn push(&mut self, w: u64) {
+        self.h = mix64(self.h ^ w);
+        self.words += 1;
+    }
+
+    /// Mix in the number of words to get the digest.
+    fn digest(&self) -> u64 {
+        mix64(self.h ^ self.words)
+    }
+}
+
+/// Create a ChaCha20 generator for the child `index` of a table.
+///
+/// The 32-byte key consists of the first four outputs of a SplitMix64
+/// generator seeded with the table digest (little endian).
+/// The child index selects the ChaCha20 stream.
+fn child_rng<T: TableEntry>(columns: &[[T; 256]], index: u64) -> ChaCha20Rng {
+    let mut seeder = SplitMix64::new(table_digest(columns));
+    let mut key = [0_u8; 32];
+    for word in key.chunks_mut(8) {
+        word.copy_from_slice(&seeder.next_u64().to_le_bytes());
+    }
+    let mut rng = ChaCha20Rng::from_seed(key);
+    rng.set_stream(index);
+    rng
+}
+
+/// Replace column `i` of a table with entries drawn from `rng`.
+///
+/// # Panics
+/// If `i` is not a valid column index.
+fn reseed_column_from_rng<T: TableEntry, R: RngCore + ?Sized>(
+    columns: &mut [[T; 256]],
+    i: usize,
+    rng: &mut R,
+) {
+    assert!(
+        i < columns.len(),
+        "column index {} out of range for a table with {} columns",
+        i,
+        columns.len()
+    );
+    for entry in columns[i].iter_mut() {
+        *entry = T::from_rng(rng);
+    }
+}
+
+/// Split a 64-bit hash value into a bucket in `0..width` (upper 63 bits) and a sign (lowest bit).
+fn bucket_and_sign(h: u64, width: usize) -> (usize, i8) {
+    let bucket = mul_high_64(h & !1, width as u64) as usize;
+    (bucket, 1 - 2 * (h & 1) as i8)
+}
+
+/// Check that fingerprints have between 1 and 16 bits
+fn check_fingerprint_bits(bits: u8) -> u8 {
+    assert!(
+        (1..=16).contains(&bits),
+        "fingerprints need between 1 and 16 bits, not {}",
+        bits
+    );
+    bits
+}
+
+/// Split a 64-bit hash value into a register index (top `p` bits) and the rank of the other bits.
+fn hll_parts(h: u64, p: u8) -> (usize, u8) {
+    assert!(
+        (4..=18).contains(&p),
+        "the precision of HyperLogLog has to be in 4..=18, not {}",
+        p
+    );
+    let index = (h >> (64 - p)) as usize;
+    let rank = (h << p).leading_zeros().min(64 - u32::from(p)) + 1;
+    (index, rank as u8)
+}
+
+/// Map a 64-bit hash value to the midpoint of its interval among `2^52` in `(0, 1)`
+#[cfg(feature = "std")]
+fn open_unit(h: u64) -> f64 {
+    ((h >> 12) as f64 + 0.5) * (1.0 / (1_u64 << 52) as f64)
+}
+
+/// Compute the high 64 bits of the 128-bit product of `a` and `b`.
+///
+/// On 64-bit targets, this is a single widening multiplication. Other targets
+/// often lack a fast 64x64->128 bit multiplication, so they use `mul_high_64_portable`.
+#[cfg(target_pointer_width = "64")]
+const fn mul_high_64(a: u64, b: u64) -> u64 {
+    ((a as u128 * b as u128) >> 64) as u64
+}
+
+/// Compute the high 64 bits of the 128-bit product of `a` and `b`.
+#[cfg(not(target_pointer_width = "64"))]
+const fn mul_high_64(a: u64, b: u64) -> u64 {
+    mul_high_64_portable(a, b)
+}
+
+/// Compute the high 64 bits of the 128-bit product of `a` and `b` using 32-bit halves.
+///
+/// With `a = a1 * 2^32 + a0` and `b = b1 * 2^32 + b0`, the product is
+/// `a1 * b1 * 2^64 + (a1 * b0 + a0 * b1) * 2^32 + a0 * b0`,
+/// where all partial products fit into 64 bits.
+#[cfg_attr(target_pointer_width = "64", allow(dead_code))]
+const fn mul_high_64_portable(a: u64, b: u64) -> u64 {
+    let (a0, a1) = (a & 0xFFFF_FFFF, a >> 32);
+    let (b0, b1) = (b & 0xFFFF_FFFF, b >> 32);
+    let low = a0 * b0;
+    let middle = a1 * b0 + (low >> 32);
+    let middle2 = a0 * b1 + (middle & 0xFFFF_FFFF);
+    a1 * b1 + (middle >> 32) + (middle2 >> 32)
+}
+
+/// Read a little-endian integer of `width` bytes starting at `offset`
+const fn read_le(bytes: &[u8], offset: usize, width: usize) -> u128 {
+    let mut x = 0;
+    let mut k = width;
+    while k > 0 {
+        k -= 1;
+        x = (x << 8) | bytes[offset + k] as u128;
+    }
+    x
+}
+
+/// Split up a 16bit number into 8bit chunks.
+///
+/// Chunks are in little-endian order, i.e. chunk `i` holds bits `8 * i` to `8 * i + 7`
+/// and is looked up in column `i` of a table. This is the same as `x.to_le_bytes()`.
+pub const fn byte_chunks_16(x: u16) -> [u8; 2] {
+    [(x & 0x00FF) as u8, ((x & 0xFF00) >> 8) as u8]
+}
+
+/// Split up a 32bit number into 8bit chunks.
+///
+/// Chunks are in little-endian order, i.e. chunk `i` holds bits `8 * i` to `8 * i + 7`
+/// and is looked up in column `i` of a table. This is the same as `x.to_le_bytes()`.
+pub const fn byte_chunks_32(x: u32) -> [u8; 4] {
     [
         (x & 0x0000_00FF) as u8,
         ((x & 0x0000_FF00) >> 8) as u8,
@@ -50,8 +579,11 @@ fn byte_chunks_32(x: u32) -> [u8; 4] {
     ]
 }
 
-/// Split up a 64bit number into 8bit chunks
-fn byte_chunks_64(x: u64) -> [u8; 8] {
+/// Split up a 64bit number into 8bit chunks.
+///
+/// Chunks are in little-endian order, i.e. chunk `i` holds bits `8 * i` to `8 * i + 7`
+/// and is looked up in column `i` of a table. This is the same as `x.to_le_bytes()`.
+pub const fn byte_chunks_64(x: u64) -> [u8; 8] {
     [
         (x & 0x0000_0000_0000_00FF) as u8,
         ((x & 0x0000_0000_0000_FF00) >> 8) as u8,
@@ -64,91 +596,3669 @@ fn byte_chunks_64(x: u64) -> [u8; 8] {
     ]
 }
 
-/// A universal hash function for 32-bit integers using simple tabulation.
+/// Number of keys copied to the stack at a time by `hash_in_place`
+const IN_PLACE_BLOCK: usize = 64;
+
+/// Overwrite keys with their hash values, hashing copies of blocks of keys with `hash_into`
+fn hash_in_place_with<K: Copy + Default, F: Fn(&[K], &mut [K])>(keys: &mut [K], hash_into: F) {
+    let mut block = [K::default(); IN_PLACE_BLOCK];
+    for chunk in keys.chunks_mut(IN_PLACE_BLOCK) {
+        let block = &mut block[..chunk.len()];
+        block.copy_from_slice(chunk);
+        hash_into(block, chunk);
+    }
+}
+
+/// Split up a 128bit number into 8bit chunks.
+///
+/// Chunks are in little-endian order, i.e. chunk `i` holds bits `8 * i` to `8 * i + 7`
+/// and is looked up in column `i` of a table. This is the same as `x.to_le_bytes()`.
+pub const fn byte_chunks_128(x: u128) -> [u8; 16] {
+    x.to_le_bytes()
+}
+
+/// Reassemble a 16bit number from its 8bit chunks, the inverse of `byte_chunks_16`.
+pub const fn from_byte_chunks_16(chunks: [u8; 2]) -> u16 {
+    u16::from_le_bytes(chunks)
+}
+
+/// Reassemble a 32bit number from its 8bit chunks, the inverse of `byte_chunks_32`.
+pub const fn from_byte_chunks_32(chunks: [u8; 4]) -> u32 {
+    u32::from_le_bytes(chunks)
+}
+
+/// Reassemble a 64bit number from its 8bit chunks, the inverse of `byte_chunks_64`.
+pub const fn from_byte_chunks_64(chunks: [u8; 8]) -> u64 {
+    u64::from_le_bytes(chunks)
+}
+
+/// Reassemble a 128bit number from its 8bit chunks, the inverse of `byte_chunks_128`.
+pub const fn from_byte_chunks_128(chunks: [u8; 16]) -> u128 {
+    u128::from_le_bytes(chunks)
+}
+
+/// Get the canonical bit pattern of a 32bit floating point number.
+///
+/// `-0.0` is mapped to the bits of `0.0` and all NaNs (of any sign and payload)
+/// to the positive quiet NaN `0x7FC0_0000`. All other numbers, including
+/// infinities, keep their bit pattern. Hash `x.to_bits()` directly to distinguish them.
+pub fn canonical_f32_bits(x: f32) -> u32 {
+    if x.is_nan() {
+        0x7FC0_0000
+    } else if x == 0.0 {
+        0
+    } else {
+        x.to_bits()
+    }
+}
+
+/// Get the canonical bit pattern of a 64bit floating point number.
+///
+/// `-0.0` is mapped to the bits of `0.0` and all NaNs (of any sign and payload)
+/// to the positive quiet NaN `0x7FF8_0000_0000_0000`. All other numbers,
+/// including infinities, keep their bit pattern. Hash `x.to_bits()` directly to distinguish them.
+pub fn canonical_f64_bits(x: f64) -> u64 {
+    if x.is_nan() {
+        0x7FF8_0000_0000_0000
+    } else if x == 0.0 {
+        0
+    } else {
+        x.to_bits()
+    }
+}
+
+/// Pack a byte string of at most 8 bytes into a 64bit number.
+///
+/// The bytes are the little-endian chunks of the number, i.e. byte `i` of the
+/// string is chunk `i`. Strings shorter than 8 bytes are right-padded with the
+/// sentinel byte `0xF8 | len`, e.g. `"ab"` is packed as the chunks
+/// `[0x61, 0x62, 0xFA, 0xFA, 0xFA, 0xFA, 0xFA, 0xFA]`.
+///
+/// Strings shorter than 8 bytes never collide with each other, so trailing zero bytes
+/// are significant. A string of 8 bytes collides with a shorter string if it ends with
+/// the respective sentinel bytes. Bytes `0xF8` to `0xFF` do not occur in UTF-8,
+/// so packing is injective on `str`s.
+///
+/// # Panics
+/// If the string is longer than 8 bytes.
+pub fn pack_short_bytes(bytes: &[u8]) -> u64 {
+    assert!(
+        bytes.len() <= 8,
+        "short keys can have at most 8 bytes, got {}",
+        bytes.len()
+    );
+    let mut chunks = [0xF8 | bytes.len() as u8; 8];
+    chunks[..bytes.len()].copy_from_slice(bytes);
+    from_byte_chunks_64(chunks)
+}
+
+/// Count the bits in which two 64bit fingerprints differ, e.g. SimHash fingerprints.
+pub const fn hamming_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+/// Combine weighted 64bit hash values into a SimHash fingerprint
+fn simhash_of(hashes: impl Iterator<Item = (u64, f32)>) -> u64 {
+    let mut sums = [0_f64; 64];
+    for (h, weight) in hashes {
+        if weight.is_nan() {
+            continue;
+        }
+        for (bit, sum) in sums.iter_mut().enumerate() {
+            if (h >> bit) & 1 == 1 {
+                *sum += f64::from(weight);
+            } else {
+                *sum -= f64::from(weight);
+            }
+        }
+    }
+    sums.iter()
+        .enumerate()
+        .filter(|(_, sum)| **sum > 0.0)
+        .fold(0, |fingerprint, (bit, _)| fingerprint | (1 << bit))
+}
+
+/// Types of the table entries and hash values of `TabSimple` and `TabTwisted`.
+///
+/// This trait is implemented for `u16`, `u32`, `u64` and `u128`, and cannot be implemented
+/// outside of this crate.
+pub trait TabValue:
+    TableEntry
+    + Copy
+    + Default
+    + Eq
+    + core::hash::Hash
+    + core::fmt::Debug
+    + core::ops::BitXor<Output = Self>
+    + core::ops::BitXorAssign
+{
+}
+
+impl TabValue for u16 {}
+impl TabValue for u32 {}
+impl TabValue for u64 {}
+impl TabValue for u128 {}
+
+/// Implement the table plumbing shared by `TabSimple` and `TabTwisted`.
+///
+/// This generates the constructors, the conversions of the table, serialization,
+/// sampling, `Default` and `Debug` for a struct with a single field
+/// `table: [[V; 256]; CHARS]`. `$name_of` maps the size of `V` in bytes and `CHARS`
+/// to the name of the alias shown by `Debug`.
+macro_rules! table_core {
+    ($ty:ident, $name_of:ident) => {
+        impl<V: TabValue, const CHARS: usize> $ty<V, CHARS> {
+            /// Create a new hash function with a random table.
+            #[cfg(feature = "getrandom")]
+            pub fn new() -> Self {
+                $ty::from_rng(&mut os_seeded_rng())
+            }
+
+            /// Create a new hash function with a table read directly from OS entropy.
+            ///
+            /// The table is filled from the entropy source of the operating system
+            /// without any user space random number generator in between.
+            /// Entries are read as little-endian integers from consecutive bytes,
+            /// column by column, each column from entry 0 to 255.
+            #[cfg(feature = "getrandom")]
+            pub fn new_from_os_entropy() -> Result<Self, EntropyError> {
+                let mut source = OsEntropy;
+                let mut reader = EntropyReader::new(&mut source);
+                let mut table = [[V::default(); 256]; CHARS];
+                for column in table.iter_mut() {
+                    for entry in column.iter_mut() {
+                        *entry = reader.read_entry()?;
+                    }
+                }
+                Ok($ty { table })
+            }
+
+            /// Create a new hash function seeded from an entropy source.
+            ///
+            /// A 256-bit seed is read from `source` and expanded into the table
+            /// using a xoshiro256** generator, just like `new` does with the
+            /// entropy source of the operating system.
+            pub fn new_from_entropy<E: EntropySource + ?Sized>(
+                source: &mut E,
+            ) -> Result<Self, E::Error> {
+                let mut rng = Xoshiro256StarStar::from_entropy(source)?;
+                Ok($ty::from_rng(&mut rng))
+            }
+
+            /// Create a new hash function with a table derived from a seed.
+            ///
+            /// The same seed always yields the same table, see the crate
+            /// documentation for how the seed is expanded.
+            pub fn new_with_seed(seed: u64) -> Self {
+                $ty::from_rng(&mut SplitMix64::new(seed))
+            }
+
+            /// Create a new hash function from a 32-byte seed.
+            ///
+            /// The seed is used as ChaCha20 key to expand it into the table,
+            /// see the crate documentation for details.
+            pub fn from_seed_bytes(seed: [u8; 32]) -> Self {
+                $ty::from_rng(&mut ChaCha20Rng::from_seed(seed))
+            }
+
+            /// Derive the child hash function `index` from this hash function.
+            ///
+            /// Children are deterministic, i.e. a parent with the same table always
+            /// yields the same child for an index, and distinct indices yield
+            /// distinct children. The table of a child is drawn from a ChaCha20
+            /// generator keyed with a digest of the parent table, using `index`
+            /// as stream, see the crate documentation for details.
+            pub fn derive_child(&self, index: u64) -> Self {
+                $ty::from_rng(&mut child_rng(&self.table[..], index))
+            }
+
+            /// Get the id of this hash function, a 64-bit digest of its table.
+            ///
+            /// Equal tables always have the same id, across versions, platforms
+            /// and serialization round trips. See the crate documentation for the algorithm.
+            pub fn id(&self) -> u64 {
+                table_digest(&self.table[..])
+            }
+
+            /// Create a new hash function with a table drawn from `rng`.
+            ///
+            /// See the crate documentation for the order in which entries are drawn.
+            #[cfg(feature = "rand")]
+            pub fn new_with_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+                $ty::from_rng(rng)
+            }
+
+            /// Generate a table drawing entries from `rng` column by column
+            fn from_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+                let mut table = [[V::default(); 256]; CHARS];
+                for column in table.iter_mut() {
+                    for entry in column.iter_mut() {
+                        *entry = TableEntry::from_rng(rng);
+                    }
+                }
+                $ty { table }
+            }
+
+            /// Replace the table of this hash function with a new random table.
+            ///
+            /// The new table is generated completely before it replaces the old one.
+            #[cfg(feature = "getrandom")]
+            pub fn reseed(&mut self) {
+                *self = $ty::new();
+            }
+
+            /// Replace the table of this hash function with a table drawn from `rng`.
+            ///
+            /// Entries are drawn in the same order as for `new_with_rng`.
+            #[cfg(feature = "rand")]
+            pub fn reseed_with_rng<R: RngCore + ?Sized>(&mut self, rng: &mut R) {
+                *self = $ty::from_rng(rng);
+            }
+
+            /// Replace the table of this hash function with a table derived from a seed.
+            ///
+            /// Afterwards, this hash function is identical to `new_with_seed(seed)`.
+            pub fn reseed_from_seed(&mut self, seed: u64) {
+                *self = $ty::new_with_seed(seed);
+            }
+
+            /// Replace column `i` of the table with new random entries.
+            ///
+            /// All other columns are left untouched.
+            ///
+            /// # Panics
+            /// If `i` is not a valid column index, i.e. at least the number of bytes of a key.
+            #[cfg(feature = "getrandom")]
+            pub fn reseed_column(&mut self, i: usize) {
+                reseed_column_from_rng(&mut self.table[..], i, &mut os_seeded_rng());
+            }
+
+            /// Replace column `i` of the table with entries derived from a seed.
+            ///
+            /// The new column is identical to the first column of `new_with_seed(seed)`.
+            /// All other columns are left untouched.
+            ///
+            /// # Panics
+            /// If `i` is not a valid column index, i.e. at least the number of bytes of a key.
+            pub fn reseed_column_from_seed(&mut self, i: usize, seed: u64) {
+                reseed_column_from_rng(&mut self.table[..], i, &mut SplitMix64::new(seed));
+            }
+
+            /// Export the table of this hash function as a nested vector.
+            #[cfg(feature = "alloc")]
+            pub fn to_vec(&self) -> Vec<Vec<V>> {
+                self.table.iter().map(|column| column.to_vec()).collect()
+            }
+
+            /// Create a new hash function from a nested vector.
+            ///
+            /// # Panics
+            /// If there are not `CHARS` columns of 256 entries each.
+            #[cfg(feature = "alloc")]
+            pub fn from_vec(table_data: Vec<Vec<V>>) -> Self {
+                let mut table = [[V::default(); 256]; CHARS];
+                assert_eq!(table_data.len(), CHARS);
+                for (column, values) in table.iter_mut().zip(table_data.iter()) {
+                    assert_eq!(values.len(), 256);
+                    column.copy_from_slice(values);
+                }
+                $ty { table }
+            }
+
+            /// Create a new hash function with a given table.
+            ///
+            /// This is a `const fn`, i.e. hash functions with a fixed table can be
+            /// stored in a `static` without any initialization at runtime.
+            pub const fn with_table(table: [[V; 256]; CHARS]) -> Self {
+                $ty { table }
+            }
+
+            /// Get the table used by this hash function.
+            pub const fn get_table(&self) -> [[V; 256]; CHARS] {
+                self.table
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<V: TabValue + Serialize, const CHARS: usize> Serialize for $ty<V, CHARS> {
+            fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                _VecTable {
+                    table: self.to_vec(),
+                }
+                .serialize(s)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de, V: TabValue + Deserialize<'de>, const CHARS: usize> Deserialize<'de>
+            for $ty<V, CHARS>
+        {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                let raw = RawTable::<V>::deserialize(deserializer)?;
+                raw.check(CHARS).map_err(serde::de::Error::custom)?;
+                Ok($ty::from_vec(raw.table))
+            }
+        }
+
+        #[cfg(feature = "rand")]
+        impl<V: TabValue, const CHARS: usize> Distribution<$ty<V, CHARS>> for Standard {
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $ty<V, CHARS> {
+                $ty::from_rng(rng)
+            }
+        }
+
+        #[cfg(feature = "getrandom")]
+        impl<V: TabValue, const CHARS: usize> Default for $ty<V, CHARS> {
+            /// Create a new hash function with a random table, just like `new`.
+            ///
+            /// Note that the default is randomized, i.e. every call yields a different
+            /// hash function. Use `new_with_seed` for a deterministic hash function.
+            fn default() -> Self {
+                $ty::new()
+            }
+        }
+
+        impl<V: TabValue, const CHARS: usize> core::fmt::Debug for $ty<V, CHARS> {
+            /// Show the id of the hash function as fingerprint instead of all entries.
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                let fingerprint = self.id();
+                let name = $name_of(core::mem::size_of::<V>(), CHARS);
+                f.debug_struct(name)
+                    .field("fingerprint", &format_args!("{:#018x}", fingerprint))
+                    .finish()
+            }
+        }
+    };
+}
+
+/// Serialized form of the tables of `TabSimple` and `TabTwisted`
+#[cfg(feature = "serde")]
+#[derive(Clone, Serialize)]
+struct _VecTable<V> {
+    table: Vec<Vec<V>>,
+}
+
+/// Unvalidated deserialized form of the tables of `TabSimple` and `TabTwisted`
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct RawTable<V> {
+    table: Vec<Vec<V>>,
+}
+
+#[cfg(feature = "serde")]
+impl<V> RawTable<V> {
+    /// Check that the table has `chars` columns of 256 entries
+    fn check(&self, chars: usize) -> Result<(), alloc::string::String> {
+        if self.table.len() != chars || self.table.iter().any(|column| column.len() != 256) {
+            return Err(alloc::format!(
+                "a table needs {} columns of 256 entries",
+                chars
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Simple tabulation with a table of `CHARS` columns of 256 entries of type `V`.
+///
+/// This is the table plumbing shared by the simple tabulation hash functions, which are
+/// type aliases for a value type and a number of characters:
+///
+/// | type | `V` | `CHARS` | keys |
+/// |------|-----|---------|------|
+/// | `Tab16Simple` | `u16` | 2 | `u16` |
+/// | `Tab32Simple` | `u32` | 4 | `u32` |
+/// | `Tab32To64Simple` | `u64` | 4 | `u32` |
+/// | `Tab48Simple` | `u64` | 6 | 48-bit `u64` |
+/// | `Tab64Simple` | `u64` | 8 | `u64` |
+/// | `Tab64To32Simple` | `u32` | 8 | `u64` |
+/// | `Tab128Simple` | `u128` | 16 | `u128` |
+///
+/// The width of the hash values is decoupled from the width of the keys, as the
+/// `To` variants show. A key is split into `CHARS` bytes by the `hash` method of
+/// each alias, which passes them to `hash_chars` as a `[u8; CHARS]`, so a key
+/// width that does not match the table is rejected at compile time.
+///
+/// All aliases are serialized as a struct with the single field `table`,
+/// a list of `CHARS` columns of 256 entries each.
 ///
 /// Usage:
 /// ```rust
-/// use tab_hash::Tab32Simple;
+/// use tab_hash::{Tab32Simple, TabSimple};
 ///
-/// let keys = vec![0, 8, 15, 47, 11];
-/// let simple = Tab32Simple::new();
-/// for k in keys {
-///     println!("{}", simple.hash(k));
-/// }
+/// let simple: TabSimple<u32, 4> = Tab32Simple::new_with_seed(42);
+/// assert_eq!(simple.hash_chars([1, 2, 3, 4]), simple.hash(0x0403_0201));
 /// ```
-#[derive(Clone, Deserialize)]
-pub struct Tab32Simple {
-    #[serde(deserialize_with = "tab32simple_from_vec")]
-    table: [[u32; 256]; 4],
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct TabSimple<V, const CHARS: usize> {
+    table: [[V; 256]; CHARS],
 }
 
-impl Tab32Simple {
-    /// Create a new simple tabulation hash function with a random table.
-    pub fn new() -> Self {
-        Tab32Simple {
-            table: Tab32Simple::initialize_table(),
+table_core!(TabSimple, tab_simple_name);
+
+impl<V: TabValue, const CHARS: usize> TabSimple<V, CHARS> {
+    /// Combine two hash functions into one computing the XOR of their hash values.
+    ///
+    /// Since simple tabulation hash values are the XOR of table entries, the table of
+    /// the result is the entrywise XOR of both tables, so
+    /// `a.xor_compose(&b).hash(x) == a.hash(x) ^ b.hash(x)` for every key,
+    /// and the result costs no more than `a` to evaluate.
+    /// If either table is random and independent of the other, so is the result.
+    pub fn xor_compose(&self, other: &Self) -> Self {
+        let mut table = self.table;
+        for (column, other_column) in table.iter_mut().zip(other.table.iter()) {
+            for (entry, other_entry) in column.iter_mut().zip(other_column.iter()) {
+                *entry ^= *other_entry;
+            }
         }
+        TabSimple::with_table(table)
     }
 
-    /// Create a new simple tabulation hash function with a random table.
-    pub fn to_vec(&self) -> Vec<Vec<u32>> {
-        let mut vec = Vec::with_capacity(4);
-        for col in self.table.iter() {
-            vec.push(col.to_vec());
+    /// Compute the hash value of a key given by its characters.
+    ///
+    /// Character `i` is looked up in column `i`, and the hash value is the XOR of
+    /// the entries. The `hash` method of each alias splits its keys into bytes from
+    /// the least significant one, e.g. `Tab32Simple::hash(x)` is
+    /// `hash_chars(byte_chunks_32(x))`.
+    pub fn hash_chars(&self, chars: [u8; CHARS]) -> V {
+        let mut h = V::default(); // initialize hash values as 0
+
+        for (column, c) in self.table.iter().zip(chars.iter()) {
+            h ^= column[*c as usize];
         }
-        vec
+        h
+    }
+
+    /// Hash the keys of a slice into `out`, splitting keys into characters with `chars`.
+    ///
+    /// Keys are hashed in blocks of four, looking up a column for all four keys
+    /// before moving on to the next column, so the lookups of different keys
+    /// are independent and can be in flight at the same time.
+    fn hash_batch<K: Copy, F: Fn(K) -> [u8; CHARS]>(&self, keys: &[K], out: &mut [V], chars: F) {
+        assert_eq!(
+            keys.len(),
+            out.len(),
+            "the output needs one slot for every key"
+        );
+        let mut key_blocks = keys.chunks_exact(4);
+        let mut out_blocks = out.chunks_exact_mut(4);
+        for (k, o) in (&mut key_blocks).zip(&mut out_blocks) {
+            let c = [chars(k[0]), chars(k[1]), chars(k[2]), chars(k[3])];
+            let mut h = [V::default(); 4];
+            for (i, column) in self.table.iter().enumerate() {
+                h[0] ^= column[c[0][i] as usize];
+                h[1] ^= column[c[1][i] as usize];
+                h[2] ^= column[c[2][i] as usize];
+                h[3] ^= column[c[3][i] as usize];
+            }
+            o.copy_from_slice(&h);
+        }
+        for (k, o) in key_blocks
+            .remainder()
+            .iter()
+            .zip(out_blocks.into_remainder())
+        {
+            *o = self.hash_chars(chars(*k));
+        }
+    }
+}
+
+/// Name of an alias of `TabSimple`, for `Debug`
+fn tab_simple_name(value_bytes: usize, chars: usize) -> &'static str {
+    match (value_bytes, chars) {
+        (2, 2) => "Tab16Simple",
+        (4, 4) => "Tab32Simple",
+        (8, 4) => "Tab32To64Simple",
+        (8, 6) => "Tab48Simple",
+        (8, 8) => "Tab64Simple",
+        (4, 8) => "Tab64To32Simple",
+        (16, 16) => "Tab128Simple",
+        _ => "TabSimple",
+    }
+}
+
+/// Twisted tabulation with a table of `CHARS` columns of 256 entries of type `V`.
+///
+/// This is the table plumbing shared by the twisted tabulation hash functions, which are
+/// type aliases for an entry type and a number of characters:
+///
+/// | type | `V` | `CHARS` | keys | hash values |
+/// |------|-----|---------|------|-------------|
+/// | `Tab32Twisted` | `u64` | 4 | `u32` | `u32` |
+/// | `Tab32To64Twisted` | `u128` | 4 | `u32` | `u64` |
+/// | `Tab64Twisted` | `u128` | 8 | `u64` | `u64` |
+///
+/// The first `CHARS - 1` characters of a key are looked up like for simple tabulation.
+/// The last character is XORed with the lowest byte of the XOR `h` of these entries,
+/// the twist, and looked up in the last column. The hash value of the aliases is the
+/// high half of the resulting entry, so the low half, which determines the twist,
+/// never shows up in a hash value.
+///
+/// All aliases are serialized as a struct with the single field `table`,
+/// a list of `CHARS` columns of 256 entries each.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::{Tab32Twisted, TabTwisted};
+///
+/// let twisted: TabTwisted<u64, 4> = Tab32Twisted::new_with_seed(42);
+/// println!("{}", twisted.hash(42));
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct TabTwisted<V, const CHARS: usize> {
+    table: [[V; 256]; CHARS],
+}
+
+table_core!(TabTwisted, tab_twisted_name);
+
+impl<V: TabValue, const CHARS: usize> TabTwisted<V, CHARS> {
+    /// Compute the entry of a key given by its characters, together with the twisted
+    /// last character
+    fn twisted_state(&self, chars: [u8; CHARS]) -> (V, u8) {
+        const { assert!(CHARS > 0, "twisted tabulation needs at least one character") };
+        let mut h = V::default(); // initialize hash values as 0
+        for (column, c) in self.table.iter().zip(chars.iter()).take(CHARS - 1) {
+            h ^= column[*c as usize];
+        }
+        // compute address for last chunk by XOring the lowest byte of the
+        // current hash value with the content of the last chunk of the key
+        let c = chars[CHARS - 1] ^ h.low_byte();
+        h ^= self.table[CHARS - 1][c as usize];
+        (h, c)
+    }
+
+    /// Hash the keys of a slice into `out`, splitting keys into characters with `chars`
+    /// and mapping the entries of the keys to hash values with `finish`.
+    ///
+    /// Keys are hashed in blocks of four, like `TabSimple::hash_batch`.
+    fn hash_batch<K: Copy, H, F: Fn(K) -> [u8; CHARS], G: Fn(V) -> H>(
+        &self,
+        keys: &[K],
+        out: &mut [H],
+        chars: F,
+        finish: G,
+    ) {
+        const { assert!(CHARS > 0, "twisted tabulation needs at least one character") };
+        assert_eq!(
+            keys.len(),
+            out.len(),
+            "the output needs one slot for every key"
+        );
+        let last = CHARS - 1;
+        let mut key_blocks = keys.chunks_exact(4);
+        let mut out_blocks = out.chunks_exact_mut(4);
+        for (k, o) in (&mut key_blocks).zip(&mut out_blocks) {
+            let c = [chars(k[0]), chars(k[1]), chars(k[2]), chars(k[3])];
+            let mut h = [V::default(); 4];
+            for (i, column) in self.table[..last].iter().enumerate() {
+                h[0] ^= column[c[0][i] as usize];
+                h[1] ^= column[c[1][i] as usize];
+                h[2] ^= column[c[2][i] as usize];
+                h[3] ^= column[c[3][i] as usize];
+            }
+            let column = &self.table[last];
+            for ((h, c), o) in h.iter_mut().zip(c.iter()).zip(o.iter_mut()) {
+                *h ^= column[(c[last] ^ h.low_byte()) as usize];
+                *o = finish(*h);
+            }
+        }
+        for (k, o) in key_blocks
+            .remainder()
+            .iter()
+            .zip(out_blocks.into_remainder())
+        {
+            *o = finish(self.twisted_state(chars(*k)).0);
+        }
+    }
+}
+
+/// Name of an alias of `TabTwisted`, for `Debug`
+fn tab_twisted_name(value_bytes: usize, chars: usize) -> &'static str {
+    match (value_bytes, chars) {
+        (8, 4) => "Tab32Twisted",
+        (16, 4) => "Tab32To64Twisted",
+        (16, 8) => "Tab64Twisted",
+        _ => "TabTwisted",
+    }
+}
+
+/// A universal hash function for 16-bit integers using simple tabulation.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::Tab16Simple;
+///
+/// let keys = vec![0, 8, 15, 47, 11];
+/// let simple = Tab16Simple::new_with_seed(42);
+/// for k in keys {
+///     println!("{}", simple.hash(k));
+/// }
+/// ```
+pub type Tab16Simple = TabSimple<u16, 2>;
+
+impl Tab16Simple {
+    /// Create a new simple tabulation hash function from the little-endian bytes of a table.
+    ///
+    /// Entries are read column by column, each column from entry 0 to 255,
+    /// as written by `to_le_bytes`. Combined with `include_bytes!`,
+    /// this embeds a table into the binary at compile time.
+    pub const fn from_le_bytes(bytes: &[u8; 1024]) -> Self {
+        let mut table = [[0; 256]; 2];
+        let mut i = 0;
+        while i < 2 * 256 {
+            table[i / 256][i % 256] = read_le(bytes, 2 * i, 2) as u16;
+            i += 1;
+        }
+        Tab16Simple { table }
+    }
+
+    /// Export the table of this hash function as little-endian bytes.
+    ///
+    /// This is the inverse of `from_le_bytes`.
+    pub fn to_le_bytes(&self) -> [u8; 1024] {
+        let mut bytes = [0; 1024];
+        for (chunk, entry) in bytes.chunks_mut(2).zip(self.table.iter().flatten()) {
+            chunk.copy_from_slice(&entry.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Compute simple tabulation hash value for a 16bit integer number.
+    pub fn hash(&self, x: u16) -> u16 {
+        self.hash_chars(byte_chunks_16(x))
+    }
+
+    /// Compute the hash value of a key of a custom type, see `TabKey`.
+    ///
+    /// This is equivalent to hashing the 16bit integer with the same byte chunks.
+    pub fn hash_key<K: TabKey<Chunks = [u8; 2]>>(&self, key: K) -> u16 {
+        self.hash(from_byte_chunks_16(key.chunks()))
+    }
+
+    /// Compute the hash value of a plain old data key of 2 bytes.
+    ///
+    /// The key is hashed like the 16bit integer with the same bytes in memory,
+    /// i.e. the integer obtained by transmuting the key (native byte order).
+    /// Keys of a different size are rejected at compile time.
+    #[cfg(feature = "bytemuck")]
+    pub fn hash_pod<T: bytemuck::Pod>(&self, key: &T) -> u16 {
+        const {
+            assert!(
+                core::mem::size_of::<T>() == 2,
+                "key size does not match the table"
+            )
+        };
+        let mut bytes = [0; 2];
+        bytes.copy_from_slice(bytemuck::bytes_of(key));
+        self.hash(u16::from_ne_bytes(bytes))
+    }
+
+    /// Borrow this hash function as a closure, e.g. for `Iterator::map`.
+    pub fn as_fn(&self) -> impl Fn(u16) -> u16 + '_ {
+        move |x| self.hash(x)
+    }
+
+    /// Turn this hash function into an owned closure, e.g. to move it into a thread.
+    pub fn into_fn(self) -> impl Fn(u16) -> u16 {
+        move |x| self.hash(x)
+    }
+}
+
+impl TabHash for Tab16Simple {
+    type Key = u16;
+    type Entry = u16;
+
+    fn hash(&self, key: u16) -> u16 {
+        Tab16Simple::hash(self, key)
+    }
+
+    #[cfg(feature = "getrandom")]
+    fn random() -> Self {
+        Tab16Simple::new()
+    }
+
+    fn new_with_seed(seed: u64) -> Self {
+        Tab16Simple::new_with_seed(seed)
+    }
+
+    fn id(&self) -> u64 {
+        Tab16Simple::id(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn to_vec(&self) -> Vec<Vec<u16>> {
+        Tab16Simple::to_vec(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn from_vec(table_data: Vec<Vec<u16>>) -> Self {
+        Tab16Simple::from_vec(table_data)
+    }
+}
+
+/// A universal hash function for 32-bit integers using simple tabulation.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::Tab32Simple;
+///
+/// let keys = vec![0, 8, 15, 47, 11];
+/// let simple = Tab32Simple::new_with_seed(42);
+/// for k in keys {
+///     println!("{}", simple.hash(k));
+/// }
+/// ```
+pub type Tab32Simple = TabSimple<u32, 4>;
+
+impl Tab32Simple {
+    /// Create a new simple tabulation hash function from the little-endian bytes of a table.
+    ///
+    /// Entries are read column by column, each column from entry 0 to 255,
+    /// as written by `to_le_bytes`. Combined with `include_bytes!`,
+    /// this embeds a table into the binary at compile time.
+    pub const fn from_le_bytes(bytes: &[u8; 4096]) -> Self {
+        let mut table = [[0; 256]; 4];
+        let mut i = 0;
+        while i < 4 * 256 {
+            table[i / 256][i % 256] = read_le(bytes, 4 * i, 4) as u32;
+            i += 1;
+        }
+        Tab32Simple { table }
+    }
+
+    /// Export the table of this hash function as little-endian bytes.
+    ///
+    /// This is the inverse of `from_le_bytes`.
+    pub fn to_le_bytes(&self) -> [u8; 4096] {
+        let mut bytes = [0; 4096];
+        for (chunk, entry) in bytes.chunks_mut(4).zip(self.table.iter().flatten()) {
+            chunk.copy_from_slice(&entry.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Compute simple tabulation hash value for a 32bit integer number.
+    pub fn hash(&self, x: u32) -> u32 {
+        self.hash_chars(byte_chunks_32(x))
+    }
+
+    /// Compute the hash values of a slice of keys into `out`.
+    ///
+    /// This is equivalent to `out[i] = hash(keys[i])` for every `i`, but faster for large
+    /// slices, since keys are hashed in blocks of four with interleaved lookups.
+    /// With the `simd` feature, keys are hashed in blocks of eight with AVX2 gathers
+    /// instead if the CPU supports them, and with the `portable-simd` feature, with the
+    /// gathers of `core::simd`.
+    ///
+    /// # Panics
+    /// If `keys` and `out` have different lengths.
+    pub fn hash_into(&self, keys: &[u32], out: &mut [u32]) {
+        let hashed = self.hash_blocks_simd(keys, out);
+        self.hash_batch(&keys[hashed..], &mut out[hashed..], byte_chunks_32)
+    }
+
+    /// Hash blocks of keys with the SIMD kernel selected by `batch_kernel`, returning the
+    /// number of keys hashed.
+    #[cfg(any(
+        all(feature = "simd", target_arch = "x86_64"),
+        feature = "portable-simd"
+    ))]
+    fn hash_blocks_simd(&self, keys: &[u32], out: &mut [u32]) -> usize {
+        assert_eq!(
+            keys.len(),
+            out.len(),
+            "the output needs one slot for every key"
+        );
+        match batch_kernel() {
+            #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+            BatchKernel::Avx2 | BatchKernel::Avx512 => simd::hash_blocks_32(&self.table, keys, out),
+            #[cfg(feature = "portable-simd")]
+            BatchKernel::Portable => portable_simd::hash_blocks_32(&self.table, keys, out),
+            _ => 0,
+        }
+    }
+
+    /// Without SIMD kernels, all keys are hashed by the scalar batch loop
+    #[cfg(not(any(
+        all(feature = "simd", target_arch = "x86_64"),
+        feature = "portable-simd"
+    )))]
+    fn hash_blocks_simd(&self, _keys: &[u32], _out: &mut [u32]) -> usize {
+        0
+    }
+
+    /// Compute the hash values of a slice of keys, see `hash_into`.
+    #[cfg(feature = "alloc")]
+    pub fn hash_many(&self, keys: &[u32]) -> Vec<u32> {
+        let mut out = vec![0; keys.len()];
+        self.hash_into(keys, &mut out);
+        out
+    }
+
+    /// Overwrite every key of a slice with its hash value, e.g. to pseudonymize ids.
+    ///
+    /// This is equivalent to `keys[i] = hash(keys[i])` for every `i`, and batches the
+    /// lookups like `hash_into` without allocating.
+    pub fn hash_in_place(&self, keys: &mut [u32]) {
+        hash_in_place_with(keys, |keys, out| self.hash_into(keys, out))
+    }
+
+    /// Map the hash value of a key to a bucket in `0..n`.
+    ///
+    /// This is the canonical way to map keys to `n` buckets. It uses Lemire's
+    /// multiply-shift reduction `((hash as u64 * n as u64) >> 32) as u32`,
+    /// which is faster than `hash % n` and, unlike it, keeps the order of hash values.
+    /// Each bucket receives either `floor(2^32 / n)` or `ceil(2^32 / n)` hash values.
+    ///
+    /// # Panics
+    /// In debug builds, if `n` is 0. In release builds, the result is then 0.
+    pub fn hash_to_range(&self, x: u32, n: u32) -> u32 {
+        debug_assert!(n > 0, "cannot map hash values to an empty range");
+        ((u64::from(self.hash(x)) * u64::from(n)) >> 32) as u32
+    }
+
+    /// Map the hash value of a key to a floating point number in `[0, 1)`.
+    ///
+    /// The result is `hash as f64 * 2^-32`, which is exact since all 32 bits of the
+    /// hash value fit into the mantissa. The largest result is `1 - 2^-32`.
+    pub fn hash_unit(&self, x: u32) -> f64 {
+        f64::from(self.hash(x)) * (1.0 / (1_u64 << 32) as f64)
+    }
+
+    /// Decide deterministically whether to sample a key with probability `p`.
+    ///
+    /// This is `hash_unit(x) < p`, i.e. `hash as f64 * 2^-32 < p`, where the product is exact.
+    /// Equivalently, the 32bit hash value is smaller than `p * 2^32` (as a real number).
+    /// Hence `ceil(p * 2^32)` of the hash values are sampled, i.e. keys are sampled with a
+    /// probability of `p` rounded up to a multiple of `2^-32`, and a key sampled for some `p`
+    /// is sampled for all larger ones.
+    /// `p = 0` samples no key and `p = 1` samples all keys.
+    ///
+    /// # Panics
+    /// If `p` is not in `[0, 1]`, e.g. if it is NaN.
+    pub fn sample(&self, x: u32, p: f64) -> bool {
+        assert!(
+            (0.0..=1.0).contains(&p),
+            "sampling probabilities have to be in [0, 1], not {}",
+            p
+        );
+        self.hash_unit(x) < p
+    }
+
+    /// Decide deterministically whether to sample a key at a rate of `ppm` parts per million.
+    ///
+    /// This is `hash_to_range(x, 1_000_000) < ppm`, i.e. a key is sampled if its bucket
+    /// among one million buckets is one of the first `ppm`. Since each bucket receives
+    /// the same number of hash values up to one, the rate is exact up to a relative error
+    /// of `1_000_000 / 2^32`. A key sampled at some rate is sampled at all larger ones.
+    ///
+    /// # Panics
+    /// If `ppm` is larger than `1_000_000`.
+    pub fn sample_rate_per_million(&self, x: u32, ppm: u32) -> bool {
+        assert!(
+            ppm <= 1_000_000,
+            "sampling rates can be at most 1000000 parts per million, not {}",
+            ppm
+        );
+        self.hash_to_range(x, 1_000_000) < ppm
+    }
+
+    /// Get the top `k` bits of the hash value of a key.
+    ///
+    /// The top bits are the most significant bits of the hash value returned by `hash`,
+    /// returned as the low `k` bits of the result, i.e. `hash >> (32 - k)`.
+    /// For `k = 0`, the result is 0.
+    ///
+    /// # Panics
+    /// If `k` is larger than 32.
+    pub fn hash_top_bits(&self, x: u32, k: u32) -> u32 {
+        assert!(k <= 32, "cannot take {} bits of a 32bit hash value", k);
+        self.hash(x).checked_shr(32 - k).unwrap_or(0)
+    }
+
+    /// Split the hash value of a key into its top `p` bits and the remaining low bits.
+    ///
+    /// Returns `(hash >> (32 - p), hash & (2^(32 - p) - 1))`, i.e. the top `p` bits as
+    /// with `hash_top_bits`, e.g. as register index, and the other `32 - p` bits, e.g. to
+    /// compute a rank. The hash value is `top << (32 - p) | low`.
+    /// For `p = 0`, the top bits are 0; for `p = 32`, the low bits are 0.
+    ///
+    /// # Panics
+    /// If `p` is larger than 32.
+    pub fn hash_split_bits(&self, x: u32, p: u32) -> (u32, u32) {
+        assert!(p <= 32, "cannot take {} bits of a 32bit hash value", p);
+        let h = self.hash(x);
+        let low_bits = 32 - p;
+        let top = h.checked_shr(low_bits).unwrap_or(0);
+        let low = h & u32::MAX.checked_shr(p).unwrap_or(0);
+        (top, low)
+    }
+
+    /// Compute a nonzero fingerprint of `bits` bits for a key, e.g. for cuckoo filters.
+    ///
+    /// Fingerprints are taken from the lowest 16 bits of the hash value, `low = hash as u16`,
+    /// which are mapped to `1..2^bits` by `((low * (2^bits - 1)) >> 16) + 1`, just like for
+    /// `Tab64Simple`. Hence they are never 0, which usually marks empty slots, and all
+    /// nonzero fingerprints are about equally likely.
+    ///
+    /// Buckets are taken from the top bits instead: `hash_top_bits(x, k)` uses disjoint bits
+    /// for `k <= 16`, and so does `hash_to_range(x, n)` for powers of two `n <= 2^16`.
+    /// For other `n`, the low 16 bits change the bucket of about a fraction of `n / 2^16`
+    /// of the hash values by one, so use `Tab64Simple` for filters with many buckets.
+    ///
+    /// # Panics
+    /// If `bits` is 0 or larger than 16.
+    pub fn fingerprint(&self, x: u32, bits: u8) -> u16 {
+        let range = (1_u32 << check_fingerprint_bits(bits)) - 1;
+        (((u32::from(self.hash(x) as u16) * range) >> 16) + 1) as u16
+    }
+
+    /// Compute the alternate bucket of a fingerprint stored in `bucket` of a cuckoo filter.
+    ///
+    /// This is the partial-key cuckoo hashing construction `bucket XOR hash(fp)`,
+    /// reduced to the `nbuckets` buckets of the filter. It is an involution,
+    /// i.e. `alt_bucket(alt_bucket(b, fp, n), fp, n) == b`, so the alternate bucket can be
+    /// computed from either bucket without knowing the key. If the low bits of `hash(fp)`
+    /// are all 0, both buckets are the same.
+    ///
+    /// Take the first bucket of a key from the top bits of its hash value, e.g.
+    /// `hash_top_bits(x, k)` for `2^k` buckets, since `fingerprint` uses the low 16 bits.
+    ///
+    /// Since the 32bit hash value of the fingerprint can only flip the low 32 bits of the
+    /// bucket, filters with more than `2^32` buckets are rejected. Use `Tab64Simple` for them.
+    ///
+    /// # Panics
+    /// If `nbuckets` is not a power of two or larger than `2^32`, or `bucket` is not
+    /// smaller than `nbuckets`.
+    pub fn alt_bucket(&self, bucket: usize, fp: u16, nbuckets: usize) -> usize {
+        assert!(
+            nbuckets.is_power_of_two(),
+            "the number of buckets has to be a power of two, not {}",
+            nbuckets
+        );
+        assert!(
+            nbuckets as u64 <= 1 << 32,
+            "a 32bit hash function supports at most 2^32 buckets, not {}",
+            nbuckets
+        );
+        assert!(
+            bucket < nbuckets,
+            "bucket {} is out of range for {} buckets",
+            bucket,
+            nbuckets
+        );
+        (bucket ^ self.hash(u32::from(fp)) as usize) & (nbuckets - 1)
+    }
+
+    /// Compute the hash value of a signed 32bit integer number.
+    ///
+    /// The two's complement bit pattern of `x` is hashed,
+    /// i.e. this is the same as `hash(x as u32)`, e.g. `-1` hashes like `u32::MAX`.
+    pub fn hash_i32(&self, x: i32) -> u32 {
+        self.hash(x as u32)
+    }
+
+    /// Compute the hash value of a non-zero 32bit integer number.
+    ///
+    /// This is the same as `hash(x.get())`.
+    pub fn hash_nonzero(&self, x: NonZeroU32) -> u32 {
+        self.hash(x.get())
+    }
+
+    /// Compute a hash value of a 32bit integer number that is never 0.
+    ///
+    /// A hash value of 0 is replaced by 1, other hash values are returned unchanged.
+    /// Hence keys hashing to 0 collide with keys hashing to 1, i.e. the hash value 1
+    /// occurs with twice the probability of any other nonzero value, and 0 never occurs.
+    pub fn hash_nonzero_out(&self, x: u32) -> NonZeroU32 {
+        NonZeroU32::new(self.hash(x)).unwrap_or(NonZeroU32::MIN)
+    }
+
+    /// Compute the hash value of a 32bit floating point number.
+    ///
+    /// The canonical bit pattern of `x` is hashed, see `canonical_f32_bits`, so `0.0`
+    /// and `-0.0` have the same hash value, as do all NaNs.
+    pub fn hash_f32(&self, x: f32) -> u32 {
+        self.hash(canonical_f32_bits(x))
+    }
+
+    /// Compute the hash value of an IPv4 address.
+    ///
+    /// The address is hashed as the integer `u32::from(a)`, i.e. its octets in network
+    /// byte order (big endian). Hence the first octet is looked up in column 3
+    /// and the last octet in column 0, e.g. `192.168.0.1` is hashed as `0xC0A8_0001`.
+    #[cfg(feature = "net")]
+    pub fn hash_ipv4(&self, a: Ipv4Addr) -> u32 {
+        self.hash(u32::from(a))
+    }
+
+    /// Compute a hash value for a slice of 32bit integer numbers.
+    ///
+    /// The hash values are chained: starting with the hash value of the length
+    /// of the slice (modulo 2^32), each element is XORed into the current
+    /// hash value, which is then hashed again, i.e. `[a, b]` is hashed as
+    /// `hash(hash(hash(2) ^ a) ^ b)`. This takes one hash computation per element
+    /// plus one for the length.
+    ///
+    /// - The hash value depends on the order of the elements, e.g. `[a, b]` and `[b, a]`
+    ///   hash to different values unless they collide by chance.
+    /// - The hash value depends on the length, e.g. `[1]` and `[1, 0]`
+    ///   hash to different values unless they collide by chance.
+    /// - The empty slice hashes to `hash(0)`.
+    ///
+    /// Note that this is not a tabulation hash function itself, so the independence
+    /// guarantees of tabulation hashing do not carry over to slices.
+    pub fn hash_slice(&self, xs: &[u32]) -> u32 {
+        let mut h = self.hash(xs.len() as u32);
+        for x in xs.iter() {
+            h = self.hash(h ^ *x);
+        }
+        h
+    }
+
+    /// Compute the hash value of a key of a custom type, see `TabKey`.
+    ///
+    /// This is equivalent to hashing the 32bit integer with the same byte chunks.
+    pub fn hash_key<K: TabKey<Chunks = [u8; 4]>>(&self, key: K) -> u32 {
+        self.hash(from_byte_chunks_32(key.chunks()))
+    }
+
+    /// Compute the hash value of a plain old data key of 4 bytes.
+    ///
+    /// The key is hashed like the 32bit integer with the same bytes in memory,
+    /// i.e. the integer obtained by transmuting the key (native byte order).
+    /// Keys of a different size are rejected at compile time.
+    #[cfg(feature = "bytemuck")]
+    pub fn hash_pod<T: bytemuck::Pod>(&self, key: &T) -> u32 {
+        const {
+            assert!(
+                core::mem::size_of::<T>() == 4,
+                "key size does not match the table"
+            )
+        };
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(bytemuck::bytes_of(key));
+        self.hash(u32::from_ne_bytes(bytes))
+    }
+
+    /// Borrow this hash function as a closure, e.g. for `Iterator::map`.
+    pub fn as_fn(&self) -> impl Fn(u32) -> u32 + '_ {
+        move |x| self.hash(x)
+    }
+
+    /// Turn this hash function into an owned closure, e.g. to move it into a thread.
+    pub fn into_fn(self) -> impl Fn(u32) -> u32 {
+        move |x| self.hash(x)
+    }
+}
+
+impl TabHash for Tab32Simple {
+    type Key = u32;
+    type Entry = u32;
+
+    fn hash(&self, key: u32) -> u32 {
+        Tab32Simple::hash(self, key)
+    }
+
+    #[cfg(feature = "getrandom")]
+    fn random() -> Self {
+        Tab32Simple::new()
+    }
+
+    fn new_with_seed(seed: u64) -> Self {
+        Tab32Simple::new_with_seed(seed)
+    }
+
+    fn id(&self) -> u64 {
+        Tab32Simple::id(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn to_vec(&self) -> Vec<Vec<u32>> {
+        Tab32Simple::to_vec(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn from_vec(table_data: Vec<Vec<u32>>) -> Self {
+        Tab32Simple::from_vec(table_data)
+    }
+}
+
+impl Hash32Fn for Tab32Simple {
+    fn hash32(&self, x: u32) -> u32 {
+        self.hash(x)
+    }
+}
+
+impl Mixer32 for Tab32Simple {
+    fn mix(&self, x: u32) -> u32 {
+        self.hash(x)
+    }
+}
+
+/// A universal hash function for 32-bit integers with 64-bit hash values using simple tabulation.
+///
+/// Like `Tab32Simple`, a key is split into four bytes, so only four lookups are needed,
+/// but table entries and hash values have 64 bits. This keeps collisions unlikely
+/// even for billions of keys, at half the table size of `Tab64Simple`.
+///
+/// `Tab32Twisted` has the same table layout and computes a 64-bit state, which is
+/// the XOR of the same four entries, except that the last lookup is twisted.
+/// Of this state, `Tab32Twisted::hash` only returns the high half, and
+/// `Tab32Twisted::hash_pair` also the low half, which depends on the twist.
+/// Use this type if you need a full 64-bit simple tabulation hash value.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::Tab32To64Simple;
+///
+/// let keys = vec![0, 8, 15, 47, 11];
+/// let simple = Tab32To64Simple::new_with_seed(42);
+/// for k in keys {
+///     println!("{}", simple.hash(k));
+/// }
+/// ```
+pub type Tab32To64Simple = TabSimple<u64, 4>;
+
+impl Tab32To64Simple {
+    /// Create a new simple tabulation hash function from the little-endian bytes of a table.
+    ///
+    /// Entries are read column by column, each column from entry 0 to 255,
+    /// as written by `to_le_bytes`. Combined with `include_bytes!`,
+    /// this embeds a table into the binary at compile time.
+    pub const fn from_le_bytes(bytes: &[u8; 8192]) -> Self {
+        let mut table = [[0; 256]; 4];
+        let mut i = 0;
+        while i < 4 * 256 {
+            table[i / 256][i % 256] = read_le(bytes, 8 * i, 8) as u64;
+            i += 1;
+        }
+        Tab32To64Simple { table }
+    }
+
+    /// Export the table of this hash function as little-endian bytes.
+    ///
+    /// This is the inverse of `from_le_bytes`.
+    pub fn to_le_bytes(&self) -> [u8; 8192] {
+        let mut bytes = [0; 8192];
+        for (chunk, entry) in bytes.chunks_mut(8).zip(self.table.iter().flatten()) {
+            chunk.copy_from_slice(&entry.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Compute simple tabulation hash value with 64 bits for a 32bit integer number.
+    pub fn hash(&self, x: u32) -> u64 {
+        self.hash_chars(byte_chunks_32(x))
+    }
+
+    /// Compute the hash value of a key of a custom type, see `TabKey`.
+    ///
+    /// This is equivalent to hashing the 32bit integer with the same byte chunks.
+    pub fn hash_key<K: TabKey<Chunks = [u8; 4]>>(&self, key: K) -> u64 {
+        self.hash(from_byte_chunks_32(key.chunks()))
+    }
+
+    /// Compute the SimHash fingerprint of a set of weighted features.
+    ///
+    /// For each bit position, the weights of all features whose hash value has a 1 bit
+    /// at this position are added, and the weights of all others are subtracted.
+    /// Bit `i` of the fingerprint is 1 if the sum of position `i` is positive, and 0 if
+    /// it is negative or exactly 0. Similar sets of weighted features have fingerprints
+    /// with a small `hamming_distance`.
+    ///
+    /// - A feature with a weight of 0 does not contribute, features with a NaN weight are ignored.
+    /// - A negative weight counts against the bits of the feature, i.e. it contributes
+    ///   like the complement of its hash value with the absolute weight.
+    /// - Features occurring several times contribute once per occurrence.
+    /// - The empty feature set, and any set whose weights cancel out, has the fingerprint 0.
+    pub fn simhash(&self, features: impl IntoIterator<Item = (u32, f32)>) -> u64 {
+        simhash_of(features.into_iter().map(|(x, w)| (self.hash(x), w)))
+    }
+
+    /// Compute the hash value of a plain old data key of 4 bytes.
+    ///
+    /// The key is hashed like the 32bit integer with the same bytes in memory,
+    /// i.e. the integer obtained by transmuting the key (native byte order).
+    /// Keys of a different size are rejected at compile time.
+    #[cfg(feature = "bytemuck")]
+    pub fn hash_pod<T: bytemuck::Pod>(&self, key: &T) -> u64 {
+        const {
+            assert!(
+                core::mem::size_of::<T>() == 4,
+                "key size does not match the table"
+            )
+        };
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(bytemuck::bytes_of(key));
+        self.hash(u32::from_ne_bytes(bytes))
+    }
+
+    /// Borrow this hash function as a closure, e.g. for `Iterator::map`.
+    pub fn as_fn(&self) -> impl Fn(u32) -> u64 + '_ {
+        move |x| self.hash(x)
+    }
+
+    /// Turn this hash function into an owned closure, e.g. to move it into a thread.
+    pub fn into_fn(self) -> impl Fn(u32) -> u64 {
+        move |x| self.hash(x)
+    }
+}
+
+/// A universal hash function for 64-bit integers using simple tabulation.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::Tab64Simple;
+///
+/// let keys = vec![0, 8, 15, 47, 11];
+/// let simple = Tab64Simple::new_with_seed(42);
+/// for k in keys {
+///     println!("{}", simple.hash(k));
+/// }
+/// ```
+pub type Tab64Simple = TabSimple<u64, 8>;
+
+impl Tab64Simple {
+    /// Create a new simple tabulation hash function from the little-endian bytes of a table.
+    ///
+    /// Entries are read column by column, each column from entry 0 to 255,
+    /// as written by `to_le_bytes`. Combined with `include_bytes!`,
+    /// this embeds a table into the binary at compile time.
+    pub const fn from_le_bytes(bytes: &[u8; 16384]) -> Self {
+        let mut table = [[0; 256]; 8];
+        let mut i = 0;
+        while i < 8 * 256 {
+            table[i / 256][i % 256] = read_le(bytes, 8 * i, 8) as u64;
+            i += 1;
+        }
+        Tab64Simple { table }
+    }
+
+    /// Export the table of this hash function as little-endian bytes.
+    ///
+    /// This is the inverse of `from_le_bytes`.
+    pub fn to_le_bytes(&self) -> [u8; 16384] {
+        let mut bytes = [0; 16384];
+        for (chunk, entry) in bytes.chunks_mut(8).zip(self.table.iter().flatten()) {
+            chunk.copy_from_slice(&entry.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Compute simple tabulation hash value for a 64bit integer number.
+    pub fn hash(&self, x: u64) -> u64 {
+        self.hash_chars(byte_chunks_64(x))
+    }
+
+    /// Compute the hash values of a slice of keys into `out`.
+    ///
+    /// This is equivalent to `out[i] = hash(keys[i])` for every `i`, but faster for large
+    /// slices, since keys are hashed in blocks of four with interleaved lookups.
+    ///
+    /// # Panics
+    /// If `keys` and `out` have different lengths.
+    pub fn hash_into(&self, keys: &[u64], out: &mut [u64]) {
+        self.hash_batch(keys, out, byte_chunks_64)
+    }
+
+    /// Compute the hash values of a slice of keys, see `hash_into`.
+    #[cfg(feature = "alloc")]
+    pub fn hash_many(&self, keys: &[u64]) -> Vec<u64> {
+        let mut out = vec![0; keys.len()];
+        self.hash_into(keys, &mut out);
+        out
+    }
+
+    /// Overwrite every key of a slice with its hash value, e.g. to pseudonymize ids.
+    ///
+    /// This is equivalent to `keys[i] = hash(keys[i])` for every `i`, and batches the
+    /// lookups like `hash_into` without allocating.
+    pub fn hash_in_place(&self, keys: &mut [u64]) {
+        hash_in_place_with(keys, |keys, out| self.hash_into(keys, out))
+    }
+
+    /// Map the hash value of a key to a bucket in `0..n`.
+    ///
+    /// This is the canonical way to map keys to `n` buckets. It uses Lemire's
+    /// multiply-shift reduction `((hash as u128 * n as u128) >> 64) as u64`,
+    /// which is faster than `hash % n` and, unlike it, keeps the order of hash values.
+    /// Each bucket receives either `floor(2^64 / n)` or `ceil(2^64 / n)` hash values.
+    ///
+    /// On targets without 64-bit pointers, which usually lack a fast widening
+    /// multiplication, the high bits of the product are computed from four
+    /// 32x32->64 bit products instead. Both paths yield identical results.
+    ///
+    /// # Panics
+    /// In debug builds, if `n` is 0. In release builds, the result is then 0.
+    pub fn hash_to_range(&self, x: u64, n: u64) -> u64 {
+        debug_assert!(n > 0, "cannot map hash values to an empty range");
+        mul_high_64(self.hash(x), n)
+    }
+
+    /// Get two candidate buckets in `0..n` for a key, e.g. for load balancing with
+    /// the "power of two choices".
+    ///
+    /// The candidates are derived from the high and the low 32 bits of the hash value.
+    /// Since all bits of the table entries are independent, these halves are hash values
+    /// of two independent simple tabulation hash functions with 32-bit tables.
+    /// Each half is mapped to `0..n` with the multiply-shift reduction of `hash_to_range`,
+    /// i.e. `(half * n) >> 32`. Hence both buckets are smaller than `n`, but at most
+    /// `2^32` buckets can be chosen, which is no restriction for practical numbers of bins.
+    ///
+    /// # Panics
+    /// In debug builds, if `n` is 0. In release builds, both buckets are then 0.
+    pub fn two_choices(&self, x: u64, n: u64) -> (u64, u64) {
+        debug_assert!(n > 0, "cannot map hash values to an empty range");
+        let h = self.hash(x);
+        (
+            mul_high_64(h & 0xFFFF_FFFF_0000_0000, n),
+            mul_high_64(h << 32, n),
+        )
+    }
+
+    /// Choose the less loaded one of the two candidate buckets of a key.
+    ///
+    /// The candidates are `two_choices(x, loads.len())`. The bucket with the smaller
+    /// load is returned, the first candidate on ties. Updating the loads is
+    /// left to the caller.
+    ///
+    /// # Panics
+    /// If `loads` is empty.
+    pub fn choose_less_loaded(&self, x: u64, loads: &[u64]) -> usize {
+        assert!(!loads.is_empty(), "cannot choose from zero buckets");
+        let (first, second) = self.two_choices(x, loads.len() as u64);
+        let (first, second) = (first as usize, second as usize);
+        if loads[second] < loads[first] {
+            second
+        } else {
+            first
+        }
+    }
+
+    /// Choose the node for a key by rendezvous (highest random weight) hashing.
+    ///
+    /// Each node gets the score `hash(hash(x) ^ hash(node))` and the node with the
+    /// highest score is returned, the one with the largest id on ties. Removing a node
+    /// only moves the keys it was chosen for, and adding one only moves keys to it.
+    /// The order of the nodes does not matter, but each evaluation takes linear time,
+    /// so this suits small numbers of nodes. See `ConsistentRing` for many nodes.
+    ///
+    /// # Errors
+    /// If `nodes` is empty.
+    pub fn rendezvous(&self, x: u64, nodes: &[u64]) -> Result<u64, NoNodesError> {
+        rendezvous::rendezvous(|y| self.hash(y), x, nodes)
+    }
+
+    /// Choose the node for a key by weighted rendezvous hashing.
+    ///
+    /// The score `hash(hash(x) ^ hash(node))` of `rendezvous` is mapped to `u` in `(0, 1)`
+    /// like in `hash_exp`, and the node with the highest `-weight / ln(u)` is returned,
+    /// the one with the largest id on ties. Then each node is chosen for a fraction of the
+    /// keys proportional to its weight. Removing a node only moves the keys it was chosen for.
+    ///
+    /// # Errors
+    /// If `nodes` is empty.
+    ///
+    /// # Panics
+    /// If a weight is not positive and finite.
+    #[cfg(feature = "std")]
+    pub fn rendezvous_weighted(&self, x: u64, nodes: &[(u64, f64)]) -> Result<u64, NoNodesError> {
+        rendezvous::rendezvous_weighted(|y| self.hash(y), x, nodes)
+    }
+
+    /// Map a key to one of `num_buckets` buckets with jump consistent hashing.
+    ///
+    /// This is the algorithm of Lamping and Veach, with the linear congruential generator
+    /// replaced by hashing: step `i` draws `r = hash(hash(x) ^ hash(i))` and jumps from
+    /// bucket `b` to `floor((b + 1) * 2^53 / ((r >> 11) + 1))`, until the jump leaves
+    /// `0..num_buckets`. The last bucket reached is returned. This takes `O(ln(num_buckets))`
+    /// hash evaluations and no memory besides the table.
+    ///
+    /// The step is hashed before it is combined with the key, since the draws
+    /// `hash(hash(x) + i)` of consecutive steps would only differ in the entries of the
+    /// low bytes of `hash(x) + i` under simple tabulation, and would not be independent.
+    ///
+    /// Every bucket receives `1 / num_buckets` of the keys in expectation. The sequence of
+    /// jumps of a key does not depend on `num_buckets`, so increasing `num_buckets`
+    /// only moves keys into the new buckets, and decreasing it only moves the keys of
+    /// the removed (highest) buckets. Unlike with a ring, only the last bucket can be removed.
+    ///
+    /// # Panics
+    /// If `num_buckets` is 0.
+    pub fn jump_bucket(&self, x: u64, num_buckets: u32) -> u32 {
+        assert!(num_buckets > 0, "cannot map keys to zero buckets");
+        let h = self.hash(x);
+        let (mut bucket, mut jump) = (0, 0);
+        let mut step: u64 = 0;
+        while jump < u64::from(num_buckets) {
+            bucket = jump;
+            let r = self.hash(h ^ self.hash(step));
+            step += 1;
+            // saturates at u64::MAX for large jumps
+            jump = ((bucket + 1) as f64 * ((1_u64 << 53) as f64 / ((r >> 11) + 1) as f64)) as u64;
+        }
+        bucket as u32
+    }
+
+    /// Get `k` positions in `0..m` of a key for a Bloom filter from a single hash evaluation.
+    ///
+    /// This uses the double hashing scheme of Kirsch and Mitzenmacher: with `h1` and `h2`
+    /// the high and the low 32 bits of the hash value, position `i` is
+    /// `(h1 + i * h2) mod m`. Hence a single table with one lookup per byte of the key
+    /// suffices for any `k`, instead of `k` hash functions.
+    ///
+    /// The positions of a key are not independent. They form an arithmetic progression,
+    /// which repeats positions if `h2 mod m` shares a divisor with `m` and collapses to a
+    /// single position if `h2 mod m` is 0. Kirsch and Mitzenmacher show that the false
+    /// positive rate is asymptotically the same as with `k` independent hash functions,
+    /// nonetheless. Both halves are reduced with `mod m`, which is slightly biased
+    /// unless `m` is much smaller than `2^32`.
+    ///
+    /// # Panics
+    /// If `m` is 0.
+    pub fn bloom_indices(
+        &self,
+        x: u64,
+        k: u32,
+        m: usize,
+    ) -> impl ExactSizeIterator<Item = usize> + Clone {
+        assert!(m > 0, "a Bloom filter needs at least one position");
+        let m = m as u64;
+        let h = self.hash(x);
+        let mut position = (h >> 32) % m;
+        let step = (h & 0xFFFF_FFFF) % m;
+        (0..k).map(move |_| {
+            let current = position;
+            // add the step modulo m without overflowing
+            position = if position >= m - step {
+                position - (m - step)
+            } else {
+                position + step
+            };
+            current as usize
+        })
+    }
+
+    /// Get a sign of `+1` or `-1` for a key, e.g. for Count Sketch or the AMS sketch.
+    ///
+    /// The sign is taken from the lowest bit of the hash value: `+1` if it is 0,
+    /// `-1` if it is 1. Use `hash_bucket_sign` to get a bucket along with the sign.
+    pub fn hash_sign(&self, x: u64) -> i8 {
+        1 - 2 * (self.hash(x) & 1) as i8
+    }
+
+    /// Get a bucket in `0..width` and a sign of `+1` or `-1` for a key.
+    ///
+    /// The sign is the lowest bit of the hash value, just like `hash_sign`.
+    /// The bucket is computed from the upper 63 bits with the multiply-shift reduction
+    /// of `hash_to_range`, i.e. `((hash & !1) * width) >> 64`. Since bucket and sign
+    /// are taken from disjoint bits of the hash value, and all bits of the table entries
+    /// are independent, the sign is independent of the bucket.
+    ///
+    /// # Panics
+    /// If `width` is 0.
+    pub fn hash_bucket_sign(&self, x: u64, width: usize) -> (usize, i8) {
+        assert!(width > 0, "cannot map hash values to an empty range");
+        bucket_and_sign(self.hash(x), width)
+    }
+
+    /// Split the hash value of a key into a register index and a rank for HyperLogLog.
+    ///
+    /// The register index consists of the top `p` bits of the hash value, i.e. it is
+    /// in `0..2^p`. The rank is the position of the first 1 bit in the remaining `64 - p` bits,
+    /// counting from 1 at the most significant one, i.e. their number of leading zeros plus 1.
+    /// If all of them are 0, the rank is `65 - p`. See `HyperLogLog` for a sketch using this.
+    ///
+    /// # Panics
+    /// If `p` is not in `4..=18`.
+    pub fn hll_parts(&self, x: u64, p: u8) -> (usize, u8) {
+        hll_parts(self.hash(x), p)
+    }
+
+    /// Map the hash value of a key to a floating point number in `[0, 1)`.
+    ///
+    /// The result is `(hash >> 11) as f64 * 2^-53`, i.e. the top 53 bits of the hash
+    /// value are used as mantissa, which is exact. The largest result is `1 - 2^-53`.
+    pub fn hash_unit(&self, x: u64) -> f64 {
+        (self.hash(x) >> 11) as f64 * (1.0 / (1_u64 << 53) as f64)
+    }
+
+    /// Decide deterministically whether to sample a key with probability `p`.
+    ///
+    /// This is `hash_unit(x) < p`, i.e. `(hash >> 11) as f64 * 2^-53 < p`, where the product
+    /// is exact. Equivalently, the top 53 bits of the hash value are smaller than `p * 2^53`
+    /// (as a real number). Hence `ceil(p * 2^53)` of these values are sampled, i.e. keys are
+    /// sampled with a probability of `p` rounded up to a multiple of `2^-53`, and a key sampled
+    /// for some `p` is sampled for all larger ones.
+    /// `p = 0` samples no key and `p = 1` samples all keys.
+    ///
+    /// # Panics
+    /// If `p` is not in `[0, 1]`, e.g. if it is NaN.
+    pub fn sample(&self, x: u64, p: f64) -> bool {
+        assert!(
+            (0.0..=1.0).contains(&p),
+            "sampling probabilities have to be in [0, 1], not {}",
+            p
+        );
+        self.hash_unit(x) < p
+    }
+
+    /// Decide deterministically whether to sample a key at a rate of `ppm` parts per million.
+    ///
+    /// This is `hash_to_range(x, 1_000_000) < ppm`, i.e. a key is sampled if its bucket
+    /// among one million buckets is one of the first `ppm`. Since each bucket receives
+    /// the same number of hash values up to one, the rate is exact up to a relative error
+    /// of `1_000_000 / 2^64`. A key sampled at some rate is sampled at all larger ones.
+    ///
+    /// # Panics
+    /// If `ppm` is larger than `1_000_000`.
+    pub fn sample_rate_per_million(&self, x: u64, ppm: u32) -> bool {
+        assert!(
+            ppm <= 1_000_000,
+            "sampling rates can be at most 1000000 parts per million, not {}",
+            ppm
+        );
+        self.hash_to_range(x, 1_000_000) < u64::from(ppm)
+    }
+
+    /// Map the hash value of a key to an exponentially distributed number with mean 1.
+    ///
+    /// The top 52 bits of the hash value are mapped to the midpoint of their interval,
+    /// `u = ((hash >> 12) as f64 + 0.5) * 2^-52`, which is exact in `f64`. Hence `u` lies
+    /// in `[2^-53, 1 - 2^-53]` and is never 0, unlike `hash_unit`. The result is `-ln(u)`,
+    /// which lies between about `1.1e-16` and `53 ln 2`, about 36.7.
+    #[cfg(feature = "std")]
+    pub fn hash_exp(&self, x: u64) -> f64 {
+        -open_unit(self.hash(x)).ln()
+    }
+
+    /// Compute the priority of a key with a weight, e.g. for priority sampling.
+    ///
+    /// The priority is `hash_exp(x) / weight`, i.e. exponentially distributed with rate
+    /// `weight`. Selecting the keys with the smallest priorities yields a weighted sample
+    /// without replacement.
+    ///
+    /// # Panics
+    /// If `weight` is not positive and finite.
+    #[cfg(feature = "std")]
+    pub fn priority(&self, x: u64, weight: f64) -> f64 {
+        assert!(
+            weight > 0.0 && weight.is_finite(),
+            "weights have to be positive and finite, not {}",
+            weight
+        );
+        self.hash_exp(x) / weight
+    }
+
+    /// Get the top `k` bits of the hash value of a key.
+    ///
+    /// The top bits are the most significant bits of the hash value returned by `hash`,
+    /// returned as the low `k` bits of the result, i.e. `hash >> (64 - k)`.
+    /// For `k = 0`, the result is 0.
+    ///
+    /// # Panics
+    /// If `k` is larger than 64.
+    pub fn hash_top_bits(&self, x: u64, k: u32) -> u64 {
+        assert!(k <= 64, "cannot take {} bits of a 64bit hash value", k);
+        self.hash(x).checked_shr(64 - k).unwrap_or(0)
+    }
+
+    /// Split the hash value of a key into its top `p` bits and the remaining low bits.
+    ///
+    /// Returns `(hash >> (64 - p), hash & (2^(64 - p) - 1))`, i.e. the top `p` bits as
+    /// with `hash_top_bits`, e.g. as register index, and the other `64 - p` bits, e.g. to
+    /// compute a rank. The hash value is `top << (64 - p) | low`.
+    /// For `p = 0`, the top bits are 0; for `p = 64`, the low bits are 0.
+    ///
+    /// # Panics
+    /// If `p` is larger than 64.
+    pub fn hash_split_bits(&self, x: u64, p: u32) -> (u64, u64) {
+        assert!(p <= 64, "cannot take {} bits of a 64bit hash value", p);
+        let h = self.hash(x);
+        let low_bits = 64 - p;
+        let top = h.checked_shr(low_bits).unwrap_or(0);
+        let low = h & u64::MAX.checked_shr(p).unwrap_or(0);
+        (top, low)
+    }
+
+    /// Compute a nonzero fingerprint of `bits` bits for a key, e.g. for cuckoo filters.
+    ///
+    /// Fingerprints are taken from the lowest 16 bits of the hash value, `low = hash as u16`,
+    /// which are mapped to `1..2^bits` by `((low * (2^bits - 1)) >> 16) + 1`. Hence they are
+    /// never 0, which usually marks empty slots, and all nonzero fingerprints are about
+    /// equally likely. Use `fingerprint_with_zero` if 0 is a valid fingerprint.
+    ///
+    /// Buckets are taken from the top bits instead: `hash_top_bits(x, k)` uses disjoint bits
+    /// for `k <= 48`, and so does `hash_to_range(x, n)` for powers of two `n <= 2^48`.
+    /// For other `n`, the low 16 bits change the bucket of at most a fraction of `n / 2^48`
+    /// of the hash values by one, e.g. about `2^-16` for a table with `2^32` buckets.
+    ///
+    /// # Panics
+    /// If `bits` is 0 or larger than 16.
+    pub fn fingerprint(&self, x: u64, bits: u8) -> u16 {
+        let range = (1_u32 << check_fingerprint_bits(bits)) - 1;
+        (((u32::from(self.hash(x) as u16) * range) >> 16) + 1) as u16
+    }
+
+    /// Compute a fingerprint of `bits` bits for a key, which may be 0.
+    ///
+    /// The fingerprint is the lowest `bits` bits of the hash value, i.e.
+    /// `hash & (2^bits - 1)`. Like `fingerprint`, this only uses the lowest 16 bits,
+    /// which are disjoint from the bits used by `hash_top_bits` and `hash_to_range`
+    /// for tables of up to `2^48` buckets.
+    ///
+    /// # Panics
+    /// If `bits` is 0 or larger than 16.
+    pub fn fingerprint_with_zero(&self, x: u64, bits: u8) -> u16 {
+        let mask = (1_u32 << check_fingerprint_bits(bits)) - 1;
+        (self.hash(x) as u32 & mask) as u16
+    }
+
+    /// Compute the alternate bucket of a fingerprint stored in `bucket` of a cuckoo filter.
+    ///
+    /// This is the partial-key cuckoo hashing construction `bucket XOR hash(fp)`,
+    /// reduced to the `nbuckets` buckets of the filter. It is an involution,
+    /// i.e. `alt_bucket(alt_bucket(b, fp, n), fp, n) == b`, so the alternate bucket can be
+    /// computed from either bucket without knowing the key. If the low bits of `hash(fp)`
+    /// are all 0, both buckets are the same.
+    ///
+    /// Take the first bucket of a key from the top bits of its hash value, e.g.
+    /// `hash_top_bits(x, k)` for `2^k` buckets, since `fingerprint` uses the low 16 bits.
+    ///
+    /// # Panics
+    /// If `nbuckets` is not a power of two or `bucket` is not smaller than `nbuckets`.
+    pub fn alt_bucket(&self, bucket: usize, fp: u16, nbuckets: usize) -> usize {
+        assert!(
+            nbuckets.is_power_of_two(),
+            "the number of buckets has to be a power of two, not {}",
+            nbuckets
+        );
+        assert!(
+            bucket < nbuckets,
+            "bucket {} is out of range for {} buckets",
+            bucket,
+            nbuckets
+        );
+        (bucket ^ self.hash(u64::from(fp)) as usize) & (nbuckets - 1)
+    }
+
+    /// Compute the hash value of a signed 64bit integer number.
+    ///
+    /// The two's complement bit pattern of `x` is hashed,
+    /// i.e. this is the same as `hash(x as u64)`, e.g. `-1` hashes like `u64::MAX`.
+    pub fn hash_i64(&self, x: i64) -> u64 {
+        self.hash(x as u64)
+    }
+
+    /// Compute the hash value of a non-zero 64bit integer number.
+    ///
+    /// This is the same as `hash(x.get())`.
+    pub fn hash_nonzero(&self, x: NonZeroU64) -> u64 {
+        self.hash(x.get())
+    }
+
+    /// Compute a hash value of a 64bit integer number that is never 0.
+    ///
+    /// A hash value of 0 is replaced by 1, other hash values are returned unchanged.
+    /// Hence keys hashing to 0 collide with keys hashing to 1, i.e. the hash value 1
+    /// occurs with twice the probability of any other nonzero value, and 0 never occurs.
+    pub fn hash_nonzero_out(&self, x: u64) -> NonZeroU64 {
+        NonZeroU64::new(self.hash(x)).unwrap_or(NonZeroU64::MIN)
+    }
+
+    /// Compute the hash value of a 64bit floating point number.
+    ///
+    /// The canonical bit pattern of `x` is hashed, see `canonical_f64_bits`, so `0.0`
+    /// and `-0.0` have the same hash value, as do all NaNs.
+    pub fn hash_f64(&self, x: f64) -> u64 {
+        self.hash(canonical_f64_bits(x))
+    }
+
+    /// Compute the hash value of an IPv4 socket address.
+    ///
+    /// The socket address is hashed as the 48bit integer `u32::from(ip) << 16 | port`,
+    /// i.e. the octets of the address followed by the port, both in network byte order
+    /// (big endian), e.g. `192.168.0.1:8080` is hashed as `0xC0A8_0001_1F90`.
+    #[cfg(feature = "net")]
+    pub fn hash_socketaddr_v4(&self, a: SocketAddrV4) -> u64 {
+        self.hash((u64::from(u32::from(*a.ip())) << 16) | u64::from(a.port()))
+    }
+
+    /// Compute a hash value for a slice of 64bit integer numbers.
+    ///
+    /// The hash values are chained: starting with the hash value of the length
+    /// of the slice (modulo 2^64), each element is XORed into the current
+    /// hash value, which is then hashed again, i.e. `[a, b]` is hashed as
+    /// `hash(hash(hash(2) ^ a) ^ b)`. This takes one hash computation per element
+    /// plus one for the length.
+    ///
+    /// - The hash value depends on the order of the elements, e.g. `[a, b]` and `[b, a]`
+    ///   hash to different values unless they collide by chance.
+    /// - The hash value depends on the length, e.g. `[1]` and `[1, 0]`
+    ///   hash to different values unless they collide by chance.
+    /// - The empty slice hashes to `hash(0)`.
+    ///
+    /// Note that this is not a tabulation hash function itself, so the independence
+    /// guarantees of tabulation hashing do not carry over to slices.
+    pub fn hash_slice(&self, xs: &[u64]) -> u64 {
+        let mut h = self.hash(xs.len() as u64);
+        for x in xs.iter() {
+            h = self.hash(h ^ *x);
+        }
+        h
+    }
+
+    /// Compute the hash value of a byte string of at most 8 bytes.
+    ///
+    /// The string is packed into a 64bit number with `pack_short_bytes`, which is hashed.
+    ///
+    /// # Panics
+    /// If the string is longer than 8 bytes.
+    pub fn hash_short_bytes(&self, b: &[u8]) -> u64 {
+        self.hash(pack_short_bytes(b))
+    }
+
+    /// Compute the hash value of a string of at most 8 bytes, e.g. a short ASCII tag.
+    ///
+    /// This is the same as `hash_short_bytes` of the UTF-8 bytes of the string.
+    ///
+    /// # Panics
+    /// If the string is longer than 8 bytes.
+    pub fn hash_short_str(&self, s: &str) -> u64 {
+        self.hash_short_bytes(s.as_bytes())
+    }
+
+    /// Compute the hash value of a 2-bit packed k-mer of length `k`, see the `kmer` module.
+    ///
+    /// All bits except for the lowest `2 * k` are cleared before hashing,
+    /// so that unused high bits do not change the hash value.
+    ///
+    /// # Panics
+    /// If `k` is larger than 32.
+    pub fn hash_kmer(&self, packed: u64, k: usize) -> u64 {
+        self.hash(kmer::mask_kmer(packed, k))
+    }
+
+    /// Compute a strand-independent hash value of a 2-bit packed k-mer of length `k`.
+    ///
+    /// The canonical form of the k-mer, the lexicographically smaller one of the k-mer
+    /// and its reverse complement, is hashed (see `kmer::canonical_kmer`).
+    /// Hence a k-mer and its reverse complement have the same hash value.
+    ///
+    /// # Panics
+    /// If `k` is larger than 32.
+    pub fn hash_canonical(&self, packed: u64, k: usize) -> u64 {
+        self.hash(kmer::canonical_kmer(packed, k))
+    }
+
+    /// Select the minimizers of a sequence of k-mers.
+    ///
+    /// For each window of `w` consecutive k-mers, the k-mer with the smallest
+    /// hash value is selected. If several k-mers of a window have the smallest
+    /// hash value, the leftmost one is selected. Each selected k-mer is reported
+    /// once as `(position, kmer)`, in increasing order of position, even if
+    /// it is selected for several overlapping windows. A sequence of less than
+    /// `w` k-mers forms a single window, i.e. its minimum is reported.
+    ///
+    /// K-mers are hashed with `hash`, use `kmer::canonical_kmer` (or `kmer::mask_kmer`)
+    /// to prepare them. This takes linear time in the number of k-mers.
+    ///
+    /// # Panics
+    /// If `w` is 0.
+    #[cfg(feature = "alloc")]
+    pub fn minimizers<'a, I>(
+        &'a self,
+        kmers: I,
+        w: usize,
+    ) -> impl Iterator<Item = (usize, u64)> + 'a
+    where
+        I: IntoIterator<Item = u64>,
+        I::IntoIter: 'a,
+    {
+        kmer::Minimizers::new(kmers.into_iter(), w, move |x| self.hash(x))
+    }
+
+    /// Compute the hash value of a key of a custom type, see `TabKey`.
+    ///
+    /// This is equivalent to hashing the 64bit integer with the same byte chunks.
+    pub fn hash_key<K: TabKey<Chunks = [u8; 8]>>(&self, key: K) -> u64 {
+        self.hash(from_byte_chunks_64(key.chunks()))
+    }
+
+    /// Compute the hash value of a plain old data key of 8 bytes.
+    ///
+    /// The key is hashed like the 64bit integer with the same bytes in memory,
+    /// i.e. the integer obtained by transmuting the key (native byte order).
+    /// Keys of a different size are rejected at compile time.
+    #[cfg(feature = "bytemuck")]
+    pub fn hash_pod<T: bytemuck::Pod>(&self, key: &T) -> u64 {
+        const {
+            assert!(
+                core::mem::size_of::<T>() == 8,
+                "key size does not match the table"
+            )
+        };
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(bytemuck::bytes_of(key));
+        self.hash(u64::from_ne_bytes(bytes))
+    }
+
+    /// Borrow this hash function as a closure, e.g. for `Iterator::map`.
+    pub fn as_fn(&self) -> impl Fn(u64) -> u64 + '_ {
+        move |x| self.hash(x)
+    }
+
+    /// Turn this hash function into an owned closure, e.g. to move it into a thread.
+    pub fn into_fn(self) -> impl Fn(u64) -> u64 {
+        move |x| self.hash(x)
+    }
+}
+
+impl TabHash for Tab64Simple {
+    type Key = u64;
+    type Entry = u64;
+
+    fn hash(&self, key: u64) -> u64 {
+        Tab64Simple::hash(self, key)
+    }
+
+    #[cfg(feature = "getrandom")]
+    fn random() -> Self {
+        Tab64Simple::new()
+    }
+
+    fn new_with_seed(seed: u64) -> Self {
+        Tab64Simple::new_with_seed(seed)
+    }
+
+    fn id(&self) -> u64 {
+        Tab64Simple::id(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn to_vec(&self) -> Vec<Vec<u64>> {
+        Tab64Simple::to_vec(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn from_vec(table_data: Vec<Vec<u64>>) -> Self {
+        Tab64Simple::from_vec(table_data)
+    }
+}
+
+impl Hash64Fn for Tab64Simple {
+    fn hash64(&self, x: u64) -> u64 {
+        self.hash(x)
+    }
+}
+
+impl Mixer64 for Tab64Simple {
+    fn mix(&self, x: u64) -> u64 {
+        self.hash(x)
+    }
+}
+
+/// A universal hash function for 64-bit integers with 32-bit hash values using simple tabulation.
+///
+/// Like `Tab64Simple`, a key is split into eight bytes, but table entries and hash
+/// values have 32 bits. The table takes 8 KiB instead of the 16 KiB of `Tab64Simple`,
+/// which adds up when keeping many hash functions, e.g. for sketches.
+///
+/// This is a family of its own with 32-bit tables, not a truncation of `Tab64Simple`
+/// hash values at runtime. Since entries of random tables are independent and uniform,
+/// its hash values are distributed just like the low 32 bits of `Tab64Simple` hash values.
+/// Hash values of both agree if the table holds the low 32 bits of the `Tab64Simple` table.
+/// Note that this is the case for `new_with_seed` with the same seed, since entries
+/// of at most 32 bits keep the low bits of each SplitMix64 output, but not for
+/// the other constructors.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::Tab64To32Simple;
+///
+/// let keys = vec![0, 8, 15, 47, 11];
+/// let simple = Tab64To32Simple::new_with_seed(42);
+/// for k in keys {
+///     println!("{}", simple.hash(k));
+/// }
+/// ```
+pub type Tab64To32Simple = TabSimple<u32, 8>;
+
+impl Tab64To32Simple {
+    /// Create a new simple tabulation hash function from the little-endian bytes of a table.
+    ///
+    /// Entries are read column by column, each column from entry 0 to 255,
+    /// as written by `to_le_bytes`. Combined with `include_bytes!`,
+    /// this embeds a table into the binary at compile time.
+    pub const fn from_le_bytes(bytes: &[u8; 8192]) -> Self {
+        let mut table = [[0; 256]; 8];
+        let mut i = 0;
+        while i < 8 * 256 {
+            table[i / 256][i % 256] = read_le(bytes, 4 * i, 4) as u32;
+            i += 1;
+        }
+        Tab64To32Simple { table }
+    }
+
+    /// Export the table of this hash function as little-endian bytes.
+    ///
+    /// This is the inverse of `from_le_bytes`.
+    pub fn to_le_bytes(&self) -> [u8; 8192] {
+        let mut bytes = [0; 8192];
+        for (chunk, entry) in bytes.chunks_mut(4).zip(self.table.iter().flatten()) {
+            chunk.copy_from_slice(&entry.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Compute simple tabulation hash value with 32 bits for a 64bit integer number.
+    pub fn hash(&self, x: u64) -> u32 {
+        self.hash_chars(byte_chunks_64(x))
+    }
+
+    /// Compute the hash value of a key of a custom type, see `TabKey`.
+    ///
+    /// This is equivalent to hashing the 64bit integer with the same byte chunks.
+    pub fn hash_key<K: TabKey<Chunks = [u8; 8]>>(&self, key: K) -> u32 {
+        self.hash(from_byte_chunks_64(key.chunks()))
+    }
+
+    /// Compute the hash value of a plain old data key of 8 bytes.
+    ///
+    /// The key is hashed like the 64bit integer with the same bytes in memory,
+    /// i.e. the integer obtained by transmuting the key (native byte order).
+    /// Keys of a different size are rejected at compile time.
+    #[cfg(feature = "bytemuck")]
+    pub fn hash_pod<T: bytemuck::Pod>(&self, key: &T) -> u32 {
+        const {
+            assert!(
+                core::mem::size_of::<T>() == 8,
+                "key size does not match the table"
+            )
+        };
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(bytemuck::bytes_of(key));
+        self.hash(u64::from_ne_bytes(bytes))
+    }
+
+    /// Borrow this hash function as a closure, e.g. for `Iterator::map`.
+    pub fn as_fn(&self) -> impl Fn(u64) -> u32 + '_ {
+        move |x| self.hash(x)
+    }
+
+    /// Turn this hash function into an owned closure, e.g. to move it into a thread.
+    pub fn into_fn(self) -> impl Fn(u64) -> u32 {
+        move |x| self.hash(x)
+    }
+}
+
+/// A universal hash function for 48-bit integers using simple tabulation.
+///
+/// Keys are stored in a `u64` with the top 16 bits set to 0, e.g. 2-bit encoded
+/// 24-mers or MAC addresses. With six columns instead of eight, the table takes
+/// 12 KiB instead of the 16 KiB of `Tab64Simple`, so more of it fits into the cache,
+/// and each key takes six instead of eight lookups. Hash values are 64-bit.
+/// Given the same first six columns, hash values are the same as for `Tab64Simple`
+/// if entry 0 of its last two columns is 0.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::Tab48Simple;
+///
+/// let keys = vec![0, 8, 15, 47, 11, 0xFFFF_FFFF_FFFF];
+/// let simple = Tab48Simple::new_with_seed(42);
+/// for k in keys {
+///     println!("{}", simple.hash(k));
+/// }
+/// ```
+pub type Tab48Simple = TabSimple<u64, 6>;
+
+impl Tab48Simple {
+    /// Create a new simple tabulation hash function from the little-endian bytes of a table.
+    ///
+    /// Entries are read column by column, each column from entry 0 to 255,
+    /// as written by `to_le_bytes`. Combined with `include_bytes!`,
+    /// this embeds a table into the binary at compile time.
+    pub const fn from_le_bytes(bytes: &[u8; 12288]) -> Self {
+        let mut table = [[0; 256]; 6];
+        let mut i = 0;
+        while i < 6 * 256 {
+            table[i / 256][i % 256] = read_le(bytes, 8 * i, 8) as u64;
+            i += 1;
+        }
+        Tab48Simple { table }
+    }
+
+    /// Export the table of this hash function as little-endian bytes.
+    ///
+    /// This is the inverse of `from_le_bytes`.
+    pub fn to_le_bytes(&self) -> [u8; 12288] {
+        let mut bytes = [0; 12288];
+        for (chunk, entry) in bytes.chunks_mut(8).zip(self.table.iter().flatten()) {
+            chunk.copy_from_slice(&entry.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Compute simple tabulation hash value for a 48bit integer number stored in a `u64`.
+    ///
+    /// The low six bytes of `x` are looked up in the six columns of the table,
+    /// just like the low six bytes of a key in the first six columns of a `Tab64Simple`.
+    ///
+    /// # Panics
+    /// In debug builds, if any of the top 16 bits of `x` is set.
+    /// In release builds, the top 16 bits are ignored.
+    pub fn hash(&self, x: u64) -> u64 {
+        debug_assert!(
+            x >> 48 == 0,
+            "Tab48Simple can only hash 48bit keys, got {:#x}",
+            x
+        );
+        let c = byte_chunks_64(x);
+        self.hash_chars([c[0], c[1], c[2], c[3], c[4], c[5]])
+    }
+
+    /// Compute the hash value of an IPv4 socket address.
+    ///
+    /// The socket address is hashed as the 48bit integer `u32::from(ip) << 16 | port`,
+    /// i.e. the octets of the address followed by the port, both in network byte order
+    /// (big endian), e.g. `192.168.0.1:8080` is hashed as `0xC0A8_0001_1F90`.
+    #[cfg(feature = "net")]
+    pub fn hash_socketaddr_v4(&self, a: SocketAddrV4) -> u64 {
+        self.hash((u64::from(u32::from(*a.ip())) << 16) | u64::from(a.port()))
+    }
+
+    /// Borrow this hash function as a closure, e.g. for `Iterator::map`.
+    pub fn as_fn(&self) -> impl Fn(u64) -> u64 + '_ {
+        move |x| self.hash(x)
+    }
+
+    /// Turn this hash function into an owned closure, e.g. to move it into a thread.
+    pub fn into_fn(self) -> impl Fn(u64) -> u64 {
+        move |x| self.hash(x)
+    }
+}
+
+impl TabHash for Tab48Simple {
+    type Key = u64;
+    type Entry = u64;
+
+    fn hash(&self, key: u64) -> u64 {
+        Tab48Simple::hash(self, key)
+    }
+
+    #[cfg(feature = "getrandom")]
+    fn random() -> Self {
+        Tab48Simple::new()
+    }
+
+    fn new_with_seed(seed: u64) -> Self {
+        Tab48Simple::new_with_seed(seed)
+    }
+
+    fn id(&self) -> u64 {
+        Tab48Simple::id(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn to_vec(&self) -> Vec<Vec<u64>> {
+        Tab48Simple::to_vec(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn from_vec(table_data: Vec<Vec<u64>>) -> Self {
+        Tab48Simple::from_vec(table_data)
+    }
+}
+
+/// A universal hash function for 128-bit integers using simple tabulation.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::Tab128Simple;
+///
+/// let keys = vec![0, 8, 15, 47, 11];
+/// let simple = Tab128Simple::new_with_seed(42);
+/// for k in keys {
+///     println!("{}", simple.hash(k));
+/// }
+/// ```
+pub type Tab128Simple = TabSimple<u128, 16>;
+
+impl Tab128Simple {
+    /// Create a new simple tabulation hash function from the little-endian bytes of a table.
+    ///
+    /// Entries are read column by column, each column from entry 0 to 255,
+    /// as written by `to_le_bytes`. Combined with `include_bytes!`,
+    /// this embeds a table into the binary at compile time.
+    pub const fn from_le_bytes(bytes: &[u8; 65536]) -> Self {
+        let mut table = [[0; 256]; 16];
+        let mut i = 0;
+        while i < 16 * 256 {
+            table[i / 256][i % 256] = read_le(bytes, 16 * i, 16);
+            i += 1;
+        }
+        Tab128Simple { table }
+    }
+
+    /// Export the table of this hash function as little-endian bytes.
+    ///
+    /// This is the inverse of `from_le_bytes`.
+    pub fn to_le_bytes(&self) -> [u8; 65536] {
+        let mut bytes = [0; 65536];
+        for (chunk, entry) in bytes.chunks_mut(16).zip(self.table.iter().flatten()) {
+            chunk.copy_from_slice(&entry.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Compute simple tabulation hash value for a 128bit integer number.
+    pub fn hash(&self, x: u128) -> u128 {
+        self.hash_chars(byte_chunks_128(x))
+    }
+
+    /// Compute the hash value of an IPv6 address.
+    ///
+    /// The address is hashed as the integer `u128::from(a)`, i.e. its octets in network
+    /// byte order (big endian). Hence the first octet is looked up in column 15
+    /// and the last octet in column 0, e.g. `2001:db8::1` is hashed as
+    /// `0x2001_0DB8_0000_0000_0000_0000_0000_0001`.
+    #[cfg(feature = "net")]
+    pub fn hash_ipv6(&self, a: Ipv6Addr) -> u128 {
+        self.hash(u128::from(a))
+    }
+
+    /// Compute the hash value of a key of a custom type, see `TabKey`.
+    ///
+    /// This is equivalent to hashing the 128bit integer with the same byte chunks.
+    pub fn hash_key<K: TabKey<Chunks = [u8; 16]>>(&self, key: K) -> u128 {
+        self.hash(from_byte_chunks_128(key.chunks()))
+    }
+
+    /// Compute the hash value of a plain old data key of 16 bytes.
+    ///
+    /// The key is hashed like the 128bit integer with the same bytes in memory,
+    /// i.e. the integer obtained by transmuting the key (native byte order).
+    /// Keys of a different size are rejected at compile time.
+    #[cfg(feature = "bytemuck")]
+    pub fn hash_pod<T: bytemuck::Pod>(&self, key: &T) -> u128 {
+        const {
+            assert!(
+                core::mem::size_of::<T>() == 16,
+                "key size does not match the table"
+            )
+        };
+        let mut bytes = [0; 16];
+        bytes.copy_from_slice(bytemuck::bytes_of(key));
+        self.hash(u128::from_ne_bytes(bytes))
+    }
+
+    /// Borrow this hash function as a closure, e.g. for `Iterator::map`.
+    pub fn as_fn(&self) -> impl Fn(u128) -> u128 + '_ {
+        move |x| self.hash(x)
+    }
+
+    /// Turn this hash function into an owned closure, e.g. to move it into a thread.
+    pub fn into_fn(self) -> impl Fn(u128) -> u128 {
+        move |x| self.hash(x)
+    }
+}
+
+impl TabHash for Tab128Simple {
+    type Key = u128;
+    type Entry = u128;
+
+    fn hash(&self, key: u128) -> u128 {
+        Tab128Simple::hash(self, key)
+    }
+
+    #[cfg(feature = "getrandom")]
+    fn random() -> Self {
+        Tab128Simple::new()
+    }
+
+    fn new_with_seed(seed: u64) -> Self {
+        Tab128Simple::new_with_seed(seed)
+    }
+
+    fn id(&self) -> u64 {
+        Tab128Simple::id(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn to_vec(&self) -> Vec<Vec<u128>> {
+        Tab128Simple::to_vec(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn from_vec(table_data: Vec<Vec<u128>>) -> Self {
+        Tab128Simple::from_vec(table_data)
+    }
+}
+
+/// A universal hash function for 32-bit integers using twisted tabulation.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::Tab32Twisted;
+///
+/// let keys = vec![0, 8, 15, 47, 11];
+/// let twisted = Tab32Twisted::new_with_seed(42);
+/// for k in keys {
+///     println!("{}", twisted.hash(k));
+/// }
+/// ```
+pub type Tab32Twisted = TabTwisted<u64, 4>;
+
+impl Tab32Twisted {
+    /// Create a new twisted tabulation hash function from the little-endian bytes of a table.
+    ///
+    /// Entries are read column by column, each column from entry 0 to 255,
+    /// as written by `to_le_bytes`. Combined with `include_bytes!`,
+    /// this embeds a table into the binary at compile time.
+    pub const fn from_le_bytes(bytes: &[u8; 8192]) -> Self {
+        let mut table = [[0; 256]; 4];
+        let mut i = 0;
+        while i < 4 * 256 {
+            table[i / 256][i % 256] = read_le(bytes, 8 * i, 8) as u64;
+            i += 1;
+        }
+        Tab32Twisted { table }
+    }
+
+    /// Export the table of this hash function as little-endian bytes.
+    ///
+    /// This is the inverse of `from_le_bytes`.
+    pub fn to_le_bytes(&self) -> [u8; 8192] {
+        let mut bytes = [0; 8192];
+        for (chunk, entry) in bytes.chunks_mut(8).zip(self.table.iter().flatten()) {
+            chunk.copy_from_slice(&entry.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Compute twisted tabulation hash value for a 32bit integer number.
+    pub fn hash(&self, x: u32) -> u32 {
+        // shift out the 32 low bits of the resulting hash
+        self.state(x).overflowing_shr(32).0 as u32
+    }
+
+    /// Compute the hash values of a slice of keys into `out`.
+    ///
+    /// This is equivalent to `out[i] = hash(keys[i])` for every `i`, but faster for large
+    /// slices, since keys are hashed in blocks of four with interleaved lookups.
+    ///
+    /// # Panics
+    /// If `keys` and `out` have different lengths.
+    pub fn hash_into(&self, keys: &[u32], out: &mut [u32]) {
+        // shift out the 32 low bits of the resulting hash
+        self.hash_batch(keys, out, byte_chunks_32, |h| (h >> 32) as u32)
+    }
+
+    /// Compute the hash values of a slice of keys, see `hash_into`.
+    #[cfg(feature = "alloc")]
+    pub fn hash_many(&self, keys: &[u32]) -> Vec<u32> {
+        let mut out = vec![0; keys.len()];
+        self.hash_into(keys, &mut out);
+        out
+    }
+
+    /// Overwrite every key of a slice with its hash value, e.g. to pseudonymize ids.
+    ///
+    /// This is equivalent to `keys[i] = hash(keys[i])` for every `i`, and batches the
+    /// lookups like `hash_into` without allocating.
+    pub fn hash_in_place(&self, keys: &mut [u32]) {
+        hash_in_place_with(keys, |keys, out| self.hash_into(keys, out))
+    }
+
+    /// Compute the hash value of a key together with its twisted last character.
+    ///
+    /// The character is `c = chunks[3] ^ (h & 0xFF)`, where `chunks[3]` is the most
+    /// significant byte of the key and `h` the XOR of the entries of the first three
+    /// columns, i.e. the index of the entry of the last column. The hash value is
+    /// identical to `hash(x)`. This is meant for inspecting how the twist acts on
+    /// structured sets of keys.
+    pub fn hash_with_twist(&self, x: u32) -> (u32, u8) {
+        let (h, c) = self.state_with_twist(x);
+        ((h >> 32) as u32, c)
+    }
+
+    /// Compute two 32bit hash values of a key from the internal 64bit state.
+    ///
+    /// The first value is the high half of the state, i.e. identical to `hash(x)`.
+    /// The second value is the low half, which `hash` discards.
+    /// This yields two hash values for the cost of one, e.g. for Bloom filters
+    /// or cuckoo hashing.
+    ///
+    /// Note that the low half depends on the twist: the lowest byte of the XOR of the
+    /// first three entries selects the entry of the last column, and then becomes
+    /// the lowest byte of the low half (XORed with that entry). Hence the low half is
+    /// not a twisted tabulation hash value itself and not independent of the high half
+    /// in the theoretical sense, although both look independent in practice.
+    pub fn hash_pair(&self, x: u32) -> (u32, u32) {
+        let h = self.state(x);
+        ((h >> 32) as u32, h as u32)
+    }
+
+    /// Get the double hashing probe sequence of a key for an open addressing table.
+    ///
+    /// With `(h1, h2) = hash_pair(x)`, the sequence starts at slot `h1 mod capacity`
+    /// and advances by a step derived from `h2`, modulo the capacity. The step is
+    /// `1 + h2 mod (capacity - 1)`, increased (wrapping around to 1) until it is coprime
+    /// to the capacity. For power-of-two capacities, this makes the step odd.
+    /// Hence the sequence visits every slot exactly once, i.e. it yields `capacity` probes,
+    /// and ends. Use `cycle` to repeat it. No memory is allocated.
+    pub fn probe_sequence(
+        &self,
+        x: u32,
+        capacity: usize,
+    ) -> impl ExactSizeIterator<Item = usize> + Clone {
+        let (h1, h2) = self.hash_pair(x);
+        probe::ProbeSequence::new(h1, h2, capacity)
+    }
+
+    /// Compute the 64bit state of twisted tabulation hashing for a key
+    fn state(&self, x: u32) -> u64 {
+        self.state_with_twist(x).0
+    }
+
+    /// Compute the 64bit state together with the twisted last character
+    fn state_with_twist(&self, x: u32) -> (u64, u8) {
+        self.twisted_state(byte_chunks_32(x))
+    }
+
+    /// Map the hash value of a key to a bucket in `0..n`.
+    ///
+    /// This is the canonical way to map keys to `n` buckets. It uses Lemire's
+    /// multiply-shift reduction `((hash as u64 * n as u64) >> 32) as u32`,
+    /// which is faster than `hash % n` and, unlike it, keeps the order of hash values.
+    /// Each bucket receives either `floor(2^32 / n)` or `ceil(2^32 / n)` hash values.
+    ///
+    /// # Panics
+    /// In debug builds, if `n` is 0. In release builds, the result is then 0.
+    pub fn hash_to_range(&self, x: u32, n: u32) -> u32 {
+        debug_assert!(n > 0, "cannot map hash values to an empty range");
+        ((u64::from(self.hash(x)) * u64::from(n)) >> 32) as u32
+    }
+
+    /// Map the hash value of a key to a floating point number in `[0, 1)`.
+    ///
+    /// The result is `hash as f64 * 2^-32`, which is exact since all 32 bits of the
+    /// hash value fit into the mantissa. The largest result is `1 - 2^-32`.
+    pub fn hash_unit(&self, x: u32) -> f64 {
+        f64::from(self.hash(x)) * (1.0 / (1_u64 << 32) as f64)
+    }
+
+    /// Decide deterministically whether to sample a key with probability `p`.
+    ///
+    /// This is `hash_unit(x) < p`, i.e. `hash as f64 * 2^-32 < p`, where the product is exact.
+    /// Equivalently, the 32bit hash value is smaller than `p * 2^32` (as a real number).
+    /// Hence `ceil(p * 2^32)` of the hash values are sampled, i.e. keys are sampled with a
+    /// probability of `p` rounded up to a multiple of `2^-32`, and a key sampled for some `p`
+    /// is sampled for all larger ones.
+    /// `p = 0` samples no key and `p = 1` samples all keys.
+    ///
+    /// # Panics
+    /// If `p` is not in `[0, 1]`, e.g. if it is NaN.
+    pub fn sample(&self, x: u32, p: f64) -> bool {
+        assert!(
+            (0.0..=1.0).contains(&p),
+            "sampling probabilities have to be in [0, 1], not {}",
+            p
+        );
+        self.hash_unit(x) < p
+    }
+
+    /// Decide deterministically whether to sample a key at a rate of `ppm` parts per million.
+    ///
+    /// This is `hash_to_range(x, 1_000_000) < ppm`, i.e. a key is sampled if its bucket
+    /// among one million buckets is one of the first `ppm`. Since each bucket receives
+    /// the same number of hash values up to one, the rate is exact up to a relative error
+    /// of `1_000_000 / 2^32`. A key sampled at some rate is sampled at all larger ones.
+    ///
+    /// # Panics
+    /// If `ppm` is larger than `1_000_000`.
+    pub fn sample_rate_per_million(&self, x: u32, ppm: u32) -> bool {
+        assert!(
+            ppm <= 1_000_000,
+            "sampling rates can be at most 1000000 parts per million, not {}",
+            ppm
+        );
+        self.hash_to_range(x, 1_000_000) < ppm
+    }
+
+    /// Get the top `k` bits of the hash value of a key.
+    ///
+    /// The top bits are the most significant bits of the hash value returned by `hash`,
+    /// returned as the low `k` bits of the result, i.e. `hash >> (32 - k)`.
+    /// For `k = 0`, the result is 0.
+    ///
+    /// # Panics
+    /// If `k` is larger than 32.
+    pub fn hash_top_bits(&self, x: u32, k: u32) -> u32 {
+        assert!(k <= 32, "cannot take {} bits of a 32bit hash value", k);
+        self.hash(x).checked_shr(32 - k).unwrap_or(0)
+    }
+
+    /// Split the hash value of a key into its top `p` bits and the remaining low bits.
+    ///
+    /// Returns `(hash >> (32 - p), hash & (2^(32 - p) - 1))`, i.e. the top `p` bits as
+    /// with `hash_top_bits`, e.g. as register index, and the other `32 - p` bits, e.g. to
+    /// compute a rank. The hash value is `top << (32 - p) | low`.
+    /// For `p = 0`, the top bits are 0; for `p = 32`, the low bits are 0.
+    ///
+    /// # Panics
+    /// If `p` is larger than 32.
+    pub fn hash_split_bits(&self, x: u32, p: u32) -> (u32, u32) {
+        assert!(p <= 32, "cannot take {} bits of a 32bit hash value", p);
+        let h = self.hash(x);
+        let low_bits = 32 - p;
+        let top = h.checked_shr(low_bits).unwrap_or(0);
+        let low = h & u32::MAX.checked_shr(p).unwrap_or(0);
+        (top, low)
+    }
+
+    /// Compute the hash value of a signed 32bit integer number.
+    ///
+    /// The two's complement bit pattern of `x` is hashed,
+    /// i.e. this is the same as `hash(x as u32)`, e.g. `-1` hashes like `u32::MAX`.
+    pub fn hash_i32(&self, x: i32) -> u32 {
+        self.hash(x as u32)
+    }
+
+    /// Compute the hash value of a non-zero 32bit integer number.
+    ///
+    /// This is the same as `hash(x.get())`.
+    pub fn hash_nonzero(&self, x: NonZeroU32) -> u32 {
+        self.hash(x.get())
+    }
+
+    /// Compute a hash value of a 32bit integer number that is never 0.
+    ///
+    /// A hash value of 0 is replaced by 1, other hash values are returned unchanged.
+    /// Hence keys hashing to 0 collide with keys hashing to 1, i.e. the hash value 1
+    /// occurs with twice the probability of any other nonzero value, and 0 never occurs.
+    pub fn hash_nonzero_out(&self, x: u32) -> NonZeroU32 {
+        NonZeroU32::new(self.hash(x)).unwrap_or(NonZeroU32::MIN)
+    }
+
+    /// Compute the hash value of a 32bit floating point number.
+    ///
+    /// The canonical bit pattern of `x` is hashed, see `canonical_f32_bits`, so `0.0`
+    /// and `-0.0` have the same hash value, as do all NaNs.
+    pub fn hash_f32(&self, x: f32) -> u32 {
+        self.hash(canonical_f32_bits(x))
+    }
+
+    /// Compute the hash value of an IPv4 address.
+    ///
+    /// The address is hashed as the integer `u32::from(a)`, i.e. its octets in network
+    /// byte order (big endian). Hence the first octet is looked up in column 3
+    /// and the last octet in column 0, e.g. `192.168.0.1` is hashed as `0xC0A8_0001`.
+    #[cfg(feature = "net")]
+    pub fn hash_ipv4(&self, a: Ipv4Addr) -> u32 {
+        self.hash(u32::from(a))
+    }
+
+    /// Compute the hash value of a key of a custom type, see `TabKey`.
+    ///
+    /// This is equivalent to hashing the 32bit integer with the same byte chunks.
+    pub fn hash_key<K: TabKey<Chunks = [u8; 4]>>(&self, key: K) -> u32 {
+        self.hash(from_byte_chunks_32(key.chunks()))
+    }
+
+    /// Compute the hash value of a plain old data key of 4 bytes.
+    ///
+    /// The key is hashed like the 32bit integer with the same bytes in memory,
+    /// i.e. the integer obtained by transmuting the key (native byte order).
+    /// Keys of a different size are rejected at compile time.
+    #[cfg(feature = "bytemuck")]
+    pub fn hash_pod<T: bytemuck::Pod>(&self, key: &T) -> u32 {
+        const {
+            assert!(
+                core::mem::size_of::<T>() == 4,
+                "key size does not match the table"
+            )
+        };
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(bytemuck::bytes_of(key));
+        self.hash(u32::from_ne_bytes(bytes))
+    }
+
+    /// Borrow this hash function as a closure, e.g. for `Iterator::map`.
+    pub fn as_fn(&self) -> impl Fn(u32) -> u32 + '_ {
+        move |x| self.hash(x)
+    }
+
+    /// Turn this hash function into an owned closure, e.g. to move it into a thread.
+    pub fn into_fn(self) -> impl Fn(u32) -> u32 {
+        move |x| self.hash(x)
+    }
+}
+
+impl TabHash for Tab32Twisted {
+    type Key = u32;
+    type Entry = u64;
+
+    fn hash(&self, key: u32) -> u32 {
+        Tab32Twisted::hash(self, key)
+    }
+
+    #[cfg(feature = "getrandom")]
+    fn random() -> Self {
+        Tab32Twisted::new()
+    }
+
+    fn new_with_seed(seed: u64) -> Self {
+        Tab32Twisted::new_with_seed(seed)
+    }
+
+    fn id(&self) -> u64 {
+        Tab32Twisted::id(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn to_vec(&self) -> Vec<Vec<u64>> {
+        Tab32Twisted::to_vec(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn from_vec(table_data: Vec<Vec<u64>>) -> Self {
+        Tab32Twisted::from_vec(table_data)
+    }
+}
+
+impl Hash32Fn for Tab32Twisted {
+    fn hash32(&self, x: u32) -> u32 {
+        self.hash(x)
+    }
+}
+
+impl Mixer32 for Tab32Twisted {
+    fn mix(&self, x: u32) -> u32 {
+        self.hash(x)
+    }
+}
+
+/// A universal hash function for 32-bit integers with 64-bit hash values using twisted tabulation.
+///
+/// Like `Tab32Twisted`, but with 128-bit table entries, of which the high 64 bits
+/// of the final state are returned as hash value. See `Tab32To64Simple`.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::Tab32To64Twisted;
+///
+/// let keys = vec![0, 8, 15, 47, 11];
+/// let twisted = Tab32To64Twisted::new_with_seed(42);
+/// for k in keys {
+///     println!("{}", twisted.hash(k));
+/// }
+/// ```
+pub type Tab32To64Twisted = TabTwisted<u128, 4>;
+
+impl Tab32To64Twisted {
+    /// Create a new twisted tabulation hash function from the little-endian bytes of a table.
+    ///
+    /// Entries are read column by column, each column from entry 0 to 255,
+    /// as written by `to_le_bytes`. Combined with `include_bytes!`,
+    /// this embeds a table into the binary at compile time.
+    pub const fn from_le_bytes(bytes: &[u8; 16384]) -> Self {
+        let mut table = [[0; 256]; 4];
+        let mut i = 0;
+        while i < 4 * 256 {
+            table[i / 256][i % 256] = read_le(bytes, 16 * i, 16);
+            i += 1;
+        }
+        Tab32To64Twisted { table }
+    }
+
+    /// Export the table of this hash function as little-endian bytes.
+    ///
+    /// This is the inverse of `from_le_bytes`.
+    pub fn to_le_bytes(&self) -> [u8; 16384] {
+        let mut bytes = [0; 16384];
+        for (chunk, entry) in bytes.chunks_mut(16).zip(self.table.iter().flatten()) {
+            chunk.copy_from_slice(&entry.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Compute twisted tabulation hash value with 64 bits for a 32bit integer number.
+    pub fn hash(&self, x: u32) -> u64 {
+        let (h, _) = self.twisted_state(byte_chunks_32(x));
+        // shift out the 64 low bits of the resulting hash
+        (h >> 64) as u64
+    }
+
+    /// Compute the hash value of a key of a custom type, see `TabKey`.
+    ///
+    /// This is equivalent to hashing the 32bit integer with the same byte chunks.
+    pub fn hash_key<K: TabKey<Chunks = [u8; 4]>>(&self, key: K) -> u64 {
+        self.hash(from_byte_chunks_32(key.chunks()))
+    }
+
+    /// Compute the SimHash fingerprint of a set of weighted features.
+    ///
+    /// For each bit position, the weights of all features whose hash value has a 1 bit
+    /// at this position are added, and the weights of all others are subtracted.
+    /// Bit `i` of the fingerprint is 1 if the sum of position `i` is positive, and 0 if
+    /// it is negative or exactly 0. Similar sets of weighted features have fingerprints
+    /// with a small `hamming_distance`.
+    ///
+    /// - A feature with a weight of 0 does not contribute, features with a NaN weight are ignored.
+    /// - A negative weight counts against the bits of the feature, i.e. it contributes
+    ///   like the complement of its hash value with the absolute weight.
+    /// - Features occurring several times contribute once per occurrence.
+    /// - The empty feature set, and any set whose weights cancel out, has the fingerprint 0.
+    pub fn simhash(&self, features: impl IntoIterator<Item = (u32, f32)>) -> u64 {
+        simhash_of(features.into_iter().map(|(x, w)| (self.hash(x), w)))
+    }
+
+    /// Compute the hash value of a plain old data key of 4 bytes.
+    ///
+    /// The key is hashed like the 32bit integer with the same bytes in memory,
+    /// i.e. the integer obtained by transmuting the key (native byte order).
+    /// Keys of a different size are rejected at compile time.
+    #[cfg(feature = "bytemuck")]
+    pub fn hash_pod<T: bytemuck::Pod>(&self, key: &T) -> u64 {
+        const {
+            assert!(
+                core::mem::size_of::<T>() == 4,
+                "key size does not match the table"
+            )
+        };
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(bytemuck::bytes_of(key));
+        self.hash(u32::from_ne_bytes(bytes))
+    }
+
+    /// Borrow this hash function as a closure, e.g. for `Iterator::map`.
+    pub fn as_fn(&self) -> impl Fn(u32) -> u64 + '_ {
+        move |x| self.hash(x)
+    }
+
+    /// Turn this hash function into an owned closure, e.g. to move it into a thread.
+    pub fn into_fn(self) -> impl Fn(u32) -> u64 {
+        move |x| self.hash(x)
+    }
+}
+
+/// A universal hash function for 64-bit integers using twisted tabulation.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::Tab64Twisted;
+///
+/// let keys = vec![0, 8, 15, 47, 11];
+/// let twisted = Tab64Twisted::new_with_seed(42);
+/// for k in keys {
+///     println!("{}", twisted.hash(k));
+/// }
+/// ```
+pub type Tab64Twisted = TabTwisted<u128, 8>;
+
+impl Tab64Twisted {
+    /// Create a new twisted tabulation hash function from the little-endian bytes of a table.
+    ///
+    /// Entries are read column by column, each column from entry 0 to 255,
+    /// as written by `to_le_bytes`. Combined with `include_bytes!`,
+    /// this embeds a table into the binary at compile time.
+    pub const fn from_le_bytes(bytes: &[u8; 32768]) -> Self {
+        let mut table = [[0; 256]; 8];
+        let mut i = 0;
+        while i < 8 * 256 {
+            table[i / 256][i % 256] = read_le(bytes, 16 * i, 16);
+            i += 1;
+        }
+        Tab64Twisted { table }
+    }
+
+    /// Export the table of this hash function as little-endian bytes.
+    ///
+    /// This is the inverse of `from_le_bytes`.
+    pub fn to_le_bytes(&self) -> [u8; 32768] {
+        let mut bytes = [0; 32768];
+        for (chunk, entry) in bytes.chunks_mut(16).zip(self.table.iter().flatten()) {
+            chunk.copy_from_slice(&entry.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Compute twisted tabulation hash value for a 64bit integer number.
+    pub fn hash(&self, x: u64) -> u64 {
+        self.hash_with_twist(x).0
+    }
+
+    /// Compute the hash values of a slice of keys into `out`.
+    ///
+    /// This is equivalent to `out[i] = hash(keys[i])` for every `i`, but faster for large
+    /// slices, since keys are hashed in blocks of four with interleaved lookups.
+    /// With the `simd` feature, keys are hashed in blocks of eight with AVX-512 gathers
+    /// for the first seven columns instead if the CPU supports them.
+    ///
+    /// # Panics
+    /// If `keys` and `out` have different lengths.
+    pub fn hash_into(&self, keys: &[u64], out: &mut [u64]) {
+        let hashed = self.hash_blocks_simd(keys, out);
+        // shift out the 64 low bits of the resulting hash
+        self.hash_batch(&keys[hashed..], &mut out[hashed..], byte_chunks_64, |h| {
+            (h >> 64) as u64
+        })
+    }
+
+    /// Hash blocks of keys with the AVX-512 kernel of the `simd` feature if it is selected
+    /// by `batch_kernel`, returning the number of keys hashed.
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    fn hash_blocks_simd(&self, keys: &[u64], out: &mut [u64]) -> usize {
+        assert_eq!(
+            keys.len(),
+            out.len(),
+            "the output needs one slot for every key"
+        );
+        match batch_kernel() {
+            BatchKernel::Avx512 => simd::hash_blocks_64_twisted(&self.table, keys, out),
+            _ => 0,
+        }
+    }
+
+    /// Without SIMD kernels, all keys are hashed by the scalar batch loop
+    #[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+    fn hash_blocks_simd(&self, _keys: &[u64], _out: &mut [u64]) -> usize {
+        0
+    }
+
+    /// Compute the hash values of a slice of keys, see `hash_into`.
+    #[cfg(feature = "alloc")]
+    pub fn hash_many(&self, keys: &[u64]) -> Vec<u64> {
+        let mut out = vec![0; keys.len()];
+        self.hash_into(keys, &mut out);
+        out
+    }
+
+    /// Overwrite every key of a slice with its hash value, e.g. to pseudonymize ids.
+    ///
+    /// This is equivalent to `keys[i] = hash(keys[i])` for every `i`, and batches the
+    /// lookups like `hash_into` without allocating.
+    pub fn hash_in_place(&self, keys: &mut [u64]) {
+        hash_in_place_with(keys, |keys, out| self.hash_into(keys, out))
+    }
+
+    /// Compute the hash value of a key together with its twisted last character.
+    ///
+    /// The character is `c = chunks[7] ^ (h & 0xFF)`, where `chunks[7]` is the most
+    /// significant byte of the key and `h` the XOR of the entries of the first seven
+    /// columns, i.e. the index of the entry of the last column. The hash value is
+    /// identical to `hash(x)`. This is meant for inspecting how the twist acts on
+    /// structured sets of keys.
+    pub fn hash_with_twist(&self, x: u64) -> (u64, u8) {
+        let (h, c) = self.twisted_state(byte_chunks_64(x));
+        // shift out the 64 low bits of the resulting hash
+        ((h >> 64) as u64, c)
+    }
+
+    /// Map the hash value of a key to a bucket in `0..n`.
+    ///
+    /// This is the canonical way to map keys to `n` buckets. It uses Lemire's
+    /// multiply-shift reduction `((hash as u128 * n as u128) >> 64) as u64`,
+    /// which is faster than `hash % n` and, unlike it, keeps the order of hash values.
+    /// Each bucket receives either `floor(2^64 / n)` or `ceil(2^64 / n)` hash values.
+    ///
+    /// On targets without 64-bit pointers, which usually lack a fast widening
+    /// multiplication, the high bits of the product are computed from four
+    /// 32x32->64 bit products instead. Both paths yield identical results.
+    ///
+    /// # Panics
+    /// In debug builds, if `n` is 0. In release builds, the result is then 0.
+    pub fn hash_to_range(&self, x: u64, n: u64) -> u64 {
+        debug_assert!(n > 0, "cannot map hash values to an empty range");
+        mul_high_64(self.hash(x), n)
+    }
+
+    /// Get two candidate buckets in `0..n` for a key, e.g. for load balancing with
+    /// the "power of two choices".
+    ///
+    /// The candidates are derived from the high and the low 32 bits of the hash value.
+    /// Both halves use the same entries of the table, including the one selected by the
+    /// twist. However, the twist only depends on the low 64 bits of the entries,
+    /// which are not part of the hash value. Given the entries selected for a key,
+    /// both halves are made of independent bits, so they behave like hash values of two
+    /// twisted tabulation hash functions that share their twist, but not their entries.
+    /// Each half is mapped to `0..n` with the multiply-shift reduction of `hash_to_range`,
+    /// i.e. `(half * n) >> 32`. Hence both buckets are smaller than `n`, but at most
+    /// `2^32` buckets can be chosen, which is no restriction for practical numbers of bins.
+    ///
+    /// # Panics
+    /// In debug builds, if `n` is 0. In release builds, both buckets are then 0.
+    pub fn two_choices(&self, x: u64, n: u64) -> (u64, u64) {
+        debug_assert!(n > 0, "cannot map hash values to an empty range");
+        let h = self.hash(x);
+        (
+            mul_high_64(h & 0xFFFF_FFFF_0000_0000, n),
+            mul_high_64(h << 32, n),
+        )
+    }
+
+    /// Choose the less loaded one of the two candidate buckets of a key.
+    ///
+    /// The candidates are `two_choices(x, loads.len())`. The bucket with the smaller
+    /// load is returned, the first candidate on ties. Updating the loads is
+    /// left to the caller.
+    ///
+    /// # Panics
+    /// If `loads` is empty.
+    pub fn choose_less_loaded(&self, x: u64, loads: &[u64]) -> usize {
+        assert!(!loads.is_empty(), "cannot choose from zero buckets");
+        let (first, second) = self.two_choices(x, loads.len() as u64);
+        let (first, second) = (first as usize, second as usize);
+        if loads[second] < loads[first] {
+            second
+        } else {
+            first
+        }
+    }
+
+    /// Choose the node for a key by rendezvous (highest random weight) hashing.
+    ///
+    /// Each node gets the score `hash(hash(x) ^ hash(node))` and the node with the
+    /// highest score is returned, the one with the largest id on ties. Removing a node
+    /// only moves the keys it was chosen for, and adding one only moves keys to it.
+    /// The order of the nodes does not matter, but each evaluation takes linear time,
+    /// so this suits small numbers of nodes. See `ConsistentRing` for many nodes.
+    ///
+    /// # Errors
+    /// If `nodes` is empty.
+    pub fn rendezvous(&self, x: u64, nodes: &[u64]) -> Result<u64, NoNodesError> {
+        rendezvous::rendezvous(|y| self.hash(y), x, nodes)
+    }
+
+    /// Choose the node for a key by weighted rendezvous hashing.
+    ///
+    /// The score `hash(hash(x) ^ hash(node))` of `rendezvous` is mapped to `u` in `(0, 1)`
+    /// like in `hash_exp`, and the node with the highest `-weight / ln(u)` is returned,
+    /// the one with the largest id on ties. Then each node is chosen for a fraction of the
+    /// keys proportional to its weight. Removing a node only moves the keys it was chosen for.
+    ///
+    /// # Errors
+    /// If `nodes` is empty.
+    ///
+    /// # Panics
+    /// If a weight is not positive and finite.
+    #[cfg(feature = "std")]
+    pub fn rendezvous_weighted(&self, x: u64, nodes: &[(u64, f64)]) -> Result<u64, NoNodesError> {
+        rendezvous::rendezvous_weighted(|y| self.hash(y), x, nodes)
+    }
+
+    /// Map a key to one of `num_buckets` buckets with jump consistent hashing.
+    ///
+    /// This is the algorithm of Lamping and Veach, with the linear congruential generator
+    /// replaced by hashing: step `i` draws `r = hash(hash(x) ^ hash(i))` and jumps from
+    /// bucket `b` to `floor((b + 1) * 2^53 / ((r >> 11) + 1))`, until the jump leaves
+    /// `0..num_buckets`. The last bucket reached is returned. This takes `O(ln(num_buckets))`
+    /// hash evaluations and no memory besides the table.
+    ///
+    /// The steps are drawn like for `Tab64Simple::jump_bucket`, which needs the hashed step.
+    /// With twisted tabulation, the draws `hash(hash(x) + i)` of consecutive steps would
+    /// already differ in a pseudo-random entry of the last column, but both types use
+    /// the same scheme.
+    ///
+    /// Every bucket receives `1 / num_buckets` of the keys in expectation. The sequence of
+    /// jumps of a key does not depend on `num_buckets`, so increasing `num_buckets`
+    /// only moves keys into the new buckets, and decreasing it only moves the keys of
+    /// the removed (highest) buckets. Unlike with a ring, only the last bucket can be removed.
+    ///
+    /// # Panics
+    /// If `num_buckets` is 0.
+    pub fn jump_bucket(&self, x: u64, num_buckets: u32) -> u32 {
+        assert!(num_buckets > 0, "cannot map keys to zero buckets");
+        let h = self.hash(x);
+        let (mut bucket, mut jump) = (0, 0);
+        let mut step: u64 = 0;
+        while jump < u64::from(num_buckets) {
+            bucket = jump;
+            let r = self.hash(h ^ self.hash(step));
+            step += 1;
+            // saturates at u64::MAX for large jumps
+            jump = ((bucket + 1) as f64 * ((1_u64 << 53) as f64 / ((r >> 11) + 1) as f64)) as u64;
+        }
+        bucket as u32
+    }
+
+    /// Get `k` positions in `0..m` of a key for a Bloom filter from a single hash evaluation.
+    ///
+    /// This uses the double hashing scheme of Kirsch and Mitzenmacher: with `h1` and `h2`
+    /// the high and the low 32 bits of the hash value, position `i` is
+    /// `(h1 + i * h2) mod m`. Hence a single table with one lookup per byte of the key
+    /// suffices for any `k`, instead of `k` hash functions.
+    ///
+    /// The positions of a key are not independent. They form an arithmetic progression,
+    /// which repeats positions if `h2 mod m` shares a divisor with `m` and collapses to a
+    /// single position if `h2 mod m` is 0. Kirsch and Mitzenmacher show that the false
+    /// positive rate is asymptotically the same as with `k` independent hash functions,
+    /// nonetheless. Both halves are reduced with `mod m`, which is slightly biased
+    /// unless `m` is much smaller than `2^32`.
+    ///
+    /// # Panics
+    /// If `m` is 0.
+    pub fn bloom_indices(
+        &self,
+        x: u64,
+        k: u32,
+        m: usize,
+    ) -> impl ExactSizeIterator<Item = usize> + Clone {
+        assert!(m > 0, "a Bloom filter needs at least one position");
+        let m = m as u64;
+        let h = self.hash(x);
+        let mut position = (h >> 32) % m;
+        let step = (h & 0xFFFF_FFFF) % m;
+        (0..k).map(move |_| {
+            let current = position;
+            // add the step modulo m without overflowing
+            position = if position >= m - step {
+                position - (m - step)
+            } else {
+                position + step
+            };
+            current as usize
+        })
+    }
+
+    /// Get a sign of `+1` or `-1` for a key, e.g. for Count Sketch or the AMS sketch.
+    ///
+    /// The sign is taken from the lowest bit of the hash value: `+1` if it is 0,
+    /// `-1` if it is 1. Use `hash_bucket_sign` to get a bucket along with the sign.
+    pub fn hash_sign(&self, x: u64) -> i8 {
+        1 - 2 * (self.hash(x) & 1) as i8
+    }
+
+    /// Get a bucket in `0..width` and a sign of `+1` or `-1` for a key.
+    ///
+    /// The sign is the lowest bit of the hash value, just like `hash_sign`.
+    /// The bucket is computed from the upper 63 bits with the multiply-shift reduction
+    /// of `hash_to_range`, i.e. `((hash & !1) * width) >> 64`. Bucket and sign are taken
+    /// from disjoint bits of the high halves of the entries. The twist only selects the
+    /// entry of the last column from the low halves, which are independent of the high
+    /// halves, so the sign is independent of the bucket.
+    ///
+    /// # Panics
+    /// If `width` is 0.
+    pub fn hash_bucket_sign(&self, x: u64, width: usize) -> (usize, i8) {
+        assert!(width > 0, "cannot map hash values to an empty range");
+        bucket_and_sign(self.hash(x), width)
+    }
+
+    /// Split the hash value of a key into a register index and a rank for HyperLogLog.
+    ///
+    /// The register index consists of the top `p` bits of the hash value, i.e. it is
+    /// in `0..2^p`. The rank is the position of the first 1 bit in the remaining `64 - p` bits,
+    /// counting from 1 at the most significant one, i.e. their number of leading zeros plus 1.
+    /// If all of them are 0, the rank is `65 - p`. See `HyperLogLog` for a sketch using this.
+    ///
+    /// # Panics
+    /// If `p` is not in `4..=18`.
+    pub fn hll_parts(&self, x: u64, p: u8) -> (usize, u8) {
+        hll_parts(self.hash(x), p)
+    }
+
+    /// Map the hash value of a key to a floating point number in `[0, 1)`.
+    ///
+    /// The result is `(hash >> 11) as f64 * 2^-53`, i.e. the top 53 bits of the hash
+    /// value are used as mantissa, which is exact. The largest result is `1 - 2^-53`.
+    pub fn hash_unit(&self, x: u64) -> f64 {
+        (self.hash(x) >> 11) as f64 * (1.0 / (1_u64 << 53) as f64)
+    }
+
+    /// Decide deterministically whether to sample a key with probability `p`.
+    ///
+    /// This is `hash_unit(x) < p`, i.e. `(hash >> 11) as f64 * 2^-53 < p`, where the product
+    /// is exact. Equivalently, the top 53 bits of the hash value are smaller than `p * 2^53`
+    /// (as a real number). Hence `ceil(p * 2^53)` of these values are sampled, i.e. keys are
+    /// sampled with a probability of `p` rounded up to a multiple of `2^-53`, and a key sampled
+    /// for some `p` is sampled for all larger ones.
+    /// `p = 0` samples no key and `p = 1` samples all keys.
+    ///
+    /// # Panics
+    /// If `p` is not in `[0, 1]`, e.g. if it is NaN.
+    pub fn sample(&self, x: u64, p: f64) -> bool {
+        assert!(
+            (0.0..=1.0).contains(&p),
+            "sampling probabilities have to be in [0, 1], not {}",
+            p
+        );
+        self.hash_unit(x) < p
+    }
+
+    /// Decide deterministically whether to sample a key at a rate of `ppm` parts per million.
+    ///
+    /// This is `hash_to_range(x, 1_000_000) < ppm`, i.e. a key is sampled if its bucket
+    /// among one million buckets is one of the first `ppm`. Since each bucket receives
+    /// the same number of hash values up to one, the rate is exact up to a relative error
+    /// of `1_000_000 / 2^64`. A key sampled at some rate is sampled at all larger ones.
+    ///
+    /// # Panics
+    /// If `ppm` is larger than `1_000_000`.
+    pub fn sample_rate_per_million(&self, x: u64, ppm: u32) -> bool {
+        assert!(
+            ppm <= 1_000_000,
+            "sampling rates can be at most 1000000 parts per million, not {}",
+            ppm
+        );
+        self.hash_to_range(x, 1_000_000) < u64::from(ppm)
+    }
+
+    /// Map the hash value of a key to an exponentially distributed number with mean 1.
+    ///
+    /// The top 52 bits of the hash value are mapped to the midpoint of their interval,
+    /// `u = ((hash >> 12) as f64 + 0.5) * 2^-52`, which is exact in `f64`. Hence `u` lies
+    /// in `[2^-53, 1 - 2^-53]` and is never 0, unlike `hash_unit`. The result is `-ln(u)`,
+    /// which lies between about `1.1e-16` and `53 ln 2`, about 36.7.
+    #[cfg(feature = "std")]
+    pub fn hash_exp(&self, x: u64) -> f64 {
+        -open_unit(self.hash(x)).ln()
+    }
+
+    /// Compute the priority of a key with a weight, e.g. for priority sampling.
+    ///
+    /// The priority is `hash_exp(x) / weight`, i.e. exponentially distributed with rate
+    /// `weight`. Selecting the keys with the smallest priorities yields a weighted sample
+    /// without replacement.
+    ///
+    /// # Panics
+    /// If `weight` is not positive and finite.
+    #[cfg(feature = "std")]
+    pub fn priority(&self, x: u64, weight: f64) -> f64 {
+        assert!(
+            weight > 0.0 && weight.is_finite(),
+            "weights have to be positive and finite, not {}",
+            weight
+        );
+        self.hash_exp(x) / weight
+    }
+
+    /// Get the top `k` bits of the hash value of a key.
+    ///
+    /// The top bits are the most significant bits of the hash value returned by `hash`,
+    /// returned as the low `k` bits of the result, i.e. `hash >> (64 - k)`.
+    /// For `k = 0`, the result is 0.
+    ///
+    /// # Panics
+    /// If `k` is larger than 64.
+    pub fn hash_top_bits(&self, x: u64, k: u32) -> u64 {
+        assert!(k <= 64, "cannot take {} bits of a 64bit hash value", k);
+        self.hash(x).checked_shr(64 - k).unwrap_or(0)
+    }
+
+    /// Split the hash value of a key into its top `p` bits and the remaining low bits.
+    ///
+    /// Returns `(hash >> (64 - p), hash & (2^(64 - p) - 1))`, i.e. the top `p` bits as
+    /// with `hash_top_bits`, e.g. as register index, and the other `64 - p` bits, e.g. to
+    /// compute a rank. The hash value is `top << (64 - p) | low`.
+    /// For `p = 0`, the top bits are 0; for `p = 64`, the low bits are 0.
+    ///
+    /// # Panics
+    /// If `p` is larger than 64.
+    pub fn hash_split_bits(&self, x: u64, p: u32) -> (u64, u64) {
+        assert!(p <= 64, "cannot take {} bits of a 64bit hash value", p);
+        let h = self.hash(x);
+        let low_bits = 64 - p;
+        let top = h.checked_shr(low_bits).unwrap_or(0);
+        let low = h & u64::MAX.checked_shr(p).unwrap_or(0);
+        (top, low)
+    }
+
+    /// Compute a nonzero fingerprint of `bits` bits for a key, e.g. for cuckoo filters.
+    ///
+    /// Fingerprints are taken from the lowest 16 bits of the hash value, `low = hash as u16`,
+    /// which are mapped to `1..2^bits` by `((low * (2^bits - 1)) >> 16) + 1`. Hence they are
+    /// never 0, which usually marks empty slots, and all nonzero fingerprints are about
+    /// equally likely. Use `fingerprint_with_zero` if 0 is a valid fingerprint.
+    ///
+    /// Buckets are taken from the top bits instead: `hash_top_bits(x, k)` uses disjoint bits
+    /// for `k <= 48`, and so does `hash_to_range(x, n)` for powers of two `n <= 2^48`.
+    /// For other `n`, the low 16 bits change the bucket of at most a fraction of `n / 2^48`
+    /// of the hash values by one, e.g. about `2^-16` for a table with `2^32` buckets.
+    ///
+    /// # Panics
+    /// If `bits` is 0 or larger than 16.
+    pub fn fingerprint(&self, x: u64, bits: u8) -> u16 {
+        let range = (1_u32 << check_fingerprint_bits(bits)) - 1;
+        (((u32::from(self.hash(x) as u16) * range) >> 16) + 1) as u16
+    }
+
+    /// Compute a fingerprint of `bits` bits for a key, which may be 0.
+    ///
+    /// The fingerprint is the lowest `bits` bits of the hash value, i.e.
+    /// `hash & (2^bits - 1)`. Like `fingerprint`, this only uses the lowest 16 bits,
+    /// which are disjoint from the bits used by `hash_top_bits` and `hash_to_range`
+    /// for tables of up to `2^48` buckets.
+    ///
+    /// # Panics
+    /// If `bits` is 0 or larger than 16.
+    pub fn fingerprint_with_zero(&self, x: u64, bits: u8) -> u16 {
+        let mask = (1_u32 << check_fingerprint_bits(bits)) - 1;
+        (self.hash(x) as u32 & mask) as u16
+    }
+
+    /// Compute the hash value of a signed 64bit integer number.
+    ///
+    /// The two's complement bit pattern of `x` is hashed,
+    /// i.e. this is the same as `hash(x as u64)`, e.g. `-1` hashes like `u64::MAX`.
+    pub fn hash_i64(&self, x: i64) -> u64 {
+        self.hash(x as u64)
+    }
+
+    /// Compute the hash value of a non-zero 64bit integer number.
+    ///
+    /// This is the same as `hash(x.get())`.
+    pub fn hash_nonzero(&self, x: NonZeroU64) -> u64 {
+        self.hash(x.get())
+    }
+
+    /// Compute a hash value of a 64bit integer number that is never 0.
+    ///
+    /// A hash value of 0 is replaced by 1, other hash values are returned unchanged.
+    /// Hence keys hashing to 0 collide with keys hashing to 1, i.e. the hash value 1
+    /// occurs with twice the probability of any other nonzero value, and 0 never occurs.
+    pub fn hash_nonzero_out(&self, x: u64) -> NonZeroU64 {
+        NonZeroU64::new(self.hash(x)).unwrap_or(NonZeroU64::MIN)
+    }
+
+    /// Compute the hash value of a 64bit floating point number.
+    ///
+    /// The canonical bit pattern of `x` is hashed, see `canonical_f64_bits`, so `0.0`
+    /// and `-0.0` have the same hash value, as do all NaNs.
+    pub fn hash_f64(&self, x: f64) -> u64 {
+        self.hash(canonical_f64_bits(x))
+    }
+
+    /// Compute the hash value of an IPv4 socket address.
+    ///
+    /// The socket address is hashed as the 48bit integer `u32::from(ip) << 16 | port`,
+    /// i.e. the octets of the address followed by the port, both in network byte order
+    /// (big endian), e.g. `192.168.0.1:8080` is hashed as `0xC0A8_0001_1F90`.
+    #[cfg(feature = "net")]
+    pub fn hash_socketaddr_v4(&self, a: SocketAddrV4) -> u64 {
+        self.hash((u64::from(u32::from(*a.ip())) << 16) | u64::from(a.port()))
+    }
+
+    /// Compute the hash value of a byte string of at most 8 bytes.
+    ///
+    /// The string is packed into a 64bit number with `pack_short_bytes`, which is hashed.
+    ///
+    /// # Panics
+    /// If the string is longer than 8 bytes.
+    pub fn hash_short_bytes(&self, b: &[u8]) -> u64 {
+        self.hash(pack_short_bytes(b))
+    }
+
+    /// Compute the hash value of a string of at most 8 bytes, e.g. a short ASCII tag.
+    ///
+    /// This is the same as `hash_short_bytes` of the UTF-8 bytes of the string.
+    ///
+    /// # Panics
+    /// If the string is longer than 8 bytes.
+    pub fn hash_short_str(&self, s: &str) -> u64 {
+        self.hash_short_bytes(s.as_bytes())
+    }
+
+    /// Compute the hash value of a 2-bit packed k-mer of length `k`, see the `kmer` module.
+    ///
+    /// All bits except for the lowest `2 * k` are cleared before hashing,
+    /// so that unused high bits do not change the hash value.
+    ///
+    /// # Panics
+    /// If `k` is larger than 32.
+    pub fn hash_kmer(&self, packed: u64, k: usize) -> u64 {
+        self.hash(kmer::mask_kmer(packed, k))
+    }
+
+    /// Compute a strand-independent hash value of a 2-bit packed k-mer of length `k`.
+    ///
+    /// The canonical form of the k-mer, the lexicographically smaller one of the k-mer
+    /// and its reverse complement, is hashed (see `kmer::canonical_kmer`).
+    /// Hence a k-mer and its reverse complement have the same hash value.
+    ///
+    /// # Panics
+    /// If `k` is larger than 32.
+    pub fn hash_canonical(&self, packed: u64, k: usize) -> u64 {
+        self.hash(kmer::canonical_kmer(packed, k))
+    }
+
+    /// Select the minimizers of a sequence of k-mers.
+    ///
+    /// For each window of `w` consecutive k-mers, the k-mer with the smallest
+    /// hash value is selected. If several k-mers of a window have the smallest
+    /// hash value, the leftmost one is selected. Each selected k-mer is reported
+    /// once as `(position, kmer)`, in increasing order of position, even if
+    /// it is selected for several overlapping windows. A sequence of less than
+    /// `w` k-mers forms a single window, i.e. its minimum is reported.
+    ///
+    /// K-mers are hashed with `hash`, use `kmer::canonical_kmer` (or `kmer::mask_kmer`)
+    /// to prepare them. This takes linear time in the number of k-mers.
+    ///
+    /// # Panics
+    /// If `w` is 0.
+    #[cfg(feature = "alloc")]
+    pub fn minimizers<'a, I>(
+        &'a self,
+        kmers: I,
+        w: usize,
+    ) -> impl Iterator<Item = (usize, u64)> + 'a
+    where
+        I: IntoIterator<Item = u64>,
+        I::IntoIter: 'a,
+    {
+        kmer::Minimizers::new(kmers.into_iter(), w, move |x| self.hash(x))
+    }
+
+    /// Compute the hash value of a key of a custom type, see `TabKey`.
+    ///
+    /// This is equivalent to hashing the 64bit integer with the same byte chunks.
+    pub fn hash_key<K: TabKey<Chunks = [u8; 8]>>(&self, key: K) -> u64 {
+        self.hash(from_byte_chunks_64(key.chunks()))
+    }
+
+    /// Compute the hash value of a plain old data key of 8 bytes.
+    ///
+    /// The key is hashed like the 64bit integer with the same bytes in memory,
+    /// i.e. the integer obtained by transmuting the key (native byte order).
+    /// Keys of a different size are rejected at compile time.
+    #[cfg(feature = "bytemuck")]
+    pub fn hash_pod<T: bytemuck::Pod>(&self, key: &T) -> u64 {
+        const {
+            assert!(
+                core::mem::size_of::<T>() == 8,
+                "key size does not match the table"
+            )
+        };
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(bytemuck::bytes_of(key));
+        self.hash(u64::from_ne_bytes(bytes))
+    }
+
+    /// Borrow this hash function as a closure, e.g. for `Iterator::map`.
+    pub fn as_fn(&self) -> impl Fn(u64) -> u64 + '_ {
+        move |x| self.hash(x)
+    }
+
+    /// Turn this hash function into an owned closure, e.g. to move it into a thread.
+    pub fn into_fn(self) -> impl Fn(u64) -> u64 {
+        move |x| self.hash(x)
+    }
+}
+
+impl TabHash for Tab64Twisted {
+    type Key = u64;
+    type Entry = u128;
+
+    fn hash(&self, key: u64) -> u64 {
+        Tab64Twisted::hash(self, key)
+    }
+
+    #[cfg(feature = "getrandom")]
+    fn random() -> Self {
+        Tab64Twisted::new()
+    }
+
+    fn new_with_seed(seed: u64) -> Self {
+        Tab64Twisted::new_with_seed(seed)
+    }
+
+    fn id(&self) -> u64 {
+        Tab64Twisted::id(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn to_vec(&self) -> Vec<Vec<u128>> {
+        Tab64Twisted::to_vec(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn from_vec(table_data: Vec<Vec<u128>>) -> Self {
+        Tab64Twisted::from_vec(table_data)
+    }
+}
+
+impl Hash64Fn for Tab64Twisted {
+    fn hash64(&self, x: u64) -> u64 {
+        self.hash(x)
+    }
+}
+
+impl Mixer64 for Tab64Twisted {
+    fn mix(&self, x: u64) -> u64 {
+        self.hash(x)
+    }
+}
+
+/// A universal hash function for 128-bit integers using twisted tabulation.
+///
+/// Twisted tabulation for 128-bit keys requires 256-bit table entries.
+/// Each entry is stored as a pair of 128-bit words `[low, high]`.
+/// Since the resulting table takes up 128 KiB, it is stored on the heap.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::Tab128Twisted;
+///
+/// let keys = vec![0, 8, 15, 47, 11];
+/// let twisted = Tab128Twisted::new();
+/// for k in keys {
+///     println!("{}", twisted.hash(k));
+/// }
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[cfg(feature = "alloc")]
+pub struct Tab128Twisted {
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "tab128twisted_from_vec"))]
+    table: Box<[[[u128; 2]; 256]; 16]>,
+}
+
+#[cfg(feature = "alloc")]
+impl Tab128Twisted {
+    /// Create a new twisted tabulation hash function with a random table.
+    #[cfg(feature = "getrandom")]
+    pub fn new() -> Self {
+        Tab128Twisted::from_rng(&mut os_seeded_rng())
+    }
+
+    /// Create a new twisted tabulation hash function with a table read directly from OS entropy.
+    ///
+    /// The table is filled from the entropy source of the operating system
+    /// without any user space random number generator in between.
+    /// Entries are read as little-endian integers from consecutive bytes,
+    /// column by column, each column from entry 0 to 255.
+    #[cfg(feature = "getrandom")]
+    pub fn new_from_os_entropy() -> Result<Self, EntropyError> {
+        let mut source = OsEntropy;
+        let mut reader = EntropyReader::new(&mut source);
+        let mut table = Tab128Twisted::zeroed_table();
+        for column in table.iter_mut() {
+            for entry in column.iter_mut() {
+                *entry = reader.read_entry()?;
+            }
+        }
+        Ok(Tab128Twisted { table })
+    }
+
+    /// Create a new twisted tabulation hash function seeded from an entropy source.
+    ///
+    /// A 256-bit seed is read from `source` and expanded into the table
+    /// using a xoshiro256** generator, just like `new` does with the
+    /// entropy source of the operating system.
+    pub fn new_from_entropy<E: EntropySource + ?Sized>(source: &mut E) -> Result<Self, E::Error> {
+        let mut rng = Xoshiro256StarStar::from_entropy(source)?;
+        Ok(Tab128Twisted::from_rng(&mut rng))
+    }
+
+    /// Create a new twisted tabulation hash function with a table derived from a seed.
+    ///
+    /// The same seed always yields the same table, see the crate
+    /// documentation for how the seed is expanded.
+    pub fn new_with_seed(seed: u64) -> Self {
+        Tab128Twisted::from_rng(&mut SplitMix64::new(seed))
+    }
+
+    /// Create a new twisted tabulation hash function from a 32-byte seed.
+    ///
+    /// The seed is used as ChaCha20 key to expand it into the table,
+    /// see the crate documentation for details.
+    pub fn from_seed_bytes(seed: [u8; 32]) -> Self {
+        Tab128Twisted::from_rng(&mut ChaCha20Rng::from_seed(seed))
+    }
+
+    /// Derive the child hash function `index` from this hash function.
+    ///
+    /// Children are deterministic, i.e. a parent with the same table always
+    /// yields the same child for an index, and distinct indices yield
+    /// distinct children. The table of a child is drawn from a ChaCha20
+    /// generator keyed with a digest of the parent table, using `index`
+    /// as stream, see the crate documentation for details.
+    pub fn derive_child(&self, index: u64) -> Self {
+        Tab128Twisted::from_rng(&mut child_rng(&self.table[..], index))
+    }
+
+    /// Get the id of this hash function, a 64-bit digest of its table.
+    ///
+    /// Equal tables always have the same id, across versions, platforms
+    /// and serialization round trips. See the crate documentation for the algorithm.
+    pub fn id(&self) -> u64 {
+        table_digest(&self.table[..])
+    }
+
+    /// Create a new twisted tabulation hash function with a table drawn from `rng`.
+    ///
+    /// See the crate documentation for the order in which entries are drawn.
+    #[cfg(feature = "rand")]
+    pub fn new_with_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        Tab128Twisted::from_rng(rng)
+    }
+
+    /// Generate a table drawing entries from `rng` column by column
+    fn from_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        Tab128Twisted {
+            table: Tab128Twisted::random_table(rng),
+        }
+    }
+
+    /// Replace the table of this hash function with a new random table.
+    ///
+    /// The new table is generated completely before it replaces the old one.
+    #[cfg(feature = "getrandom")]
+    pub fn reseed(&mut self) {
+        *self = Tab128Twisted::new();
+    }
+
+    /// Replace the table of this hash function with a table drawn from `rng`.
+    ///
+    /// Entries are drawn in the same order as for `new_with_rng`.
+    #[cfg(feature = "rand")]
+    pub fn reseed_with_rng<R: RngCore + ?Sized>(&mut self, rng: &mut R) {
+        *self = Tab128Twisted::from_rng(rng);
+    }
+
+    /// Replace the table of this hash function with a table derived from a seed.
+    ///
+    /// Afterwards, this hash function is identical to `new_with_seed(seed)`.
+    pub fn reseed_from_seed(&mut self, seed: u64) {
+        *self = Tab128Twisted::new_with_seed(seed);
+    }
+
+    /// Replace column `i` of the table with new random entries.
+    ///
+    /// All other columns are left untouched.
+    ///
+    /// # Panics
+    /// If `i` is not a valid column index, i.e. at least the number of bytes of a key.
+    #[cfg(feature = "getrandom")]
+    pub fn reseed_column(&mut self, i: usize) {
+        reseed_column_from_rng(&mut self.table[..], i, &mut os_seeded_rng());
+    }
+
+    /// Replace column `i` of the table with entries derived from a seed.
+    ///
+    /// The new column is identical to the first column of `new_with_seed(seed)`.
+    /// All other columns are left untouched.
+    ///
+    /// # Panics
+    /// If `i` is not a valid column index, i.e. at least the number of bytes of a key.
+    pub fn reseed_column_from_seed(&mut self, i: usize, seed: u64) {
+        reseed_column_from_rng(&mut self.table[..], i, &mut SplitMix64::new(seed));
+    }
+
+    /// Export the table of this hash function as a nested vector.
+    #[cfg(feature = "alloc")]
+    pub fn to_vec(&self) -> Vec<Vec<[u128; 2]>> {
+        let mut vec = Vec::with_capacity(16);
+        for col in self.table.iter() {
+            vec.push(col.to_vec());
+        }
+        vec
+    }
+
+    /// Create a new twisted tabulation hash function from a nested vector.
+    #[cfg(feature = "alloc")]
+    pub fn from_vec(table_data: Vec<Vec<[u128; 2]>>) -> Self {
+        let mut table = Tab128Twisted::zeroed_table();
+        assert_eq!(table_data.len(), 16);
+        for (i, column) in table_data.iter().enumerate() {
+            assert_eq!(column.len(), 256);
+            for (j, value) in column.iter().enumerate() {
+                table[i][j] = *value;
+            }
+        }
+        Tab128Twisted { table }
+    }
+
+    /// Create a new twisted tabulation hash function with a given table.
+    pub fn with_table(table: [[[u128; 2]; 256]; 16]) -> Self {
+        Tab128Twisted {
+            table: Box::new(table),
+        }
+    }
+
+    /// Create a new twisted tabulation hash function from the little-endian bytes of a table.
+    ///
+    /// Entries are read column by column, each column from entry 0 to 255,
+    /// as written by `to_le_bytes`. Each entry consists of its low 128 bits
+    /// followed by its high 128 bits.
+    /// Unlike for the other hash functions, this is not a `const fn`,
+    /// since the table is allocated on the heap.
+    pub fn from_le_bytes(bytes: &[u8; 131072]) -> Self {
+        let mut table = Tab128Twisted::zeroed_table();
+        for (i, chunk) in bytes.chunks(32).enumerate() {
+            table[i / 256][i % 256] = [read_le(chunk, 0, 16), read_le(chunk, 16, 16)];
+        }
+        Tab128Twisted { table }
+    }
+
+    /// Export the table of this hash function as little-endian bytes.
+    ///
+    /// This is the inverse of `from_le_bytes`.
+    pub fn to_le_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(131072);
+        for entry in self.table.iter().flatten() {
+            bytes.extend_from_slice(&entry[0].to_le_bytes());
+            bytes.extend_from_slice(&entry[1].to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Allocate a table filled with zeros directly on the heap
+    fn zeroed_table() -> Box<[[[u128; 2]; 256]; 16]> {
+        let columns = vec![[[0_u128; 2]; 256]; 16].into_boxed_slice();
+        match core::convert::TryFrom::try_from(columns) {
+            Ok(table) => table,
+            Err(_) => unreachable!("table has exactly 16 columns"),
+        }
+    }
+
+    /// Generate a table of 256bit entries drawn from `rng`
+    fn random_table<R: RngCore + ?Sized>(rng: &mut R) -> Box<[[[u128; 2]; 256]; 16]> {
+        let mut table = Tab128Twisted::zeroed_table();
+        for column in table.iter_mut() {
+            for entry in column.iter_mut() {
+                *entry = TableEntry::from_rng(rng);
+            }
+        }
+        table
+    }
+
+    /// Get the table used by this hash function.
+    pub fn get_table(&self) -> [[[u128; 2]; 256]; 16] {
+        *self.table
     }
 
-    /// Create a new simple tabulation hash function with a random table.
-    pub fn from_vec(table_data: Vec<Vec<u32>>) -> Self {
-        let mut table = [[0_u32; 256]; 4];
-        assert_eq!(table_data.len(), 4);
-        for (i, column) in table_data.iter().enumerate() {
-            assert_eq!(column.len(), 256);
-            for (j, value) in column.iter().enumerate() {
-                table[i][j] = *value;
-            }
+    /// Compute twisted tabulation hash value for a 128bit integer number.
+    pub fn hash(&self, x: u128) -> u128 {
+        // the 256bit hash value is kept as a low and a high 128bit word
+        let mut h_low: u128 = 0;
+        let mut h_high: u128 = 0;
+        let chunks = byte_chunks_128(x);
+        for (i, c) in chunks[0..15].iter().enumerate() {
+            let [low, high] = self.table[i][*c as usize];
+            h_low ^= low;
+            h_high ^= high;
         }
-        Tab32Simple { table }
+        // compute address for last chunk by XOring the lowest byte of the
+        // current hash value with the content of the last chunk of the key
+        let c = chunks[15] ^ (h_low & 0xFF) as u8;
+        // only the high word is needed after the twist, since the
+        // 128 low bits of the resulting hash are shifted out
+        h_high ^= self.table[15][c as usize][1];
+
+        h_high
     }
 
-    /// Create a new simple tabulation hash function with a given table.
-    pub fn with_table(table: [[u32; 256]; 4]) -> Self {
-        Tab32Simple { table }
+    /// Compute the hash value of an IPv6 address.
+    ///
+    /// The address is hashed as the integer `u128::from(a)`, i.e. its octets in network
+    /// byte order (big endian). Hence the first octet is looked up in column 15
+    /// and the last octet in column 0, e.g. `2001:db8::1` is hashed as
+    /// `0x2001_0DB8_0000_0000_0000_0000_0000_0001`.
+    #[cfg(feature = "net")]
+    pub fn hash_ipv6(&self, a: Ipv6Addr) -> u128 {
+        self.hash(u128::from(a))
     }
 
-    /// Generate a table of 32bit uints for simple tabulation hashing
-    fn initialize_table() -> [[u32; 256]; 4] {
-        let table: [[u32; 256]; 4] =
-            array_init::array_init(|_| array_init::array_init(|_| rand::random()));
-        table
+    /// Compute the hash value of a key of a custom type, see `TabKey`.
+    ///
+    /// This is equivalent to hashing the 128bit integer with the same byte chunks.
+    pub fn hash_key<K: TabKey<Chunks = [u8; 16]>>(&self, key: K) -> u128 {
+        self.hash(from_byte_chunks_128(key.chunks()))
     }
 
-    /// Get the table used by this hash function.
-    pub fn get_table(&self) -> [[u32; 256]; 4] {
-        self.table
+    /// Compute the hash value of a plain old data key of 16 bytes.
+    ///
+    /// The key is hashed like the 128bit integer with the same bytes in memory,
+    /// i.e. the integer obtained by transmuting the key (native byte order).
+    /// Keys of a different size are rejected at compile time.
+    #[cfg(feature = "bytemuck")]
+    pub fn hash_pod<T: bytemuck::Pod>(&self, key: &T) -> u128 {
+        const {
+            assert!(
+                core::mem::size_of::<T>() == 16,
+                "key size does not match the table"
+            )
+        };
+        let mut bytes = [0; 16];
+        bytes.copy_from_slice(bytemuck::bytes_of(key));
+        self.hash(u128::from_ne_bytes(bytes))
     }
 
-    /// Compute simple tabulation hash value for a 32bit integer number.
-    pub fn hash(&self, x: u32) -> u32 {
-        let mut h: u32 = 0; // initialize hash values as 0
+    /// Borrow this hash function as a closure, e.g. for `Iterator::map`.
+    pub fn as_fn(&self) -> impl Fn(u128) -> u128 + '_ {
+        move |x| self.hash(x)
+    }
 
-        for (i, c) in byte_chunks_32(x).iter().enumerate() {
-            h ^= self.table[i as usize][*c as usize];
-        }
-        h
+    /// Turn this hash function into an owned closure, e.g. to move it into a thread.
+    pub fn into_fn(self) -> impl Fn(u128) -> u128 {
+        move |x| self.hash(x)
     }
 }
 
+/// Table of 256bit entries used by `Tab128Twisted`
+#[cfg(feature = "serde")]
+type Tab128TwistedTable = [[[u128; 2]; 256]; 16];
+
 /// Custom serialization converting nested array to a nested vec (cannot be derived)
-fn tab32simple_from_vec<'de, D>(deserializer: D) -> Result<[[u32; 256]; 4], D::Error>
+#[cfg(feature = "serde")]
+fn tab128twisted_from_vec<'de, D>(deserializer: D) -> Result<Box<Tab128TwistedTable>, D::Error>
 where
     D: Deserializer<'de>,
 {
-    let table_data: Vec<Vec<u32>> = Deserialize::deserialize(deserializer)?;
+    let table_data: Vec<Vec<[u128; 2]>> = Deserialize::deserialize(deserializer)?;
 
-    let mut table = [[0_u32; 256]; 4];
-    assert_eq!(table_data.len(), 4);
+    let mut table = Tab128Twisted::zeroed_table();
+    assert_eq!(table_data.len(), 16);
     for (i, column) in table_data.iter().enumerate() {
         assert_eq!(column.len(), 256);
         for (j, value) in column.iter().enumerate() {
@@ -158,51 +4268,250 @@ where
     Ok(table)
 }
 
+#[cfg(feature = "serde")]
 #[derive(Clone, Serialize)]
-struct _VecTab32Simple {
-    table: Vec<Vec<u32>>,
+struct _VecTab128Twisted {
+    table: Vec<Vec<[u128; 2]>>,
 }
 
-impl Serialize for Tab32Simple {
+#[cfg(feature = "serde")]
+impl Serialize for Tab128Twisted {
     fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        _VecTab32Simple {
+        _VecTab128Twisted {
             table: self.to_vec(),
         }
         .serialize(s)
     }
 }
 
-/// A universal hash function for 64-bit integers using simple tabulation.
+#[cfg(all(feature = "rand", feature = "alloc"))]
+impl Distribution<Tab128Twisted> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Tab128Twisted {
+        Tab128Twisted::from_rng(rng)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TabHash for Tab128Twisted {
+    type Key = u128;
+    type Entry = [u128; 2];
+
+    fn hash(&self, key: u128) -> u128 {
+        Tab128Twisted::hash(self, key)
+    }
+
+    #[cfg(feature = "getrandom")]
+    fn random() -> Self {
+        Tab128Twisted::new()
+    }
+
+    fn new_with_seed(seed: u64) -> Self {
+        Tab128Twisted::new_with_seed(seed)
+    }
+
+    fn id(&self) -> u64 {
+        Tab128Twisted::id(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn to_vec(&self) -> Vec<Vec<[u128; 2]>> {
+        Tab128Twisted::to_vec(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn from_vec(table_data: Vec<Vec<[u128; 2]>>) -> Self {
+        Tab128Twisted::from_vec(table_data)
+    }
+}
+
+#[cfg(all(feature = "getrandom", feature = "alloc"))]
+impl Default for Tab128Twisted {
+    /// Create a new hash function with a random table, just like `new`.
+    ///
+    /// Note that the default is randomized, i.e. every call yields a different
+    /// hash function. Use `new_with_seed` for a deterministic hash function.
+    fn default() -> Self {
+        Tab128Twisted::new()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::fmt::Debug for Tab128Twisted {
+    /// Show the id of the hash function as fingerprint instead of all entries.
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let fingerprint = self.id();
+        f.debug_struct("Tab128Twisted")
+            .field("fingerprint", &format_args!("{:#018x}", fingerprint))
+            .finish()
+    }
+}
+
+/// A universal hash function for pairs of 32-bit integers using simple tabulation.
+///
+/// The pair is treated as a key of eight characters, four bytes of each element,
+/// and hashed with a table of eight columns of 32-bit entries.
+/// This is the same as hashing the packed key `(b as u64) << 32 | a as u64` with
+/// `Tab64Simple` and keeping the low 32 bits of the hash value, if the tables
+/// of both functions agree in these bits. Otherwise, it is an independent family.
+/// The elements of a pair are looked up in different columns, so the pair `(a, b)`
+/// and the swapped pair `(b, a)` hash to independent values.
 ///
 /// Usage:
 /// ```rust
-/// use tab_hash::Tab64Simple;
+/// use tab_hash::Tab32Pair;
 ///
-/// let keys = vec![0, 8, 15, 47, 11];
-/// let simple = Tab64Simple::new();
-/// for k in keys {
-///     println!("{}", simple.hash(k));
+/// let edges = vec![(0, 8), (8, 0), (47, 11)];
+/// let pair = Tab32Pair::new_with_seed(42);
+/// for (source, target) in edges {
+///     println!("{}", pair.hash_pair(source, target));
 /// }
 /// ```
-#[derive(Clone, Deserialize)]
-pub struct Tab64Simple {
-    #[serde(deserialize_with = "tab64simple_from_vec")]
-    table: [[u64; 256]; 8],
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+pub struct Tab32Pair {
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "tab32pair_from_vec"))]
+    table: [[u32; 256]; 8],
 }
 
-impl Tab64Simple {
+impl Tab32Pair {
     /// Create a new simple tabulation hash function with a random table.
+    #[cfg(feature = "getrandom")]
     pub fn new() -> Self {
-        Tab64Simple {
-            table: Tab64Simple::initialize_table(),
+        Tab32Pair::from_rng(&mut os_seeded_rng())
+    }
+
+    /// Create a new simple tabulation hash function with a table read directly from OS entropy.
+    ///
+    /// The table is filled from the entropy source of the operating system
+    /// without any user space random number generator in between.
+    /// Entries are read as little-endian integers from consecutive bytes,
+    /// column by column, each column from entry 0 to 255.
+    #[cfg(feature = "getrandom")]
+    pub fn new_from_os_entropy() -> Result<Self, EntropyError> {
+        let mut source = OsEntropy;
+        let mut reader = EntropyReader::new(&mut source);
+        let mut table = [[0; 256]; 8];
+        for column in table.iter_mut() {
+            for entry in column.iter_mut() {
+                *entry = reader.read_entry()?;
+            }
         }
+        Ok(Tab32Pair { table })
     }
 
-    /// Create a new simple tabulation hash function with a random table.
-    pub fn to_vec(&self) -> Vec<Vec<u64>> {
+    /// Create a new simple tabulation hash function seeded from an entropy source.
+    ///
+    /// A 256-bit seed is read from `source` and expanded into the table
+    /// using a xoshiro256** generator, just like `new` does with the
+    /// entropy source of the operating system.
+    pub fn new_from_entropy<E: EntropySource + ?Sized>(source: &mut E) -> Result<Self, E::Error> {
+        let mut rng = Xoshiro256StarStar::from_entropy(source)?;
+        Ok(Tab32Pair::from_rng(&mut rng))
+    }
+
+    /// Create a new simple tabulation hash function with a table derived from a seed.
+    ///
+    /// The same seed always yields the same table, see the crate
+    /// documentation for how the seed is expanded.
+    pub fn new_with_seed(seed: u64) -> Self {
+        Tab32Pair::from_rng(&mut SplitMix64::new(seed))
+    }
+
+    /// Create a new simple tabulation hash function from a 32-byte seed.
+    ///
+    /// The seed is used as ChaCha20 key to expand it into the table,
+    /// see the crate documentation for details.
+    pub fn from_seed_bytes(seed: [u8; 32]) -> Self {
+        Tab32Pair::from_rng(&mut ChaCha20Rng::from_seed(seed))
+    }
+
+    /// Derive the child hash function `index` from this hash function.
+    ///
+    /// Children are deterministic, i.e. a parent with the same table always
+    /// yields the same child for an index, and distinct indices yield
+    /// distinct children. The table of a child is drawn from a ChaCha20
+    /// generator keyed with a digest of the parent table, using `index`
+    /// as stream, see the crate documentation for details.
+    pub fn derive_child(&self, index: u64) -> Self {
+        Tab32Pair::from_rng(&mut child_rng(&self.table[..], index))
+    }
+
+    /// Get the id of this hash function, a 64-bit digest of its table.
+    ///
+    /// Equal tables always have the same id, across versions, platforms
+    /// and serialization round trips. See the crate documentation for the algorithm.
+    pub fn id(&self) -> u64 {
+        table_digest(&self.table[..])
+    }
+
+    /// Create a new simple tabulation hash function with a table drawn from `rng`.
+    ///
+    /// See the crate documentation for the order in which entries are drawn.
+    #[cfg(feature = "rand")]
+    pub fn new_with_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        Tab32Pair::from_rng(rng)
+    }
+
+    /// Generate a table drawing entries from `rng` column by column
+    fn from_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        Tab32Pair {
+            table: array_init::array_init(|_| {
+                array_init::array_init(|_| TableEntry::from_rng(rng))
+            }),
+        }
+    }
+
+    /// Replace the table of this hash function with a new random table.
+    ///
+    /// The new table is generated completely before it replaces the old one.
+    #[cfg(feature = "getrandom")]
+    pub fn reseed(&mut self) {
+        *self = Tab32Pair::new();
+    }
+
+    /// Replace the table of this hash function with a table drawn from `rng`.
+    ///
+    /// Entries are drawn in the same order as for `new_with_rng`.
+    #[cfg(feature = "rand")]
+    pub fn reseed_with_rng<R: RngCore + ?Sized>(&mut self, rng: &mut R) {
+        *self = Tab32Pair::from_rng(rng);
+    }
+
+    /// Replace the table of this hash function with a table derived from a seed.
+    ///
+    /// Afterwards, this hash function is identical to `new_with_seed(seed)`.
+    pub fn reseed_from_seed(&mut self, seed: u64) {
+        *self = Tab32Pair::new_with_seed(seed);
+    }
+
+    /// Replace column `i` of the table with new random entries.
+    ///
+    /// All other columns are left untouched.
+    ///
+    /// # Panics
+    /// If `i` is not a valid column index, i.e. at least the number of bytes of a key.
+    #[cfg(feature = "getrandom")]
+    pub fn reseed_column(&mut self, i: usize) {
+        reseed_column_from_rng(&mut self.table[..], i, &mut os_seeded_rng());
+    }
+
+    /// Replace column `i` of the table with entries derived from a seed.
+    ///
+    /// The new column is identical to the first column of `new_with_seed(seed)`.
+    /// All other columns are left untouched.
+    ///
+    /// # Panics
+    /// If `i` is not a valid column index, i.e. at least the number of bytes of a key.
+    pub fn reseed_column_from_seed(&mut self, i: usize, seed: u64) {
+        reseed_column_from_rng(&mut self.table[..], i, &mut SplitMix64::new(seed));
+    }
+
+    /// Export the table of this hash function as a nested vector.
+    #[cfg(feature = "alloc")]
+    pub fn to_vec(&self) -> Vec<Vec<u32>> {
         let mut vec = Vec::with_capacity(8);
         for col in self.table.iter() {
             vec.push(col.to_vec());
@@ -210,9 +4519,10 @@ impl Tab64Simple {
         vec
     }
 
-    /// Create a new simple tabulation hash function with a random table.
-    pub fn from_vec(table_data: Vec<Vec<u64>>) -> Self {
-        let mut table = [[0_u64; 256]; 8];
+    /// Create a new simple tabulation hash function from a nested vector.
+    #[cfg(feature = "alloc")]
+    pub fn from_vec(table_data: Vec<Vec<u32>>) -> Self {
+        let mut table = [[0_u32; 256]; 8];
         assert_eq!(table_data.len(), 8);
         for (i, column) in table_data.iter().enumerate() {
             assert_eq!(column.len(), 256);
@@ -220,45 +4530,84 @@ impl Tab64Simple {
                 table[i][j] = *value;
             }
         }
-        Tab64Simple { table }
+        Tab32Pair { table }
     }
 
     /// Create a new simple tabulation hash function with a given table.
-    pub fn with_table(table: [[u64; 256]; 8]) -> Self {
-        Tab64Simple { table }
+    ///
+    /// This is a `const fn`, i.e. hash functions with a fixed table can be
+    /// stored in a `static` without any initialization at runtime.
+    pub const fn with_table(table: [[u32; 256]; 8]) -> Self {
+        Tab32Pair { table }
     }
 
-    /// Generate a table of 64bit uints for simple tabulation hashing
-    fn initialize_table() -> [[u64; 256]; 8] {
-        let table: [[u64; 256]; 8] =
-            array_init::array_init(|_| array_init::array_init(|_| rand::random()));
-        table
+    /// Create a new simple tabulation hash function from the little-endian bytes of a table.
+    ///
+    /// Entries are read column by column, each column from entry 0 to 255,
+    /// as written by `to_le_bytes`. Combined with `include_bytes!`,
+    /// this embeds a table into the binary at compile time.
+    pub const fn from_le_bytes(bytes: &[u8; 8192]) -> Self {
+        let mut table = [[0; 256]; 8];
+        let mut i = 0;
+        while i < 8 * 256 {
+            table[i / 256][i % 256] = read_le(bytes, 4 * i, 4) as u32;
+            i += 1;
+        }
+        Tab32Pair { table }
+    }
+
+    /// Export the table of this hash function as little-endian bytes.
+    ///
+    /// This is the inverse of `from_le_bytes`.
+    pub fn to_le_bytes(&self) -> [u8; 8192] {
+        let mut bytes = [0; 8192];
+        for (chunk, entry) in bytes.chunks_mut(4).zip(self.table.iter().flatten()) {
+            chunk.copy_from_slice(&entry.to_le_bytes());
+        }
+        bytes
     }
 
     /// Get the table used by this hash function.
-    pub fn get_table(&self) -> [[u64; 256]; 8] {
+    pub const fn get_table(&self) -> [[u32; 256]; 8] {
         self.table
     }
 
-    /// Compute simple tabulation hash value for a 64bit integer number.
-    pub fn hash(&self, x: u64) -> u64 {
-        let mut h: u64 = 0; // initialize hash values as 0
+    /// Compute simple tabulation hash value for a pair of 32bit integer numbers.
+    ///
+    /// The bytes of `a` are looked up in columns 0 to 3, the bytes of `b`
+    /// in columns 4 to 7 (both little endian).
+    pub fn hash_pair(&self, a: u32, b: u32) -> u32 {
+        let mut h: u32 = 0; // initialize hash values as 0
 
-        for (i, c) in byte_chunks_64(x).iter().enumerate() {
-            h ^= self.table[i as usize][*c as usize];
+        for (i, c) in byte_chunks_32(a).iter().enumerate() {
+            h ^= self.table[i][*c as usize];
+        }
+        for (i, c) in byte_chunks_32(b).iter().enumerate() {
+            h ^= self.table[i + 4][*c as usize];
         }
         h
     }
+
+    /// Borrow this hash function as a closure, e.g. for `Iterator::map`.
+    pub fn as_fn(&self) -> impl Fn(u32, u32) -> u32 + '_ {
+        move |a, b| self.hash_pair(a, b)
+    }
+
+    /// Turn this hash function into an owned closure, e.g. to move it into a thread.
+    pub fn into_fn(self) -> impl Fn(u32, u32) -> u32 {
+        move |a, b| self.hash_pair(a, b)
+    }
 }
 
 /// Custom serialization converting nested array to a nested vec (cannot be derived)
-fn tab64simple_from_vec<'de, D>(deserializer: D) -> Result<[[u64; 256]; 8], D::Error>
+#[cfg(feature = "serde")]
+fn tab32pair_from_vec<'de, D>(deserializer: D) -> Result<[[u32; 256]; 8], D::Error>
 where
     D: Deserializer<'de>,
 {
-    let table_data: Vec<Vec<u64>> = Deserialize::deserialize(deserializer)?;
+    let table_data: Vec<Vec<u32>> = Deserialize::deserialize(deserializer)?;
 
-    let mut table = [[0_u64; 256]; 8];
+    let mut table = [[0_u32; 256]; 8];
     assert_eq!(table_data.len(), 8);
     for (i, column) in table_data.iter().enumerate() {
         assert_eq!(column.len(), 256);
@@ -269,115 +4618,295 @@ where
     Ok(table)
 }
 
+#[cfg(feature = "serde")]
 #[derive(Clone, Serialize)]
-struct _VecTab64Simple {
-    table: Vec<Vec<u64>>,
+struct _VecTab32Pair {
+    table: Vec<Vec<u32>>,
 }
 
-impl Serialize for Tab64Simple {
+#[cfg(feature = "serde")]
+impl Serialize for Tab32Pair {
     fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        _VecTab64Simple {
+        _VecTab32Pair {
             table: self.to_vec(),
         }
         .serialize(s)
     }
 }
 
-/// A universal hash function for 32-bit integers using twisted tabulation.
+#[cfg(feature = "rand")]
+impl Distribution<Tab32Pair> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Tab32Pair {
+        Tab32Pair::from_rng(rng)
+    }
+}
+
+#[cfg(feature = "getrandom")]
+impl Default for Tab32Pair {
+    /// Create a new hash function with a random table, just like `new`.
+    ///
+    /// Note that the default is randomized, i.e. every call yields a different
+    /// hash function. Use `new_with_seed` for a deterministic hash function.
+    fn default() -> Self {
+        Tab32Pair::new()
+    }
+}
+
+impl core::fmt::Debug for Tab32Pair {
+    /// Show the id of the hash function as fingerprint instead of all entries.
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let fingerprint = self.id();
+        f.debug_struct("Tab32Pair")
+            .field("fingerprint", &format_args!("{:#018x}", fingerprint))
+            .finish()
+    }
+}
+
+/// A universal hash function for byte array keys of `N` bytes using simple tabulation.
+///
+/// Byte `i` of a key is looked up in column `i` of the table.
+/// For `N = 8`, this is the same as `Tab64Simple` with the same table,
+/// hashing the little-endian bytes of a 64-bit key, see `byte_chunks_64`.
+/// For `N = 4`, this is the same as `Tab64Simple` hashing the zero-extended
+/// 32-bit key, if the first four columns agree and the entry 0 of the other
+/// four columns is 0.
 ///
 /// Usage:
 /// ```rust
-/// use tab_hash::Tab32Twisted;
+/// use tab_hash::TabBytes;
 ///
-/// let keys = vec![0, 8, 15, 47, 11];
-/// let twisted = Tab32Twisted::new();
-/// for k in keys {
-///     println!("{}", twisted.hash(k));
+/// let macs = vec![[0x00, 0x1B, 0x44, 0x11, 0x3A, 0xB7], [0x02, 0x42, 0xAC, 0x11, 0x00, 0x02]];
+/// let simple: TabBytes<6> = TabBytes::new_with_seed(42);
+/// for mac in macs {
+///     println!("{}", simple.hash(&mac));
 /// }
 /// ```
-#[derive(Clone, Deserialize)]
-pub struct Tab32Twisted {
-    #[serde(deserialize_with = "tab32twisted_from_vec")]
-    table: [[u64; 256]; 4],
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+pub struct TabBytes<const N: usize> {
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "tabbytes_from_vec"))]
+    table: [[u64; 256]; N],
 }
 
-impl Tab32Twisted {
-    /// Create a new twisted tabulation hash function with a random table.
+impl<const N: usize> TabBytes<N> {
+    /// Create a new simple tabulation hash function with a random table.
+    #[cfg(feature = "getrandom")]
     pub fn new() -> Self {
-        Tab32Twisted {
-            table: Tab32Twisted::initialize_table(),
+        TabBytes::from_rng(&mut os_seeded_rng())
+    }
+
+    /// Create a new simple tabulation hash function with a table read directly from OS entropy.
+    ///
+    /// The table is filled from the entropy source of the operating system
+    /// without any user space random number generator in between.
+    /// Entries are read as little-endian integers from consecutive bytes,
+    /// column by column, each column from entry 0 to 255.
+    #[cfg(feature = "getrandom")]
+    pub fn new_from_os_entropy() -> Result<Self, EntropyError> {
+        let mut source = OsEntropy;
+        let mut reader = EntropyReader::new(&mut source);
+        let mut table = [[0; 256]; N];
+        for column in table.iter_mut() {
+            for entry in column.iter_mut() {
+                *entry = reader.read_entry()?;
+            }
         }
+        Ok(TabBytes { table })
     }
 
-    /// Create a new simple tabulation hash function with a random table.
+    /// Create a new simple tabulation hash function seeded from an entropy source.
+    ///
+    /// A 256-bit seed is read from `source` and expanded into the table
+    /// using a xoshiro256** generator, just like `new` does with the
+    /// entropy source of the operating system.
+    pub fn new_from_entropy<E: EntropySource + ?Sized>(source: &mut E) -> Result<Self, E::Error> {
+        let mut rng = Xoshiro256StarStar::from_entropy(source)?;
+        Ok(TabBytes::from_rng(&mut rng))
+    }
+
+    /// Create a new simple tabulation hash function with a table derived from a seed.
+    ///
+    /// The same seed always yields the same table, see the crate
+    /// documentation for how the seed is expanded.
+    pub fn new_with_seed(seed: u64) -> Self {
+        TabBytes::from_rng(&mut SplitMix64::new(seed))
+    }
+
+    /// Create a new simple tabulation hash function from a 32-byte seed.
+    ///
+    /// The seed is used as ChaCha20 key to expand it into the table,
+    /// see the crate documentation for details.
+    pub fn from_seed_bytes(seed: [u8; 32]) -> Self {
+        TabBytes::from_rng(&mut ChaCha20Rng::from_seed(seed))
+    }
+
+    /// Derive the child hash function `index` from this hash function.
+    ///
+    /// Children are deterministic, i.e. a parent with the same table always
+    /// yields the same child for an index, and distinct indices yield
+    /// distinct children. The table of a child is drawn from a ChaCha20
+    /// generator keyed with a digest of the parent table, using `index`
+    /// as stream, see the crate documentation for details.
+    pub fn derive_child(&self, index: u64) -> Self {
+        TabBytes::from_rng(&mut child_rng(&self.table[..], index))
+    }
+
+    /// Get the id of this hash function, a 64-bit digest of its table.
+    ///
+    /// Equal tables always have the same id, across versions, platforms
+    /// and serialization round trips. See the crate documentation for the algorithm.
+    pub fn id(&self) -> u64 {
+        table_digest(&self.table[..])
+    }
+
+    /// Create a new simple tabulation hash function with a table drawn from `rng`.
+    ///
+    /// See the crate documentation for the order in which entries are drawn.
+    #[cfg(feature = "rand")]
+    pub fn new_with_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        TabBytes::from_rng(rng)
+    }
+
+    /// Generate a table drawing entries from `rng` column by column
+    fn from_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        let mut table = [[0; 256]; N];
+        for column in table.iter_mut() {
+            for entry in column.iter_mut() {
+                *entry = TableEntry::from_rng(rng);
+            }
+        }
+        TabBytes { table }
+    }
+
+    /// Replace the table of this hash function with a new random table.
+    ///
+    /// The new table is generated completely before it replaces the old one.
+    #[cfg(feature = "getrandom")]
+    pub fn reseed(&mut self) {
+        *self = TabBytes::new();
+    }
+
+    /// Replace the table of this hash function with a table drawn from `rng`.
+    ///
+    /// Entries are drawn in the same order as for `new_with_rng`.
+    #[cfg(feature = "rand")]
+    pub fn reseed_with_rng<R: RngCore + ?Sized>(&mut self, rng: &mut R) {
+        *self = TabBytes::from_rng(rng);
+    }
+
+    /// Replace the table of this hash function with a table derived from a seed.
+    ///
+    /// Afterwards, this hash function is identical to `new_with_seed(seed)`.
+    pub fn reseed_from_seed(&mut self, seed: u64) {
+        *self = TabBytes::new_with_seed(seed);
+    }
+
+    /// Replace column `i` of the table with new random entries.
+    ///
+    /// All other columns are left untouched.
+    ///
+    /// # Panics
+    /// If `i` is not a valid column index, i.e. at least `N`.
+    #[cfg(feature = "getrandom")]
+    pub fn reseed_column(&mut self, i: usize) {
+        reseed_column_from_rng(&mut self.table[..], i, &mut os_seeded_rng());
+    }
+
+    /// Replace column `i` of the table with entries derived from a seed.
+    ///
+    /// The new column is identical to the first column of `new_with_seed(seed)`.
+    /// All other columns are left untouched.
+    ///
+    /// # Panics
+    /// If `i` is not a valid column index, i.e. at least `N`.
+    pub fn reseed_column_from_seed(&mut self, i: usize, seed: u64) {
+        reseed_column_from_rng(&mut self.table[..], i, &mut SplitMix64::new(seed));
+    }
+
+    /// Export the table of this hash function as a nested vector.
+    #[cfg(feature = "alloc")]
     pub fn to_vec(&self) -> Vec<Vec<u64>> {
-        let mut vec = Vec::with_capacity(4);
+        let mut vec = Vec::with_capacity(N);
         for col in self.table.iter() {
             vec.push(col.to_vec());
         }
         vec
     }
 
-    /// Create a new simple tabulation hash function with a random table.
+    /// Create a new simple tabulation hash function from a nested vector.
+    #[cfg(feature = "alloc")]
     pub fn from_vec(table_data: Vec<Vec<u64>>) -> Self {
-        let mut table = [[0_u64; 256]; 4];
-        assert_eq!(table_data.len(), 4);
+        let mut table = [[0_u64; 256]; N];
+        assert_eq!(table_data.len(), N);
         for (i, column) in table_data.iter().enumerate() {
             assert_eq!(column.len(), 256);
             for (j, value) in column.iter().enumerate() {
                 table[i][j] = *value;
             }
         }
-        Tab32Twisted { table }
+        TabBytes { table }
     }
 
-    /// Create a new twisted tabulation hash function with a given table.
-    pub fn with_table(table: [[u64; 256]; 4]) -> Self {
-        Tab32Twisted { table }
-    }
-
-    /// Generate a table of 64bit uints for twisted tabulation hashing
-    fn initialize_table() -> [[u64; 256]; 4] {
-        let table: [[u64; 256]; 4] =
-            array_init::array_init(|_| array_init::array_init(|_| rand::random()));
-        table
+    /// Create a new simple tabulation hash function with a given table.
+    ///
+    /// This is a `const fn`, i.e. hash functions with a fixed table can be
+    /// stored in a `static` without any initialization at runtime.
+    pub const fn with_table(table: [[u64; 256]; N]) -> Self {
+        TabBytes { table }
     }
 
     /// Get the table used by this hash function.
-    pub fn get_table(&self) -> [[u64; 256]; 4] {
+    pub const fn get_table(&self) -> [[u64; 256]; N] {
         self.table
     }
 
-    /// Compute twisted tabulation hash value for a 32bit integer number.
-    pub fn hash(&self, x: u32) -> u32 {
+    /// Compute simple tabulation hash value for a key of `N` bytes.
+    pub fn hash(&self, key: &[u8; N]) -> u64 {
         let mut h: u64 = 0; // initialize hash values as 0
-        let chunks = byte_chunks_32(x);
-        for (i, c) in chunks[0..3].iter().enumerate() {
-            h ^= self.table[i as usize][*c as usize];
+
+        for (i, c) in key.iter().enumerate() {
+            h ^= self.table[i][*c as usize];
         }
-        // compute address for last chunk by XOring the lowest byte of the
-        // current hash value with the content of the last chunk of the key
-        let c = chunks[3] ^ (h & 0xFF) as u8;
-        h ^= self.table[3][c as usize];
-        // shift out the 32 low bits of the resulting hash
-        h = h.overflowing_shr(32).0;
+        h
+    }
+
+    /// Borrow this hash function as a closure, e.g. for `Iterator::map`.
+    pub fn as_fn(&self) -> impl Fn(&[u8; N]) -> u64 + '_ {
+        move |key| self.hash(key)
+    }
 
-        h as u32
+    /// Turn this hash function into an owned closure, e.g. to move it into a thread.
+    pub fn into_fn(self) -> impl Fn(&[u8; N]) -> u64 {
+        move |key| self.hash(key)
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl TabBytes<16> {
+    /// Compute the simple tabulation hash value of a UUID.
+    ///
+    /// Byte `i` of `u.as_bytes()`, i.e. of the UUID in the big-endian byte order
+    /// of RFC 4122 (the order of its hex digits), is looked up in column `i`.
+    /// E.g. the first byte of `67e55044-10b1-426f-9247-bb680e5fe0c8` is `0x67`
+    /// and looked up in column 0.
+    pub fn hash_uuid(&self, u: &uuid::Uuid) -> u64 {
+        self.hash(u.as_bytes())
     }
 }
 
 /// Custom serialization converting nested array to a nested vec (cannot be derived)
-fn tab32twisted_from_vec<'de, D>(deserializer: D) -> Result<[[u64; 256]; 4], D::Error>
+#[cfg(feature = "serde")]
+fn tabbytes_from_vec<'de, D, const N: usize>(deserializer: D) -> Result<[[u64; 256]; N], D::Error>
 where
     D: Deserializer<'de>,
 {
     let table_data: Vec<Vec<u64>> = Deserialize::deserialize(deserializer)?;
 
-    let mut table = [[0_u64; 256]; 4];
-    assert_eq!(table_data.len(), 4);
+    let mut table = [[0_u64; 256]; N];
+    assert_eq!(table_data.len(), N);
     for (i, column) in table_data.iter().enumerate() {
         assert_eq!(column.len(), 256);
         for (j, value) in column.iter().enumerate() {
@@ -387,115 +4916,307 @@ where
     Ok(table)
 }
 
+#[cfg(feature = "serde")]
 #[derive(Clone, Serialize)]
-struct _VecTab32Twisted {
+struct _VecTabBytes {
     table: Vec<Vec<u64>>,
 }
 
-impl Serialize for Tab32Twisted {
+#[cfg(feature = "serde")]
+impl<const N: usize> Serialize for TabBytes<N> {
     fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        _VecTab32Twisted {
+        _VecTabBytes {
             table: self.to_vec(),
         }
         .serialize(s)
     }
 }
 
-/// A universal hash function for 64-bit integers using twisted tabulation.
+#[cfg(feature = "rand")]
+impl<const N: usize> Distribution<TabBytes<N>> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> TabBytes<N> {
+        TabBytes::from_rng(rng)
+    }
+}
+
+#[cfg(feature = "getrandom")]
+impl<const N: usize> Default for TabBytes<N> {
+    /// Create a new hash function with a random table, just like `new`.
+    ///
+    /// Note that the default is randomized, i.e. every call yields a different
+    /// hash function. Use `new_with_seed` for a deterministic hash function.
+    fn default() -> Self {
+        TabBytes::new()
+    }
+}
+
+impl<const N: usize> core::fmt::Debug for TabBytes<N> {
+    /// Show the id of the hash function as fingerprint instead of all entries.
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let fingerprint = self.id();
+        f.debug_struct("TabBytes")
+            .field("fingerprint", &format_args!("{:#018x}", fingerprint))
+            .finish()
+    }
+}
+
+/// A universal hash function for byte array keys of `N` bytes using twisted tabulation.
+///
+/// The first `N - 1` bytes of a key are looked up in the respective columns,
+/// the last byte is twisted with the intermediate hash value, just like
+/// the last byte of `Tab64Twisted`. For `N = 8`, this is the same as `Tab64Twisted`
+/// with the same table, hashing the little-endian bytes of a 64-bit key.
 ///
 /// Usage:
 /// ```rust
-/// use tab_hash::Tab64Twisted;
+/// use tab_hash::TabBytesTwisted;
 ///
-/// let keys = vec![0, 8, 15, 47, 11];
-/// let twisted = Tab64Twisted::new();
-/// for k in keys {
-///     println!("{}", twisted.hash(k));
+/// let uuids = vec![[0x55; 16], [0xAA; 16]];
+/// let twisted: TabBytesTwisted<16> = TabBytesTwisted::new_with_seed(42);
+/// for uuid in uuids {
+///     println!("{}", twisted.hash(&uuid));
 /// }
 /// ```
-#[derive(Clone, Deserialize)]
-pub struct Tab64Twisted {
-    #[serde(deserialize_with = "tab64twisted_from_vec")]
-    table: [[u128; 256]; 8],
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+pub struct TabBytesTwisted<const N: usize> {
+    #[cfg_attr(
+        feature = "serde",
+        serde(deserialize_with = "tabbytestwisted_from_vec")
+    )]
+    table: [[u128; 256]; N],
 }
 
-impl Tab64Twisted {
+impl<const N: usize> TabBytesTwisted<N> {
     /// Create a new twisted tabulation hash function with a random table.
+    #[cfg(feature = "getrandom")]
     pub fn new() -> Self {
-        Tab64Twisted {
-            table: Tab64Twisted::initialize_table(),
+        TabBytesTwisted::from_rng(&mut os_seeded_rng())
+    }
+
+    /// Create a new twisted tabulation hash function with a table read directly from OS entropy.
+    ///
+    /// The table is filled from the entropy source of the operating system
+    /// without any user space random number generator in between.
+    /// Entries are read as little-endian integers from consecutive bytes,
+    /// column by column, each column from entry 0 to 255.
+    #[cfg(feature = "getrandom")]
+    pub fn new_from_os_entropy() -> Result<Self, EntropyError> {
+        let mut source = OsEntropy;
+        let mut reader = EntropyReader::new(&mut source);
+        let mut table = [[0; 256]; N];
+        for column in table.iter_mut() {
+            for entry in column.iter_mut() {
+                *entry = reader.read_entry()?;
+            }
         }
+        Ok(TabBytesTwisted { table })
     }
 
-    /// Create a new simple tabulation hash function with a random table.
+    /// Create a new twisted tabulation hash function seeded from an entropy source.
+    ///
+    /// A 256-bit seed is read from `source` and expanded into the table
+    /// using a xoshiro256** generator, just like `new` does with the
+    /// entropy source of the operating system.
+    pub fn new_from_entropy<E: EntropySource + ?Sized>(source: &mut E) -> Result<Self, E::Error> {
+        let mut rng = Xoshiro256StarStar::from_entropy(source)?;
+        Ok(TabBytesTwisted::from_rng(&mut rng))
+    }
+
+    /// Create a new twisted tabulation hash function with a table derived from a seed.
+    ///
+    /// The same seed always yields the same table, see the crate
+    /// documentation for how the seed is expanded.
+    pub fn new_with_seed(seed: u64) -> Self {
+        TabBytesTwisted::from_rng(&mut SplitMix64::new(seed))
+    }
+
+    /// Create a new twisted tabulation hash function from a 32-byte seed.
+    ///
+    /// The seed is used as ChaCha20 key to expand it into the table,
+    /// see the crate documentation for details.
+    pub fn from_seed_bytes(seed: [u8; 32]) -> Self {
+        TabBytesTwisted::from_rng(&mut ChaCha20Rng::from_seed(seed))
+    }
+
+    /// Derive the child hash function `index` from this hash function.
+    ///
+    /// Children are deterministic, i.e. a parent with the same table always
+    /// yields the same child for an index, and distinct indices yield
+    /// distinct children. The table of a child is drawn from a ChaCha20
+    /// generator keyed with a digest of the parent table, using `index`
+    /// as stream, see the crate documentation for details.
+    pub fn derive_child(&self, index: u64) -> Self {
+        TabBytesTwisted::from_rng(&mut child_rng(&self.table[..], index))
+    }
+
+    /// Get the id of this hash function, a 64-bit digest of its table.
+    ///
+    /// Equal tables always have the same id, across versions, platforms
+    /// and serialization round trips. See the crate documentation for the algorithm.
+    pub fn id(&self) -> u64 {
+        table_digest(&self.table[..])
+    }
+
+    /// Create a new twisted tabulation hash function with a table drawn from `rng`.
+    ///
+    /// See the crate documentation for the order in which entries are drawn.
+    #[cfg(feature = "rand")]
+    pub fn new_with_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        TabBytesTwisted::from_rng(rng)
+    }
+
+    /// Generate a table drawing entries from `rng` column by column
+    fn from_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        let mut table = [[0; 256]; N];
+        for column in table.iter_mut() {
+            for entry in column.iter_mut() {
+                *entry = TableEntry::from_rng(rng);
+            }
+        }
+        TabBytesTwisted { table }
+    }
+
+    /// Replace the table of this hash function with a new random table.
+    ///
+    /// The new table is generated completely before it replaces the old one.
+    #[cfg(feature = "getrandom")]
+    pub fn reseed(&mut self) {
+        *self = TabBytesTwisted::new();
+    }
+
+    /// Replace the table of this hash function with a table drawn from `rng`.
+    ///
+    /// Entries are drawn in the same order as for `new_with_rng`.
+    #[cfg(feature = "rand")]
+    pub fn reseed_with_rng<R: RngCore + ?Sized>(&mut self, rng: &mut R) {
+        *self = TabBytesTwisted::from_rng(rng);
+    }
+
+    /// Replace the table of this hash function with a table derived from a seed.
+    ///
+    /// Afterwards, this hash function is identical to `new_with_seed(seed)`.
+    pub fn reseed_from_seed(&mut self, seed: u64) {
+        *self = TabBytesTwisted::new_with_seed(seed);
+    }
+
+    /// Replace column `i` of the table with new random entries.
+    ///
+    /// All other columns are left untouched.
+    ///
+    /// # Panics
+    /// If `i` is not a valid column index, i.e. at least `N`.
+    #[cfg(feature = "getrandom")]
+    pub fn reseed_column(&mut self, i: usize) {
+        reseed_column_from_rng(&mut self.table[..], i, &mut os_seeded_rng());
+    }
+
+    /// Replace column `i` of the table with entries derived from a seed.
+    ///
+    /// The new column is identical to the first column of `new_with_seed(seed)`.
+    /// All other columns are left untouched.
+    ///
+    /// # Panics
+    /// If `i` is not a valid column index, i.e. at least `N`.
+    pub fn reseed_column_from_seed(&mut self, i: usize, seed: u64) {
+        reseed_column_from_rng(&mut self.table[..], i, &mut SplitMix64::new(seed));
+    }
+
+    /// Export the table of this hash function as a nested vector.
+    #[cfg(feature = "alloc")]
     pub fn to_vec(&self) -> Vec<Vec<u128>> {
-        let mut vec = Vec::with_capacity(8);
+        let mut vec = Vec::with_capacity(N);
         for col in self.table.iter() {
             vec.push(col.to_vec());
         }
         vec
     }
 
-    /// Create a new simple tabulation hash function with a random table.
+    /// Create a new simple tabulation hash function from a nested vector.
+    #[cfg(feature = "alloc")]
     pub fn from_vec(table_data: Vec<Vec<u128>>) -> Self {
-        let mut table = [[0_u128; 256]; 8];
-        assert_eq!(table_data.len(), 8);
+        let mut table = [[0_u128; 256]; N];
+        assert_eq!(table_data.len(), N);
         for (i, column) in table_data.iter().enumerate() {
             assert_eq!(column.len(), 256);
             for (j, value) in column.iter().enumerate() {
                 table[i][j] = *value;
             }
         }
-        Tab64Twisted { table }
+        TabBytesTwisted { table }
     }
 
     /// Create a new twisted tabulation hash function with a given table.
-    pub fn with_table(table: [[u128; 256]; 8]) -> Self {
-        Tab64Twisted { table }
-    }
-
-    /// Generate a table of 128bit uints for twisted tabulation hashing
-    fn initialize_table() -> [[u128; 256]; 8] {
-        let table: [[u128; 256]; 8] =
-            array_init::array_init(|_| array_init::array_init(|_| rand::random()));
-        table
+    ///
+    /// This is a `const fn`, i.e. hash functions with a fixed table can be
+    /// stored in a `static` without any initialization at runtime.
+    pub const fn with_table(table: [[u128; 256]; N]) -> Self {
+        TabBytesTwisted { table }
     }
 
     /// Get the table used by this hash function.
-    pub fn get_table(&self) -> [[u128; 256]; 8] {
+    pub const fn get_table(&self) -> [[u128; 256]; N] {
         self.table
     }
 
-    /// Compute twisted tabulation hash value for a 64bit integer number.
-    pub fn hash(&self, x: u64) -> u64 {
+    /// Compute twisted tabulation hash value for a key of `N` bytes.
+    ///
+    /// Keys have to consist of at least one byte, which is checked at compile time.
+    pub fn hash(&self, key: &[u8; N]) -> u64 {
+        const { assert!(N > 0, "keys have to consist of at least one byte") };
         let mut h: u128 = 0; // initialize hash values as 0
-        let chunks = byte_chunks_64(x);
-        for (i, c) in chunks[0..7].iter().enumerate() {
-            h ^= self.table[i as usize][*c as usize];
+        for (i, c) in key[0..N - 1].iter().enumerate() {
+            h ^= self.table[i][*c as usize];
         }
         // compute address for last chunk by XOring the lowest byte of the
         // current hash value with the content of the last chunk of the key
-        let c = chunks[7] ^ (h & 0xFF) as u8;
-        h ^= self.table[7][c as usize];
+        let c = key[N - 1] ^ (h & 0xFF) as u8;
+        h ^= self.table[N - 1][c as usize];
         // shift out the 64 low bits of the resulting hash
         h = h.overflowing_shr(64).0;
 
         h as u64
     }
+
+    /// Borrow this hash function as a closure, e.g. for `Iterator::map`.
+    pub fn as_fn(&self) -> impl Fn(&[u8; N]) -> u64 + '_ {
+        move |key| self.hash(key)
+    }
+
+    /// Turn this hash function into an owned closure, e.g. to move it into a thread.
+    pub fn into_fn(self) -> impl Fn(&[u8; N]) -> u64 {
+        move |key| self.hash(key)
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl TabBytesTwisted<16> {
+    /// Compute the twisted tabulation hash value of a UUID.
+    ///
+    /// Byte `i` of `u.as_bytes()`, i.e. of the UUID in the big-endian byte order
+    /// of RFC 4122 (the order of its hex digits), is looked up in column `i`.
+    /// E.g. the first byte of `67e55044-10b1-426f-9247-bb680e5fe0c8` is `0x67`
+    /// and looked up in column 0.
+    pub fn hash_uuid(&self, u: &uuid::Uuid) -> u64 {
+        self.hash(u.as_bytes())
+    }
 }
 
 /// Custom serialization converting nested array to a nested vec (cannot be derived)
-fn tab64twisted_from_vec<'de, D>(deserializer: D) -> Result<[[u128; 256]; 8], D::Error>
+#[cfg(feature = "serde")]
+fn tabbytestwisted_from_vec<'de, D, const N: usize>(
+    deserializer: D,
+) -> Result<[[u128; 256]; N], D::Error>
 where
     D: Deserializer<'de>,
 {
     let table_data: Vec<Vec<u128>> = Deserialize::deserialize(deserializer)?;
 
-    let mut table = [[0_u128; 256]; 8];
-    assert_eq!(table_data.len(), 8);
+    let mut table = [[0_u128; 256]; N];
+    assert_eq!(table_data.len(), N);
     for (i, column) in table_data.iter().enumerate() {
         assert_eq!(column.len(), 256);
         for (j, value) in column.iter().enumerate() {
@@ -505,44 +5226,99 @@ where
     Ok(table)
 }
 
+#[cfg(feature = "serde")]
 #[derive(Clone, Serialize)]
-struct _VecTab64Twisted {
+struct _VecTabBytesTwisted {
     table: Vec<Vec<u128>>,
 }
 
-impl Serialize for Tab64Twisted {
+#[cfg(feature = "serde")]
+impl<const N: usize> Serialize for TabBytesTwisted<N> {
     fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        _VecTab64Twisted {
+        _VecTabBytesTwisted {
             table: self.to_vec(),
         }
         .serialize(s)
     }
 }
 
+#[cfg(feature = "rand")]
+impl<const N: usize> Distribution<TabBytesTwisted<N>> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> TabBytesTwisted<N> {
+        TabBytesTwisted::from_rng(rng)
+    }
+}
+
+#[cfg(feature = "getrandom")]
+impl<const N: usize> Default for TabBytesTwisted<N> {
+    /// Create a new hash function with a random table, just like `new`.
+    ///
+    /// Note that the default is randomized, i.e. every call yields a different
+    /// hash function. Use `new_with_seed` for a deterministic hash function.
+    fn default() -> Self {
+        TabBytesTwisted::new()
+    }
+}
+
+impl<const N: usize> core::fmt::Debug for TabBytesTwisted<N> {
+    /// Show the id of the hash function as fingerprint instead of all entries.
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let fingerprint = self.id();
+        f.debug_struct("TabBytesTwisted")
+            .field("fingerprint", &format_args!("{:#018x}", fingerprint))
+            .finish()
+    }
+}
+
 // Tests for private methods
 #[test]
-fn byte_chunking_32() {
-    let random_bytes: [u8; 400] = array_init::array_init(|_| rand::random());
-    for four_bytes in random_bytes.chunks(4) {
-        let mut number = 0_u32;
-        for byte in four_bytes.iter().rev() {
-            number = (number << 8) | *byte as u32;
-        }
-        assert_eq!(four_bytes, byte_chunks_32(number));
-    }
+#[cfg(feature = "getrandom")]
+fn entries_from_le_bytes() {
+    let bytes: [u8; 32] = array_init::array_init(|i| i as u8);
+    assert_eq!(u16::from_le_slice(&bytes[..2]), 0x0100);
+    assert_eq!(u32::from_le_slice(&bytes[..4]), 0x0302_0100);
+    assert_eq!(u64::from_le_slice(&bytes[..8]), 0x0706_0504_0302_0100);
+    assert_eq!(
+        u128::from_le_slice(&bytes[..16]),
+        0x0f0e_0d0c_0b0a_0908_0706_0504_0302_0100
+    );
+    assert_eq!(
+        <[u128; 2]>::from_le_slice(&bytes),
+        [
+            0x0f0e_0d0c_0b0a_0908_0706_0504_0302_0100,
+            0x1f1e_1d1c_1b1a_1918_1716_1514_1312_1110
+        ]
+    );
 }
 
 #[test]
-fn byte_chunking_64() {
-    let random_bytes: [u8; 480] = array_init::array_init(|_| rand::random());
-    for four_bytes in random_bytes.chunks(8) {
-        let mut number = 0_u64;
-        for byte in four_bytes.iter().rev() {
-            number = (number << 8) | *byte as u64;
-        }
-        assert_eq!(four_bytes, byte_chunks_64(number));
+fn portable_widening_multiplication() {
+    let values = [
+        0,
+        1,
+        2,
+        0xFFFF_FFFF,
+        0x1_0000_0000,
+        0xDEAD_BEEF_0BAD_F00D,
+        u64::MAX - 1,
+        u64::MAX,
+    ];
+    for a in values.iter() {
+        for b in values.iter() {
+            let expected = ((*a as u128 * *b as u128) >> 64) as u64;
+            assert_eq!(mul_high_64_portable(*a, *b), expected);
+            assert_eq!(mul_high_64(*a, *b), expected);
+        }
+    }
+    let mut rng = SplitMix64::new(42);
+    for _ in 0..10_000 {
+        let (a, b) = (rng.next_u64(), rng.next_u64());
+        assert_eq!(
+            mul_high_64_portable(a, b),
+            ((a as u128 * b as u128) >> 64) as u64
+        );
     }
 }
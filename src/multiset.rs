@@ -0,0 +1,125 @@
+//! Incremental fingerprints of multisets.
+use crate::splitmix::SplitMix64;
+use crate::Tab64Simple;
+use rand_core::RngCore;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// An order-independent fingerprint of a multiset of 64-bit keys.
+///
+/// The fingerprint consists of two sums modulo `2^64`, one of the hash values of all keys
+/// under each of two independent `Tab64Simple` hash functions. Adding a key adds its
+/// hash values, removing it subtracts them. Hence the fingerprint only depends on
+/// the multiplicities of the keys, not on the order of the operations, and the
+/// fingerprints form a group: a key can be removed before (or without) being added,
+/// and its multiplicity is negative then. Two fingerprints with the same hash functions
+/// are combined by adding their sums, which yields the fingerprint of the union
+/// of both multisets (adding the multiplicities).
+///
+/// The digest is 128 bits wide, but this is not a cryptographic hash:
+///
+/// - Anyone who knows the tables can construct different multisets with the same digest.
+///   Keep the hash functions secret if the keys can be chosen by an adversary.
+/// - Multiplicities are only tracked modulo `2^64`. If all multiplicities of two
+///   multisets differ by multiples of `2^j`, the sums only differ in their upper
+///   `64 - j` bits, so collisions are more likely.
+/// - Tabulation hashing is only 3-independent, so there are no formal collision bounds for
+///   multisets differing in more than three keys, although collisions are rare in practice.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::MultisetFingerprint;
+///
+/// let mut a = MultisetFingerprint::new_with_seed(42);
+/// let mut b = a.clone();
+/// a.add(1);
+/// a.add(2);
+/// b.add(2);
+/// b.add(1);
+/// assert_eq!(a.digest(), b.digest());
+/// a.remove(1);
+/// a.remove(2);
+/// assert_eq!(a.digest(), 0);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MultisetFingerprint {
+    hash_functions: [Tab64Simple; 2],
+    sums: [u64; 2],
+}
+
+impl MultisetFingerprint {
+    /// Create the fingerprint of an empty multiset with random hash functions.
+    #[cfg(feature = "getrandom")]
+    pub fn new() -> Self {
+        MultisetFingerprint::with_hash_functions([Tab64Simple::new(), Tab64Simple::new()])
+    }
+
+    /// Create the fingerprint of an empty multiset with hash functions derived from a seed.
+    ///
+    /// The seeds of both hash functions are drawn from a SplitMix64 generator seeded
+    /// with `seed`, and passed to `Tab64Simple::new_with_seed`.
+    pub fn new_with_seed(seed: u64) -> Self {
+        let mut seeds = SplitMix64::new(seed);
+        MultisetFingerprint::with_hash_functions([
+            Tab64Simple::new_with_seed(seeds.next_u64()),
+            Tab64Simple::new_with_seed(seeds.next_u64()),
+        ])
+    }
+
+    /// Create the fingerprint of an empty multiset using the given hash functions.
+    pub fn with_hash_functions(hash_functions: [Tab64Simple; 2]) -> Self {
+        MultisetFingerprint {
+            hash_functions,
+            sums: [0; 2],
+        }
+    }
+
+    /// Add one occurrence of a key.
+    pub fn add(&mut self, key: u64) {
+        for (sum, hash_function) in self.sums.iter_mut().zip(self.hash_functions.iter()) {
+            *sum = sum.wrapping_add(hash_function.hash(key));
+        }
+    }
+
+    /// Remove one occurrence of a key, which does not have to be added before.
+    pub fn remove(&mut self, key: u64) {
+        for (sum, hash_function) in self.sums.iter_mut().zip(self.hash_functions.iter()) {
+            *sum = sum.wrapping_sub(hash_function.hash(key));
+        }
+    }
+
+    /// Add the keys of another fingerprint, i.e. all their occurrences.
+    ///
+    /// # Panics
+    /// If the fingerprints use different hash functions.
+    pub fn combine(&mut self, other: &MultisetFingerprint) {
+        assert!(
+            self.hash_functions == other.hash_functions,
+            "cannot combine fingerprints with different hash functions"
+        );
+        for (sum, other_sum) in self.sums.iter_mut().zip(other.sums.iter()) {
+            *sum = sum.wrapping_add(*other_sum);
+        }
+    }
+
+    /// Get the digest of the multiset, the first sum in the high and the second in the low half.
+    ///
+    /// The digest of the empty multiset is 0.
+    pub fn digest(&self) -> u128 {
+        (u128::from(self.sums[0]) << 64) | u128::from(self.sums[1])
+    }
+
+    /// Get the hash functions.
+    pub fn hash_functions(&self) -> &[Tab64Simple; 2] {
+        &self.hash_functions
+    }
+}
+
+#[cfg(feature = "getrandom")]
+impl Default for MultisetFingerprint {
+    /// Create the fingerprint of an empty multiset with random hash functions, just like `new`.
+    fn default() -> Self {
+        MultisetFingerprint::new()
+    }
+}
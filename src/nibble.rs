@@ -0,0 +1,228 @@
+//! Simple tabulation hashing of 32-bit keys with 4-bit characters.
+#[cfg(feature = "getrandom")]
+use crate::entropy::OsEntropy;
+use crate::splitmix::SplitMix64;
+use crate::xoshiro::Xoshiro256StarStar;
+#[cfg(feature = "getrandom")]
+use crate::{os_seeded_rng, EntropyError};
+use crate::{EntropySource, Hash32Fn, TabHash, TableEntry, TableWords};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "rand")]
+use rand::distributions::{Distribution, Standard};
+#[cfg(feature = "rand")]
+use rand::Rng;
+use rand_chacha::ChaCha20Rng;
+use rand_core::{RngCore, SeedableRng};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A universal hash function for 32-bit integers using simple tabulation with 4-bit characters.
+///
+/// A key is split into eight nibbles, from the least to the most significant, which are
+/// looked up in the eight columns of the table, respectively. Each column has only 16
+/// entries, so the whole table takes 512 bytes instead of the 4 KiB of `Tab32Simple`.
+/// It is meant for targets with very little memory, e.g. microcontrollers, and works
+/// without `std` and `alloc`.
+///
+/// The small table comes at a price:
+///
+/// - A hash value costs eight lookups instead of four.
+/// - Like simple tabulation with any characters, it is 3-independent.
+/// - Most other guarantees of simple tabulation, e.g. the concentration bounds and
+///   the bounds for linear probing of Patrascu and Thorup, are proven for a constant
+///   number `c` of characters from an alphabet of size `s`, and their error terms
+///   grow with `c` and shrink only polynomially in `s`. With eight characters from an
+///   alphabet of 16, these bounds are much weaker than for bytes, so do not rely on more
+///   than 3-independence.
+///
+/// Tables are drawn column by column, each column from entry 0 to 15,
+/// one `next_u32` call per entry, like for the other hash functions.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::Tab32Nibble;
+///
+/// static HASH: Tab32Nibble = Tab32Nibble::with_table([[0x9e37_79b9; 16]; 8]);
+///
+/// let simple = Tab32Nibble::new_with_seed(42);
+/// for k in [0, 8, 15, 47, 11] {
+///     println!("{} {}", simple.hash(k), HASH.hash(k));
+/// }
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Tab32Nibble {
+    table: [[u32; 16]; 8],
+}
+
+impl Tab32Nibble {
+    /// Create a new simple tabulation hash function with a random table.
+    #[cfg(feature = "getrandom")]
+    pub fn new() -> Self {
+        Tab32Nibble::from_rng(&mut os_seeded_rng())
+    }
+
+    /// Create a new simple tabulation hash function seeded from an entropy source.
+    ///
+    /// A 256-bit seed is read from `source` and expanded into the table
+    /// using a xoshiro256** generator, just like `new` does with the
+    /// entropy source of the operating system.
+    pub fn new_from_entropy<E: EntropySource + ?Sized>(source: &mut E) -> Result<Self, E::Error> {
+        let mut rng = Xoshiro256StarStar::from_entropy(source)?;
+        Ok(Tab32Nibble::from_rng(&mut rng))
+    }
+
+    /// Create a new simple tabulation hash function seeded from OS entropy.
+    ///
+    /// This is `new_from_entropy` with the entropy source of the operating system,
+    /// i.e. like `new`, but returns an error instead of panicking if there is no entropy.
+    #[cfg(feature = "getrandom")]
+    pub fn new_from_os_entropy() -> Result<Self, EntropyError> {
+        Tab32Nibble::new_from_entropy(&mut OsEntropy)
+    }
+
+    /// Create a new simple tabulation hash function with a table derived from a seed.
+    ///
+    /// The seed is expanded with SplitMix64, see the crate documentation.
+    pub fn new_with_seed(seed: u64) -> Self {
+        Tab32Nibble::from_rng(&mut SplitMix64::new(seed))
+    }
+
+    /// Create a new simple tabulation hash function from a 32-byte seed.
+    ///
+    /// The seed is used as ChaCha20 key, see the crate documentation.
+    pub fn from_seed_bytes(seed: [u8; 32]) -> Self {
+        Tab32Nibble::from_rng(&mut ChaCha20Rng::from_seed(seed))
+    }
+
+    /// Create a new simple tabulation hash function with a table drawn from `rng`.
+    #[cfg(feature = "rand")]
+    pub fn new_with_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        Tab32Nibble::from_rng(rng)
+    }
+
+    /// Generate a table drawing entries from `rng` column by column
+    fn from_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        let mut table = [[0; 16]; 8];
+        for entry in table.iter_mut().flatten() {
+            *entry = TableEntry::from_rng(rng);
+        }
+        Tab32Nibble { table }
+    }
+
+    /// Create a new simple tabulation hash function with a given table.
+    ///
+    /// This is a `const fn`, i.e. hash functions with a fixed table can be
+    /// stored in a `static` without any initialization at runtime.
+    pub const fn with_table(table: [[u32; 16]; 8]) -> Self {
+        Tab32Nibble { table }
+    }
+
+    /// Get the table used by this hash function.
+    pub const fn get_table(&self) -> [[u32; 16]; 8] {
+        self.table
+    }
+
+    /// Get the id of this hash function, a 64-bit digest of its table.
+    pub fn id(&self) -> u64 {
+        let mut words = TableWords::default();
+        for entry in self.table.iter().flatten() {
+            entry.for_each_word(|w| words.push(w));
+        }
+        words.digest()
+    }
+
+    /// Export the table of this hash function as a nested vector of columns.
+    #[cfg(feature = "alloc")]
+    pub fn to_vec(&self) -> Vec<Vec<u32>> {
+        self.table.iter().map(|column| column.to_vec()).collect()
+    }
+
+    /// Create a new simple tabulation hash function from a nested vector of columns.
+    ///
+    /// # Panics
+    /// If there are not 8 columns of 16 entries each.
+    #[cfg(feature = "alloc")]
+    pub fn from_vec(table_data: Vec<Vec<u32>>) -> Self {
+        assert_eq!(table_data.len(), 8);
+        let mut table = [[0; 16]; 8];
+        for (column, values) in table.iter_mut().zip(table_data.iter()) {
+            assert_eq!(values.len(), 16);
+            column.copy_from_slice(values);
+        }
+        Tab32Nibble { table }
+    }
+
+    /// Compute simple tabulation hash value for a 32bit integer number.
+    pub fn hash(&self, x: u32) -> u32 {
+        let mut h = 0;
+        for (i, column) in self.table.iter().enumerate() {
+            h ^= column[((x >> (4 * i)) & 0xF) as usize];
+        }
+        h
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Distribution<Tab32Nibble> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Tab32Nibble {
+        Tab32Nibble::from_rng(rng)
+    }
+}
+
+impl TabHash for Tab32Nibble {
+    type Key = u32;
+    type Entry = u32;
+
+    fn hash(&self, key: u32) -> u32 {
+        Tab32Nibble::hash(self, key)
+    }
+
+    #[cfg(feature = "getrandom")]
+    fn random() -> Self {
+        Tab32Nibble::new()
+    }
+
+    fn new_with_seed(seed: u64) -> Self {
+        Tab32Nibble::new_with_seed(seed)
+    }
+
+    fn id(&self) -> u64 {
+        Tab32Nibble::id(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn to_vec(&self) -> Vec<Vec<u32>> {
+        Tab32Nibble::to_vec(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn from_vec(table_data: Vec<Vec<u32>>) -> Self {
+        Tab32Nibble::from_vec(table_data)
+    }
+}
+
+#[cfg(feature = "getrandom")]
+impl Default for Tab32Nibble {
+    /// Create a new hash function with a random table, just like `new`.
+    fn default() -> Self {
+        Tab32Nibble::new()
+    }
+}
+
+impl core::fmt::Debug for Tab32Nibble {
+    /// Show the id of the hash function as fingerprint instead of all entries.
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let fingerprint = self.id();
+        f.debug_struct("Tab32Nibble")
+            .field("fingerprint", &format_args!("{:#018x}", fingerprint))
+            .finish()
+    }
+}
+
+impl Hash32Fn for Tab32Nibble {
+    fn hash32(&self, x: u32) -> u32 {
+        self.hash(x)
+    }
+}
@@ -0,0 +1,128 @@
+//! Batch hashing on multiple threads.
+use crate::{
+    Tab128Simple, Tab128Twisted, Tab16Simple, Tab32Simple, Tab32To64Simple, Tab32To64Twisted,
+    Tab32Twisted, Tab48Simple, Tab64Simple, Tab64To32Simple, Tab64Twisted,
+};
+use std::thread;
+
+/// Minimum number of keys hashed by a thread.
+///
+/// Hashing this many keys with `hash_into` takes around 100µs, which is well above the
+/// cost of spawning and joining a thread. Smaller inputs are split into fewer chunks,
+/// down to a single chunk hashed on the calling thread.
+const MIN_KEYS_PER_THREAD: usize = 1 << 16;
+
+/// Split keys and output into at most `threads` chunks of at least `MIN_KEYS_PER_THREAD`
+/// keys, and hash each chunk with `hash_into` on its own scoped thread
+fn par_hash_with<K, H, F>(keys: &[K], out: &mut [H], threads: usize, hash_into: F)
+where
+    K: Sync,
+    H: Send,
+    F: Fn(&[K], &mut [H]) + Sync,
+{
+    assert_eq!(
+        keys.len(),
+        out.len(),
+        "the output needs one slot for every key"
+    );
+    let chunks = threads.min(keys.len() / MIN_KEYS_PER_THREAD).max(1);
+    if chunks == 1 {
+        return hash_into(keys, out);
+    }
+    let chunk_len = keys.len().div_ceil(chunks);
+    let hash_into = &hash_into;
+    thread::scope(|scope| {
+        let mut key_chunks = keys.chunks(chunk_len);
+        let mut out_chunks = out.chunks_mut(chunk_len);
+        // hash the first chunk on the calling thread, after spawning the others
+        let first = (key_chunks.next(), out_chunks.next());
+        for (k, o) in key_chunks.zip(out_chunks) {
+            scope.spawn(move || hash_into(k, o));
+        }
+        if let (Some(k), Some(o)) = first {
+            hash_into(k, o);
+        }
+    });
+}
+
+/// Number of threads used by `par_hash_into`
+fn available_threads() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Implement the parallel batch methods for a hash function, hashing each chunk with
+/// its `hash_into` method, or key by key with `hash` for hash functions without one
+macro_rules! impl_par_hash {
+    ($ty:ident, $key:ty) => {
+        impl_par_hash!(
+            $ty,
+            $key,
+            $key,
+            |function: &$ty, keys: &[$key], out: &mut [$key]| { function.hash_into(keys, out) }
+        );
+    };
+    ($ty:ident, $key:ty, $hash:ty) => {
+        impl_par_hash!(
+            $ty,
+            $key,
+            $hash,
+            |function: &$ty, keys: &[$key], out: &mut [$hash]| {
+                for (h, key) in out.iter_mut().zip(keys) {
+                    *h = function.hash(*key);
+                }
+            }
+        );
+    };
+    ($ty:ident, $key:ty, $hash:ty, $hash_chunk:expr) => {
+        impl $ty {
+            /// Compute the hash values of a slice of keys into `out` on multiple threads.
+            ///
+            /// The keys are split into one chunk per available core, each hashed on its
+            /// own thread with the batch loop of `hash_into` if there is one, or key by key,
+            /// so the hash values are exactly those of `hash`. Every thread hashes at least
+            /// 65536 keys, so small slices use fewer threads, and slices of less than
+            /// 131072 keys are hashed on the calling thread without spawning any threads.
+            ///
+            /// # Panics
+            /// If `keys` and `out` have different lengths.
+            pub fn par_hash_into(&self, keys: &[$key], out: &mut [$hash]) {
+                self.par_hash_into_with_threads(keys, out, available_threads())
+            }
+
+            /// Compute the hash values of a slice of keys into `out` on at most `threads`
+            /// threads, including the calling thread, see `par_hash_into`.
+            ///
+            /// # Panics
+            /// If `keys` and `out` have different lengths.
+            pub fn par_hash_into_with_threads(
+                &self,
+                keys: &[$key],
+                out: &mut [$hash],
+                threads: usize,
+            ) {
+                let hash_chunk = $hash_chunk;
+                par_hash_with(keys, out, threads, |keys, out| hash_chunk(self, keys, out))
+            }
+
+            /// Compute the hash values of a slice of keys on multiple threads,
+            /// see `par_hash_into`.
+            pub fn par_hash_many(&self, keys: &[$key]) -> Vec<$hash> {
+                let mut out = vec![0; keys.len()];
+                self.par_hash_into(keys, &mut out);
+                out
+            }
+        }
+    };
+}
+
+impl_par_hash!(Tab32Simple, u32);
+impl_par_hash!(Tab32Twisted, u32);
+impl_par_hash!(Tab64Simple, u64);
+impl_par_hash!(Tab64Twisted, u64);
+impl_par_hash!(Tab16Simple, u16, u16);
+impl_par_hash!(Tab48Simple, u64, u64);
+impl_par_hash!(Tab128Simple, u128, u128);
+impl_par_hash!(Tab128Twisted, u128, u128);
+impl_par_hash!(Tab32To64Simple, u32, u64);
+impl_par_hash!(Tab32To64Twisted, u32, u64);
+impl_par_hash!(Tab64To32Simple, u64, u32);
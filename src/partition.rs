@@ -0,0 +1,108 @@
+//! Stable assignment of keys to bins.
+use crate::{mul_high_64, Tab64Simple, TabHash};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A partitioner assigning 64-bit keys to `num_bins` bins.
+///
+/// The bin of a key is `((hash as u128 * num_bins as u128) >> 64) as usize`, i.e. the
+/// multiply-shift reduction of `hash_to_range`. Each bin receives either `floor(2^64 / b)`
+/// or `ceil(2^64 / b)` hash values, so all bins are equally likely up to `2^-64`.
+///
+/// The assignment only depends on the hash function and the number of bins.
+/// When serialized, both are stored, so a deserialized partitioner assigns every key
+/// to the same bin as the original. Changing the number of bins changes the
+/// assignment of almost all keys. Unlike with consistent hashing,
+/// there is no way to only move a small fraction of the keys.
+///
+/// By default, `Tab64Simple` is used, but any `TabHash` with 64-bit keys works.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::Partitioner;
+///
+/// let partitioner: Partitioner = Partitioner::new_with_seed(16, 42);
+/// assert!(partitioner.bin(7) < 16);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "RawPartitioner<H>",
+        bound(serialize = "H: Serialize", deserialize = "H: Deserialize<'de>")
+    )
+)]
+pub struct Partitioner<H = Tab64Simple> {
+    num_bins: usize,
+    hash_function: H,
+}
+
+/// Unvalidated serialized form of a `Partitioner`
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct RawPartitioner<H> {
+    num_bins: usize,
+    hash_function: H,
+}
+
+#[cfg(feature = "serde")]
+impl<H> core::convert::TryFrom<RawPartitioner<H>> for Partitioner<H> {
+    type Error = &'static str;
+
+    fn try_from(raw: RawPartitioner<H>) -> Result<Self, Self::Error> {
+        if raw.num_bins == 0 {
+            return Err("a partitioner needs at least one bin");
+        }
+        Ok(Partitioner {
+            num_bins: raw.num_bins,
+            hash_function: raw.hash_function,
+        })
+    }
+}
+
+impl<H: TabHash<Key = u64>> Partitioner<H> {
+    /// Create a partitioner with a random hash function.
+    ///
+    /// # Panics
+    /// If `num_bins` is 0.
+    #[cfg(feature = "getrandom")]
+    pub fn new(num_bins: usize) -> Self {
+        Partitioner::with_hash_function(num_bins, H::random())
+    }
+
+    /// Create a partitioner with a hash function created by `new_with_seed`.
+    ///
+    /// # Panics
+    /// If `num_bins` is 0.
+    pub fn new_with_seed(num_bins: usize, seed: u64) -> Self {
+        Partitioner::with_hash_function(num_bins, H::new_with_seed(seed))
+    }
+
+    /// Create a partitioner with the given hash function.
+    ///
+    /// # Panics
+    /// If `num_bins` is 0.
+    pub fn with_hash_function(num_bins: usize, hash_function: H) -> Self {
+        assert!(num_bins > 0, "a partitioner needs at least one bin");
+        Partitioner {
+            num_bins,
+            hash_function,
+        }
+    }
+
+    /// Get the bin of a key, which is in `0..num_bins`.
+    pub fn bin(&self, key: u64) -> usize {
+        mul_high_64(self.hash_function.hash(key), self.num_bins as u64) as usize
+    }
+
+    /// Get the number of bins.
+    pub fn num_bins(&self) -> usize {
+        self.num_bins
+    }
+
+    /// Get the hash function.
+    pub fn hash_function(&self) -> &H {
+        &self.hash_function
+    }
+}
@@ -0,0 +1,251 @@
+//! Random permutations of 32-bit integers.
+use crate::splitmix::SplitMix64;
+use crate::Tab16Simple;
+use rand_core::RngCore;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Number of Feistel rounds of `Perm32`
+const ROUNDS: usize = 4;
+
+/// A random-looking permutation of `u32`, i.e. a hash function without collisions.
+///
+/// The permutation is a Feistel network of 4 rounds. A key is split into its high
+/// and low 16 bits `(l, r)`, and round `i` maps `(l, r)` to `(r, l ^ f_i(r))`, where the
+/// round function `f_i` is a `Tab16Simple` hash function. The result is `(l << 16) | r`.
+/// Each round is a bijection, since `l` can be recovered from `r` and `l ^ f_i(r)` for
+/// any round function. Hence `invert` undoes `permute` for every key, by undoing
+/// the rounds in reverse order.
+///
+/// Unlike a hash function, the permutation maps distinct keys to distinct values,
+/// e.g. to replace ids by pseudonyms. It is not a cryptographic cipher, so do not use
+/// it where pseudonyms must not be traced back by someone who sees many pairs of keys
+/// and values.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::Perm32;
+///
+/// let perm = Perm32::new_with_seed(42);
+/// let pseudonym = perm.permute(1234);
+/// assert_eq!(perm.invert(pseudonym), 1234);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Perm32 {
+    rounds: [Tab16Simple; ROUNDS],
+}
+
+impl Perm32 {
+    /// Create a permutation with random round functions.
+    #[cfg(feature = "getrandom")]
+    pub fn new() -> Self {
+        Perm32::with_round_functions([
+            Tab16Simple::new(),
+            Tab16Simple::new(),
+            Tab16Simple::new(),
+            Tab16Simple::new(),
+        ])
+    }
+
+    /// Create a permutation with round functions derived from a seed.
+    ///
+    /// The seeds of the rounds are drawn from a SplitMix64 generator seeded with `seed`,
+    /// and passed to `Tab16Simple::new_with_seed`.
+    pub fn new_with_seed(seed: u64) -> Self {
+        let mut seeds = SplitMix64::new(seed);
+        let mut round = || Tab16Simple::new_with_seed(seeds.next_u64());
+        Perm32::with_round_functions([round(), round(), round(), round()])
+    }
+
+    /// Create a permutation using the given round functions, in order.
+    pub const fn with_round_functions(rounds: [Tab16Simple; 4]) -> Self {
+        Perm32 { rounds }
+    }
+
+    /// Create a permutation from the tables of the round functions, in order.
+    pub const fn with_tables(tables: [[[u16; 256]; 2]; 4]) -> Self {
+        Perm32::with_round_functions([
+            Tab16Simple::with_table(tables[0]),
+            Tab16Simple::with_table(tables[1]),
+            Tab16Simple::with_table(tables[2]),
+            Tab16Simple::with_table(tables[3]),
+        ])
+    }
+
+    /// Get the round functions.
+    pub fn round_functions(&self) -> &[Tab16Simple; 4] {
+        &self.rounds
+    }
+
+    /// Map a key to its image under the permutation.
+    pub fn permute(&self, x: u32) -> u32 {
+        feistel(&self.rounds, 16, x)
+    }
+
+    /// Map an image back to its key, i.e. `invert(permute(x)) == x` for all `x`.
+    pub fn invert(&self, y: u32) -> u32 {
+        feistel_inverse(&self.rounds, 16, y)
+    }
+}
+
+#[cfg(feature = "getrandom")]
+impl Default for Perm32 {
+    /// Create a permutation with random round functions, just like `new`.
+    fn default() -> Self {
+        Perm32::new()
+    }
+}
+
+/// Apply the Feistel network to `2 * half_bits` bits, truncating the round functions
+fn feistel(rounds: &[Tab16Simple; ROUNDS], half_bits: u32, x: u32) -> u32 {
+    let mask = ((1_u64 << half_bits) - 1) as u32;
+    let (mut l, mut r) = (x >> half_bits, x & mask);
+    for round in rounds.iter() {
+        let next = l ^ (u32::from(round.hash(r as u16)) & mask);
+        l = r;
+        r = next;
+    }
+    (l << half_bits) | r
+}
+
+/// Undo `feistel` by undoing the rounds in reverse order
+fn feistel_inverse(rounds: &[Tab16Simple; ROUNDS], half_bits: u32, y: u32) -> u32 {
+    let mask = ((1_u64 << half_bits) - 1) as u32;
+    let (mut l, mut r) = (y >> half_bits, y & mask);
+    for round in rounds.iter().rev() {
+        let previous = r ^ (u32::from(round.hash(l as u16)) & mask);
+        r = l;
+        l = previous;
+    }
+    (l << half_bits) | r
+}
+
+/// A random-looking permutation of the integers in `0..n`, for any `n` up to `2^32`.
+///
+/// Let `2k` be the smallest even number of bits for the numbers in `0..n`, so `4^k >= n`.
+/// The permutation applies the Feistel network of a `Perm32` to `2k` bits, i.e. to halves
+/// of `k` bits with the round functions truncated to their low `k` bits (for `k = 16`,
+/// this is exactly `Perm32::permute`). This permutes `0..4^k`, and is repeated until the
+/// result lands in `0..n` ("cycle walking"). `invert` walks back with the inverse network.
+///
+/// Walking stops at the latest when it returns to the starting number, which is in `0..n`,
+/// so it always terminates. Since `4^k < 4n`, it visits at most `4^k - n < 3n` numbers
+/// outside of `0..n`, so a call applies the network at most `3n - 2` times (once for
+/// `n = 1`). Since more than a quarter of the numbers in `0..4^k` are in `0..n`,
+/// fewer than 4 applications are expected for a random permutation.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::PermDomain;
+///
+/// let perm = PermDomain::new_with_seed(1000, 42);
+/// let shuffled = perm.permute(7);
+/// assert!(shuffled < 1000);
+/// assert_eq!(perm.invert(shuffled), 7);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawPermDomain"))]
+pub struct PermDomain {
+    n: u64,
+    permutation: Perm32,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    half_bits: u32,
+}
+
+/// Unvalidated serialized form of a `PermDomain`
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct RawPermDomain {
+    n: u64,
+    permutation: Perm32,
+}
+
+#[cfg(feature = "serde")]
+impl core::convert::TryFrom<RawPermDomain> for PermDomain {
+    type Error = &'static str;
+
+    fn try_from(raw: RawPermDomain) -> Result<Self, Self::Error> {
+        if raw.n == 0 || raw.n > 1 << 32 {
+            return Err("the domain of a permutation needs between 1 and 2^32 numbers");
+        }
+        Ok(PermDomain::new(raw.n, raw.permutation))
+    }
+}
+
+impl PermDomain {
+    /// Create a permutation of `0..n` using the round functions of `permutation`.
+    ///
+    /// # Panics
+    /// If `n` is 0 or larger than `2^32`.
+    pub fn new(n: u64, permutation: Perm32) -> Self {
+        assert!(
+            n > 0 && n <= 1 << 32,
+            "the domain of a permutation needs between 1 and 2^32 numbers, not {}",
+            n
+        );
+        let bits = 64 - (n - 1).leading_zeros();
+        PermDomain {
+            n,
+            permutation,
+            half_bits: bits.div_ceil(2),
+        }
+    }
+
+    /// Create a permutation of `0..n` with `Perm32::new_with_seed`.
+    ///
+    /// # Panics
+    /// If `n` is 0 or larger than `2^32`.
+    pub fn new_with_seed(n: u64, seed: u64) -> Self {
+        PermDomain::new(n, Perm32::new_with_seed(seed))
+    }
+
+    /// Get the size of the domain, `n`.
+    pub fn len(&self) -> u64 {
+        self.n
+    }
+
+    /// Check if the domain is empty, which it never is.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Get the underlying permutation of `u32`.
+    pub fn permutation(&self) -> &Perm32 {
+        &self.permutation
+    }
+
+    /// Map a number in `0..n` to its image in `0..n`.
+    ///
+    /// # Panics
+    /// If `i` is not smaller than `n`.
+    pub fn permute(&self, i: u64) -> u64 {
+        self.walk(i, feistel)
+    }
+
+    /// Map an image in `0..n` back to its number, i.e. `invert(permute(i)) == i`.
+    ///
+    /// # Panics
+    /// If `i` is not smaller than `n`.
+    pub fn invert(&self, i: u64) -> u64 {
+        self.walk(i, feistel_inverse)
+    }
+
+    /// Apply a Feistel network on `2 * half_bits` bits until the result is in `0..n`
+    fn walk(&self, i: u64, network: fn(&[Tab16Simple; ROUNDS], u32, u32) -> u32) -> u64 {
+        assert!(
+            i < self.n,
+            "{} is out of range for a permutation of {} numbers",
+            i,
+            self.n
+        );
+        let mut x = i as u32;
+        loop {
+            x = network(&self.permutation.rounds, self.half_bits, x);
+            if u64::from(x) < self.n {
+                return u64::from(x);
+            }
+        }
+    }
+}
@@ -0,0 +1,202 @@
+//! Polynomial hashing over a Mersenne prime field, finalized by simple tabulation.
+#[cfg(feature = "getrandom")]
+use crate::os_seeded_rng;
+use crate::splitmix::SplitMix64;
+use crate::{Hash64Fn, Tab64Simple};
+use alloc::vec::Vec;
+use rand_core::RngCore;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The Mersenne prime `2^89 - 1`, the size of the field of the polynomial
+const PRIME: u128 = (1 << 89) - 1;
+
+/// Reduce a number below `2^128` modulo `PRIME`, using `2^89 = 1`
+fn reduce(x: u128) -> u128 {
+    let r = (x & PRIME) + (x >> 89);
+    if r >= PRIME {
+        r - PRIME
+    } else {
+        r
+    }
+}
+
+/// Multiply two field elements modulo `PRIME`
+fn mul_mod(a: u128, b: u128) -> u128 {
+    let (a_high, a_low) = (a >> 64, a & u128::from(u64::MAX));
+    let (b_high, b_low) = (b >> 64, b & u128::from(u64::MAX));
+    // the product of values below 2^89 has at most 178 bits, kept as `high * 2^128 + low`
+    let middle = a_high * b_low + a_low * b_high;
+    let (low, carry) = (a_low * b_low).overflowing_add(middle << 64);
+    let high = a_high * b_high + (middle >> 64) + u128::from(carry);
+    // 2^128 = 2^39 modulo PRIME, and `high` has at most 50 bits
+    reduce((low & PRIME) + (low >> 89) + (high << 39))
+}
+
+/// Draw a field element uniformly at random, rejecting the single 89-bit value `PRIME`
+fn coefficient_from_rng<R: RngCore + ?Sized>(rng: &mut R) -> u128 {
+    loop {
+        let low = u128::from(rng.next_u64());
+        let high = u128::from(rng.next_u64());
+        let c = ((high << 64) | low) & PRIME;
+        if c < PRIME {
+            return c;
+        }
+    }
+}
+
+/// A `k`-independent hash function for 64-bit keys.
+///
+/// A key `x` is first mapped to `a_0 + a_1 x + ... + a_(k-1) x^(k-1)` modulo the Mersenne
+/// prime `p = 2^89 - 1`, for random coefficients `a_i`. Since all keys are smaller than `p`,
+/// the values of any `k` distinct keys are independent and uniformly distributed in the
+/// field. The low 64 bits of this value are then hashed with a `Tab64Simple`.
+///
+/// The independence claim and its caveats:
+///
+/// - The polynomial is exactly `k`-independent over the field.
+/// - Truncating a uniform field element to 64 bits misses uniformity by at most `2^-89`,
+///   since all residues but one have the same number of preimages.
+/// - Simple tabulation is a fixed function for the `k` values, so it cannot create
+///   dependencies between them. It maps independent uniform values to independent uniform
+///   hash values, unless the values happen to form a set in which every byte occurs an even
+///   number of times at each position, which is negligibly unlikely for random values.
+///   Hence the hash values of any `k` keys are independent up to a negligible statistical
+///   error, but this is not exact `k`-independence.
+/// - The finalizer does not add independence beyond `k`. It scrambles the structured values
+///   of low-degree polynomials, e.g. of consecutive keys under a linear polynomial.
+/// - Evaluating the polynomial costs `k` multiplications modulo `p`, so this is several
+///   times slower than twisted tabulation already for small `k`.
+///
+/// Draws from a generator take the table of the `Tab64Simple` first, followed by the
+/// coefficients `a_0` to `a_(k-1)`, each from two 64-bit words (low word first), masked to
+/// 89 bits and redrawn if equal to `p`. Hence `new_with_seed(k, seed)` uses the table of
+/// `Tab64Simple::new_with_seed(seed)`.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::TabPoly64;
+///
+/// let five_independent = TabPoly64::new_with_seed(5, 42);
+/// assert_eq!(five_independent.independence(), 5);
+/// println!("{}", five_independent.hash(7));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawTabPoly64"))]
+pub struct TabPoly64 {
+    coefficients: Vec<u128>,
+    hash_function: Tab64Simple,
+}
+
+/// Unvalidated serialized form of a `TabPoly64`
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct RawTabPoly64 {
+    coefficients: Vec<u128>,
+    hash_function: Tab64Simple,
+}
+
+#[cfg(feature = "serde")]
+impl core::convert::TryFrom<RawTabPoly64> for TabPoly64 {
+    type Error = &'static str;
+
+    fn try_from(raw: RawTabPoly64) -> Result<Self, Self::Error> {
+        if raw.coefficients.is_empty() {
+            return Err("the independence has to be at least 1");
+        }
+        if raw.coefficients.iter().any(|c| *c >= PRIME) {
+            return Err("coefficients have to be smaller than 2^89 - 1");
+        }
+        Ok(TabPoly64 {
+            coefficients: raw.coefficients,
+            hash_function: raw.hash_function,
+        })
+    }
+}
+
+impl TabPoly64 {
+    /// Create a `k`-independent hash function with random coefficients and table.
+    ///
+    /// # Panics
+    /// If `k` is 0.
+    #[cfg(feature = "getrandom")]
+    pub fn new(k: usize) -> Self {
+        TabPoly64::from_rng(k, &mut os_seeded_rng())
+    }
+
+    /// Create a `k`-independent hash function with coefficients and table derived from a seed.
+    ///
+    /// The seed is expanded with SplitMix64, see the crate documentation.
+    ///
+    /// # Panics
+    /// If `k` is 0.
+    pub fn new_with_seed(k: usize, seed: u64) -> Self {
+        TabPoly64::from_rng(k, &mut SplitMix64::new(seed))
+    }
+
+    /// Draw the table and then `k` coefficients from `rng`
+    fn from_rng<R: RngCore + ?Sized>(k: usize, rng: &mut R) -> Self {
+        assert!(k > 0, "the independence has to be at least 1");
+        let hash_function = Tab64Simple::from_rng(rng);
+        let coefficients = (0..k).map(|_| coefficient_from_rng(rng)).collect();
+        TabPoly64 {
+            coefficients,
+            hash_function,
+        }
+    }
+
+    /// Create a hash function from the coefficients `a_0, ..., a_(k-1)` and a finalizer.
+    ///
+    /// # Panics
+    /// If there are no coefficients, or a coefficient is not smaller than `2^89 - 1`.
+    pub fn with_coefficients(coefficients: Vec<u128>, hash_function: Tab64Simple) -> Self {
+        assert!(
+            !coefficients.is_empty(),
+            "the independence has to be at least 1"
+        );
+        assert!(
+            coefficients.iter().all(|c| *c < PRIME),
+            "coefficients have to be smaller than 2^89 - 1"
+        );
+        TabPoly64 {
+            coefficients,
+            hash_function,
+        }
+    }
+
+    /// Get the independence `k`, i.e. the number of coefficients.
+    pub fn independence(&self) -> usize {
+        self.coefficients.len()
+    }
+
+    /// Get the coefficients `a_0, ..., a_(k-1)` of the polynomial.
+    pub fn coefficients(&self) -> &[u128] {
+        &self.coefficients
+    }
+
+    /// Get the simple tabulation hash function used as finalizer.
+    pub fn hash_function(&self) -> &Tab64Simple {
+        &self.hash_function
+    }
+
+    /// Evaluate the polynomial on a key, a value smaller than `2^89 - 1`.
+    pub fn polynomial(&self, x: u64) -> u128 {
+        let x = u128::from(x);
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(0, |acc, c| reduce(mul_mod(acc, x) + c))
+    }
+
+    /// Compute the hash value of a key.
+    pub fn hash(&self, x: u64) -> u64 {
+        self.hash_function.hash(self.polynomial(x) as u64)
+    }
+}
+
+impl Hash64Fn for TabPoly64 {
+    fn hash64(&self, x: u64) -> u64 {
+        self.hash(x)
+    }
+}
@@ -0,0 +1,51 @@
+//! Batch hashing with the portable SIMD types of `core::simd`, which requires nightly Rust.
+use core::simd::num::SimdUint;
+use core::simd::Simd;
+
+/// Number of keys hashed at once
+const LANES: usize = 8;
+
+/// Whether the kernel is used on the target.
+///
+/// On x86-64, the kernel needs AVX2 or AVX-512 enabled at compile time, e.g. with
+/// `-C target-feature=+avx2`. Without them, the backend splits gathers into moves between
+/// lanes and scalar loads, which is slower than the scalar batch loop.
+///
+/// aarch64 and wasm32 with `simd128` have no gather instructions either, but their vector
+/// registers are always available, so the gathers become independent scalar loads into
+/// the lanes and each column takes a single vector XOR. The kernel is used on these
+/// targets, checked against `hash` by the same tests as on x86-64. If the `batch`
+/// benchmark shows it to be slower than the scalar batch loop on a CPU, `force_scalar`
+/// switches back to the scalar batch loop.
+pub(crate) const TARGET_SUPPORTED: bool = cfg!(any(
+    target_feature = "avx2",
+    target_feature = "avx512f",
+    target_arch = "aarch64",
+    all(target_arch = "wasm32", target_feature = "simd128")
+));
+
+/// Hash blocks of eight keys with the table of a `Tab32Simple`, gathering the entries of
+/// the eight keys from one column at a time.
+///
+/// Returns the number of keys hashed, a multiple of eight, which is 0 on targets the kernel
+/// is not used on, see `TARGET_SUPPORTED`. The remaining keys at the end of the slice
+/// have to be hashed by the caller.
+pub(crate) fn hash_blocks_32(table: &[[u32; 256]; 4], keys: &[u32], out: &mut [u32]) -> usize {
+    if !TARGET_SUPPORTED {
+        return 0;
+    }
+    let low_byte = Simd::splat(0xFF);
+    let key_blocks = keys.chunks_exact(LANES);
+    let hashed = LANES * key_blocks.len().min(out.len() / LANES);
+    for (k, o) in key_blocks.zip(out.chunks_exact_mut(LANES)) {
+        let k = Simd::<u32, LANES>::from_slice(k);
+        let mut h = Simd::splat(0);
+        for (shift, column) in (0..32).step_by(8).zip(table.iter()) {
+            let chars = (k >> Simd::splat(shift)) & low_byte;
+            // all characters are in bounds of the columns, so no lane takes the default
+            h ^= Simd::gather_or_default(column, chars.cast::<usize>());
+        }
+        h.copy_to_slice(o);
+    }
+    hashed
+}
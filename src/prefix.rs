@@ -0,0 +1,118 @@
+//! Simple tabulation of runs of keys sharing their high bytes.
+use crate::{byte_chunks_32, Tab32Simple};
+use core::ops::Range;
+
+/// A `Tab32Simple` with the high bytes of the keys fixed to a prefix.
+///
+/// Simple tabulation XORs the entries of the bytes of a key, so the entries of the
+/// bytes of the prefix are XORed once when the prefix is set. Hashing the remaining
+/// low bytes, the suffix, then only needs one lookup per byte, e.g. one or two lookups
+/// instead of four for keys that only differ in their low 8 or 16 bits. The hash values
+/// are exactly those of the hash function, i.e. `hash_suffix(low) == hash(prefix | low)`.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::Tab32Simple;
+///
+/// let simple = Tab32Simple::new_with_seed(42);
+/// let shard = simple.with_prefix(0x1234_0000, 2);
+/// assert_eq!(shard.hash_suffix(0x5678), simple.hash(0x1234_5678));
+/// for h in shard.hash_suffixes(0..1000) {
+///     println!("{}", h);
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PrefixHasher<'a> {
+    hash_function: &'a Tab32Simple,
+    prefix: u32,
+    prefix_hash: u32,
+    suffix_bytes: usize,
+}
+
+impl Tab32Simple {
+    /// Fix the `prefix_bytes` high bytes of the keys to those of `prefix`.
+    ///
+    /// # Panics
+    /// If `prefix_bytes` is larger than 4, or `prefix` has bits set below its
+    /// `prefix_bytes` high bytes.
+    pub fn with_prefix(&self, prefix: u32, prefix_bytes: u8) -> PrefixHasher<'_> {
+        assert!(
+            prefix_bytes <= 4,
+            "a prefix of a 32-bit key has at most 4 bytes, not {}",
+            prefix_bytes
+        );
+        let suffix_bytes = 4 - prefix_bytes as usize;
+        assert!(
+            prefix & suffix_mask(suffix_bytes) == 0,
+            "the prefix {:#x} has bits set below its {} high bytes",
+            prefix,
+            prefix_bytes
+        );
+        let chunks = byte_chunks_32(prefix);
+        let mut prefix_hash = 0;
+        for (column, c) in self.table.iter().zip(chunks.iter()).skip(suffix_bytes) {
+            prefix_hash ^= column[*c as usize];
+        }
+        PrefixHasher {
+            hash_function: self,
+            prefix,
+            prefix_hash,
+            suffix_bytes,
+        }
+    }
+}
+
+/// Mask of the low `suffix_bytes` bytes of a 32-bit key
+fn suffix_mask(suffix_bytes: usize) -> u32 {
+    ((1_u64 << (8 * suffix_bytes)) - 1) as u32
+}
+
+impl<'a> PrefixHasher<'a> {
+    /// Get the prefix, i.e. the key with all bytes of the suffix set to zero.
+    pub fn prefix(&self) -> u32 {
+        self.prefix
+    }
+
+    /// Get the number of low bytes of a key not fixed by the prefix.
+    pub fn suffix_bytes(&self) -> usize {
+        self.suffix_bytes
+    }
+
+    /// Get the hash function.
+    pub fn hash_function(&self) -> &'a Tab32Simple {
+        self.hash_function
+    }
+
+    /// Compute the hash value of the key `prefix | low`.
+    ///
+    /// # Panics
+    /// If `low` has bits set outside of the suffix.
+    #[inline]
+    pub fn hash_suffix(&self, low: u32) -> u32 {
+        assert!(
+            low & !suffix_mask(self.suffix_bytes) == 0,
+            "the suffix {:#x} has more than {} bytes",
+            low,
+            self.suffix_bytes
+        );
+        let c = byte_chunks_32(low).map(usize::from);
+        let t = &self.hash_function.table;
+        // unrolled for each length, since the length is the same for a whole run
+        match self.suffix_bytes {
+            0 => self.prefix_hash,
+            1 => self.prefix_hash ^ t[0][c[0]],
+            2 => self.prefix_hash ^ t[0][c[0]] ^ t[1][c[1]],
+            3 => self.prefix_hash ^ t[0][c[0]] ^ t[1][c[1]] ^ t[2][c[2]],
+            _ => self.prefix_hash ^ t[0][c[0]] ^ t[1][c[1]] ^ t[2][c[2]] ^ t[3][c[3]],
+        }
+    }
+
+    /// Compute the hash values of the keys `prefix | low` for all `low` in a range, in order.
+    ///
+    /// # Panics
+    /// When the iterator reaches a suffix of the range with bits set outside of the suffix.
+    pub fn hash_suffixes(&self, lows: Range<u32>) -> impl Iterator<Item = u32> + 'a {
+        let hasher = *self;
+        lows.map(move |low| hasher.hash_suffix(low))
+    }
+}
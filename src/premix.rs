@@ -0,0 +1,140 @@
+//! Simple tabulation hashing of keys premixed by a multiplication.
+#[cfg(feature = "getrandom")]
+use crate::os_seeded_rng;
+use crate::splitmix::SplitMix64;
+use crate::{Hash64Fn, Tab64Simple};
+use rand_core::RngCore;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A hash function for 64-bit keys applying `Tab64Simple` to premixed keys.
+///
+/// The hash value of a key `x` is `tab(premix(x))`, where
+/// `premix(x) = x * multiplier mod 2^64` (i.e. `x.wrapping_mul(multiplier)`) for a random
+/// odd `multiplier`, and `tab` is the simple tabulation hash function. Since the multiplier
+/// is odd, the premix is a bijection of `u64`, so it never maps distinct keys to the same
+/// value, and the hash function keeps all guarantees of simple tabulation.
+/// Multiplication only propagates bits upward: byte `i` of `premix(x)` depends on bytes
+/// `0..=i` of `x`. So premixed keys still differ in the lowest byte in which the keys
+/// differ, and usually in all higher ones, e.g. for sequential integers. But keys that
+/// agree in their low bytes also agree in the low bytes of their premixed values.
+///
+/// The premix costs one multiplication per key. Both the multiplier and the table are
+/// serialized, so the hash function can be reproduced elsewhere with the formula above.
+/// Draws from a generator take the table first, followed by one 64-bit word with its
+/// lowest bit set as multiplier. Hence `new_with_seed(seed)` uses the table of
+/// `Tab64Simple::new_with_seed(seed)`.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::Tab64Premixed;
+///
+/// let premixed = Tab64Premixed::new_with_seed(42);
+/// for k in 0..5 {
+///     println!("{}", premixed.hash(k));
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawTab64Premixed"))]
+pub struct Tab64Premixed {
+    multiplier: u64,
+    hash_function: Tab64Simple,
+}
+
+/// Unvalidated serialized form of a `Tab64Premixed`
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct RawTab64Premixed {
+    multiplier: u64,
+    hash_function: Tab64Simple,
+}
+
+#[cfg(feature = "serde")]
+impl core::convert::TryFrom<RawTab64Premixed> for Tab64Premixed {
+    type Error = &'static str;
+
+    fn try_from(raw: RawTab64Premixed) -> Result<Self, Self::Error> {
+        if raw.multiplier & 1 == 0 {
+            return Err("the multiplier of the premix has to be odd");
+        }
+        Ok(Tab64Premixed {
+            multiplier: raw.multiplier,
+            hash_function: raw.hash_function,
+        })
+    }
+}
+
+impl Tab64Premixed {
+    /// Create a new hash function with a random multiplier and table.
+    #[cfg(feature = "getrandom")]
+    pub fn new() -> Self {
+        Tab64Premixed::from_rng(&mut os_seeded_rng())
+    }
+
+    /// Create a new hash function with multiplier and table derived from a seed.
+    ///
+    /// The seed is expanded with SplitMix64, see the crate documentation.
+    pub fn new_with_seed(seed: u64) -> Self {
+        Tab64Premixed::from_rng(&mut SplitMix64::new(seed))
+    }
+
+    /// Draw the table and then the multiplier from `rng`
+    fn from_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        let hash_function = Tab64Simple::from_rng(rng);
+        Tab64Premixed {
+            multiplier: rng.next_u64() | 1,
+            hash_function,
+        }
+    }
+
+    /// Create a new hash function from a multiplier and a simple tabulation hash function.
+    ///
+    /// # Panics
+    /// If the multiplier is even.
+    pub fn with_multiplier(multiplier: u64, hash_function: Tab64Simple) -> Self {
+        assert!(
+            multiplier & 1 == 1,
+            "the multiplier of the premix has to be odd, not {}",
+            multiplier
+        );
+        Tab64Premixed {
+            multiplier,
+            hash_function,
+        }
+    }
+
+    /// Get the multiplier of the premix.
+    pub fn multiplier(&self) -> u64 {
+        self.multiplier
+    }
+
+    /// Get the simple tabulation hash function applied after the premix.
+    pub fn hash_function(&self) -> &Tab64Simple {
+        &self.hash_function
+    }
+
+    /// Premix a key, i.e. multiply it with the multiplier modulo `2^64`.
+    pub fn premix(&self, x: u64) -> u64 {
+        x.wrapping_mul(self.multiplier)
+    }
+
+    /// Compute the hash value of a key, the simple tabulation hash value of its premix.
+    pub fn hash(&self, x: u64) -> u64 {
+        self.hash_function.hash(self.premix(x))
+    }
+}
+
+#[cfg(feature = "getrandom")]
+impl Default for Tab64Premixed {
+    /// Create a new hash function with a random multiplier and table, just like `new`.
+    fn default() -> Self {
+        Tab64Premixed::new()
+    }
+}
+
+impl Hash64Fn for Tab64Premixed {
+    fn hash64(&self, x: u64) -> u64 {
+        self.hash(x)
+    }
+}
@@ -0,0 +1,72 @@
+//! Probe sequences for open addressing.
+
+/// Double hashing probe sequence, see `Tab32Twisted::probe_sequence`.
+#[derive(Clone, Debug)]
+pub(crate) struct ProbeSequence {
+    position: usize,
+    step: usize,
+    capacity: usize,
+    remaining: usize,
+}
+
+impl ProbeSequence {
+    /// Create a probe sequence starting at `h1 mod capacity` with a step derived from `h2`.
+    ///
+    /// The step is `1 + h2 mod (capacity - 1)`, increased until it is coprime
+    /// to the capacity, so that the sequence visits every slot once.
+    pub(crate) fn new(h1: u32, h2: u32, capacity: usize) -> Self {
+        if capacity <= 1 {
+            return ProbeSequence {
+                position: 0,
+                step: 0,
+                capacity,
+                remaining: capacity,
+            };
+        }
+        let mut step = 1 + h2 as usize % (capacity - 1);
+        while gcd(step, capacity) != 1 {
+            step = if step + 1 == capacity { 1 } else { step + 1 };
+        }
+        ProbeSequence {
+            position: h1 as usize % capacity,
+            step,
+            capacity,
+            remaining: capacity,
+        }
+    }
+}
+
+impl Iterator for ProbeSequence {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let probe = self.position;
+        // add the step modulo the capacity without overflowing
+        self.position = if self.position >= self.capacity - self.step {
+            self.position - (self.capacity - self.step)
+        } else {
+            self.position + self.step
+        };
+        Some(probe)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for ProbeSequence {}
+
+/// Greatest common divisor of `a` and `b`
+fn gcd(mut a: usize, mut b: usize) -> usize {
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a
+}
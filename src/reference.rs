@@ -0,0 +1,322 @@
+//! Published reference hash functions and known answers.
+//!
+//! The reference tables are the seeded tables for `REFERENCE_SEED_V1`,
+//! i.e. `TAB32_SIMPLE_V1` is identical to `Tab32Simple::new_with_seed(REFERENCE_SEED_V1)`.
+//! For each hash function, the expected hash values of the reference keys
+//! of the respective width are published as well. Use them to pin hash
+//! values in your own tests, or call `self_check` to verify at runtime
+//! that this crate computes the same hashes on the current platform.
+//!
+//! All constants are versioned by their name. Published versions never change;
+//! should the reference set ever need to change, new constants are added under
+//! a new version instead.
+use crate::splitmix::{mix64, GOLDEN_GAMMA};
+#[cfg(feature = "alloc")]
+use crate::Tab128Twisted;
+use crate::{Tab128Simple, Tab16Simple, Tab32Simple, Tab32Twisted, Tab64Simple, Tab64Twisted};
+use core::fmt;
+
+/// The current version of the reference set.
+pub const REFERENCE_VERSION: u32 = 1;
+
+/// Seed of all reference tables of version 1.
+pub const REFERENCE_SEED_V1: u64 = 0x07AB_4A54_5EED_0001;
+
+/// Expand a seed into a table like `new_with_seed` does, at compile time.
+///
+/// Each entry consumes `$words` SplitMix64 outputs, starting with the lowest word,
+/// and keeps the low bits of the result.
+macro_rules! seeded_table {
+    ($entry:ty, $columns:expr, $words:expr, $seed:expr) => {{
+        let mut table: [[$entry; 256]; $columns] = [[0; 256]; $columns];
+        let mut state: u64 = $seed;
+        let mut i = 0;
+        while i < $columns * 256 {
+            let mut entry: u128 = 0;
+            let mut k = 0;
+            while k < $words {
+                entry |= (mix64(state) as u128) << (64 * k);
+                state = state.wrapping_add(GOLDEN_GAMMA);
+                k += 1;
+            }
+            table[i / 256][i % 256] = entry as $entry;
+            i += 1;
+        }
+        table
+    }};
+}
+
+/// Reference simple tabulation hash function for 16-bit keys (version 1).
+pub const TAB16_SIMPLE_V1: Tab16Simple =
+    Tab16Simple::with_table(seeded_table!(u16, 2, 1, REFERENCE_SEED_V1));
+
+/// Reference simple tabulation hash function for 32-bit keys (version 1).
+pub const TAB32_SIMPLE_V1: Tab32Simple =
+    Tab32Simple::with_table(seeded_table!(u32, 4, 1, REFERENCE_SEED_V1));
+
+/// Reference simple tabulation hash function for 64-bit keys (version 1).
+pub const TAB64_SIMPLE_V1: Tab64Simple =
+    Tab64Simple::with_table(seeded_table!(u64, 8, 1, REFERENCE_SEED_V1));
+
+/// Reference simple tabulation hash function for 128-bit keys (version 1).
+pub const TAB128_SIMPLE_V1: Tab128Simple =
+    Tab128Simple::with_table(seeded_table!(u128, 16, 2, REFERENCE_SEED_V1));
+
+/// Reference twisted tabulation hash function for 32-bit keys (version 1).
+pub const TAB32_TWISTED_V1: Tab32Twisted =
+    Tab32Twisted::with_table(seeded_table!(u64, 4, 1, REFERENCE_SEED_V1));
+
+/// Reference twisted tabulation hash function for 64-bit keys (version 1).
+pub const TAB64_TWISTED_V1: Tab64Twisted =
+    Tab64Twisted::with_table(seeded_table!(u128, 8, 2, REFERENCE_SEED_V1));
+
+/// Reference twisted tabulation hash function for 128-bit keys (version 1).
+///
+/// Since its table lives on the heap, this is a function instead of a constant.
+#[cfg(feature = "alloc")]
+pub fn tab128_twisted_v1() -> Tab128Twisted {
+    Tab128Twisted::new_with_seed(REFERENCE_SEED_V1)
+}
+
+/// Reference keys for 16-bit hash functions (version 1).
+pub const KEYS16_V1: [u16; 8] = [0, 1, 0x8000, 0xBEEF, 0x0201, u16::MAX, 42, 1337];
+
+/// Reference keys for 32-bit hash functions (version 1).
+pub const KEYS32_V1: [u32; 8] = [
+    0,
+    1,
+    0x8000_0000,
+    0xDEAD_BEEF,
+    0x0403_0201,
+    u32::MAX,
+    42,
+    1337,
+];
+
+/// Reference keys for 64-bit hash functions (version 1).
+pub const KEYS64_V1: [u64; 8] = [
+    0,
+    1,
+    0x8000_0000_0000_0000,
+    0xDEAD_BEEF_CAFE_BABE,
+    0x0807_0605_0403_0201,
+    u64::MAX,
+    42,
+    1337,
+];
+
+/// Reference keys for 128-bit hash functions (version 1).
+pub const KEYS128_V1: [u128; 8] = [
+    0,
+    1,
+    0x8000_0000_0000_0000_0000_0000_0000_0000,
+    0xDEAD_BEEF_CAFE_BABE_0123_4567_89AB_CDEF,
+    0x100F_0E0D_0C0B_0A09_0807_0605_0403_0201,
+    u128::MAX,
+    42,
+    1337,
+];
+
+/// Hash values of `KEYS16_V1` under `TAB16_SIMPLE_V1`.
+pub const EXPECTED_TAB16_SIMPLE_V1: [u16; 8] = [
+    0x7562, 0x35b4, 0x0d2a, 0xac3a, 0x0dd2, 0xd759, 0x385a, 0x6ae0,
+];
+
+/// Hash values of `KEYS32_V1` under `TAB32_SIMPLE_V1`.
+pub const EXPECTED_TAB32_SIMPLE_V1: [u32; 8] = [
+    0x56d2_9dde,
+    0x0a3b_dd08,
+    0x52d2_6703,
+    0x8bdf_beae,
+    0x0168_80f5,
+    0xcde7_84d5,
+    0x1b2a_d0e6,
+    0xbb3f_825c,
+];
+
+/// Hash values of `KEYS64_V1` under `TAB64_SIMPLE_V1`.
+pub const EXPECTED_TAB64_SIMPLE_V1: [u64; 8] = [
+    0x4b25_0daf_4c97_385c,
+    0xa5e6_44cd_107e_788a,
+    0x5b3d_4762_779f_f360,
+    0xc655_69b7_3b91_2986,
+    0xc19d_8cbf_564c_c67a,
+    0x6ba7_2ccd_63ee_2e97,
+    0x9354_2e6e_016f_7564,
+    0xb790_92dc_a17a_27de,
+];
+
+/// Hash values of `KEYS128_V1` under `TAB128_SIMPLE_V1`.
+pub const EXPECTED_TAB128_SIMPLE_V1: [u128; 8] = [
+    0x947f_5c00_b15c_0920_9f0a_c736_9ac7_d6ec,
+    0x9e69_66f6_fa5e_c083_1c52_0c86_0a54_624e,
+    0xc0ec_46a1_5141_07e1_932a_5319_b2b8_05e5,
+    0x4e4e_d2a3_08f1_b4da_638d_0c60_1474_e949,
+    0xf6fb_cf1c_b827_6870_10e0_dc9b_7d85_9f17,
+    0x289e_4fe3_f1a5_81e5_eb88_4dd0_9905_c222,
+    0xf2f0_e033_e233_8c69_7151_9d4c_6071_2f10,
+    0x3d97_3aef_d319_0000_feb7_9b17_3704_da8d,
+];
+
+/// Hash values of `KEYS32_V1` under `TAB32_TWISTED_V1`.
+pub const EXPECTED_TAB32_TWISTED_V1: [u32; 8] = [
+    0x28ba_e106,
+    0x7080_989f,
+    0x0204_9fbe,
+    0x2325_a8a0,
+    0xf05e_49ba,
+    0xc48c_9da5,
+    0xb772_9542,
+    0x0915_6e71,
+];
+
+/// Hash values of `KEYS64_V1` under `TAB64_TWISTED_V1`.
+pub const EXPECTED_TAB64_TWISTED_V1: [u64; 8] = [
+    0x35e1_74c2_bf79_48d4,
+    0xfb98_c07a_9e78_778b,
+    0x19ad_8859_a861_fbe1,
+    0xfa78_5bda_4c60_7fb0,
+    0xfad1_cbe8_40f2_becf,
+    0x665e_3971_8410_55b7,
+    0x48b9_204f_2cf1_ea92,
+    0xe677_d4c6_b444_9ea8,
+];
+
+/// Hash values of `KEYS128_V1` under `tab128_twisted_v1()`.
+pub const EXPECTED_TAB128_TWISTED_V1: [u128; 8] = [
+    0x71cb_e576_e7ca_d15d_2864_85af_f66c_c91e,
+    0x54bd_8521_9d16_2e68_0d5b_6d30_eb09_1f45,
+    0x80e3_0082_18db_10a5_d202_b99c_7630_008d,
+    0x9314_ca0a_96cf_912a_1f55_1895_2992_b707,
+    0x2dea_f03f_2c98_7ee8_af48_1cde_cdb6_ca79,
+    0x313a_8911_3d2a_59cd_3cee_c5dd_ae50_312f,
+    0xf37f_a66f_5a4d_394c_3e99_d842_2703_24ae,
+    0xf647_f037_280f_04b0_d47a_acd6_ecd3_b914,
+];
+
+/// A reference hash value that was not reproduced by `self_check`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SelfCheckError {
+    /// Name of the failing hash function
+    pub hash_function: &'static str,
+    /// The reference key
+    pub key: u128,
+    /// The published hash value of the key
+    pub expected: u128,
+    /// The hash value computed on this platform
+    pub actual: u128,
+}
+
+impl fmt::Display for SelfCheckError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} hashed key {:#x} to {:#x} instead of {:#x}",
+            self.hash_function, self.key, self.actual, self.expected
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SelfCheckError {}
+
+/// Compare the hash values of the reference keys against the published values
+fn check<K, H>(
+    hash_function: &'static str,
+    keys: &[K],
+    expected: &[H],
+    hash: impl Fn(K) -> H,
+) -> Result<(), SelfCheckError>
+where
+    K: Copy + Into<u128>,
+    H: Copy + Into<u128> + PartialEq,
+{
+    for (key, expected) in keys.iter().zip(expected.iter()) {
+        let actual = hash(*key);
+        if actual != *expected {
+            return Err(SelfCheckError {
+                hash_function,
+                key: (*key).into(),
+                expected: (*expected).into(),
+                actual: actual.into(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Verify that all reference hash functions of the current version compute
+/// the published hash values on this platform.
+///
+/// Returns the first mismatch, if any.
+pub fn self_check() -> Result<(), SelfCheckError> {
+    check("Tab16Simple", &KEYS16_V1, &EXPECTED_TAB16_SIMPLE_V1, |k| {
+        TAB16_SIMPLE_V1.hash(k)
+    })?;
+    check("Tab32Simple", &KEYS32_V1, &EXPECTED_TAB32_SIMPLE_V1, |k| {
+        TAB32_SIMPLE_V1.hash(k)
+    })?;
+    check("Tab64Simple", &KEYS64_V1, &EXPECTED_TAB64_SIMPLE_V1, |k| {
+        TAB64_SIMPLE_V1.hash(k)
+    })?;
+    check(
+        "Tab128Simple",
+        &KEYS128_V1,
+        &EXPECTED_TAB128_SIMPLE_V1,
+        |k| TAB128_SIMPLE_V1.hash(k),
+    )?;
+    check(
+        "Tab32Twisted",
+        &KEYS32_V1,
+        &EXPECTED_TAB32_TWISTED_V1,
+        |k| TAB32_TWISTED_V1.hash(k),
+    )?;
+    check(
+        "Tab64Twisted",
+        &KEYS64_V1,
+        &EXPECTED_TAB64_TWISTED_V1,
+        |k| TAB64_TWISTED_V1.hash(k),
+    )?;
+    #[cfg(feature = "alloc")]
+    {
+        let twisted = tab128_twisted_v1();
+        check(
+            "Tab128Twisted",
+            &KEYS128_V1,
+            &EXPECTED_TAB128_TWISTED_V1,
+            |k| twisted.hash(k),
+        )?;
+    }
+    Ok(())
+}
+
+#[test]
+fn reference_tables_match_seeded_tables() {
+    let seed = REFERENCE_SEED_V1;
+    assert_eq!(
+        TAB16_SIMPLE_V1.table,
+        Tab16Simple::new_with_seed(seed).table
+    );
+    assert_eq!(
+        TAB32_SIMPLE_V1.table,
+        Tab32Simple::new_with_seed(seed).table
+    );
+    assert!(TAB64_SIMPLE_V1.table[..] == Tab64Simple::new_with_seed(seed).table[..]);
+    assert!(TAB128_SIMPLE_V1.table[..] == Tab128Simple::new_with_seed(seed).table[..]);
+    assert_eq!(
+        TAB32_TWISTED_V1.table,
+        Tab32Twisted::new_with_seed(seed).table
+    );
+    assert!(TAB64_TWISTED_V1.table[..] == Tab64Twisted::new_with_seed(seed).table[..]);
+}
+
+#[test]
+fn self_check_detects_mismatches() {
+    assert_eq!(self_check(), Ok(()));
+    let err = check("Tab32Simple", &KEYS32_V1, &EXPECTED_TAB32_SIMPLE_V1, |k| {
+        Tab32Simple::new_with_seed(0).hash(k)
+    })
+    .unwrap_err();
+    assert_eq!(err.key, 0);
+    assert_eq!(err.expected, 0x56d2_9dde);
+}
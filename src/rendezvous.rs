@@ -0,0 +1,64 @@
+//! Rendezvous (highest random weight) hashing.
+#[cfg(feature = "std")]
+use crate::open_unit;
+use core::fmt;
+
+/// Select the node with the highest score `hash(hash(key) ^ hash(node))`,
+/// the largest id on ties.
+///
+/// The node is hashed before it is combined with the key, since the scores
+/// `hash(hash(key) ^ node)` of nodes with sequential ids would only differ in the entries
+/// of their low bytes under simple tabulation, and would not be independent.
+pub(crate) fn rendezvous<F: Fn(u64) -> u64>(
+    hash: F,
+    key: u64,
+    nodes: &[u64],
+) -> Result<u64, NoNodesError> {
+    let h = hash(key);
+    nodes
+        .iter()
+        .map(|node| (hash(h ^ hash(*node)), *node))
+        .max()
+        .map(|(_, node)| node)
+        .ok_or(NoNodesError)
+}
+
+/// Select the node with the highest score `-weight / ln(u)`, the largest id on ties.
+///
+/// `u` is the score `hash(hash(key) ^ hash(node))` of `rendezvous`, mapped to `(0, 1)`
+/// like in `hash_exp`.
+#[cfg(feature = "std")]
+pub(crate) fn rendezvous_weighted<F: Fn(u64) -> u64>(
+    hash: F,
+    key: u64,
+    nodes: &[(u64, f64)],
+) -> Result<u64, NoNodesError> {
+    let h = hash(key);
+    let mut best: Option<(f64, u64)> = None;
+    for (node, weight) in nodes {
+        assert!(
+            *weight > 0.0 && weight.is_finite(),
+            "weights have to be positive and finite, not {}",
+            weight
+        );
+        let score = -weight / open_unit(hash(h ^ hash(*node))).ln();
+        match best {
+            Some(current) if current >= (score, *node) => {}
+            _ => best = Some((score, *node)),
+        }
+    }
+    best.map(|(_, node)| node).ok_or(NoNodesError)
+}
+
+/// Rendezvous hashing needs at least one node to choose from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NoNodesError;
+
+impl fmt::Display for NoNodesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cannot choose a node from an empty list")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NoNodesError {}
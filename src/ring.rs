@@ -0,0 +1,202 @@
+//! Consistent hashing with virtual nodes.
+use crate::Tab64Simple;
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A consistent hashing ring assigning 64-bit keys to nodes.
+///
+/// Each node is placed on the ring at `vnodes` points. The point of its `i`-th virtual node
+/// is `hash(hash(node_id) + i)` (with wrapping addition), and a key is owned by the node
+/// of the first point at or after `hash(key)`, wrapping around at the end of the ring.
+/// Routing is a binary search over the sorted points.
+///
+/// Adding or removing a node leaves all other points in place. Hence removing a node
+/// only moves the keys it owned, and adding a node only moves keys to the new node.
+/// More virtual nodes per node balance the load better.
+///
+/// When serialized, the hash function and the nodes with their numbers of virtual nodes
+/// are stored. The points are recomputed when deserializing, so every deserialized copy
+/// routes keys exactly like the original.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::ConsistentRing;
+///
+/// let mut ring = ConsistentRing::new_with_seed(42);
+/// ring.add_node(1, 100).unwrap();
+/// ring.add_node(2, 100).unwrap();
+/// let owner = ring.route(12345);
+/// assert!(owner == 1 || owner == 2);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawConsistentRing"))]
+pub struct ConsistentRing {
+    hash_function: Tab64Simple,
+    /// Node ids and their numbers of virtual nodes, sorted by id
+    nodes: Vec<(u64, usize)>,
+    /// Points of all virtual nodes and their node ids, sorted
+    #[cfg_attr(feature = "serde", serde(skip))]
+    points: Vec<(u64, u64)>,
+}
+
+/// Unvalidated serialized form of a `ConsistentRing`
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct RawConsistentRing {
+    hash_function: Tab64Simple,
+    nodes: Vec<(u64, usize)>,
+}
+
+#[cfg(feature = "serde")]
+impl core::convert::TryFrom<RawConsistentRing> for ConsistentRing {
+    type Error = &'static str;
+
+    fn try_from(raw: RawConsistentRing) -> Result<Self, Self::Error> {
+        if raw.nodes.iter().any(|(_, vnodes)| *vnodes == 0) {
+            return Err("every node of a consistent hashing ring needs a virtual node");
+        }
+        if raw.nodes.windows(2).any(|pair| pair[0].0 >= pair[1].0) {
+            return Err("the nodes of a consistent hashing ring have to be sorted and distinct");
+        }
+        let mut ring = ConsistentRing::with_hash_function(raw.hash_function);
+        for (node_id, vnodes) in raw.nodes {
+            ring.add_node(node_id, vnodes)
+                .map_err(|_| "duplicate node in consistent hashing ring")?;
+        }
+        Ok(ring)
+    }
+}
+
+impl ConsistentRing {
+    /// Create an empty ring with a random hash function.
+    #[cfg(feature = "getrandom")]
+    pub fn new() -> Self {
+        ConsistentRing::with_hash_function(Tab64Simple::new())
+    }
+
+    /// Create an empty ring with a hash function created by `Tab64Simple::new_with_seed`.
+    pub fn new_with_seed(seed: u64) -> Self {
+        ConsistentRing::with_hash_function(Tab64Simple::new_with_seed(seed))
+    }
+
+    /// Create an empty ring using the given hash function.
+    pub fn with_hash_function(hash_function: Tab64Simple) -> Self {
+        ConsistentRing {
+            hash_function,
+            nodes: Vec::new(),
+            points: Vec::new(),
+        }
+    }
+
+    /// Add a node with `vnodes` virtual nodes to the ring.
+    ///
+    /// # Errors
+    /// If the ring already contains a node with this id. The ring is unchanged then.
+    ///
+    /// # Panics
+    /// If `vnodes` is 0.
+    pub fn add_node(&mut self, node_id: u64, vnodes: usize) -> Result<(), RingError> {
+        assert!(vnodes > 0, "a node needs at least one virtual node");
+        let position = match self.nodes.binary_search_by_key(&node_id, |(id, _)| *id) {
+            Ok(_) => return Err(RingError::DuplicateNode { node_id }),
+            Err(position) => position,
+        };
+        self.nodes.insert(position, (node_id, vnodes));
+        let hash_function = &self.hash_function;
+        let base = hash_function.hash(node_id);
+        self.points.extend(
+            (0..vnodes as u64).map(|i| (hash_function.hash(base.wrapping_add(i)), node_id)),
+        );
+        // ties between points of different nodes are broken by the node id
+        self.points.sort_unstable();
+        Ok(())
+    }
+
+    /// Remove a node and all its virtual nodes from the ring.
+    ///
+    /// # Errors
+    /// If the ring contains no node with this id.
+    pub fn remove_node(&mut self, node_id: u64) -> Result<(), RingError> {
+        match self.nodes.binary_search_by_key(&node_id, |(id, _)| *id) {
+            Ok(position) => {
+                self.nodes.remove(position);
+                self.points.retain(|(_, id)| *id != node_id);
+                Ok(())
+            }
+            Err(_) => Err(RingError::UnknownNode { node_id }),
+        }
+    }
+
+    /// Get the id of the node owning a key.
+    ///
+    /// # Panics
+    /// If the ring has no nodes.
+    pub fn route(&self, key: u64) -> u64 {
+        assert!(!self.is_empty(), "cannot route keys on an empty ring");
+        let h = self.hash_function.hash(key);
+        let i = self.points.partition_point(|(point, _)| *point < h);
+        self.points.get(i).unwrap_or(&self.points[0]).1
+    }
+
+    /// Get the ids of all nodes and their numbers of virtual nodes, sorted by id.
+    pub fn nodes(&self) -> &[(u64, usize)] {
+        &self.nodes
+    }
+
+    /// Get the number of nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Check if the ring has no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Get the hash function.
+    pub fn hash_function(&self) -> &Tab64Simple {
+        &self.hash_function
+    }
+}
+
+#[cfg(feature = "getrandom")]
+impl Default for ConsistentRing {
+    /// Create an empty ring with a random hash function, just like `new`.
+    fn default() -> Self {
+        ConsistentRing::new()
+    }
+}
+
+/// Nodes that cannot be added to or removed from a `ConsistentRing`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RingError {
+    /// The ring already contains a node with this id
+    DuplicateNode {
+        /// Id of the node
+        node_id: u64,
+    },
+    /// The ring contains no node with this id
+    UnknownNode {
+        /// Id of the node
+        node_id: u64,
+    },
+}
+
+impl fmt::Display for RingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RingError::DuplicateNode { node_id } => {
+                write!(f, "the ring already contains node {}", node_id)
+            }
+            RingError::UnknownNode { node_id } => {
+                write!(f, "the ring contains no node {}", node_id)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RingError {}
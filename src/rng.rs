@@ -0,0 +1,118 @@
+//! Pseudo random number generation with twisted tabulation in counter mode.
+use crate::Tab64Twisted;
+use rand_core::{Error, RngCore, SeedableRng};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A pseudo random number generator hashing a counter with `Tab64Twisted`.
+///
+/// The `i`-th 64-bit word of the stream is `hash(counter + i)`, where `counter` is the
+/// counter the generator starts with, and the counter wraps around after `2^64` words.
+/// Every method draws whole words:
+///
+/// - `next_u64` returns the next word.
+/// - `next_u32` returns the low 32 bits of the next word.
+/// - `fill_bytes` writes the next words in little-endian order, and only the
+///   first bytes of the last word if the length is not a multiple of 8.
+///
+/// The state is the hash function and the counter. When serialized, both are stored,
+/// so a deserialized generator continues with exactly the same stream.
+/// Use `with_counter` to jump to any position of a stream.
+///
+/// This generator is not cryptographically secure.
+///
+/// Usage:
+/// ```rust
+/// use rand_core::RngCore;
+/// use tab_hash::TabRng;
+///
+/// let mut rng = TabRng::new_with_seed(42);
+/// let first = rng.next_u64();
+/// let mut resumed = TabRng::with_counter(rng.hash_function().clone(), 0);
+/// assert_eq!(resumed.next_u64(), first);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TabRng {
+    hash_function: Tab64Twisted,
+    counter: u64,
+}
+
+impl TabRng {
+    /// Create a generator with a random hash function, starting at counter 0.
+    #[cfg(feature = "getrandom")]
+    pub fn new() -> Self {
+        TabRng::with_counter(Tab64Twisted::new(), 0)
+    }
+
+    /// Create a generator with a hash function created by `Tab64Twisted::new_with_seed`,
+    /// starting at counter 0.
+    pub fn new_with_seed(seed: u64) -> Self {
+        TabRng::with_counter(Tab64Twisted::new_with_seed(seed), 0)
+    }
+
+    /// Create a generator with the given hash function, starting at `counter`.
+    pub fn with_counter(hash_function: Tab64Twisted, counter: u64) -> Self {
+        TabRng {
+            hash_function,
+            counter,
+        }
+    }
+
+    /// Get the counter hashed to produce the next word.
+    pub fn counter(&self) -> u64 {
+        self.counter
+    }
+
+    /// Get the hash function.
+    pub fn hash_function(&self) -> &Tab64Twisted {
+        &self.hash_function
+    }
+}
+
+#[cfg(feature = "getrandom")]
+impl Default for TabRng {
+    /// Create a generator with a random hash function, just like `new`.
+    fn default() -> Self {
+        TabRng::new()
+    }
+}
+
+impl RngCore for TabRng {
+    /// Draw the low 32 bits of the next 64-bit word.
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let word = self.hash_function.hash(self.counter);
+        self.counter = self.counter.wrapping_add(1);
+        word
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl SeedableRng for TabRng {
+    /// A 64-bit seed in little-endian order, see `new_with_seed`.
+    type Seed = [u8; 8];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        TabRng::new_with_seed(u64::from_le_bytes(seed))
+    }
+
+    /// Create a generator just like `new_with_seed`.
+    fn seed_from_u64(state: u64) -> Self {
+        TabRng::new_with_seed(state)
+    }
+}
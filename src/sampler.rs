@@ -0,0 +1,155 @@
+//! Stable subsampling of key streams by hash thresholds.
+use crate::Tab64Twisted;
+use core::fmt;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A sampler keeping the keys of a stream whose hash value is below a threshold.
+///
+/// A key is kept if its hash value is smaller than the threshold, which is the rate
+/// times `2^64`, rounded down. The largest threshold, `u64::MAX`, keeps all keys.
+/// Since the decision only depends on the key, every occurrence of a key is kept or none.
+///
+/// The rate can only be decreased. Then every key kept at the lower rate was also
+/// kept at all higher rates, so a sample can be thinned out by filtering the kept
+/// keys with `keep`, without reading the stream again.
+/// Store `threshold` (and the hash function) to continue sampling later.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::StableSampler;
+///
+/// let mut sampler = StableSampler::new_with_seed(42, 0.5);
+/// let kept: Vec<u64> = (0..1000).filter(|key| sampler.keep(*key)).collect();
+/// sampler.set_rate(0.1).unwrap();
+/// let thinned: Vec<u64> = kept.into_iter().filter(|key| sampler.keep(*key)).collect();
+/// assert!((0..1000).filter(|key| sampler.keep(*key)).eq(thinned));
+/// assert!(sampler.set_rate(0.2).is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StableSampler {
+    hash_function: Tab64Twisted,
+    threshold: u64,
+}
+
+impl StableSampler {
+    /// Create a sampler with a random hash function.
+    ///
+    /// # Panics
+    /// If `rate` is not in `[0, 1]`.
+    #[cfg(feature = "getrandom")]
+    pub fn new(rate: f64) -> Self {
+        StableSampler::with_hash_function(Tab64Twisted::new(), rate)
+    }
+
+    /// Create a sampler with a hash function created by `Tab64Twisted::new_with_seed`.
+    ///
+    /// # Panics
+    /// If `rate` is not in `[0, 1]`.
+    pub fn new_with_seed(seed: u64, rate: f64) -> Self {
+        StableSampler::with_hash_function(Tab64Twisted::new_with_seed(seed), rate)
+    }
+
+    /// Create a sampler keeping keys at `rate` using the given hash function.
+    ///
+    /// # Panics
+    /// If `rate` is not in `[0, 1]`.
+    pub fn with_hash_function(hash_function: Tab64Twisted, rate: f64) -> Self {
+        StableSampler::with_threshold(hash_function, rate_to_threshold(rate))
+    }
+
+    /// Create a sampler with a raw threshold, e.g. one obtained from `threshold`.
+    pub fn with_threshold(hash_function: Tab64Twisted, threshold: u64) -> Self {
+        StableSampler {
+            hash_function,
+            threshold,
+        }
+    }
+
+    /// Decide whether to keep a key at the current rate.
+    pub fn keep(&self, key: u64) -> bool {
+        self.threshold == u64::MAX || self.hash_function.hash(key) < self.threshold
+    }
+
+    /// Decrease the rate at which keys are kept.
+    ///
+    /// The new threshold is `rate * 2^64`, rounded down, or `u64::MAX` for a rate of 1.
+    /// Setting the current rate again is allowed and does nothing.
+    ///
+    /// # Errors
+    /// If the new threshold is larger than the current one. Increasing the rate
+    /// would keep keys which have already been discarded. The sampler is unchanged then.
+    ///
+    /// # Panics
+    /// If `rate` is not in `[0, 1]`, e.g. if it is NaN.
+    pub fn set_rate(&mut self, rate: f64) -> Result<(), RateError> {
+        let threshold = rate_to_threshold(rate);
+        if threshold > self.threshold {
+            return Err(RateError {
+                current: self.threshold,
+                requested: threshold,
+            });
+        }
+        self.threshold = threshold;
+        Ok(())
+    }
+
+    /// Get the current rate, i.e. `threshold / 2^64`, or 1 for a threshold of `u64::MAX`.
+    pub fn rate(&self) -> f64 {
+        if self.threshold == u64::MAX {
+            1.0
+        } else {
+            self.threshold as f64 * (1.0 / (1_u128 << 64) as f64)
+        }
+    }
+
+    /// Get the threshold below which hash values are kept.
+    ///
+    /// A threshold of `u64::MAX` keeps all keys.
+    pub fn threshold(&self) -> u64 {
+        self.threshold
+    }
+
+    /// Get the hash function.
+    pub fn hash_function(&self) -> &Tab64Twisted {
+        &self.hash_function
+    }
+}
+
+/// Map a rate in `[0, 1]` to a threshold
+fn rate_to_threshold(rate: f64) -> u64 {
+    assert!(
+        (0.0..=1.0).contains(&rate),
+        "sampling rates have to be in [0, 1], not {}",
+        rate
+    );
+    if rate == 1.0 {
+        u64::MAX
+    } else {
+        // saturates at u64::MAX for rates which round to 2^64
+        (rate * (1_u128 << 64) as f64) as u64
+    }
+}
+
+/// The rate of a `StableSampler` cannot be increased.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateError {
+    /// The current threshold of the sampler
+    pub current: u64,
+    /// The threshold of the requested rate
+    pub requested: u64,
+}
+
+impl fmt::Display for RateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "cannot increase the sampling threshold from {} to {}",
+            self.current, self.requested
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RateError {}
@@ -0,0 +1,134 @@
+//! AVX2 and AVX-512 kernels for batch hashing, the only module of this crate using unsafe code.
+use core::arch::x86_64::{
+    __m256i, __m512i, _mm256_and_si256, _mm256_i32gather_epi32, _mm256_loadu_si256,
+    _mm256_set1_epi32, _mm256_setzero_si256, _mm256_srli_epi32, _mm256_storeu_si256,
+    _mm256_xor_si256, _mm512_add_epi64, _mm512_and_si512, _mm512_i64gather_epi32,
+    _mm512_i64gather_epi64, _mm512_loadu_si512, _mm512_set1_epi64, _mm512_setzero_si512,
+    _mm512_slli_epi64, _mm512_srlv_epi64, _mm512_storeu_si512, _mm512_xor_si512,
+};
+
+/// Number of keys hashed at once, the number of 32-bit lanes of an AVX2 register
+/// and the number of 64-bit lanes of an AVX-512 register
+const LANES: usize = 8;
+
+/// Hash blocks of eight keys with the table of a `Tab32Simple`, if the CPU supports AVX2.
+///
+/// Returns the number of keys hashed, a multiple of eight, which is 0 without AVX2.
+/// The remaining keys at the end of the slice have to be hashed by the caller.
+pub(crate) fn hash_blocks_32(table: &[[u32; 256]; 4], keys: &[u32], out: &mut [u32]) -> usize {
+    if is_x86_feature_detected!("avx2") {
+        // SAFETY: AVX2 is supported by the CPU
+        unsafe { hash_blocks_32_avx2(table, keys, out) }
+    } else {
+        0
+    }
+}
+
+/// Hash blocks of eight keys, gathering the entries of the eight keys from one column at a time.
+///
+/// # Safety
+/// The CPU has to support AVX2.
+#[target_feature(enable = "avx2")]
+unsafe fn hash_blocks_32_avx2(table: &[[u32; 256]; 4], keys: &[u32], out: &mut [u32]) -> usize {
+    let blocks = keys.len().min(out.len()) / LANES;
+    let low_byte = _mm256_set1_epi32(0xFF);
+    let column = |i: usize| table[i].as_ptr() as *const i32;
+    for b in 0..blocks {
+        // SAFETY: the block `LANES * b..LANES * (b + 1)` is in bounds of both slices,
+        // and all gathered indices are bytes, so they are in bounds of the columns
+        unsafe {
+            let k = _mm256_loadu_si256(keys.as_ptr().add(LANES * b) as *const __m256i);
+            let c0 = _mm256_and_si256(k, low_byte);
+            let c1 = _mm256_and_si256(_mm256_srli_epi32::<8>(k), low_byte);
+            let c2 = _mm256_and_si256(_mm256_srli_epi32::<16>(k), low_byte);
+            let c3 = _mm256_srli_epi32::<24>(k);
+            let h = _mm256_xor_si256(
+                _mm256_xor_si256(
+                    _mm256_i32gather_epi32::<4>(column(0), c0),
+                    _mm256_i32gather_epi32::<4>(column(1), c1),
+                ),
+                _mm256_xor_si256(
+                    _mm256_i32gather_epi32::<4>(column(2), c2),
+                    _mm256_i32gather_epi32::<4>(column(3), c3),
+                ),
+            );
+            _mm256_storeu_si256(out.as_mut_ptr().add(LANES * b) as *mut __m256i, h);
+        }
+    }
+    LANES * blocks
+}
+
+/// Hash blocks of eight keys with the table of a `Tab64Twisted`, if the CPU supports AVX-512.
+///
+/// Returns the number of keys hashed, a multiple of eight, which is 0 without AVX-512.
+/// The remaining keys at the end of the slice have to be hashed by the caller.
+pub(crate) fn hash_blocks_64_twisted(
+    table: &[[u128; 256]; 8],
+    keys: &[u64],
+    out: &mut [u64],
+) -> usize {
+    if is_x86_feature_detected!("avx512f") {
+        // SAFETY: AVX-512 is supported by the CPU
+        unsafe { hash_blocks_64_twisted_avx512(table, keys, out) }
+    } else {
+        0
+    }
+}
+
+/// Hash blocks of eight keys, gathering the entries of the eight keys from one of the first
+/// seven columns at a time, and looking up the entries of the twisted last column one by one.
+///
+/// Of the XOR of the first seven entries, only the high half of the entries and the low
+/// byte of their low half are needed, for the hash value and the twisted character.
+/// So the high halves are gathered as 64-bit values and the low halves as 32-bit values.
+///
+/// # Safety
+/// The CPU has to support AVX-512.
+#[target_feature(enable = "avx512f")]
+unsafe fn hash_blocks_64_twisted_avx512(
+    table: &[[u128; 256]; 8],
+    keys: &[u64],
+    out: &mut [u64],
+) -> usize {
+    let blocks = keys.len().min(out.len()) / LANES;
+    let low_byte = _mm512_set1_epi64(0xFF);
+    let one = _mm512_set1_epi64(1);
+    for b in 0..blocks {
+        let block = LANES * b..LANES * (b + 1);
+        let mut high = [0_u64; LANES];
+        let mut low = [0_u32; LANES];
+        // SAFETY: the block is in bounds of `keys`, all gathered indices address the
+        // 16 bytes of an entry of a column, and the arrays have room for all lanes
+        unsafe {
+            let k = _mm512_loadu_si512(keys[block.clone()].as_ptr() as *const __m512i);
+            let mut h_high = _mm512_setzero_si512();
+            let mut h_low = _mm256_setzero_si256();
+            for (shift, column) in (0..56).step_by(8).zip(table.iter()) {
+                let c = _mm512_and_si512(_mm512_srlv_epi64(k, _mm512_set1_epi64(shift)), low_byte);
+                // entries are little-endian u128, i.e. two u64 or four u32 per character
+                let high_index = _mm512_add_epi64(_mm512_slli_epi64::<1>(c), one);
+                let low_index = _mm512_slli_epi64::<2>(c);
+                h_high = _mm512_xor_si512(
+                    h_high,
+                    _mm512_i64gather_epi64::<8>(high_index, column.as_ptr() as *const i64),
+                );
+                h_low = _mm256_xor_si256(
+                    h_low,
+                    _mm512_i64gather_epi32::<4>(low_index, column.as_ptr() as *const i32),
+                );
+            }
+            _mm512_storeu_si512(high.as_mut_ptr() as *mut __m512i, h_high);
+            _mm256_storeu_si256(low.as_mut_ptr() as *mut __m256i, h_low);
+        }
+        for (((x, o), high), low) in keys[block.clone()]
+            .iter()
+            .zip(out[block].iter_mut())
+            .zip(high.iter())
+            .zip(low.iter())
+        {
+            let c = (x >> 56) as u8 ^ *low as u8;
+            *o = high ^ (table[7][c as usize] >> 64) as u64;
+        }
+    }
+    LANES * blocks
+}
@@ -0,0 +1,703 @@
+//! Simple tabulation hashing of 32-bit and 64-bit keys with 16-bit characters.
+#[cfg(feature = "getrandom")]
+use crate::entropy::OsEntropy;
+use crate::splitmix::SplitMix64;
+use crate::xoshiro::Xoshiro256StarStar;
+#[cfg(feature = "getrandom")]
+use crate::{os_seeded_rng, EntropyError};
+use crate::{
+    EntropySource, Hash32Fn, Hash64Fn, Tab32Simple, Tab64Simple, TabHash, TableEntry, TableWords,
+};
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use core::marker::PhantomData;
+#[cfg(feature = "rand")]
+use rand::distributions::{Distribution, Standard};
+#[cfg(feature = "rand")]
+use rand::Rng;
+use rand_chacha::ChaCha20Rng;
+use rand_core::{RngCore, SeedableRng};
+#[cfg(feature = "serde")]
+use serde::de::{self, Deserializer, Visitor};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize, Serializer};
+
+/// Number of entries of a column, one for each 16-bit character
+const CHARACTERS: usize = 1 << 16;
+
+/// Number of bytes of the table of `Tab32Simple16`, as written by `to_le_bytes`
+const TABLE_BYTES: usize = 2 * CHARACTERS * 4;
+
+/// Number of bytes of the table of `Tab64Simple16`, as written by `to_le_bytes`
+const TABLE_BYTES_64: usize = 4 * CHARACTERS * 8;
+
+/// The table of `Tab32Simple16`
+type Table16 = [[u32; CHARACTERS]; 2];
+
+/// A column of the table of `Tab64Simple16`
+type Column64x16 = Box<[u64; CHARACTERS]>;
+
+/// A universal hash function for 32-bit integers using simple tabulation with 16-bit characters.
+///
+/// A key is split into its low and its high 16 bits, which are looked up in the first
+/// and the second column of the table, respectively. This needs only two lookups per key
+/// instead of the four lookups of `Tab32Simple`, but the table has two columns of
+/// 65536 entries, i.e. 512 KiB instead of 4 KiB. Hence it is only faster if the table
+/// stays in the cache, e.g. when hashing large batches of keys in a hot loop.
+/// Since the table is that large, it is stored on the heap.
+///
+/// Tables are drawn column by column, each column from entry 0 to 65535,
+/// one `next_u32` call per entry, like for the other hash functions.
+///
+/// When serialized, the table is stored as its little-endian bytes (see `to_le_bytes`),
+/// as a byte string for binary formats and as a hex string for human-readable ones.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::Tab32Simple16;
+///
+/// let keys = vec![0, 8, 15, 47, 11];
+/// let simple = Tab32Simple16::new();
+/// for k in keys {
+///     println!("{}", simple.hash(k));
+/// }
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Tab32Simple16 {
+    table: Box<Table16>,
+}
+
+impl Tab32Simple16 {
+    /// Create a new simple tabulation hash function with a random table.
+    #[cfg(feature = "getrandom")]
+    pub fn new() -> Self {
+        Tab32Simple16::from_rng(&mut os_seeded_rng())
+    }
+
+    /// Create a new simple tabulation hash function seeded from an entropy source.
+    ///
+    /// A 256-bit seed is read from `source` and expanded into the table
+    /// using a xoshiro256** generator, just like `new` does with the
+    /// entropy source of the operating system.
+    pub fn new_from_entropy<E: EntropySource + ?Sized>(source: &mut E) -> Result<Self, E::Error> {
+        let mut rng = Xoshiro256StarStar::from_entropy(source)?;
+        Ok(Tab32Simple16::from_rng(&mut rng))
+    }
+
+    /// Create a new simple tabulation hash function seeded from OS entropy.
+    ///
+    /// This is `new_from_entropy` with the entropy source of the operating system,
+    /// i.e. like `new`, but returns an error instead of panicking if there is no entropy.
+    #[cfg(feature = "getrandom")]
+    pub fn new_from_os_entropy() -> Result<Self, EntropyError> {
+        Tab32Simple16::new_from_entropy(&mut OsEntropy)
+    }
+
+    /// Create a new simple tabulation hash function with a table derived from a seed.
+    ///
+    /// The seed is expanded with SplitMix64, see the crate documentation.
+    pub fn new_with_seed(seed: u64) -> Self {
+        Tab32Simple16::from_rng(&mut SplitMix64::new(seed))
+    }
+
+    /// Create a new simple tabulation hash function from a 32-byte seed.
+    ///
+    /// The seed is used as ChaCha20 key, see the crate documentation.
+    pub fn from_seed_bytes(seed: [u8; 32]) -> Self {
+        Tab32Simple16::from_rng(&mut ChaCha20Rng::from_seed(seed))
+    }
+
+    /// Create a new simple tabulation hash function with a table drawn from `rng`.
+    #[cfg(feature = "rand")]
+    pub fn new_with_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        Tab32Simple16::from_rng(rng)
+    }
+
+    /// Generate a table drawing entries from `rng` column by column
+    fn from_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        let mut table = Tab32Simple16::zeroed_table();
+        for entry in table.iter_mut().flatten() {
+            *entry = TableEntry::from_rng(rng);
+        }
+        Tab32Simple16 { table }
+    }
+
+    /// Allocate a table filled with zeros directly on the heap
+    fn zeroed_table() -> Box<Table16> {
+        let columns = vec![[0_u32; CHARACTERS]; 2].into_boxed_slice();
+        match core::convert::TryFrom::try_from(columns) {
+            Ok(table) => table,
+            Err(_) => unreachable!("table has exactly 2 columns"),
+        }
+    }
+
+    /// Create a new simple tabulation hash function with a given table.
+    ///
+    /// Unlike for the other hash functions, the table is passed in a box,
+    /// since it is too large to be moved around on the stack.
+    pub fn with_table(table: Box<[[u32; 65536]; 2]>) -> Self {
+        Tab32Simple16 { table }
+    }
+
+    /// Get the table used by this hash function.
+    ///
+    /// Unlike for the other hash functions, this returns a reference to the table.
+    pub fn get_table(&self) -> &[[u32; 65536]; 2] {
+        &self.table
+    }
+
+    /// Get the id of this hash function, a 64-bit digest of its table.
+    pub fn id(&self) -> u64 {
+        let mut words = TableWords::default();
+        for entry in self.table.iter().flatten() {
+            entry.for_each_word(|w| words.push(w));
+        }
+        words.digest()
+    }
+
+    /// Export the table of this hash function as a nested vector of columns.
+    pub fn to_vec(&self) -> Vec<Vec<u32>> {
+        self.table.iter().map(|column| column.to_vec()).collect()
+    }
+
+    /// Create a new simple tabulation hash function from a nested vector of columns.
+    ///
+    /// # Panics
+    /// If there are not 2 columns of 65536 entries each.
+    pub fn from_vec(table_data: Vec<Vec<u32>>) -> Self {
+        assert_eq!(table_data.len(), 2);
+        let mut table = Tab32Simple16::zeroed_table();
+        for (column, values) in table.iter_mut().zip(table_data.iter()) {
+            assert_eq!(values.len(), CHARACTERS);
+            column.copy_from_slice(values);
+        }
+        Tab32Simple16 { table }
+    }
+
+    /// Create a new simple tabulation hash function from the little-endian bytes of a table.
+    ///
+    /// Entries are read column by column, each column from entry 0 to 65535,
+    /// as written by `to_le_bytes`.
+    pub fn from_le_bytes(bytes: &[u8; 524288]) -> Self {
+        Tab32Simple16::from_le_slice(bytes)
+    }
+
+    /// Read the table from the first 524288 little-endian bytes of a slice
+    fn from_le_slice(bytes: &[u8]) -> Self {
+        let mut table = Tab32Simple16::zeroed_table();
+        for (entry, chunk) in table.iter_mut().flatten().zip(bytes.chunks(4)) {
+            *entry = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        }
+        Tab32Simple16 { table }
+    }
+
+    /// Export the table of this hash function as little-endian bytes.
+    ///
+    /// This is the inverse of `from_le_bytes`.
+    pub fn to_le_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(TABLE_BYTES);
+        for entry in self.table.iter().flatten() {
+            bytes.extend_from_slice(&entry.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Compute simple tabulation hash value for a 32bit integer number.
+    pub fn hash(&self, x: u32) -> u32 {
+        self.table[0][(x & 0xFFFF) as usize] ^ self.table[1][(x >> 16) as usize]
+    }
+}
+
+/// A hash function with 16-bit characters, serialized as the little-endian bytes of its table
+#[cfg(feature = "serde")]
+trait TableBytes: Sized {
+    /// Number of bytes of the table
+    const BYTES: usize;
+
+    /// Read the table from exactly `BYTES` little-endian bytes
+    fn from_table_bytes(bytes: &[u8]) -> Self;
+}
+
+#[cfg(feature = "serde")]
+impl TableBytes for Tab32Simple16 {
+    const BYTES: usize = TABLE_BYTES;
+
+    fn from_table_bytes(bytes: &[u8]) -> Self {
+        Tab32Simple16::from_le_slice(bytes)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TableBytes for Tab64Simple16 {
+    const BYTES: usize = TABLE_BYTES_64;
+
+    fn from_table_bytes(bytes: &[u8]) -> Self {
+        Tab64Simple16::from_le_slice(bytes)
+    }
+}
+
+/// Write the little-endian bytes of a table, as a byte string for binary formats
+/// and as a hex string for human-readable ones
+#[cfg(feature = "serde")]
+fn serialize_table_bytes<S: Serializer>(bytes: &[u8], s: S) -> Result<S::Ok, S::Error> {
+    if s.is_human_readable() {
+        let mut hex = alloc::string::String::with_capacity(2 * bytes.len());
+        for byte in bytes.iter() {
+            hex.push(core::char::from_digit(u32::from(byte >> 4), 16).unwrap_or('0'));
+            hex.push(core::char::from_digit(u32::from(byte & 0xF), 16).unwrap_or('0'));
+        }
+        s.serialize_str(&hex)
+    } else {
+        s.serialize_bytes(bytes)
+    }
+}
+
+/// Read a table written by `serialize_table_bytes`
+#[cfg(feature = "serde")]
+fn deserialize_table_bytes<'de, T: TableBytes, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(TableBytesVisitor(PhantomData))
+    } else {
+        deserializer.deserialize_bytes(TableBytesVisitor(PhantomData))
+    }
+}
+
+/// Visitor reading a table from its little-endian bytes or their hex string
+#[cfg(feature = "serde")]
+struct TableBytesVisitor<T>(PhantomData<T>);
+
+#[cfg(feature = "serde")]
+impl<'de, T: TableBytes> Visitor<'de> for TableBytesVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "the {} little-endian bytes of a table", T::BYTES)
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<Self::Value, E> {
+        if bytes.len() != T::BYTES {
+            return Err(E::invalid_length(bytes.len(), &self));
+        }
+        Ok(T::from_table_bytes(bytes))
+    }
+
+    fn visit_str<E: de::Error>(self, hex: &str) -> Result<Self::Value, E> {
+        if hex.len() != 2 * T::BYTES {
+            return Err(E::invalid_length(hex.len() / 2, &self));
+        }
+        let digits = hex.as_bytes();
+        let mut bytes = vec![0_u8; T::BYTES];
+        for (byte, pair) in bytes.iter_mut().zip(digits.chunks(2)) {
+            let digit = |d: u8| {
+                (d as char)
+                    .to_digit(16)
+                    .ok_or_else(|| E::invalid_value(de::Unexpected::Str(hex), &self))
+            };
+            *byte = (digit(pair[0])? << 4 | digit(pair[1])?) as u8;
+        }
+        self.visit_bytes(&bytes)
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = Vec::with_capacity(T::BYTES);
+        while let Some(byte) = seq.next_element::<u8>()? {
+            bytes.push(byte);
+        }
+        self.visit_bytes(&bytes)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Tab32Simple16 {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_table_bytes(&self.to_le_bytes(), s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Tab32Simple16 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_table_bytes(deserializer)
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Distribution<Tab32Simple16> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Tab32Simple16 {
+        Tab32Simple16::from_rng(rng)
+    }
+}
+
+impl TabHash for Tab32Simple16 {
+    type Key = u32;
+    type Entry = u32;
+
+    fn hash(&self, key: u32) -> u32 {
+        Tab32Simple16::hash(self, key)
+    }
+
+    #[cfg(feature = "getrandom")]
+    fn random() -> Self {
+        Tab32Simple16::new()
+    }
+
+    fn new_with_seed(seed: u64) -> Self {
+        Tab32Simple16::new_with_seed(seed)
+    }
+
+    fn id(&self) -> u64 {
+        Tab32Simple16::id(self)
+    }
+
+    fn to_vec(&self) -> Vec<Vec<u32>> {
+        Tab32Simple16::to_vec(self)
+    }
+
+    fn from_vec(table_data: Vec<Vec<u32>>) -> Self {
+        Tab32Simple16::from_vec(table_data)
+    }
+}
+
+#[cfg(feature = "getrandom")]
+impl Default for Tab32Simple16 {
+    /// Create a new hash function with a random table, just like `new`.
+    fn default() -> Self {
+        Tab32Simple16::new()
+    }
+}
+
+impl core::fmt::Debug for Tab32Simple16 {
+    /// Show the id of the hash function as fingerprint instead of all entries.
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let fingerprint = self.id();
+        f.debug_struct("Tab32Simple16")
+            .field("fingerprint", &format_args!("{:#018x}", fingerprint))
+            .finish()
+    }
+}
+
+impl Hash32Fn for Tab32Simple16 {
+    fn hash32(&self, x: u32) -> u32 {
+        self.hash(x)
+    }
+}
+
+/// A universal hash function for 64-bit integers using simple tabulation with 16-bit characters.
+///
+/// A key is split into four 16-bit characters, from the least to the most significant,
+/// which are looked up in the four columns of the table, respectively. This needs only
+/// four lookups per key instead of the eight lookups of `Tab64Simple`, but the table has
+/// four columns of 65536 entries, i.e. 2 MiB instead of 16 KiB. Each column is stored in
+/// its own box on the heap, since a whole table does not even fit on the stack of a thread
+/// in many configurations.
+///
+/// Unlike the table of `Tab32Simple16`, this table does not fit into the L2 cache of
+/// many machines, so whether it is faster depends on the keys:
+///
+/// - It wins for bulk hashing of sorted or clustered keys. Keys that are close to each
+///   other share their high characters, so most lookups hit the same few cache lines,
+///   and the lookups of the low characters move through the first column in order.
+/// - It loses for keys spread over the whole range, e.g. random keys. Then almost every
+///   lookup misses the cache, while the 16 KiB table of `Tab64Simple` stays in the L1 cache.
+/// - It loses if the hash function is used only now and then, or next to other work
+///   that needs the cache, since the table is evicted in between.
+///
+/// Creating the hash function draws 262144 entries instead of 2048, so it also
+/// only pays off if many keys are hashed. Measure with `cargo bench --bench simple16`.
+///
+/// Tables are drawn column by column, each column from entry 0 to 65535,
+/// one `next_u64` call per entry, like for the other hash functions.
+///
+/// When serialized, the table is stored as its little-endian bytes (see `to_le_bytes`),
+/// as a byte string for binary formats and as a hex string for human-readable ones.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::Tab64Simple16;
+///
+/// let simple = Tab64Simple16::new_with_seed(42);
+/// for k in 1_000_000..1_000_010 {
+///     println!("{}", simple.hash(k));
+/// }
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Tab64Simple16 {
+    table: [Column64x16; 4],
+}
+
+impl Tab64Simple16 {
+    /// Create a new simple tabulation hash function with a random table.
+    #[cfg(feature = "getrandom")]
+    pub fn new() -> Self {
+        Tab64Simple16::from_rng(&mut os_seeded_rng())
+    }
+
+    /// Create a new simple tabulation hash function seeded from an entropy source.
+    ///
+    /// A 256-bit seed is read from `source` and expanded into the table
+    /// using a xoshiro256** generator, just like `new` does with the
+    /// entropy source of the operating system.
+    pub fn new_from_entropy<E: EntropySource + ?Sized>(source: &mut E) -> Result<Self, E::Error> {
+        let mut rng = Xoshiro256StarStar::from_entropy(source)?;
+        Ok(Tab64Simple16::from_rng(&mut rng))
+    }
+
+    /// Create a new simple tabulation hash function seeded from OS entropy.
+    ///
+    /// This is `new_from_entropy` with the entropy source of the operating system,
+    /// i.e. like `new`, but returns an error instead of panicking if there is no entropy.
+    #[cfg(feature = "getrandom")]
+    pub fn new_from_os_entropy() -> Result<Self, EntropyError> {
+        Tab64Simple16::new_from_entropy(&mut OsEntropy)
+    }
+
+    /// Create a new simple tabulation hash function with a table derived from a seed.
+    ///
+    /// The seed is expanded with SplitMix64, see the crate documentation.
+    pub fn new_with_seed(seed: u64) -> Self {
+        Tab64Simple16::from_rng(&mut SplitMix64::new(seed))
+    }
+
+    /// Create a new simple tabulation hash function from a 32-byte seed.
+    ///
+    /// The seed is used as ChaCha20 key, see the crate documentation.
+    pub fn from_seed_bytes(seed: [u8; 32]) -> Self {
+        Tab64Simple16::from_rng(&mut ChaCha20Rng::from_seed(seed))
+    }
+
+    /// Create a new simple tabulation hash function with a table drawn from `rng`.
+    #[cfg(feature = "rand")]
+    pub fn new_with_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        Tab64Simple16::from_rng(rng)
+    }
+
+    /// Generate a table drawing entries from `rng` column by column
+    fn from_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        let mut table = Tab64Simple16::zeroed_table();
+        for entry in table.iter_mut().flat_map(|column| column.iter_mut()) {
+            *entry = TableEntry::from_rng(rng);
+        }
+        Tab64Simple16 { table }
+    }
+
+    /// Allocate a table filled with zeros directly on the heap
+    fn zeroed_table() -> [Column64x16; 4] {
+        let column = || match core::convert::TryFrom::try_from(vec![0_u64; CHARACTERS]) {
+            Ok(column) => column,
+            Err(_) => unreachable!("column has exactly 65536 entries"),
+        };
+        [column(), column(), column(), column()]
+    }
+
+    /// Create a new simple tabulation hash function with a given table.
+    ///
+    /// Unlike for the other hash functions, each column is passed in a box,
+    /// since the table is too large to be moved around on the stack.
+    pub fn with_table(table: [Box<[u64; 65536]>; 4]) -> Self {
+        Tab64Simple16 { table }
+    }
+
+    /// Get the table used by this hash function.
+    ///
+    /// Unlike for the other hash functions, this returns a reference to the table.
+    pub fn get_table(&self) -> &[Box<[u64; 65536]>; 4] {
+        &self.table
+    }
+
+    /// Get the id of this hash function, a 64-bit digest of its table.
+    pub fn id(&self) -> u64 {
+        let mut words = TableWords::default();
+        for entry in self.table.iter().flat_map(|column| column.iter()) {
+            entry.for_each_word(|w| words.push(w));
+        }
+        words.digest()
+    }
+
+    /// Export the table of this hash function as a nested vector of columns.
+    pub fn to_vec(&self) -> Vec<Vec<u64>> {
+        self.table.iter().map(|column| column.to_vec()).collect()
+    }
+
+    /// Create a new simple tabulation hash function from a nested vector of columns.
+    ///
+    /// # Panics
+    /// If there are not 4 columns of 65536 entries each.
+    pub fn from_vec(table_data: Vec<Vec<u64>>) -> Self {
+        assert_eq!(table_data.len(), 4);
+        let mut table = Tab64Simple16::zeroed_table();
+        for (column, values) in table.iter_mut().zip(table_data.iter()) {
+            assert_eq!(values.len(), CHARACTERS);
+            column.copy_from_slice(values);
+        }
+        Tab64Simple16 { table }
+    }
+
+    /// Create a new simple tabulation hash function from the little-endian bytes of a table.
+    ///
+    /// Entries are read column by column, each column from entry 0 to 65535,
+    /// as written by `to_le_bytes`.
+    pub fn from_le_bytes(bytes: &[u8; 2097152]) -> Self {
+        Tab64Simple16::from_le_slice(bytes)
+    }
+
+    /// Read the table from the first 2097152 little-endian bytes of a slice
+    fn from_le_slice(bytes: &[u8]) -> Self {
+        let mut table = Tab64Simple16::zeroed_table();
+        let entries = table.iter_mut().flat_map(|column| column.iter_mut());
+        for (entry, chunk) in entries.zip(bytes.chunks(8)) {
+            let mut word = [0; 8];
+            word.copy_from_slice(chunk);
+            *entry = u64::from_le_bytes(word);
+        }
+        Tab64Simple16 { table }
+    }
+
+    /// Export the table of this hash function as little-endian bytes.
+    ///
+    /// This is the inverse of `from_le_bytes`.
+    pub fn to_le_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(TABLE_BYTES_64);
+        for entry in self.table.iter().flat_map(|column| column.iter()) {
+            bytes.extend_from_slice(&entry.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Compute simple tabulation hash value for a 64bit integer number.
+    pub fn hash(&self, x: u64) -> u64 {
+        self.table[0][(x & 0xFFFF) as usize]
+            ^ self.table[1][((x >> 16) & 0xFFFF) as usize]
+            ^ self.table[2][((x >> 32) & 0xFFFF) as usize]
+            ^ self.table[3][(x >> 48) as usize]
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Tab64Simple16 {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_table_bytes(&self.to_le_bytes(), s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Tab64Simple16 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_table_bytes(deserializer)
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Distribution<Tab64Simple16> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Tab64Simple16 {
+        Tab64Simple16::from_rng(rng)
+    }
+}
+
+impl TabHash for Tab64Simple16 {
+    type Key = u64;
+    type Entry = u64;
+
+    fn hash(&self, key: u64) -> u64 {
+        Tab64Simple16::hash(self, key)
+    }
+
+    #[cfg(feature = "getrandom")]
+    fn random() -> Self {
+        Tab64Simple16::new()
+    }
+
+    fn new_with_seed(seed: u64) -> Self {
+        Tab64Simple16::new_with_seed(seed)
+    }
+
+    fn id(&self) -> u64 {
+        Tab64Simple16::id(self)
+    }
+
+    fn to_vec(&self) -> Vec<Vec<u64>> {
+        Tab64Simple16::to_vec(self)
+    }
+
+    fn from_vec(table_data: Vec<Vec<u64>>) -> Self {
+        Tab64Simple16::from_vec(table_data)
+    }
+}
+
+#[cfg(feature = "getrandom")]
+impl Default for Tab64Simple16 {
+    /// Create a new hash function with a random table, just like `new`.
+    fn default() -> Self {
+        Tab64Simple16::new()
+    }
+}
+
+impl core::fmt::Debug for Tab64Simple16 {
+    /// Show the id of the hash function as fingerprint instead of all entries.
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let fingerprint = self.id();
+        f.debug_struct("Tab64Simple16")
+            .field("fingerprint", &format_args!("{:#018x}", fingerprint))
+            .finish()
+    }
+}
+
+impl Hash64Fn for Tab64Simple16 {
+    fn hash64(&self, x: u64) -> u64 {
+        self.hash(x)
+    }
+}
+
+impl Tab32Simple {
+    /// Precompute a `Tab32Simple16` with the same hash values as this hash function.
+    ///
+    /// Since a hash value is the XOR of the entries of the bytes of a key, the entry for
+    /// the 16-bit character `c = (b1 << 8) | b0` in the low half of a key is
+    /// `T0[b0] ^ T1[b1]`, and `T2[b0] ^ T3[b1]` in the high half, where `Ti` are the
+    /// columns of this table. Hence `derive_wide().hash(x) == hash(x)` for every key,
+    /// e.g. to speed up the evaluation of a persisted hash function without changing
+    /// its hash values. The derived table is not a random table of its own,
+    /// so it only has the guarantees of this hash function, and a different `id`.
+    pub fn derive_wide(&self) -> Tab32Simple16 {
+        let mut table = Tab32Simple16::zeroed_table();
+        for (wide, pair) in table.iter_mut().zip(self.table.chunks(2)) {
+            for (c, entry) in wide.iter_mut().enumerate() {
+                *entry = pair[0][c & 0xFF] ^ pair[1][c >> 8];
+            }
+        }
+        Tab32Simple16 { table }
+    }
+}
+
+impl Tab64Simple {
+    /// Precompute a `Tab64Simple16` with the same hash values as this hash function.
+    ///
+    /// Column `i` of the derived table holds `T(2i)[b0] ^ T(2i+1)[b1]` for the 16-bit
+    /// character `(b1 << 8) | b0`, where `Tj` are the columns of this table, so
+    /// `derive_wide().hash(x) == hash(x)` for every key. See `Tab32Simple::derive_wide`,
+    /// and `Tab64Simple16` for when the larger table is faster.
+    pub fn derive_wide(&self) -> Tab64Simple16 {
+        let mut table = Tab64Simple16::zeroed_table();
+        for (wide, pair) in table.iter_mut().zip(self.table.chunks(2)) {
+            for (c, entry) in wide.iter_mut().enumerate() {
+                *entry = pair[0][c & 0xFF] ^ pair[1][c >> 8];
+            }
+        }
+        Tab64Simple16 { table }
+    }
+}
@@ -0,0 +1,1410 @@
+//! Sketches built on tabulation hashing.
+use crate::splitmix::SplitMix64;
+use crate::{bucket_and_sign, hll_parts, mul_high_64, Tab64Simple, Tab64Twisted, TabHash};
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+use core::fmt;
+use rand_core::RngCore;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A Count-Min sketch estimating the counts of 64-bit keys in a stream.
+///
+/// The sketch has `depth` rows of `width` counters. Each row has its own hash function,
+/// which maps a key to one counter of the row. Inserting a key increments its counter
+/// in every row, and the estimate of a key is the minimum of its counters.
+/// Estimates never underestimate the true count. With `width = ceil(e / epsilon)` and
+/// `depth = ceil(ln(1 / delta))`, an estimate exceeds the true count by more than
+/// `epsilon` times the total count of all keys with a probability of at most `delta`.
+///
+/// By default, rows use `Tab64Simple` hash functions, but any `TabHash` with 64-bit keys works.
+/// When serialized, the hash functions are stored alongside the counters,
+/// so a deserialized sketch continues to count and can be merged with the original one.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::CountMinSketch;
+///
+/// let mut sketch: CountMinSketch = CountMinSketch::new_with_seed(1024, 4, 42);
+/// sketch.insert(7, 3);
+/// sketch.insert(11, 1);
+/// assert!(sketch.estimate(7) >= 3);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "RawCountMinSketch<H>",
+        bound(serialize = "H: Serialize", deserialize = "H: Deserialize<'de>")
+    )
+)]
+pub struct CountMinSketch<H = Tab64Simple> {
+    width: usize,
+    hash_functions: Vec<H>,
+    /// Counters of all rows, row by row
+    counters: Vec<u64>,
+}
+
+/// Unvalidated serialized form of a `CountMinSketch`
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct RawCountMinSketch<H> {
+    width: usize,
+    hash_functions: Vec<H>,
+    counters: Vec<u64>,
+}
+
+#[cfg(feature = "serde")]
+impl<H> core::convert::TryFrom<RawCountMinSketch<H>> for CountMinSketch<H> {
+    type Error = &'static str;
+
+    fn try_from(raw: RawCountMinSketch<H>) -> Result<Self, Self::Error> {
+        if raw.width == 0 || raw.hash_functions.is_empty() {
+            return Err("a Count-Min sketch needs at least one row and one column");
+        }
+        if Some(raw.counters.len()) != raw.width.checked_mul(raw.hash_functions.len()) {
+            return Err("the number of counters does not match the dimensions of the sketch");
+        }
+        Ok(CountMinSketch {
+            width: raw.width,
+            hash_functions: raw.hash_functions,
+            counters: raw.counters,
+        })
+    }
+}
+
+impl<H: TabHash<Key = u64>> CountMinSketch<H> {
+    /// Create an empty sketch with random hash functions.
+    ///
+    /// # Panics
+    /// If `width` or `depth` is 0.
+    #[cfg(feature = "getrandom")]
+    pub fn new(width: usize, depth: usize) -> Self {
+        CountMinSketch::with_hash_functions(width, (0..depth).map(|_| H::random()).collect())
+    }
+
+    /// Create an empty sketch with hash functions derived from a seed.
+    ///
+    /// The seeds of the rows are drawn from a SplitMix64 generator seeded with `seed`,
+    /// and each row uses `new_with_seed` of its hash function with its seed.
+    ///
+    /// # Panics
+    /// If `width` or `depth` is 0.
+    pub fn new_with_seed(width: usize, depth: usize, seed: u64) -> Self {
+        CountMinSketch::with_hash_functions(width, seeded_hash_functions(depth, seed))
+    }
+
+    /// Create an empty sketch with `width` columns and one row per hash function.
+    ///
+    /// # Panics
+    /// If `width` is 0 or `hash_functions` is empty.
+    pub fn with_hash_functions(width: usize, hash_functions: Vec<H>) -> Self {
+        assert!(
+            width > 0 && !hash_functions.is_empty(),
+            "a Count-Min sketch needs at least one row and one column"
+        );
+        let counters = vec![0; width * hash_functions.len()];
+        CountMinSketch {
+            width,
+            hash_functions,
+            counters,
+        }
+    }
+
+    /// Get the number of counters per row.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Get the number of rows, i.e. the number of hash functions.
+    pub fn depth(&self) -> usize {
+        self.hash_functions.len()
+    }
+
+    /// Get the hash functions of the rows.
+    pub fn hash_functions(&self) -> &[H] {
+        &self.hash_functions
+    }
+
+    /// Get the index of the counter of `key` in `row`
+    fn index(&self, row: usize, key: u64) -> usize {
+        let column = mul_high_64(self.hash_functions[row].hash(key), self.width as u64);
+        row * self.width + column as usize
+    }
+
+    /// Add `count` occurrences of `key` to the sketch.
+    ///
+    /// Counters saturate at `u64::MAX` instead of overflowing.
+    pub fn insert(&mut self, key: u64, count: u64) {
+        for row in 0..self.depth() {
+            let i = self.index(row, key);
+            self.counters[i] = self.counters[i].saturating_add(count);
+        }
+    }
+
+    /// Estimate the number of occurrences of `key`.
+    ///
+    /// The estimate is never smaller than the true count.
+    pub fn estimate(&self, key: u64) -> u64 {
+        (0..self.depth())
+            .map(|row| self.counters[self.index(row, key)])
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Add the counts of `other` to this sketch.
+    ///
+    /// Afterwards, this sketch is identical to a sketch of both streams.
+    /// This requires both sketches to have the same dimensions and equal hash functions,
+    /// i.e. equal tables, otherwise this sketch is left unchanged and an error is returned.
+    pub fn merge(&mut self, other: &Self) -> Result<(), MergeError>
+    where
+        H: PartialEq,
+    {
+        if self.width != other.width || self.depth() != other.depth() {
+            return Err(MergeError::Dimensions {
+                expected: (self.width, self.depth()),
+                actual: (other.width, other.depth()),
+            });
+        }
+        if let Some(row) =
+            (0..self.depth()).find(|row| self.hash_functions[*row] != other.hash_functions[*row])
+        {
+            return Err(MergeError::HashFunction { row });
+        }
+        for (counter, other) in self.counters.iter_mut().zip(other.counters.iter()) {
+            *counter = counter.saturating_add(*other);
+        }
+        Ok(())
+    }
+}
+
+/// An AMS sketch estimating the second frequency moment `F2` of a stream of 64-bit keys.
+///
+/// `F2` is the sum of the squared counts of all keys, e.g. the self-join size of a relation.
+/// The sketch has `rows` rows of `columns` signed counters, and each row has its own
+/// hash function. Updating a key adds its delta, multiplied with the sign of the key,
+/// to the counter of its bucket in every row, see `Tab64Simple::hash_bucket_sign`
+/// for how bucket and sign are taken from disjoint bits of the hash value.
+///
+/// Each counter is an AMS estimator of the `F2` of the keys in its bucket,
+/// and the estimate of a row is the sum of its squared counters, i.e. `columns` times
+/// their mean. The final estimate is the median of the row estimates.
+/// The relative error of a row estimate has a standard deviation of about `sqrt(2 / columns)`,
+/// and the median makes large errors exponentially unlikely in the number of rows.
+///
+/// The sketch is linear, so deltas can be negative, e.g. for deletions.
+/// Counters wrap around instead of overflowing, which keeps the sketch linear.
+/// When serialized, the hash functions are stored alongside the counters.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::AmsSketch;
+///
+/// let mut sketch: AmsSketch = AmsSketch::new_with_seed(5, 256, 42);
+/// sketch.update(7, 3);
+/// sketch.update(11, 2);
+/// sketch.update(11, -1);
+/// println!("{}", sketch.estimate_f2()); // about 3^2 + 1^2 = 10
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "RawAmsSketch<H>",
+        bound(serialize = "H: Serialize", deserialize = "H: Deserialize<'de>")
+    )
+)]
+pub struct AmsSketch<H = Tab64Simple> {
+    columns: usize,
+    hash_functions: Vec<H>,
+    /// Counters of all rows, row by row
+    counters: Vec<i64>,
+}
+
+/// Unvalidated serialized form of an `AmsSketch`
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct RawAmsSketch<H> {
+    columns: usize,
+    hash_functions: Vec<H>,
+    counters: Vec<i64>,
+}
+
+#[cfg(feature = "serde")]
+impl<H> core::convert::TryFrom<RawAmsSketch<H>> for AmsSketch<H> {
+    type Error = &'static str;
+
+    fn try_from(raw: RawAmsSketch<H>) -> Result<Self, Self::Error> {
+        if raw.columns == 0 || raw.hash_functions.is_empty() {
+            return Err("an AMS sketch needs at least one row and one column");
+        }
+        if Some(raw.counters.len()) != raw.columns.checked_mul(raw.hash_functions.len()) {
+            return Err("the number of counters does not match the dimensions of the sketch");
+        }
+        Ok(AmsSketch {
+            columns: raw.columns,
+            hash_functions: raw.hash_functions,
+            counters: raw.counters,
+        })
+    }
+}
+
+impl<H: TabHash<Key = u64>> AmsSketch<H> {
+    /// Create an empty sketch with random hash functions.
+    ///
+    /// # Panics
+    /// If `rows` or `columns` is 0.
+    #[cfg(feature = "getrandom")]
+    pub fn new(rows: usize, columns: usize) -> Self {
+        AmsSketch::with_hash_functions(columns, (0..rows).map(|_| H::random()).collect())
+    }
+
+    /// Create an empty sketch with hash functions derived from a seed.
+    ///
+    /// The hash functions are derived like those of `CountMinSketch::new_with_seed`.
+    ///
+    /// # Panics
+    /// If `rows` or `columns` is 0.
+    pub fn new_with_seed(rows: usize, columns: usize, seed: u64) -> Self {
+        AmsSketch::with_hash_functions(columns, seeded_hash_functions(rows, seed))
+    }
+
+    /// Create an empty sketch with `columns` counters per row and one row per hash function.
+    ///
+    /// # Panics
+    /// If `columns` is 0 or `hash_functions` is empty.
+    pub fn with_hash_functions(columns: usize, hash_functions: Vec<H>) -> Self {
+        assert!(
+            columns > 0 && !hash_functions.is_empty(),
+            "an AMS sketch needs at least one row and one column"
+        );
+        let counters = vec![0; columns * hash_functions.len()];
+        AmsSketch {
+            columns,
+            hash_functions,
+            counters,
+        }
+    }
+
+    /// Get the number of rows, i.e. the number of hash functions.
+    pub fn rows(&self) -> usize {
+        self.hash_functions.len()
+    }
+
+    /// Get the number of counters per row.
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Get the hash functions of the rows.
+    pub fn hash_functions(&self) -> &[H] {
+        &self.hash_functions
+    }
+
+    /// Add `delta` occurrences of `key` to the sketch, or remove them if `delta` is negative.
+    pub fn update(&mut self, key: u64, delta: i64) {
+        for row in 0..self.rows() {
+            let (column, sign) = bucket_and_sign(self.hash_functions[row].hash(key), self.columns);
+            let i = row * self.columns + column;
+            self.counters[i] = self.counters[i].wrapping_add(i64::from(sign).wrapping_mul(delta));
+        }
+    }
+
+    /// Estimate the second frequency moment, i.e. the sum of the squared counts of all keys.
+    pub fn estimate_f2(&self) -> f64 {
+        let mut estimates: Vec<f64> = self
+            .counters
+            .chunks(self.columns)
+            .map(|row| row.iter().map(|c| (*c as f64) * (*c as f64)).sum())
+            .collect();
+        estimates.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let middle = estimates.len() / 2;
+        if estimates.len().is_multiple_of(2) {
+            (estimates[middle - 1] + estimates[middle]) / 2.0
+        } else {
+            estimates[middle]
+        }
+    }
+}
+
+/// A HyperLogLog sketch estimating the number of distinct 64-bit keys in a stream.
+///
+/// The sketch has `2^p` registers of one byte each. A key is mapped to a register and a rank
+/// with `hll_parts`, and each register holds the largest rank of its keys.
+/// Estimates have a relative standard error of about `1.04 / sqrt(2^p)`, e.g. 1.6% for `p = 12`.
+///
+/// By default, the sketch uses a `Tab64Twisted` hash function, but any `TabHash` with
+/// 64-bit keys works. Estimating requires the `std` feature for the logarithm of the
+/// small range correction. Sketches filled on `no_std` targets can be serialized
+/// or transferred as registers and estimated elsewhere.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::HyperLogLog;
+///
+/// let mut sketch: HyperLogLog = HyperLogLog::new_with_seed(12, 42);
+/// for x in 0..10_000 {
+///     sketch.insert(x % 1000);
+/// }
+/// println!("{}", sketch.estimate()); // about 1000
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "RawHyperLogLog<H>",
+        bound(serialize = "H: Serialize", deserialize = "H: Deserialize<'de>")
+    )
+)]
+pub struct HyperLogLog<H = Tab64Twisted> {
+    p: u8,
+    hash_function: H,
+    registers: Vec<u8>,
+}
+
+/// Unvalidated serialized form of a `HyperLogLog` sketch
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct RawHyperLogLog<H> {
+    p: u8,
+    hash_function: H,
+    registers: Vec<u8>,
+}
+
+#[cfg(feature = "serde")]
+impl<H> core::convert::TryFrom<RawHyperLogLog<H>> for HyperLogLog<H> {
+    type Error = &'static str;
+
+    fn try_from(raw: RawHyperLogLog<H>) -> Result<Self, Self::Error> {
+        if !(4..=18).contains(&raw.p) {
+            return Err("the precision of HyperLogLog has to be in 4..=18");
+        }
+        if raw.registers.len() != 1 << raw.p {
+            return Err("the number of registers does not match the precision");
+        }
+        if raw.registers.iter().any(|r| *r > 65 - raw.p) {
+            return Err("a register exceeds the largest possible rank");
+        }
+        Ok(HyperLogLog {
+            p: raw.p,
+            hash_function: raw.hash_function,
+            registers: raw.registers,
+        })
+    }
+}
+
+impl<H: TabHash<Key = u64>> HyperLogLog<H> {
+    /// Create an empty sketch with `2^p` registers and a random hash function.
+    ///
+    /// # Panics
+    /// If `p` is not in `4..=18`.
+    #[cfg(feature = "getrandom")]
+    pub fn new(p: u8) -> Self {
+        HyperLogLog::with_hash_function(p, H::random())
+    }
+
+    /// Create an empty sketch with `2^p` registers and a hash function derived from a seed.
+    ///
+    /// This uses `new_with_seed` of the hash function.
+    ///
+    /// # Panics
+    /// If `p` is not in `4..=18`.
+    pub fn new_with_seed(p: u8, seed: u64) -> Self {
+        HyperLogLog::with_hash_function(p, H::new_with_seed(seed))
+    }
+
+    /// Create an empty sketch with `2^p` registers using the given hash function.
+    ///
+    /// # Panics
+    /// If `p` is not in `4..=18`.
+    pub fn with_hash_function(p: u8, hash_function: H) -> Self {
+        assert!(
+            (4..=18).contains(&p),
+            "the precision of HyperLogLog has to be in 4..=18, not {}",
+            p
+        );
+        HyperLogLog {
+            p,
+            hash_function,
+            registers: vec![0; 1 << p],
+        }
+    }
+
+    /// Get the precision, i.e. the logarithm of the number of registers.
+    pub fn precision(&self) -> u8 {
+        self.p
+    }
+
+    /// Get the hash function of this sketch.
+    pub fn hash_function(&self) -> &H {
+        &self.hash_function
+    }
+
+    /// Get the registers of this sketch.
+    pub fn registers(&self) -> &[u8] {
+        &self.registers
+    }
+
+    /// Add a key to the sketch.
+    pub fn insert(&mut self, key: u64) {
+        let (index, rank) = hll_parts(self.hash_function.hash(key), self.p);
+        if rank > self.registers[index] {
+            self.registers[index] = rank;
+        }
+    }
+
+    /// Estimate the number of distinct keys added to the sketch.
+    ///
+    /// This is the raw HyperLogLog estimate of Flajolet et al. with their small range
+    /// correction, i.e. linear counting if the raw estimate is at most `2.5 * 2^p`
+    /// and some registers are still 0. Since hash values have 64 bits,
+    /// no large range correction is needed.
+    #[cfg(feature = "std")]
+    pub fn estimate(&self) -> f64 {
+        let m = self.registers.len() as f64;
+        let alpha = match self.registers.len() {
+            16 => 0.673,
+            32 => 0.697,
+            64 => 0.709,
+            _ => 0.7213 / (1.0 + 1.079 / m),
+        };
+        let sum: f64 = self
+            .registers
+            .iter()
+            .map(|r| 2_f64.powi(-i32::from(*r)))
+            .sum();
+        let raw = alpha * m * m / sum;
+        let zeros = self.registers.iter().filter(|r| **r == 0).count();
+        if raw <= 2.5 * m && zeros > 0 {
+            m * (m / zeros as f64).ln()
+        } else {
+            raw
+        }
+    }
+
+    /// Add the keys of `other` to this sketch.
+    ///
+    /// Afterwards, this sketch is identical to a sketch of both streams. Merging is
+    /// idempotent and commutative. This requires both sketches to have the same precision
+    /// and equal hash functions, otherwise this sketch is left unchanged and an error is returned.
+    pub fn merge(&mut self, other: &Self) -> Result<(), MergeError>
+    where
+        H: PartialEq,
+    {
+        if self.p != other.p {
+            return Err(MergeError::Precision {
+                expected: self.p,
+                actual: other.p,
+            });
+        }
+        if self.hash_function != other.hash_function {
+            return Err(MergeError::HashFunction { row: 0 });
+        }
+        for (register, other) in self.registers.iter_mut().zip(other.registers.iter()) {
+            *register = (*register).max(*other);
+        }
+        Ok(())
+    }
+}
+
+/// A MinHash signature of a set of 64-bit keys for estimating Jaccard similarities.
+///
+/// The signature holds the minimum hash value of all keys for each of `k` hash functions.
+/// The fraction of equal entries in the signatures of two sets estimates their Jaccard
+/// similarity, i.e. the size of their intersection divided by the size of their union,
+/// with a standard error of at most `0.5 / sqrt(k)`.
+///
+/// Signatures can only be compared if they use the same hash functions.
+/// To check this without comparing tables, the ids of the hash functions are kept
+/// alongside the signature. When serialized, the hash functions are stored alongside
+/// the signature, and their ids are recomputed when deserializing.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::MinHash;
+///
+/// let mut a: MinHash = MinHash::new_with_seed(128, 42);
+/// let mut b: MinHash = MinHash::new_with_seed(128, 42);
+/// (0..1000).for_each(|key| a.insert(key));
+/// (500..1500).for_each(|key| b.insert(key));
+/// println!("{}", a.jaccard(&b).unwrap()); // about 1/3
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "RawMinHash<H>",
+        bound(
+            serialize = "H: Serialize",
+            deserialize = "H: Deserialize<'de> + TabHash"
+        )
+    )
+)]
+pub struct MinHash<H = Tab64Simple> {
+    hash_functions: Vec<H>,
+    /// Ids of the hash functions
+    #[cfg_attr(feature = "serde", serde(skip))]
+    ids: Vec<u64>,
+    signature: Vec<u64>,
+}
+
+/// Unvalidated serialized form of a `MinHash` signature
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct RawMinHash<H> {
+    hash_functions: Vec<H>,
+    signature: Vec<u64>,
+}
+
+#[cfg(feature = "serde")]
+impl<H: TabHash> core::convert::TryFrom<RawMinHash<H>> for MinHash<H> {
+    type Error = &'static str;
+
+    fn try_from(raw: RawMinHash<H>) -> Result<Self, Self::Error> {
+        if raw.hash_functions.is_empty() {
+            return Err("a MinHash signature needs at least one hash function");
+        }
+        if raw.signature.len() != raw.hash_functions.len() {
+            return Err("the length of the signature does not match the number of hash functions");
+        }
+        Ok(MinHash {
+            ids: raw.hash_functions.iter().map(|h| h.id()).collect(),
+            hash_functions: raw.hash_functions,
+            signature: raw.signature,
+        })
+    }
+}
+
+impl<H: TabHash<Key = u64>> MinHash<H> {
+    /// Create an empty signature with `k` random hash functions.
+    ///
+    /// # Panics
+    /// If `k` is 0.
+    #[cfg(feature = "getrandom")]
+    pub fn new(k: usize) -> Self {
+        MinHash::with_hash_functions((0..k).map(|_| H::random()).collect())
+    }
+
+    /// Create an empty signature with `k` hash functions derived from a seed.
+    ///
+    /// The hash functions are derived like those of `CountMinSketch::new_with_seed`.
+    ///
+    /// # Panics
+    /// If `k` is 0.
+    pub fn new_with_seed(k: usize, seed: u64) -> Self {
+        MinHash::with_hash_functions(seeded_hash_functions(k, seed))
+    }
+
+    /// Create an empty signature with an entry for each of the hash functions.
+    ///
+    /// # Panics
+    /// If `hash_functions` is empty.
+    pub fn with_hash_functions(hash_functions: Vec<H>) -> Self {
+        assert!(
+            !hash_functions.is_empty(),
+            "a MinHash signature needs at least one hash function"
+        );
+        MinHash {
+            ids: hash_functions.iter().map(|h| h.id()).collect(),
+            signature: vec![u64::MAX; hash_functions.len()],
+            hash_functions,
+        }
+    }
+
+    /// Get the hash functions of the signature.
+    pub fn hash_functions(&self) -> &[H] {
+        &self.hash_functions
+    }
+
+    /// Get the signature, i.e. the minimum hash value of all keys for each hash function.
+    ///
+    /// Entries of an empty set are `u64::MAX`.
+    pub fn signature(&self) -> &[u64] {
+        &self.signature
+    }
+
+    /// Add a key to the set.
+    pub fn insert(&mut self, key: u64) {
+        for (minimum, h) in self.signature.iter_mut().zip(self.hash_functions.iter()) {
+            *minimum = (*minimum).min(h.hash(key));
+        }
+    }
+
+    /// Hash the bands of the signature into band keys, see `lsh_bands`.
+    ///
+    /// # Panics
+    /// If `bands * rows` is not `k`.
+    pub fn lsh_bands(&self, bands: usize, rows: usize, band_hasher: &Tab64Simple) -> Vec<u64> {
+        lsh_bands(&self.signature, bands, rows, band_hasher)
+    }
+
+    /// Check that `other` uses the same hash functions by comparing their ids
+    fn check_compatible(&self, other: &Self) -> Result<(), MergeError> {
+        if self.ids.len() != other.ids.len() {
+            return Err(MergeError::Dimensions {
+                expected: (self.ids.len(), 1),
+                actual: (other.ids.len(), 1),
+            });
+        }
+        match (0..self.ids.len()).find(|row| self.ids[*row] != other.ids[*row]) {
+            Some(row) => Err(MergeError::HashFunction { row }),
+            None => Ok(()),
+        }
+    }
+
+    /// Estimate the Jaccard similarity of the sets of this and the `other` signature.
+    ///
+    /// This is the fraction of equal entries of both signatures. Two empty sets have
+    /// a similarity of 1. Both signatures need to use the same hash functions, which is
+    /// checked by comparing their ids.
+    pub fn jaccard(&self, other: &Self) -> Result<f64, MergeError> {
+        self.check_compatible(other)?;
+        let equal = self
+            .signature
+            .iter()
+            .zip(other.signature.iter())
+            .filter(|(a, b)| a == b)
+            .count();
+        Ok(equal as f64 / self.signature.len() as f64)
+    }
+
+    /// Add the keys of `other` to this set.
+    ///
+    /// Afterwards, this signature is the signature of the union of both sets.
+    /// Both signatures need to use the same hash functions, otherwise this signature
+    /// is left unchanged and an error is returned.
+    pub fn merge(&mut self, other: &Self) -> Result<(), MergeError> {
+        self.check_compatible(other)?;
+        for (minimum, other) in self.signature.iter_mut().zip(other.signature.iter()) {
+            *minimum = (*minimum).min(*other);
+        }
+        Ok(())
+    }
+}
+
+/// Marker of empty bins of a `OnePermMinHash` signature
+const EMPTY_BIN: u64 = u64::MAX;
+
+/// A one permutation MinHash signature with optimal densification.
+///
+/// Instead of evaluating `k` hash functions per key like `MinHash`, a single hash function
+/// partitions the keys into `k` bins, and each bin holds the minimum of its keys.
+/// With `b = log2(k)`, the top `b` bits of the hash value of a key select its bin,
+/// and the remaining `64 - b` bits are the value compared within the bin.
+///
+/// Bins without keys are filled when computing the `signature` using the optimal
+/// densification of Shrivastava (2017): for an empty bin `i`, the attempts
+/// `t = 1, 2, ...` select the bin given by the top `b` bits of `hash((t << 32) | i)`,
+/// until a nonempty bin is selected, whose value is copied. Since this only depends on
+/// the hash function and on which bins are empty, densification is consistent
+/// across sets, and the fraction of equal entries of two densified signatures
+/// is an unbiased estimate of the Jaccard similarity. After `64 * k` unsuccessful
+/// attempts, which happens with a probability below `e^-64`, the next nonempty bin
+/// after `i` (wrapping around) is used instead. The signature of an empty set
+/// consists of `u64::MAX` entries only.
+///
+/// By default, the sketch uses a `Tab64Simple` hash function, but any `TabHash` with
+/// 64-bit keys works. When serialized, the hash function is stored alongside the bins.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::OnePermMinHash;
+///
+/// let mut a: OnePermMinHash = OnePermMinHash::new_with_seed(128, 42);
+/// let mut b: OnePermMinHash = OnePermMinHash::new_with_seed(128, 42);
+/// (0..1000).for_each(|key| a.insert(key));
+/// (500..1500).for_each(|key| b.insert(key));
+/// println!("{}", a.jaccard(&b).unwrap()); // about 1/3
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "RawOnePermMinHash<H>",
+        bound(
+            serialize = "H: Serialize",
+            deserialize = "H: Deserialize<'de> + TabHash"
+        )
+    )
+)]
+pub struct OnePermMinHash<H = Tab64Simple> {
+    hash_function: H,
+    /// Id of the hash function
+    #[cfg_attr(feature = "serde", serde(skip))]
+    id: u64,
+    /// Minimum value of each bin, `EMPTY_BIN` for empty bins
+    bins: Vec<u64>,
+}
+
+/// Unvalidated serialized form of a `OnePermMinHash` signature
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct RawOnePermMinHash<H> {
+    hash_function: H,
+    bins: Vec<u64>,
+}
+
+#[cfg(feature = "serde")]
+impl<H: TabHash> core::convert::TryFrom<RawOnePermMinHash<H>> for OnePermMinHash<H> {
+    type Error = &'static str;
+
+    fn try_from(raw: RawOnePermMinHash<H>) -> Result<Self, Self::Error> {
+        let k = raw.bins.len();
+        if !k.is_power_of_two() || !(2..=1 << 16).contains(&k) {
+            return Err("the number of bins has to be a power of two between 2 and 65536");
+        }
+        let max_value = u64::MAX >> k.trailing_zeros();
+        if raw.bins.iter().any(|v| *v != EMPTY_BIN && *v > max_value) {
+            return Err("a bin exceeds the largest possible value");
+        }
+        Ok(OnePermMinHash {
+            id: raw.hash_function.id(),
+            hash_function: raw.hash_function,
+            bins: raw.bins,
+        })
+    }
+}
+
+impl<H: TabHash<Key = u64>> OnePermMinHash<H> {
+    /// Create an empty signature with `k` bins and a random hash function.
+    ///
+    /// # Panics
+    /// If `k` is not a power of two between 2 and 65536.
+    #[cfg(feature = "getrandom")]
+    pub fn new(k: usize) -> Self {
+        OnePermMinHash::with_hash_function(k, H::random())
+    }
+
+    /// Create an empty signature with `k` bins and a hash function derived from a seed.
+    ///
+    /// This uses `new_with_seed` of the hash function.
+    ///
+    /// # Panics
+    /// If `k` is not a power of two between 2 and 65536.
+    pub fn new_with_seed(k: usize, seed: u64) -> Self {
+        OnePermMinHash::with_hash_function(k, H::new_with_seed(seed))
+    }
+
+    /// Create an empty signature with `k` bins using the given hash function.
+    ///
+    /// # Panics
+    /// If `k` is not a power of two between 2 and 65536.
+    pub fn with_hash_function(k: usize, hash_function: H) -> Self {
+        assert!(
+            k.is_power_of_two() && (2..=1 << 16).contains(&k),
+            "the number of bins has to be a power of two between 2 and 65536, not {}",
+            k
+        );
+        OnePermMinHash {
+            id: hash_function.id(),
+            hash_function,
+            bins: vec![EMPTY_BIN; k],
+        }
+    }
+
+    /// Get the hash function of the signature.
+    pub fn hash_function(&self) -> &H {
+        &self.hash_function
+    }
+
+    /// Get the number of bins.
+    pub fn k(&self) -> usize {
+        self.bins.len()
+    }
+
+    /// Get the bin of a hash value
+    fn bin(&self, h: u64) -> usize {
+        // k is at least 2, so the shift is less than 64
+        (h >> (64 - self.bins.len().trailing_zeros())) as usize
+    }
+
+    /// Add a key to the set.
+    pub fn insert(&mut self, key: u64) {
+        let h = self.hash_function.hash(key);
+        let bin = self.bin(h);
+        let value = h & (u64::MAX >> self.bins.len().trailing_zeros());
+        self.bins[bin] = self.bins[bin].min(value);
+    }
+
+    /// Check whether no key was added to the set.
+    pub fn is_empty(&self) -> bool {
+        self.bins.iter().all(|v| *v == EMPTY_BIN)
+    }
+
+    /// Get the bin whose value is copied into the empty `bin` by densification
+    fn donor(&self, bin: usize) -> usize {
+        let k = self.bins.len();
+        for attempt in 1..=64 * k as u64 {
+            let donor = self.bin(self.hash_function.hash((attempt << 32) | bin as u64));
+            if self.bins[donor] != EMPTY_BIN {
+                return donor;
+            }
+        }
+        (1..k)
+            .map(|offset| (bin + offset) % k)
+            .find(|donor| self.bins[*donor] != EMPTY_BIN)
+            .unwrap_or(bin)
+    }
+
+    /// Get the densified signature, i.e. the minimum of each bin, with empty bins
+    /// filled as described in the type documentation.
+    pub fn signature(&self) -> Vec<u64> {
+        if self.is_empty() {
+            return self.bins.clone();
+        }
+        (0..self.bins.len())
+            .map(|bin| match self.bins[bin] {
+                EMPTY_BIN => self.bins[self.donor(bin)],
+                value => value,
+            })
+            .collect()
+    }
+
+    /// Check that `other` uses the same hash function and number of bins
+    fn check_compatible(&self, other: &Self) -> Result<(), MergeError> {
+        if self.bins.len() != other.bins.len() {
+            return Err(MergeError::Dimensions {
+                expected: (self.bins.len(), 1),
+                actual: (other.bins.len(), 1),
+            });
+        }
+        if self.id != other.id {
+            return Err(MergeError::HashFunction { row: 0 });
+        }
+        Ok(())
+    }
+
+    /// Estimate the Jaccard similarity of the sets of this and the `other` signature.
+    ///
+    /// This is the fraction of equal entries of both densified signatures.
+    /// Two empty sets have a similarity of 1. Both signatures need to use the same
+    /// hash function, which is checked by comparing ids, and the same number of bins.
+    pub fn jaccard(&self, other: &Self) -> Result<f64, MergeError> {
+        self.check_compatible(other)?;
+        let equal = self
+            .signature()
+            .iter()
+            .zip(other.signature().iter())
+            .filter(|(a, b)| a == b)
+            .count();
+        Ok(equal as f64 / self.bins.len() as f64)
+    }
+
+    /// Add the keys of `other` to this set.
+    ///
+    /// Afterwards, this signature is the signature of the union of both sets.
+    /// Both signatures need to use the same hash function and the same number of bins,
+    /// otherwise this signature is left unchanged and an error is returned.
+    pub fn merge(&mut self, other: &Self) -> Result<(), MergeError> {
+        self.check_compatible(other)?;
+        for (minimum, other) in self.bins.iter_mut().zip(other.bins.iter()) {
+            *minimum = (*minimum).min(*other);
+        }
+        Ok(())
+    }
+}
+
+/// A bottom-k sketch estimating the number of distinct 64-bit keys in a stream.
+///
+/// The sketch keeps the `k` smallest distinct hash values of all keys, also known as
+/// k minimum values (KMV). If `h_k` is the `k`-th smallest hash value as a fraction of
+/// `2^64`, the number of distinct keys is estimated as `(k - 1) / h_k`, with a relative
+/// standard error of about `1 / sqrt(k - 2)`. Unlike `HyperLogLog`, the sketch supports
+/// set operations, e.g. estimating the Jaccard similarity of two streams.
+///
+/// By default, the sketch uses a `Tab64Twisted` hash function, but any `TabHash` with
+/// 64-bit keys works. When serialized, the hash function is stored alongside the hash values.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::BottomK;
+///
+/// let mut sketch: BottomK = BottomK::new_with_seed(256, 42);
+/// for x in 0..100_000 {
+///     sketch.insert(x % 10_000);
+/// }
+/// println!("{}", sketch.estimate_distinct()); // about 10000
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "RawBottomK<H>",
+        bound(
+            serialize = "H: Serialize",
+            deserialize = "H: Deserialize<'de> + TabHash"
+        )
+    )
+)]
+pub struct BottomK<H = Tab64Twisted> {
+    k: usize,
+    hash_function: H,
+    /// Id of the hash function
+    #[cfg_attr(feature = "serde", serde(skip))]
+    id: u64,
+    /// The smallest distinct hash values in increasing order
+    values: Vec<u64>,
+}
+
+/// Unvalidated serialized form of a `BottomK` sketch
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct RawBottomK<H> {
+    k: usize,
+    hash_function: H,
+    values: Vec<u64>,
+}
+
+#[cfg(feature = "serde")]
+impl<H: TabHash> core::convert::TryFrom<RawBottomK<H>> for BottomK<H> {
+    type Error = &'static str;
+
+    fn try_from(raw: RawBottomK<H>) -> Result<Self, Self::Error> {
+        if raw.k < 2 {
+            return Err("a bottom-k sketch needs to keep at least two hash values");
+        }
+        if raw.values.len() > raw.k {
+            return Err("the sketch holds more than k hash values");
+        }
+        if raw.values.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err("the hash values are not distinct and in increasing order");
+        }
+        Ok(BottomK {
+            k: raw.k,
+            id: raw.hash_function.id(),
+            hash_function: raw.hash_function,
+            values: raw.values,
+        })
+    }
+}
+
+impl<H: TabHash<Key = u64>> BottomK<H> {
+    /// Create an empty sketch keeping `k` hash values with a random hash function.
+    ///
+    /// # Panics
+    /// If `k` is less than 2.
+    #[cfg(feature = "getrandom")]
+    pub fn new(k: usize) -> Self {
+        BottomK::with_hash_function(k, H::random())
+    }
+
+    /// Create an empty sketch keeping `k` hash values with a hash function derived from a seed.
+    ///
+    /// This uses `new_with_seed` of the hash function.
+    ///
+    /// # Panics
+    /// If `k` is less than 2.
+    pub fn new_with_seed(k: usize, seed: u64) -> Self {
+        BottomK::with_hash_function(k, H::new_with_seed(seed))
+    }
+
+    /// Create an empty sketch keeping `k` hash values using the given hash function.
+    ///
+    /// # Panics
+    /// If `k` is less than 2.
+    pub fn with_hash_function(k: usize, hash_function: H) -> Self {
+        assert!(
+            k >= 2,
+            "a bottom-k sketch needs to keep at least two hash values"
+        );
+        BottomK {
+            k,
+            id: hash_function.id(),
+            hash_function,
+            values: Vec::with_capacity(k),
+        }
+    }
+
+    /// Get the maximum number of hash values kept by the sketch.
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Get the hash function of the sketch.
+    pub fn hash_function(&self) -> &H {
+        &self.hash_function
+    }
+
+    /// Get the smallest distinct hash values of all keys in increasing order.
+    pub fn values(&self) -> &[u64] {
+        &self.values
+    }
+
+    /// Add a key to the sketch.
+    ///
+    /// Hash values already in the sketch are ignored, so duplicate keys
+    /// do not change the sketch.
+    pub fn insert(&mut self, key: u64) {
+        let h = self.hash_function.hash(key);
+        if self.values.len() == self.k && h >= self.values[self.k - 1] {
+            return;
+        }
+        if let Err(position) = self.values.binary_search(&h) {
+            if self.values.len() == self.k {
+                self.values.pop();
+            }
+            self.values.insert(position, h);
+        }
+    }
+
+    /// Estimate the number of distinct keys added to the sketch.
+    ///
+    /// While the sketch holds fewer than `k` hash values, this is their number,
+    /// which is exact unless distinct keys have equal hash values.
+    /// Otherwise, it is `(k - 1) / h_k`, see the type documentation.
+    pub fn estimate_distinct(&self) -> f64 {
+        if self.values.len() < self.k {
+            return self.values.len() as f64;
+        }
+        let h_k = self.values[self.k - 1] as f64 / (1_u128 << 64) as f64;
+        (self.k - 1) as f64 / h_k
+    }
+
+    /// Check that `other` uses the same hash function and `k`
+    fn check_compatible(&self, other: &Self) -> Result<(), MergeError> {
+        if self.k != other.k {
+            return Err(MergeError::Dimensions {
+                expected: (self.k, 1),
+                actual: (other.k, 1),
+            });
+        }
+        if self.id != other.id {
+            return Err(MergeError::HashFunction { row: 0 });
+        }
+        Ok(())
+    }
+
+    /// Get the `k` smallest distinct hash values of both sketches in increasing order
+    fn union_values(&self, other: &Self) -> Vec<u64> {
+        let mut union = Vec::with_capacity(self.values.len() + other.values.len());
+        union.extend_from_slice(&self.values);
+        union.extend_from_slice(&other.values);
+        union.sort_unstable();
+        union.dedup();
+        union.truncate(self.k);
+        union
+    }
+
+    /// Estimate the Jaccard similarity of the streams of this and the `other` sketch.
+    ///
+    /// This uses the `k` smallest hash values of the union of both streams,
+    /// i.e. the bottom-k sketch of the union, and returns the fraction of them
+    /// that occur in both sketches. Two empty sketches have a similarity of 1.
+    /// Both sketches need to use the same hash function and `k`.
+    pub fn jaccard_estimate(&self, other: &Self) -> Result<f64, MergeError> {
+        self.check_compatible(other)?;
+        let union = self.union_values(other);
+        if union.is_empty() {
+            return Ok(1.0);
+        }
+        let both = union
+            .iter()
+            .filter(|h| {
+                self.values.binary_search(h).is_ok() && other.values.binary_search(h).is_ok()
+            })
+            .count();
+        Ok(both as f64 / union.len() as f64)
+    }
+
+    /// Add the keys of `other` to this sketch.
+    ///
+    /// Afterwards, this sketch is the sketch of the union of both streams. Both sketches
+    /// need to use the same hash function and `k`, otherwise this sketch is left unchanged
+    /// and an error is returned.
+    pub fn merge(&mut self, other: &Self) -> Result<(), MergeError> {
+        self.check_compatible(other)?;
+        self.values = self.union_values(other);
+        Ok(())
+    }
+}
+
+/// Hash the bands of a MinHash signature into band keys for locality sensitive hashing.
+///
+/// The signature is split into `bands` consecutive bands of `rows` entries each,
+/// and band `i` is hashed with `band_hasher.hash_slice(&signature[i * rows..(i + 1) * rows])`.
+/// That is, the entries of a band are combined by chaining: starting with `hash(rows)`,
+/// each entry is XORed into the current value, which is then hashed again,
+/// e.g. a band `[a, b]` has the key `hash(hash(hash(2) ^ a) ^ b)`.
+/// The band index is not part of the key, so keys are only comparable within a band.
+///
+/// Two signatures whose entries agree with probability `s`, e.g. MinHash signatures of
+/// sets with Jaccard similarity `s`, share at least one band key with probability
+/// `1 - (1 - s^rows)^bands`. This S-curve is steepest around `(1 / bands)^(1 / rows)`.
+///
+/// # Panics
+/// If `bands * rows` is not the length of the signature.
+pub fn lsh_bands(
+    signature: &[u64],
+    bands: usize,
+    rows: usize,
+    band_hasher: &Tab64Simple,
+) -> Vec<u64> {
+    assert!(
+        bands.checked_mul(rows) == Some(signature.len()),
+        "{} bands of {} rows do not match a signature of length {}",
+        bands,
+        rows,
+        signature.len()
+    );
+    if rows == 0 {
+        return vec![band_hasher.hash_slice(&[]); bands];
+    }
+    signature
+        .chunks(rows)
+        .map(|band| band_hasher.hash_slice(band))
+        .collect()
+}
+
+/// Group signatures into candidate buckets for locality sensitive hashing.
+///
+/// Signatures are banded with `lsh_bands`. Each bucket holds the indices of all signatures
+/// that share the key of one band, in increasing order. Only buckets of at least two
+/// signatures are returned, ordered by band and key. A pair of signatures may share
+/// several bands, so it can occur in several buckets.
+///
+/// # Panics
+/// If `bands * rows` is not the length of one of the signatures.
+pub fn lsh_candidates<S: AsRef<[u64]>>(
+    signatures: &[S],
+    bands: usize,
+    rows: usize,
+    band_hasher: &Tab64Simple,
+) -> Vec<Vec<usize>> {
+    let mut buckets: BTreeMap<(usize, u64), Vec<usize>> = BTreeMap::new();
+    for (i, signature) in signatures.iter().enumerate() {
+        let keys = lsh_bands(signature.as_ref(), bands, rows, band_hasher);
+        for (band, key) in keys.into_iter().enumerate() {
+            buckets.entry((band, key)).or_default().push(i);
+        }
+    }
+    buckets
+        .into_values()
+        .filter(|bucket| bucket.len() > 1)
+        .collect()
+}
+
+/// Improved consistent weighted sampling (ICWS) for estimating weighted Jaccard similarities.
+///
+/// The weighted Jaccard similarity of two sets of weighted keys is the sum of the minimum
+/// weights of all keys divided by the sum of their maximum weights, where missing keys
+/// have a weight of 0. `sample` draws `k` samples `(key, y)` from a set of weighted keys,
+/// such that sample `i` of two sets is equal with a probability of their
+/// weighted Jaccard similarity, see Ioffe (2010). Hence the fraction of equal samples,
+/// computed by `jaccard`, estimates the weighted Jaccard similarity with a standard
+/// error of at most `0.5 / sqrt(k)`.
+///
+/// Sample `i` uses its own hash function. The five uniform random numbers needed for a key
+/// are drawn from a SplitMix64 generator seeded with the hash value of the key, so the
+/// samples only depend on the tables of the hash functions. Sampling requires the `std`
+/// feature for logarithms and exponentials.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::Icws;
+///
+/// let sampler: Icws = Icws::new_with_seed(128, 42);
+/// let a = sampler.sample(vec![(1, 1.0), (2, 2.0), (3, 0.5)]);
+/// let b = sampler.sample(vec![(1, 2.0), (2, 1.0), (4, 0.5)]);
+/// println!("{}", sampler.jaccard(&a, &b)); // about (1 + 1) / (2 + 2 + 0.5 + 0.5) = 0.4
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "RawIcws<H>",
+        bound(serialize = "H: Serialize", deserialize = "H: Deserialize<'de>")
+    )
+)]
+pub struct Icws<H = Tab64Simple> {
+    hash_functions: Vec<H>,
+}
+
+/// Unvalidated serialized form of an `Icws` sampler
+#[cfg(all(feature = "std", feature = "serde"))]
+#[derive(Deserialize)]
+struct RawIcws<H> {
+    hash_functions: Vec<H>,
+}
+
+#[cfg(all(feature = "std", feature = "serde"))]
+impl<H> core::convert::TryFrom<RawIcws<H>> for Icws<H> {
+    type Error = &'static str;
+
+    fn try_from(raw: RawIcws<H>) -> Result<Self, Self::Error> {
+        if raw.hash_functions.is_empty() {
+            return Err("an ICWS sampler needs at least one hash function");
+        }
+        Ok(Icws {
+            hash_functions: raw.hash_functions,
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl<H: TabHash<Key = u64>> Icws<H> {
+    /// Create a sampler drawing `k` samples with random hash functions.
+    ///
+    /// # Panics
+    /// If `k` is 0.
+    pub fn new(k: usize) -> Self {
+        Icws::with_hash_functions((0..k).map(|_| H::random()).collect())
+    }
+
+    /// Create a sampler drawing `k` samples with hash functions derived from a seed.
+    ///
+    /// The hash functions are derived like those of `CountMinSketch::new_with_seed`.
+    ///
+    /// # Panics
+    /// If `k` is 0.
+    pub fn new_with_seed(k: usize, seed: u64) -> Self {
+        Icws::with_hash_functions(seeded_hash_functions(k, seed))
+    }
+
+    /// Create a sampler drawing one sample per hash function.
+    ///
+    /// # Panics
+    /// If `hash_functions` is empty.
+    pub fn with_hash_functions(hash_functions: Vec<H>) -> Self {
+        assert!(
+            !hash_functions.is_empty(),
+            "an ICWS sampler needs at least one hash function"
+        );
+        Icws { hash_functions }
+    }
+
+    /// Get the number of samples.
+    pub fn k(&self) -> usize {
+        self.hash_functions.len()
+    }
+
+    /// Get the hash functions of the samples.
+    pub fn hash_functions(&self) -> &[H] {
+        &self.hash_functions
+    }
+
+    /// Draw `k` samples `(key, y)` from a set of weighted keys.
+    ///
+    /// For sample `i` and a key with weight `S`, five uniform random numbers in `(0, 1)`
+    /// are drawn from a SplitMix64 generator seeded with the hash value of the key under
+    /// hash function `i`, each from the top 53 bits of an output. They yield
+    /// `r = -ln(u1 * u2)` and `c = -ln(u3 * u4)`, both `Gamma(2, 1)` distributed,
+    /// and `beta = u5`. With `t = floor(ln(S) / r + beta)` and `y = exp(r * (t - beta))`,
+    /// the key with the smallest `a = c / (y * exp(r))` is sampled together with its `y`.
+    ///
+    /// Keys should occur at most once. The samples of an empty set are empty.
+    ///
+    /// # Panics
+    /// If a weight is not positive and finite.
+    pub fn sample(&self, features: impl IntoIterator<Item = (u64, f64)>) -> Vec<(u64, f64)> {
+        // smallest ln(a) of each sample so far, with the sampled key and y
+        let mut samples: Vec<(f64, u64, f64)> = Vec::new();
+        for (key, weight) in features {
+            assert!(
+                weight > 0.0 && weight.is_finite(),
+                "weights have to be positive and finite, got {} for key {}",
+                weight,
+                key
+            );
+            let ln_weight = weight.ln();
+            for (i, h) in self.hash_functions.iter().enumerate() {
+                let mut rng = SplitMix64::new(h.hash(key));
+                let mut uniform = || ((rng.next_u64() >> 11) as f64 + 0.5) / (1_u64 << 53) as f64;
+                let r = -(uniform() * uniform()).ln();
+                let c = -(uniform() * uniform()).ln();
+                let beta = uniform();
+                let t = (ln_weight / r + beta).floor();
+                let ln_y = r * (t - beta);
+                let ln_a = c.ln() - ln_y - r;
+                if i == samples.len() {
+                    samples.push((ln_a, key, ln_y.exp()));
+                } else if ln_a < samples[i].0 {
+                    samples[i] = (ln_a, key, ln_y.exp());
+                }
+            }
+        }
+        samples.into_iter().map(|(_, key, y)| (key, y)).collect()
+    }
+
+    /// Estimate the weighted Jaccard similarity from the samples of two sets.
+    ///
+    /// This is the fraction of equal samples. Both sets have to be sampled by this sampler.
+    /// The samples of two empty sets have a similarity of 1,
+    /// those of an empty and a nonempty set a similarity of 0.
+    pub fn jaccard(&self, a: &[(u64, f64)], b: &[(u64, f64)]) -> f64 {
+        if a.is_empty() && b.is_empty() {
+            return 1.0;
+        }
+        let equal = a.iter().zip(b.iter()).filter(|(x, y)| x == y).count();
+        equal as f64 / self.k() as f64
+    }
+}
+
+/// Draw `count` hash functions with seeds from a SplitMix64 generator seeded with `seed`
+fn seeded_hash_functions<H: TabHash>(count: usize, seed: u64) -> Vec<H> {
+    let mut seeds = SplitMix64::new(seed);
+    (0..count)
+        .map(|_| H::new_with_seed(seeds.next_u64()))
+        .collect()
+}
+
+/// Sketches that cannot be merged or compared.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MergeError {
+    /// The sketches have different dimensions
+    ///
+    /// MinHash signatures, including one permutation ones, and bottom-k sketches
+    /// have a width of `k` and a depth of 1.
+    Dimensions {
+        /// Width and depth of the sketch to merge into
+        expected: (usize, usize),
+        /// Width and depth of the merged sketch
+        actual: (usize, usize),
+    },
+    /// The sketches use different hash functions
+    HashFunction {
+        /// The first row with different hash functions, 0 for sketches with a single one
+        row: usize,
+    },
+    /// The HyperLogLog sketches have different precisions
+    Precision {
+        /// Precision of the sketch to merge into
+        expected: u8,
+        /// Precision of the merged sketch
+        actual: u8,
+    },
+}
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MergeError::Dimensions { expected, actual } => write!(
+                f,
+                "cannot merge a sketch of width {} and depth {} into one of width {} and depth {}",
+                actual.0, actual.1, expected.0, expected.1
+            ),
+            MergeError::HashFunction { row } => {
+                write!(
+                    f,
+                    "cannot merge sketches with different hash functions in row {}",
+                    row
+                )
+            }
+            MergeError::Precision { expected, actual } => write!(
+                f,
+                "cannot merge a sketch of precision {} into one of precision {}",
+                actual, expected
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MergeError {}
@@ -0,0 +1,1170 @@
+//! Tabulation-permutation hashing.
+//!
+//! Tabulation-permutation hashing composes simple tabulation with a random permutation
+//! of each output character, see Jakob Bæk Tejs Houen and Mikkel Thorup,
+//! [Understanding the Moments of Tabulation Hashing via Chaoses](https://doi.org/10.4230/LIPIcs.ICALP.2022.74)
+//! (ICALP 2022). A key is hashed with simple tabulation, and then each byte of the
+//! simple tabulation hash value is replaced by its image under the permutation of its
+//! position: byte `j` (counting from the least significant one) of the hash value is
+//! `permutations[j][byte j of the simple tabulation hash value]`. This costs one more
+//! lookup per output byte, but gives concentration bounds like for fully random hashing.
+//!
+//! Tabulation-1-permutation hashing only permutes the most significant byte of the simple
+//! tabulation hash value, see `Tab64Tab1Perm`. It was introduced together with
+//! tabulation-permutation by Anders Aamand, Jakob Bæk Tejs Knudsen, Mathias Bæk Tejs
+//! Knudsen and Mikkel Thorup, [Fast Hashing with Strong Concentration Bounds](https://doi.org/10.1145/3357713.3384259)
+//! (STOC 2020).
+//!
+//! Tables are drawn like for the simple tabulation types, followed by the permutations:
+//! each permutation starts as the identity, and for `i` from 255 down to 1, entry `i` is
+//! swapped with entry `(next_u32 * (i + 1)) >> 32` (a Fisher-Yates shuffle).
+//!
+//! The id is computed like for the other hash functions, with the entries of the
+//! permutations (zero-extended to 64-bit words) following the entries of the table.
+//! `to_vec` returns the columns of the table followed by the permutations,
+//! with their entries widened to the entry type of the table.
+#[cfg(feature = "getrandom")]
+use crate::entropy::OsEntropy;
+use crate::splitmix::SplitMix64;
+use crate::xoshiro::Xoshiro256StarStar;
+#[cfg(feature = "getrandom")]
+use crate::{os_seeded_rng, EntropyError};
+use crate::{
+    EntropySource, Hash32Fn, Hash64Fn, Tab32Simple, Tab64Simple, TabHash, TableEntry, TableWords,
+};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "rand")]
+use rand::distributions::{Distribution, Standard};
+#[cfg(feature = "rand")]
+use rand::Rng;
+use rand_chacha::ChaCha20Rng;
+use rand_core::{RngCore, SeedableRng};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize, Serializer};
+
+/// Draw `N` random permutations of the bytes with Fisher-Yates shuffles
+fn permutations_from_rng<R: RngCore + ?Sized, const N: usize>(rng: &mut R) -> [[u8; 256]; N] {
+    let mut permutations = [[0; 256]; N];
+    for permutation in permutations.iter_mut() {
+        for (i, entry) in permutation.iter_mut().enumerate() {
+            *entry = i as u8;
+        }
+        for i in (1..256).rev() {
+            let j = ((u64::from(rng.next_u32()) * (i as u64 + 1)) >> 32) as usize;
+            permutation.swap(i, j);
+        }
+    }
+    permutations
+}
+
+/// Check that every column is a permutation of the bytes
+fn is_permutation(column: &[u8]) -> bool {
+    let mut seen = [false; 256];
+    column.len() == 256
+        && column
+            .iter()
+            .all(|b| !core::mem::replace(&mut seen[*b as usize], true))
+}
+
+/// A hash function for 32-bit integers using tabulation-permutation hashing.
+///
+/// The hash value is the simple tabulation hash value of `Tab32Simple` with a random
+/// permutation applied to each of its 4 bytes, see the module documentation.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::Tab32TabPerm;
+///
+/// let keys = vec![0, 8, 15, 47, 11];
+/// let tabperm = Tab32TabPerm::new_with_seed(42);
+/// for k in keys {
+///     println!("{}", tabperm.hash(k));
+/// }
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawTab32TabPerm"))]
+pub struct Tab32TabPerm {
+    simple: Tab32Simple,
+    permutations: [[u8; 256]; 4],
+}
+
+impl Tab32TabPerm {
+    /// Create a new tabulation-permutation hash function with a random table and permutations.
+    #[cfg(feature = "getrandom")]
+    pub fn new() -> Self {
+        Tab32TabPerm::from_rng(&mut os_seeded_rng())
+    }
+
+    /// Create a new tabulation-permutation hash function seeded from an entropy source.
+    ///
+    /// A 256-bit seed is read from `source` and expanded using a xoshiro256** generator,
+    /// just like `new` does with the entropy source of the operating system.
+    pub fn new_from_entropy<E: EntropySource + ?Sized>(source: &mut E) -> Result<Self, E::Error> {
+        let mut rng = Xoshiro256StarStar::from_entropy(source)?;
+        Ok(Tab32TabPerm::from_rng(&mut rng))
+    }
+
+    /// Create a new tabulation-permutation hash function seeded from OS entropy.
+    ///
+    /// This is `new_from_entropy` with the entropy source of the operating system,
+    /// i.e. like `new`, but returns an error instead of panicking if there is no entropy.
+    #[cfg(feature = "getrandom")]
+    pub fn new_from_os_entropy() -> Result<Self, EntropyError> {
+        Tab32TabPerm::new_from_entropy(&mut OsEntropy)
+    }
+
+    /// Create a new tabulation-permutation hash function derived from a seed.
+    ///
+    /// The seed is expanded with SplitMix64 like for `Tab32Simple::new_with_seed`,
+    /// so the table is the table of `Tab32Simple::new_with_seed(seed)`.
+    pub fn new_with_seed(seed: u64) -> Self {
+        Tab32TabPerm::from_rng(&mut SplitMix64::new(seed))
+    }
+
+    /// Create a new tabulation-permutation hash function from a 32-byte seed.
+    ///
+    /// The seed is used as ChaCha20 key like for `Tab32Simple::from_seed_bytes`.
+    pub fn from_seed_bytes(seed: [u8; 32]) -> Self {
+        Tab32TabPerm::from_rng(&mut ChaCha20Rng::from_seed(seed))
+    }
+
+    /// Create a new tabulation-permutation hash function drawn from `rng`.
+    ///
+    /// See the module documentation for the order in which entries are drawn.
+    #[cfg(feature = "rand")]
+    pub fn new_with_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        Tab32TabPerm::from_rng(rng)
+    }
+
+    /// Draw the table and then the permutations from `rng`
+    fn from_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        let simple = Tab32Simple::from_rng(rng);
+        Tab32TabPerm {
+            simple,
+            permutations: permutations_from_rng(rng),
+        }
+    }
+
+    /// Create a new tabulation-permutation hash function with a given table and permutations.
+    ///
+    /// `permutations[j]` is applied to byte `j` of the simple tabulation hash value,
+    /// counting from the least significant byte.
+    ///
+    /// # Panics
+    /// If one of the permutations does not contain every byte exactly once.
+    pub fn with_tables(table: [[u32; 256]; 4], permutations: [[u8; 256]; 4]) -> Self {
+        assert!(
+            permutations.iter().all(|p| is_permutation(p)),
+            "the permutation tables have to contain every byte exactly once"
+        );
+        Tab32TabPerm {
+            simple: Tab32Simple::with_table(table),
+            permutations,
+        }
+    }
+
+    /// Get the table of the simple tabulation step.
+    pub const fn get_table(&self) -> [[u32; 256]; 4] {
+        self.simple.get_table()
+    }
+
+    /// Get the permutations of the bytes of the hash value.
+    pub const fn get_permutations(&self) -> [[u8; 256]; 4] {
+        self.permutations
+    }
+
+    /// Get the id of this hash function, a 64-bit digest of its table and permutations.
+    ///
+    /// See the module documentation for the algorithm.
+    pub fn id(&self) -> u64 {
+        let mut words = TableWords::default();
+        for entry in self.simple.get_table().iter().flatten() {
+            entry.for_each_word(|w| words.push(w));
+        }
+        for entry in self.permutations.iter().flatten() {
+            words.push(u64::from(*entry));
+        }
+        words.digest()
+    }
+
+    /// Export the table followed by the permutations as a nested vector of columns.
+    #[cfg(feature = "alloc")]
+    pub fn to_vec(&self) -> Vec<Vec<u32>> {
+        let mut vec = self.simple.to_vec();
+        for permutation in self.permutations.iter() {
+            vec.push(permutation.iter().map(|b| u32::from(*b)).collect());
+        }
+        vec
+    }
+
+    /// Create a new tabulation-permutation hash function from the columns of `to_vec`.
+    ///
+    /// # Panics
+    /// If there are not 4 table columns followed by 4 permutations of 256 entries each.
+    #[cfg(feature = "alloc")]
+    pub fn from_vec(mut table_data: Vec<Vec<u32>>) -> Self {
+        assert_eq!(table_data.len(), 4 + 4);
+        let mut permutations = [[0_u8; 256]; 4];
+        for (permutation, column) in permutations.iter_mut().zip(table_data.drain(4..)) {
+            assert_eq!(column.len(), 256);
+            for (entry, value) in permutation.iter_mut().zip(column) {
+                assert!(value <= 255, "permutation entries have to be bytes");
+                *entry = value as u8;
+            }
+        }
+        let simple = Tab32Simple::from_vec(table_data);
+        Tab32TabPerm::with_tables(simple.get_table(), permutations)
+    }
+
+    /// Compute the tabulation-permutation hash value of a 32bit integer number.
+    pub fn hash(&self, x: u32) -> u32 {
+        let mut bytes = self.simple.hash(x).to_le_bytes();
+        for (byte, permutation) in bytes.iter_mut().zip(self.permutations.iter()) {
+            *byte = permutation[*byte as usize];
+        }
+        u32::from_le_bytes(bytes)
+    }
+}
+
+/// Unvalidated serialized form of a `Tab32TabPerm`
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct RawTab32TabPerm {
+    simple: Tab32Simple,
+    permutations: Vec<Vec<u8>>,
+}
+
+#[cfg(feature = "serde")]
+impl core::convert::TryFrom<RawTab32TabPerm> for Tab32TabPerm {
+    type Error = &'static str;
+
+    fn try_from(raw: RawTab32TabPerm) -> Result<Self, Self::Error> {
+        if raw.permutations.len() != 4 || !raw.permutations.iter().all(|p| is_permutation(p)) {
+            return Err("expected 4 permutations of the bytes");
+        }
+        let mut permutations = [[0; 256]; 4];
+        for (permutation, column) in permutations.iter_mut().zip(raw.permutations.iter()) {
+            permutation.copy_from_slice(column);
+        }
+        Ok(Tab32TabPerm {
+            simple: raw.simple,
+            permutations,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct _VecTab32TabPerm<'a> {
+    simple: &'a Tab32Simple,
+    permutations: Vec<Vec<u8>>,
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Tab32TabPerm {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        _VecTab32TabPerm {
+            simple: &self.simple,
+            permutations: self.permutations.iter().map(|p| p.to_vec()).collect(),
+        }
+        .serialize(s)
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Distribution<Tab32TabPerm> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Tab32TabPerm {
+        Tab32TabPerm::from_rng(rng)
+    }
+}
+
+impl TabHash for Tab32TabPerm {
+    type Key = u32;
+    type Entry = u32;
+
+    fn hash(&self, key: u32) -> u32 {
+        Tab32TabPerm::hash(self, key)
+    }
+
+    #[cfg(feature = "getrandom")]
+    fn random() -> Self {
+        Tab32TabPerm::new()
+    }
+
+    fn new_with_seed(seed: u64) -> Self {
+        Tab32TabPerm::new_with_seed(seed)
+    }
+
+    fn id(&self) -> u64 {
+        Tab32TabPerm::id(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn to_vec(&self) -> Vec<Vec<u32>> {
+        Tab32TabPerm::to_vec(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn from_vec(table_data: Vec<Vec<u32>>) -> Self {
+        Tab32TabPerm::from_vec(table_data)
+    }
+}
+
+#[cfg(feature = "getrandom")]
+impl Default for Tab32TabPerm {
+    /// Create a new hash function with a random table and permutations, just like `new`.
+    fn default() -> Self {
+        Tab32TabPerm::new()
+    }
+}
+
+impl core::fmt::Debug for Tab32TabPerm {
+    /// Show the id of the hash function as fingerprint instead of all entries.
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let fingerprint = self.id();
+        f.debug_struct("Tab32TabPerm")
+            .field("fingerprint", &format_args!("{:#018x}", fingerprint))
+            .finish()
+    }
+}
+
+impl Hash32Fn for Tab32TabPerm {
+    fn hash32(&self, x: u32) -> u32 {
+        self.hash(x)
+    }
+}
+
+/// A hash function for 64-bit integers using tabulation-permutation hashing.
+///
+/// The hash value is the simple tabulation hash value of `Tab64Simple` with a random
+/// permutation applied to each of its 8 bytes, see the module documentation.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::Tab64TabPerm;
+///
+/// let keys = vec![0, 8, 15, 47, 11];
+/// let tabperm = Tab64TabPerm::new_with_seed(42);
+/// for k in keys {
+///     println!("{}", tabperm.hash(k));
+/// }
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawTab64TabPerm"))]
+pub struct Tab64TabPerm {
+    simple: Tab64Simple,
+    permutations: [[u8; 256]; 8],
+}
+
+impl Tab64TabPerm {
+    /// Create a new tabulation-permutation hash function with a random table and permutations.
+    #[cfg(feature = "getrandom")]
+    pub fn new() -> Self {
+        Tab64TabPerm::from_rng(&mut os_seeded_rng())
+    }
+
+    /// Create a new tabulation-permutation hash function seeded from an entropy source.
+    ///
+    /// A 256-bit seed is read from `source` and expanded using a xoshiro256** generator,
+    /// just like `new` does with the entropy source of the operating system.
+    pub fn new_from_entropy<E: EntropySource + ?Sized>(source: &mut E) -> Result<Self, E::Error> {
+        let mut rng = Xoshiro256StarStar::from_entropy(source)?;
+        Ok(Tab64TabPerm::from_rng(&mut rng))
+    }
+
+    /// Create a new tabulation-permutation hash function seeded from OS entropy.
+    ///
+    /// This is `new_from_entropy` with the entropy source of the operating system,
+    /// i.e. like `new`, but returns an error instead of panicking if there is no entropy.
+    #[cfg(feature = "getrandom")]
+    pub fn new_from_os_entropy() -> Result<Self, EntropyError> {
+        Tab64TabPerm::new_from_entropy(&mut OsEntropy)
+    }
+
+    /// Create a new tabulation-permutation hash function derived from a seed.
+    ///
+    /// The seed is expanded with SplitMix64 like for `Tab64Simple::new_with_seed`,
+    /// so the table is the table of `Tab64Simple::new_with_seed(seed)`.
+    pub fn new_with_seed(seed: u64) -> Self {
+        Tab64TabPerm::from_rng(&mut SplitMix64::new(seed))
+    }
+
+    /// Create a new tabulation-permutation hash function from a 32-byte seed.
+    ///
+    /// The seed is used as ChaCha20 key like for `Tab64Simple::from_seed_bytes`.
+    pub fn from_seed_bytes(seed: [u8; 32]) -> Self {
+        Tab64TabPerm::from_rng(&mut ChaCha20Rng::from_seed(seed))
+    }
+
+    /// Create a new tabulation-permutation hash function drawn from `rng`.
+    ///
+    /// See the module documentation for the order in which entries are drawn.
+    #[cfg(feature = "rand")]
+    pub fn new_with_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        Tab64TabPerm::from_rng(rng)
+    }
+
+    /// Draw the table and then the permutations from `rng`
+    fn from_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        let simple = Tab64Simple::from_rng(rng);
+        Tab64TabPerm {
+            simple,
+            permutations: permutations_from_rng(rng),
+        }
+    }
+
+    /// Create a new tabulation-permutation hash function with a given table and permutations.
+    ///
+    /// `permutations[j]` is applied to byte `j` of the simple tabulation hash value,
+    /// counting from the least significant byte.
+    ///
+    /// # Panics
+    /// If one of the permutations does not contain every byte exactly once.
+    pub fn with_tables(table: [[u64; 256]; 8], permutations: [[u8; 256]; 8]) -> Self {
+        assert!(
+            permutations.iter().all(|p| is_permutation(p)),
+            "the permutation tables have to contain every byte exactly once"
+        );
+        Tab64TabPerm {
+            simple: Tab64Simple::with_table(table),
+            permutations,
+        }
+    }
+
+    /// Get the table of the simple tabulation step.
+    pub const fn get_table(&self) -> [[u64; 256]; 8] {
+        self.simple.get_table()
+    }
+
+    /// Get the permutations of the bytes of the hash value.
+    pub const fn get_permutations(&self) -> [[u8; 256]; 8] {
+        self.permutations
+    }
+
+    /// Get the id of this hash function, a 64-bit digest of its table and permutations.
+    ///
+    /// See the module documentation for the algorithm.
+    pub fn id(&self) -> u64 {
+        let mut words = TableWords::default();
+        for entry in self.simple.get_table().iter().flatten() {
+            entry.for_each_word(|w| words.push(w));
+        }
+        for entry in self.permutations.iter().flatten() {
+            words.push(u64::from(*entry));
+        }
+        words.digest()
+    }
+
+    /// Export the table followed by the permutations as a nested vector of columns.
+    #[cfg(feature = "alloc")]
+    pub fn to_vec(&self) -> Vec<Vec<u64>> {
+        let mut vec = self.simple.to_vec();
+        for permutation in self.permutations.iter() {
+            vec.push(permutation.iter().map(|b| u64::from(*b)).collect());
+        }
+        vec
+    }
+
+    /// Create a new tabulation-permutation hash function from the columns of `to_vec`.
+    ///
+    /// # Panics
+    /// If there are not 8 table columns followed by 8 permutations of 256 entries each.
+    #[cfg(feature = "alloc")]
+    pub fn from_vec(mut table_data: Vec<Vec<u64>>) -> Self {
+        assert_eq!(table_data.len(), 8 + 8);
+        let mut permutations = [[0_u8; 256]; 8];
+        for (permutation, column) in permutations.iter_mut().zip(table_data.drain(8..)) {
+            assert_eq!(column.len(), 256);
+            for (entry, value) in permutation.iter_mut().zip(column) {
+                assert!(value <= 255, "permutation entries have to be bytes");
+                *entry = value as u8;
+            }
+        }
+        let simple = Tab64Simple::from_vec(table_data);
+        Tab64TabPerm::with_tables(simple.get_table(), permutations)
+    }
+
+    /// Compute the tabulation-permutation hash value of a 64bit integer number.
+    pub fn hash(&self, x: u64) -> u64 {
+        let mut bytes = self.simple.hash(x).to_le_bytes();
+        for (byte, permutation) in bytes.iter_mut().zip(self.permutations.iter()) {
+            *byte = permutation[*byte as usize];
+        }
+        u64::from_le_bytes(bytes)
+    }
+}
+
+/// Unvalidated serialized form of a `Tab64TabPerm`
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct RawTab64TabPerm {
+    simple: Tab64Simple,
+    permutations: Vec<Vec<u8>>,
+}
+
+#[cfg(feature = "serde")]
+impl core::convert::TryFrom<RawTab64TabPerm> for Tab64TabPerm {
+    type Error = &'static str;
+
+    fn try_from(raw: RawTab64TabPerm) -> Result<Self, Self::Error> {
+        if raw.permutations.len() != 8 || !raw.permutations.iter().all(|p| is_permutation(p)) {
+            return Err("expected 8 permutations of the bytes");
+        }
+        let mut permutations = [[0; 256]; 8];
+        for (permutation, column) in permutations.iter_mut().zip(raw.permutations.iter()) {
+            permutation.copy_from_slice(column);
+        }
+        Ok(Tab64TabPerm {
+            simple: raw.simple,
+            permutations,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct _VecTab64TabPerm<'a> {
+    simple: &'a Tab64Simple,
+    permutations: Vec<Vec<u8>>,
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Tab64TabPerm {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        _VecTab64TabPerm {
+            simple: &self.simple,
+            permutations: self.permutations.iter().map(|p| p.to_vec()).collect(),
+        }
+        .serialize(s)
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Distribution<Tab64TabPerm> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Tab64TabPerm {
+        Tab64TabPerm::from_rng(rng)
+    }
+}
+
+impl TabHash for Tab64TabPerm {
+    type Key = u64;
+    type Entry = u64;
+
+    fn hash(&self, key: u64) -> u64 {
+        Tab64TabPerm::hash(self, key)
+    }
+
+    #[cfg(feature = "getrandom")]
+    fn random() -> Self {
+        Tab64TabPerm::new()
+    }
+
+    fn new_with_seed(seed: u64) -> Self {
+        Tab64TabPerm::new_with_seed(seed)
+    }
+
+    fn id(&self) -> u64 {
+        Tab64TabPerm::id(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn to_vec(&self) -> Vec<Vec<u64>> {
+        Tab64TabPerm::to_vec(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn from_vec(table_data: Vec<Vec<u64>>) -> Self {
+        Tab64TabPerm::from_vec(table_data)
+    }
+}
+
+#[cfg(feature = "getrandom")]
+impl Default for Tab64TabPerm {
+    /// Create a new hash function with a random table and permutations, just like `new`.
+    fn default() -> Self {
+        Tab64TabPerm::new()
+    }
+}
+
+impl core::fmt::Debug for Tab64TabPerm {
+    /// Show the id of the hash function as fingerprint instead of all entries.
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let fingerprint = self.id();
+        f.debug_struct("Tab64TabPerm")
+            .field("fingerprint", &format_args!("{:#018x}", fingerprint))
+            .finish()
+    }
+}
+
+impl Hash64Fn for Tab64TabPerm {
+    fn hash64(&self, x: u64) -> u64 {
+        self.hash(x)
+    }
+}
+
+/// A hash function for 32-bit integers using tabulation-1-permutation hashing.
+///
+/// The hash value is the simple tabulation hash value of `Tab32Simple` with a random
+/// permutation applied to its most significant byte only; the lower 3 bytes are the
+/// bytes of the simple tabulation hash value. Aamand et al. show that this already
+/// gives the concentration bounds of tabulation-permutation hashing for uses that select
+/// bins with the most significant bits of the hash value, e.g. `hash_top_bits` or
+/// multiply-shift range reduction, while evaluating the hash function only costs
+/// one lookup in a 256-byte permutation more than simple tabulation.
+///
+/// Prefer it over `Tab32Twisted` when hash values are mapped to bins through their
+/// high bits: the table entries are half as wide, and the permutation stays in the
+/// L1 cache, so both evaluate about equally fast (see `cargo bench --bench tabperm`).
+/// Keep `Tab32TabPerm` where all bits of the hash value are used individually.
+///
+/// Tables are drawn like for `Tab32TabPerm`, but with a single permutation.
+/// The id and `to_vec` follow `Tab32TabPerm` as well.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::Tab32Tab1Perm;
+///
+/// let keys = vec![0, 8, 15, 47, 11];
+/// let tab1perm = Tab32Tab1Perm::new_with_seed(42);
+/// for k in keys {
+///     println!("{}", tab1perm.hash(k));
+/// }
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawTab32Tab1Perm"))]
+pub struct Tab32Tab1Perm {
+    simple: Tab32Simple,
+    permutation: [u8; 256],
+}
+
+impl Tab32Tab1Perm {
+    /// Create a new tabulation-1-permutation hash function with a random table and permutation.
+    #[cfg(feature = "getrandom")]
+    pub fn new() -> Self {
+        Tab32Tab1Perm::from_rng(&mut os_seeded_rng())
+    }
+
+    /// Create a new tabulation-1-permutation hash function seeded from an entropy source.
+    ///
+    /// A 256-bit seed is read from `source` and expanded using a xoshiro256** generator,
+    /// just like `new` does with the entropy source of the operating system.
+    pub fn new_from_entropy<E: EntropySource + ?Sized>(source: &mut E) -> Result<Self, E::Error> {
+        let mut rng = Xoshiro256StarStar::from_entropy(source)?;
+        Ok(Tab32Tab1Perm::from_rng(&mut rng))
+    }
+
+    /// Create a new tabulation-1-permutation hash function seeded from OS entropy.
+    ///
+    /// This is `new_from_entropy` with the entropy source of the operating system,
+    /// i.e. like `new`, but returns an error instead of panicking if there is no entropy.
+    #[cfg(feature = "getrandom")]
+    pub fn new_from_os_entropy() -> Result<Self, EntropyError> {
+        Tab32Tab1Perm::new_from_entropy(&mut OsEntropy)
+    }
+
+    /// Create a new tabulation-1-permutation hash function derived from a seed.
+    ///
+    /// The seed is expanded with SplitMix64 like for `Tab32Simple::new_with_seed`,
+    /// so the table is the table of `Tab32Simple::new_with_seed(seed)`.
+    pub fn new_with_seed(seed: u64) -> Self {
+        Tab32Tab1Perm::from_rng(&mut SplitMix64::new(seed))
+    }
+
+    /// Create a new tabulation-1-permutation hash function from a 32-byte seed.
+    ///
+    /// The seed is used as ChaCha20 key like for `Tab32Simple::from_seed_bytes`.
+    pub fn from_seed_bytes(seed: [u8; 32]) -> Self {
+        Tab32Tab1Perm::from_rng(&mut ChaCha20Rng::from_seed(seed))
+    }
+
+    /// Create a new tabulation-1-permutation hash function drawn from `rng`.
+    ///
+    /// See the module documentation for the order in which entries are drawn.
+    #[cfg(feature = "rand")]
+    pub fn new_with_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        Tab32Tab1Perm::from_rng(rng)
+    }
+
+    /// Draw the table and then the permutation from `rng`
+    fn from_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        let simple = Tab32Simple::from_rng(rng);
+        let [permutation] = permutations_from_rng(rng);
+        Tab32Tab1Perm {
+            simple,
+            permutation,
+        }
+    }
+
+    /// Create a new tabulation-1-permutation hash function with a given table and permutation.
+    ///
+    /// The permutation is applied to the most significant byte of the simple
+    /// tabulation hash value.
+    ///
+    /// # Panics
+    /// If the permutation does not contain every byte exactly once.
+    pub fn with_tables(table: [[u32; 256]; 4], permutation: [u8; 256]) -> Self {
+        assert!(
+            is_permutation(&permutation),
+            "the permutation tables have to contain every byte exactly once"
+        );
+        Tab32Tab1Perm {
+            simple: Tab32Simple::with_table(table),
+            permutation,
+        }
+    }
+
+    /// Get the table of the simple tabulation step.
+    pub const fn get_table(&self) -> [[u32; 256]; 4] {
+        self.simple.get_table()
+    }
+
+    /// Get the permutation of the most significant byte of the hash value.
+    pub const fn get_permutation(&self) -> [u8; 256] {
+        self.permutation
+    }
+
+    /// Get the id of this hash function, a 64-bit digest of its table and permutation.
+    ///
+    /// See the module documentation for the algorithm.
+    pub fn id(&self) -> u64 {
+        let mut words = TableWords::default();
+        for entry in self.simple.get_table().iter().flatten() {
+            entry.for_each_word(|w| words.push(w));
+        }
+        for entry in self.permutation.iter() {
+            words.push(u64::from(*entry));
+        }
+        words.digest()
+    }
+
+    /// Export the table followed by the permutation as a nested vector of columns.
+    #[cfg(feature = "alloc")]
+    pub fn to_vec(&self) -> Vec<Vec<u32>> {
+        let mut vec = self.simple.to_vec();
+        vec.push(self.permutation.iter().map(|b| u32::from(*b)).collect());
+        vec
+    }
+
+    /// Create a new tabulation-1-permutation hash function from the columns of `to_vec`.
+    ///
+    /// # Panics
+    /// If there are not 4 table columns followed by a permutation of 256 entries each.
+    #[cfg(feature = "alloc")]
+    pub fn from_vec(mut table_data: Vec<Vec<u32>>) -> Self {
+        assert_eq!(table_data.len(), 4 + 1);
+        let mut permutation = [0_u8; 256];
+        let column = table_data.pop().unwrap_or_default();
+        assert_eq!(column.len(), 256);
+        for (entry, value) in permutation.iter_mut().zip(column) {
+            assert!(value <= 255, "permutation entries have to be bytes");
+            *entry = value as u8;
+        }
+        let simple = Tab32Simple::from_vec(table_data);
+        Tab32Tab1Perm::with_tables(simple.get_table(), permutation)
+    }
+
+    /// Compute the tabulation-1-permutation hash value of a 32bit integer number.
+    pub fn hash(&self, x: u32) -> u32 {
+        let h = self.simple.hash(x);
+        let top = u32::from(self.permutation[(h >> 24) as usize]);
+        (top << 24) | (h & 0x00ff_ffff)
+    }
+}
+
+/// Unvalidated serialized form of a `Tab32Tab1Perm`
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct RawTab32Tab1Perm {
+    simple: Tab32Simple,
+    permutation: Vec<u8>,
+}
+
+#[cfg(feature = "serde")]
+impl core::convert::TryFrom<RawTab32Tab1Perm> for Tab32Tab1Perm {
+    type Error = &'static str;
+
+    fn try_from(raw: RawTab32Tab1Perm) -> Result<Self, Self::Error> {
+        if !is_permutation(&raw.permutation) {
+            return Err("expected a permutation of the bytes");
+        }
+        let mut permutation = [0; 256];
+        permutation.copy_from_slice(&raw.permutation);
+        Ok(Tab32Tab1Perm {
+            simple: raw.simple,
+            permutation,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct _VecTab32Tab1Perm<'a> {
+    simple: &'a Tab32Simple,
+    permutation: &'a [u8],
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Tab32Tab1Perm {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        _VecTab32Tab1Perm {
+            simple: &self.simple,
+            permutation: &self.permutation,
+        }
+        .serialize(s)
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Distribution<Tab32Tab1Perm> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Tab32Tab1Perm {
+        Tab32Tab1Perm::from_rng(rng)
+    }
+}
+
+impl TabHash for Tab32Tab1Perm {
+    type Key = u32;
+    type Entry = u32;
+
+    fn hash(&self, key: u32) -> u32 {
+        Tab32Tab1Perm::hash(self, key)
+    }
+
+    #[cfg(feature = "getrandom")]
+    fn random() -> Self {
+        Tab32Tab1Perm::new()
+    }
+
+    fn new_with_seed(seed: u64) -> Self {
+        Tab32Tab1Perm::new_with_seed(seed)
+    }
+
+    fn id(&self) -> u64 {
+        Tab32Tab1Perm::id(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn to_vec(&self) -> Vec<Vec<u32>> {
+        Tab32Tab1Perm::to_vec(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn from_vec(table_data: Vec<Vec<u32>>) -> Self {
+        Tab32Tab1Perm::from_vec(table_data)
+    }
+}
+
+#[cfg(feature = "getrandom")]
+impl Default for Tab32Tab1Perm {
+    /// Create a new hash function with a random table and permutation, just like `new`.
+    fn default() -> Self {
+        Tab32Tab1Perm::new()
+    }
+}
+
+impl core::fmt::Debug for Tab32Tab1Perm {
+    /// Show the id of the hash function as fingerprint instead of all entries.
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let fingerprint = self.id();
+        f.debug_struct("Tab32Tab1Perm")
+            .field("fingerprint", &format_args!("{:#018x}", fingerprint))
+            .finish()
+    }
+}
+
+impl Hash32Fn for Tab32Tab1Perm {
+    fn hash32(&self, x: u32) -> u32 {
+        self.hash(x)
+    }
+}
+
+/// A hash function for 64-bit integers using tabulation-1-permutation hashing.
+///
+/// The hash value is the simple tabulation hash value of `Tab64Simple` with a random
+/// permutation applied to its most significant byte only; the lower 7 bytes are the
+/// bytes of the simple tabulation hash value. Aamand et al. show that this already
+/// gives the concentration bounds of tabulation-permutation hashing for uses that select
+/// bins with the most significant bits of the hash value, e.g. `hash_top_bits` or
+/// multiply-shift range reduction, while evaluating the hash function only costs
+/// one lookup in a 256-byte permutation more than simple tabulation.
+///
+/// Prefer it over `Tab64Twisted` when hash values are mapped to bins through their
+/// high bits: the table entries are half as wide, and the permutation stays in the
+/// L1 cache, so both evaluate about equally fast (see `cargo bench --bench tabperm`).
+/// Keep `Tab64TabPerm` where all bits of the hash value are used individually.
+///
+/// Tables are drawn like for `Tab64TabPerm`, but with a single permutation.
+/// The id and `to_vec` follow `Tab64TabPerm` as well.
+///
+/// Usage:
+/// ```rust
+/// use tab_hash::Tab64Tab1Perm;
+///
+/// let keys = vec![0, 8, 15, 47, 11];
+/// let tab1perm = Tab64Tab1Perm::new_with_seed(42);
+/// for k in keys {
+///     println!("{}", tab1perm.hash(k));
+/// }
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawTab64Tab1Perm"))]
+pub struct Tab64Tab1Perm {
+    simple: Tab64Simple,
+    permutation: [u8; 256],
+}
+
+impl Tab64Tab1Perm {
+    /// Create a new tabulation-1-permutation hash function with a random table and permutation.
+    #[cfg(feature = "getrandom")]
+    pub fn new() -> Self {
+        Tab64Tab1Perm::from_rng(&mut os_seeded_rng())
+    }
+
+    /// Create a new tabulation-1-permutation hash function seeded from an entropy source.
+    ///
+    /// A 256-bit seed is read from `source` and expanded using a xoshiro256** generator,
+    /// just like `new` does with the entropy source of the operating system.
+    pub fn new_from_entropy<E: EntropySource + ?Sized>(source: &mut E) -> Result<Self, E::Error> {
+        let mut rng = Xoshiro256StarStar::from_entropy(source)?;
+        Ok(Tab64Tab1Perm::from_rng(&mut rng))
+    }
+
+    /// Create a new tabulation-1-permutation hash function seeded from OS entropy.
+    ///
+    /// This is `new_from_entropy` with the entropy source of the operating system,
+    /// i.e. like `new`, but returns an error instead of panicking if there is no entropy.
+    #[cfg(feature = "getrandom")]
+    pub fn new_from_os_entropy() -> Result<Self, EntropyError> {
+        Tab64Tab1Perm::new_from_entropy(&mut OsEntropy)
+    }
+
+    /// Create a new tabulation-1-permutation hash function derived from a seed.
+    ///
+    /// The seed is expanded with SplitMix64 like for `Tab64Simple::new_with_seed`,
+    /// so the table is the table of `Tab64Simple::new_with_seed(seed)`.
+    pub fn new_with_seed(seed: u64) -> Self {
+        Tab64Tab1Perm::from_rng(&mut SplitMix64::new(seed))
+    }
+
+    /// Create a new tabulation-1-permutation hash function from a 32-byte seed.
+    ///
+    /// The seed is used as ChaCha20 key like for `Tab64Simple::from_seed_bytes`.
+    pub fn from_seed_bytes(seed: [u8; 32]) -> Self {
+        Tab64Tab1Perm::from_rng(&mut ChaCha20Rng::from_seed(seed))
+    }
+
+    /// Create a new tabulation-1-permutation hash function drawn from `rng`.
+    ///
+    /// See the module documentation for the order in which entries are drawn.
+    #[cfg(feature = "rand")]
+    pub fn new_with_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        Tab64Tab1Perm::from_rng(rng)
+    }
+
+    /// Draw the table and then the permutation from `rng`
+    fn from_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        let simple = Tab64Simple::from_rng(rng);
+        let [permutation] = permutations_from_rng(rng);
+        Tab64Tab1Perm {
+            simple,
+            permutation,
+        }
+    }
+
+    /// Create a new tabulation-1-permutation hash function with a given table and permutation.
+    ///
+    /// The permutation is applied to the most significant byte of the simple
+    /// tabulation hash value.
+    ///
+    /// # Panics
+    /// If the permutation does not contain every byte exactly once.
+    pub fn with_tables(table: [[u64; 256]; 8], permutation: [u8; 256]) -> Self {
+        assert!(
+            is_permutation(&permutation),
+            "the permutation tables have to contain every byte exactly once"
+        );
+        Tab64Tab1Perm {
+            simple: Tab64Simple::with_table(table),
+            permutation,
+        }
+    }
+
+    /// Get the table of the simple tabulation step.
+    pub const fn get_table(&self) -> [[u64; 256]; 8] {
+        self.simple.get_table()
+    }
+
+    /// Get the permutation of the most significant byte of the hash value.
+    pub const fn get_permutation(&self) -> [u8; 256] {
+        self.permutation
+    }
+
+    /// Get the id of this hash function, a 64-bit digest of its table and permutation.
+    ///
+    /// See the module documentation for the algorithm.
+    pub fn id(&self) -> u64 {
+        let mut words = TableWords::default();
+        for entry in self.simple.get_table().iter().flatten() {
+            entry.for_each_word(|w| words.push(w));
+        }
+        for entry in self.permutation.iter() {
+            words.push(u64::from(*entry));
+        }
+        words.digest()
+    }
+
+    /// Export the table followed by the permutation as a nested vector of columns.
+    #[cfg(feature = "alloc")]
+    pub fn to_vec(&self) -> Vec<Vec<u64>> {
+        let mut vec = self.simple.to_vec();
+        vec.push(self.permutation.iter().map(|b| u64::from(*b)).collect());
+        vec
+    }
+
+    /// Create a new tabulation-1-permutation hash function from the columns of `to_vec`.
+    ///
+    /// # Panics
+    /// If there are not 8 table columns followed by a permutation of 256 entries each.
+    #[cfg(feature = "alloc")]
+    pub fn from_vec(mut table_data: Vec<Vec<u64>>) -> Self {
+        assert_eq!(table_data.len(), 8 + 1);
+        let mut permutation = [0_u8; 256];
+        let column = table_data.pop().unwrap_or_default();
+        assert_eq!(column.len(), 256);
+        for (entry, value) in permutation.iter_mut().zip(column) {
+            assert!(value <= 255, "permutation entries have to be bytes");
+            *entry = value as u8;
+        }
+        let simple = Tab64Simple::from_vec(table_data);
+        Tab64Tab1Perm::with_tables(simple.get_table(), permutation)
+    }
+
+    /// Compute the tabulation-1-permutation hash value of a 64bit integer number.
+    pub fn hash(&self, x: u64) -> u64 {
+        let h = self.simple.hash(x);
+        let top = u64::from(self.permutation[(h >> 56) as usize]);
+        (top << 56) | (h & 0x00ff_ffff_ffff_ffff)
+    }
+}
+
+/// Unvalidated serialized form of a `Tab64Tab1Perm`
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct RawTab64Tab1Perm {
+    simple: Tab64Simple,
+    permutation: Vec<u8>,
+}
+
+#[cfg(feature = "serde")]
+impl core::convert::TryFrom<RawTab64Tab1Perm> for Tab64Tab1Perm {
+    type Error = &'static str;
+
+    fn try_from(raw: RawTab64Tab1Perm) -> Result<Self, Self::Error> {
+        if !is_permutation(&raw.permutation) {
+            return Err("expected a permutation of the bytes");
+        }
+        let mut permutation = [0; 256];
+        permutation.copy_from_slice(&raw.permutation);
+        Ok(Tab64Tab1Perm {
+            simple: raw.simple,
+            permutation,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct _VecTab64Tab1Perm<'a> {
+    simple: &'a Tab64Simple,
+    permutation: &'a [u8],
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Tab64Tab1Perm {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        _VecTab64Tab1Perm {
+            simple: &self.simple,
+            permutation: &self.permutation,
+        }
+        .serialize(s)
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Distribution<Tab64Tab1Perm> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Tab64Tab1Perm {
+        Tab64Tab1Perm::from_rng(rng)
+    }
+}
+
+impl TabHash for Tab64Tab1Perm {
+    type Key = u64;
+    type Entry = u64;
+
+    fn hash(&self, key: u64) -> u64 {
+        Tab64Tab1Perm::hash(self, key)
+    }
+
+    #[cfg(feature = "getrandom")]
+    fn random() -> Self {
+        Tab64Tab1Perm::new()
+    }
+
+    fn new_with_seed(seed: u64) -> Self {
+        Tab64Tab1Perm::new_with_seed(seed)
+    }
+
+    fn id(&self) -> u64 {
+        Tab64Tab1Perm::id(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn to_vec(&self) -> Vec<Vec<u64>> {
+        Tab64Tab1Perm::to_vec(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn from_vec(table_data: Vec<Vec<u64>>) -> Self {
+        Tab64Tab1Perm::from_vec(table_data)
+    }
+}
+
+#[cfg(feature = "getrandom")]
+impl Default for Tab64Tab1Perm {
+    /// Create a new hash function with a random table and permutation, just like `new`.
+    fn default() -> Self {
+        Tab64Tab1Perm::new()
+    }
+}
+
+impl core::fmt::Debug for Tab64Tab1Perm {
+    /// Show the id of the hash function as fingerprint instead of all entries.
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let fingerprint = self.id();
+        f.debug_struct("Tab64Tab1Perm")
+            .field("fingerprint", &format_args!("{:#018x}", fingerprint))
+            .finish()
+    }
+}
+
+impl Hash64Fn for Tab64Tab1Perm {
+    fn hash64(&self, x: u64) -> u64 {
+        self.hash(x)
+    }
+}
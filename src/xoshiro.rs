@@ -0,0 +1,90 @@
+//! Xoshiro256** generator used to expand entropy into random tables.
+//!
+//! This follows the reference implementation by David Blackman and
+//! Sebastiano Vigna (<https://prng.di.unimi.it/xoshiro256starstar.c>).
+use crate::splitmix::SplitMix64;
+use crate::EntropySource;
+use rand_core::{Error, RngCore};
+
+/// A Xoshiro256** pseudo random number generator
+pub(crate) struct Xoshiro256StarStar {
+    state: [u64; 4],
+}
+
+impl Xoshiro256StarStar {
+    /// Create a new generator from a 256-bit seed given as little-endian bytes.
+    pub(crate) fn from_seed(seed: [u8; 32]) -> Self {
+        let mut state = [0_u64; 4];
+        for (word, bytes) in state.iter_mut().zip(seed.chunks(8)) {
+            let mut buf = [0; 8];
+            buf.copy_from_slice(bytes);
+            *word = u64::from_le_bytes(buf);
+        }
+        // the all-zero state is a fixed point of the generator
+        if state == [0; 4] {
+            let mut seeder = SplitMix64::new(0);
+            for word in state.iter_mut() {
+                *word = seeder.next_u64();
+            }
+        }
+        Xoshiro256StarStar { state }
+    }
+
+    /// Create a new generator with a 256-bit seed taken from an entropy source.
+    pub(crate) fn from_entropy<E: EntropySource + ?Sized>(
+        source: &mut E,
+    ) -> Result<Self, E::Error> {
+        let mut seed = [0_u8; 32];
+        source.fill_bytes(&mut seed)?;
+        Ok(Xoshiro256StarStar::from_seed(seed))
+    }
+}
+
+impl RngCore for Xoshiro256StarStar {
+    /// Draw the high 32 bits of the next 64-bit word.
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let s = &mut self.state;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        rand_core::impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[test]
+fn xoshiro_reference_values() {
+    // outputs of the reference implementation for the state {1, 2, 3, 4}
+    let mut seed = [0_u8; 32];
+    for (i, bytes) in seed.chunks_mut(8).enumerate() {
+        bytes.copy_from_slice(&(i as u64 + 1).to_le_bytes());
+    }
+    let mut rng = Xoshiro256StarStar::from_seed(seed);
+    assert_eq!(rng.next_u64(), 11520);
+    assert_eq!(rng.next_u64(), 0);
+    assert_eq!(rng.next_u64(), 1_509_978_240);
+    assert_eq!(rng.next_u64(), 1_215_971_899_390_074_240);
+}
+
+#[test]
+fn xoshiro_zero_seed() {
+    let mut rng = Xoshiro256StarStar::from_seed([0; 32]);
+    assert!((0..4).any(|_| rng.next_u64() != 0));
+}
@@ -0,0 +1,163 @@
+//! Derive macro for the `TabKey` trait of the `tab-hash` crate.
+//!
+//! Use it through the `derive` feature of `tab-hash`:
+//!
+//! ```rust
+//! use tab_hash::{Tab64Simple, TabKey};
+//!
+//! #[derive(TabKey)]
+//! struct Flow {
+//!     src: u32,
+//!     dst: u32,
+//! }
+//!
+//! let simple = Tab64Simple::new_with_seed(42);
+//! let flow = Flow { src: 1, dst: 2 };
+//! assert_eq!(simple.hash_key(&flow), simple.hash(0x0000_0002_0000_0001));
+//! ```
+//!
+//! The key is split into the little-endian bytes of all fields in declaration order,
+//! i.e. the first byte of the first field is looked up in column 0 of the table.
+//! All fields have to be fixed-width integers (`u8` to `u128`, `i8` to `i128`).
+//! Fields marked with `#[tab_key(skip)]` are excluded:
+//!
+//! ```rust
+//! use tab_hash::{Tab32Simple, TabKey};
+//!
+//! #[derive(TabKey)]
+//! struct Entry {
+//!     id: u32,
+//!     #[tab_key(skip)]
+//!     label: String,
+//! }
+//!
+//! let simple = Tab32Simple::new_with_seed(42);
+//! let entry = Entry { id: 1337, label: String::from("leet") };
+//! assert_eq!(simple.hash_key(&entry), simple.hash(1337));
+//! ```
+//!
+//! Other field types are rejected:
+//!
+//! ```compile_fail
+//! use tab_hash::TabKey;
+//!
+//! #[derive(TabKey)]
+//! struct Entry {
+//!     id: usize,
+//! }
+//! ```
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Field, Fields, Index, Type};
+
+/// Derive `TabKey` for structs with fixed-width integer fields.
+///
+/// See the crate documentation for the generated byte layout.
+#[proc_macro_derive(TabKey, attributes(tab_key))]
+pub fn derive_tab_key(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Generate the `TabKey` implementation or a descriptive error
+fn expand(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                "TabKey can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut width = 0_usize;
+    let mut copies = Vec::new();
+    for (i, field) in fields.iter().enumerate() {
+        if is_skipped(field)? {
+            continue;
+        }
+        let bytes = integer_width(&field.ty).ok_or_else(|| {
+            Error::new(
+                field.ty.span(),
+                "TabKey fields must be fixed-width integers (u8 to u128 or i8 to i128), \
+                 use #[tab_key(skip)] to exclude other fields",
+            )
+        })?;
+        let access = match (&field.ident, fields) {
+            (Some(ident), Fields::Named(_)) => quote!(self.#ident),
+            _ => {
+                let index = Index::from(i);
+                quote!(self.#index)
+            }
+        };
+        let end = width + bytes;
+        copies.push(quote! {
+            chunks[#width..#end].copy_from_slice(&#access.to_le_bytes());
+        });
+        width = end;
+    }
+    if width == 0 {
+        return Err(Error::new(
+            input.ident.span(),
+            "TabKey requires at least one integer field",
+        ));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::tab_hash::TabKey for #name #ty_generics #where_clause {
+            type Chunks = [u8; #width];
+
+            fn chunks(&self) -> [u8; #width] {
+                let mut chunks = [0_u8; #width];
+                #(#copies)*
+                chunks
+            }
+        }
+    })
+}
+
+/// Check whether a field is marked with `#[tab_key(skip)]`
+fn is_skipped(field: &Field) -> Result<bool, Error> {
+    let mut skip = false;
+    for attr in field.attrs.iter() {
+        if !attr.path().is_ident("tab_key") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                skip = true;
+                Ok(())
+            } else {
+                Err(meta.error("unknown tab_key attribute, expected `skip`"))
+            }
+        })?;
+    }
+    Ok(skip)
+}
+
+/// Get the width in bytes of a fixed-width integer type
+fn integer_width(ty: &Type) -> Option<usize> {
+    let path = match ty {
+        Type::Path(path) if path.qself.is_none() => &path.path,
+        _ => return None,
+    };
+    let ident = path.get_ident()?.to_string();
+    match ident.as_str() {
+        "u8" | "i8" => Some(1),
+        "u16" | "i16" => Some(2),
+        "u32" | "i32" => Some(4),
+        "u64" | "i64" => Some(8),
+        "u128" | "i128" => Some(16),
+        _ => None,
+    }
+}
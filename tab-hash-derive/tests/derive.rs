@@ -0,0 +1,47 @@
+use tab_hash::{Tab32Simple, Tab64Simple, Tab64Twisted, TabKey};
+
+#[derive(TabKey)]
+struct Flow {
+    src: u32,
+    dst: u32,
+}
+
+#[derive(TabKey)]
+struct Packed(u16, #[tab_key(skip)] String, i8, u8);
+
+/// A table with entry `j` of column `i` set to `j << (8 * i)`, i.e. hashing is the identity
+fn identity_table() -> [[u64; 256]; 8] {
+    let mut table = [[0; 256]; 8];
+    for (i, column) in table.iter_mut().enumerate() {
+        for (j, entry) in column.iter_mut().enumerate() {
+            *entry = (j as u64) << (8 * i);
+        }
+    }
+    table
+}
+
+#[test]
+fn two_field_layout() {
+    let flow = Flow {
+        src: 0x0403_0201,
+        dst: 0x0807_0605,
+    };
+    assert_eq!(flow.chunks(), [1, 2, 3, 4, 5, 6, 7, 8]);
+
+    // byte i of the key is looked up in column i
+    let simple = Tab64Simple::with_table(identity_table());
+    assert_eq!(simple.hash_key(&flow), 0x0807_0605_0403_0201);
+    assert_eq!(simple.hash_key(&flow), simple.hash(0x0807_0605_0403_0201));
+
+    let twisted = Tab64Twisted::new_with_seed(42);
+    assert_eq!(twisted.hash_key(flow), twisted.hash(0x0807_0605_0403_0201));
+}
+
+#[test]
+fn skipped_and_signed_fields() {
+    let key = Packed(0x0201, String::from("ignored"), -1, 4);
+    assert_eq!(key.chunks(), [1, 2, 0xFF, 4]);
+    assert_eq!(key.1, "ignored");
+    let simple = Tab32Simple::new_with_seed(42);
+    assert_eq!(simple.hash_key(&key), simple.hash(0x04FF_0201));
+}
@@ -1,6 +1,22 @@
-use array_init;
-use rand;
-use tab_hash::{Tab32Simple, Tab32Twisted, Tab64Simple, Tab64Twisted};
+// without `std`, the tests using random hash functions and their helpers are left out
+#![cfg_attr(not(feature = "std"), allow(dead_code, unused_imports))]
+use std::convert::TryInto;
+#[cfg(feature = "std")]
+use tab_hash::Icws;
+use tab_hash::{
+    hamming_distance, Mixer32, Mixer64, MultisetFingerprint, NoNodesError, Partitioner, Perm32,
+    PermDomain, PrefixHasher, RateError, StableSampler, Tab128Simple, Tab16Simple, Tab32Double,
+    Tab32Nibble, Tab32Pair, Tab32Simple, Tab32Tab1Perm, Tab32TabPerm, Tab32To64Simple,
+    Tab32To64Twisted, Tab32Twisted, Tab48Simple, Tab64Premixed, Tab64Simple, Tab64Tab1Perm,
+    Tab64TabPerm, Tab64To32Simple, Tab64Twisted, TabBytes, TabBytesTwisted, TabHash,
+    TabHashIterExt, TabRng, TabSimple, TabTwisted,
+};
+#[cfg(feature = "alloc")]
+use tab_hash::{
+    lsh_bands, lsh_candidates, AmsSketch, BottomK, ConsistentRing, CountMinSketch, HyperLogLog,
+    MergeError, MinHash, OnePermMinHash, RingError, Tab128Twisted, Tab32Simple16, Tab64Double,
+    Tab64Simple16, TabPoly64,
+};
 
 extern "C" {
     fn SimpleTab32(x: u32, H: &[[u32; 256]; 4]) -> u32;
@@ -10,6 +26,7 @@ extern "C" {
 }
 
 #[test]
+#[cfg(feature = "std")]
 fn simple32_vs_reference_implementation() {
     for _ in 0..100 {
         let simple_tabhash = Tab32Simple::new();
@@ -26,6 +43,7 @@ fn simple32_vs_reference_implementation() {
 }
 
 #[test]
+#[cfg(feature = "std")]
 fn twisted32_vs_reference_implementation() {
     for _ in 0..10 {
         let twisted_tabhash = Tab32Twisted::new();
@@ -43,6 +61,7 @@ fn twisted32_vs_reference_implementation() {
 }
 
 #[test]
+#[cfg(feature = "std")]
 fn simple64_vs_reference_implementation() {
     for _ in 0..100 {
         let simple_tabhash = Tab64Simple::new();
@@ -59,6 +78,7 @@ fn simple64_vs_reference_implementation() {
 }
 
 #[test]
+#[cfg(feature = "std")]
 fn twisted64_vs_reference_implementation() {
     for _ in 0..10 {
         let twisted_tabhash = Tab64Twisted::new();
@@ -103,6 +123,108 @@ fn simple_vs_fixed_value() {
     assert_eq!(simple_tabhash.hash(key), result);
 }
 
+#[test]
+fn simple16_vs_fixed_value() {
+    // chunks      1        0
+    let key = 0b_00000010_00000001;
+
+    // assemble table for testing
+    let mut byte_1 = [0; 256];
+    let mut byte_2 = [0; 256];
+    byte_1[1] = 0b_0110_0000_0000_0111;
+    byte_2[2] = 0b_0100_0000_0000_1011;
+    let table = [byte_1, byte_2];
+
+    //    0110000000000111
+    // ^  0100000000001011
+    // -------------------
+    //    0010000000001100
+    let result = 0b_0010_0000_0000_1100;
+
+    let simple_tabhash = Tab16Simple::with_table(table);
+    assert_eq!(simple_tabhash.hash(key), result);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn simple16_exhaustive() {
+    let simple_tabhash = Tab16Simple::new();
+    let restored = Tab16Simple::with_table(simple_tabhash.get_table());
+
+    let mut seen = vec![false; 1 << 16];
+    let mut bit_counts = [0_u32; 16];
+    for key in 0..=u16::MAX {
+        let h = simple_tabhash.hash(key);
+        // hashing is deterministic, also across instances sharing a table
+        assert_eq!(h, simple_tabhash.hash(key));
+        assert_eq!(h, restored.hash(key));
+        seen[h as usize] = true;
+        for (bit, count) in bit_counts.iter_mut().enumerate() {
+            *count += ((h >> bit) & 1) as u32;
+        }
+    }
+
+    // A random function hits about 63% of all values. Anything below half
+    // of that points to a degenerate table or broken chunking.
+    let distinct = seen.iter().filter(|s| **s).count();
+    assert!(distinct > 20_000, "only {} distinct hash values", distinct);
+
+    // Each output bit is the XOR of two random table bits and should hence
+    // be set for about half of the keys.
+    for count in bit_counts.iter() {
+        assert!(
+            *count > 16_384 && *count < 49_152,
+            "bit counts {:?}",
+            bit_counts
+        );
+    }
+}
+
+#[test]
+fn simple128_vs_fixed_value() {
+    // chunks 15 and 14 hold 0x02 and 0x01, chunk 0 holds 0x03, all others 0
+    let key: u128 = 0x_0201_0000_0000_0000_0000_0000_0000_0003;
+
+    // assemble table for testing
+    let mut table = [[0; 256]; 16];
+    table[0][3] = 0b_0111;
+    table[0][0] = 0b_1000; // must not be used, chunk 0 is 3
+    table[14][1] = 0b_1011 << 64;
+    table[15][2] = 0b_1101 << 120;
+    for column in table[1..14].iter_mut() {
+        column[0] = 1;
+    }
+
+    //   0b_1101 << 120
+    // ^ 0b_1011 << 64
+    // ^ 0b_0111
+    // ^ 13 * 1 (columns 1..14 all look up their entry 0)
+    let result: u128 = (0b_1101 << 120) ^ (0b_1011 << 64) ^ 0b_0111 ^ 1;
+
+    let simple_tabhash = Tab128Simple::with_table(table);
+    assert_eq!(simple_tabhash.hash(key), result);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn simple128_chunk_order_matches_64() {
+    // A 128-bit key whose upper 64 bits are zero has to be split up like
+    // the corresponding 64-bit key, given that the tables coincide.
+    let h64 = Tab64Simple::new();
+    let mut table = [[0_u128; 256]; 16];
+    for (column, column_64) in table.iter_mut().zip(h64.get_table().iter()) {
+        for (entry, entry_64) in column.iter_mut().zip(column_64.iter()) {
+            *entry = *entry_64 as u128;
+        }
+    }
+    let h128 = Tab128Simple::with_table(table);
+
+    let random_keys: [u64; 100] = array_init::array_init(|_| rand::random());
+    for key in random_keys.iter() {
+        assert_eq!(h64.hash(*key) as u128, h128.hash(*key as u128));
+    }
+}
+
 #[test]
 fn twisted_vs_fixed_value() {
     // chunks           3        2        1        0
@@ -138,9 +260,155 @@ fn twisted_vs_fixed_value() {
 
     let simple_tabhash = Tab32Twisted::with_table(table);
     assert_eq!(simple_tabhash.hash(key), result);
+    assert_eq!(simple_tabhash.hash_with_twist(key), (result, 0b101));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn twisted_hash_with_twist() {
+    let twisted32 = Tab32Twisted::new();
+    let twisted64 = Tab64Twisted::new();
+    let table32 = twisted32.get_table();
+    let table64 = twisted64.get_table();
+    for _ in 0..1000 {
+        let key: u64 = rand::random();
+        let (h, c) = twisted32.hash_with_twist(key as u32);
+        assert_eq!(h, twisted32.hash(key as u32));
+        let low = table32[0][key as u8 as usize]
+            ^ table32[1][(key >> 8) as u8 as usize]
+            ^ table32[2][(key >> 16) as u8 as usize];
+        assert_eq!(c, (key >> 24) as u8 ^ low as u8);
+
+        let (h, c) = twisted64.hash_with_twist(key);
+        assert_eq!(h, twisted64.hash(key));
+        let low = (0..7).fold(0, |acc, i| {
+            acc ^ table64[i][(key >> (8 * i)) as u8 as usize]
+        });
+        assert_eq!(c, (key >> 56) as u8 ^ low as u8);
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn twisted128_vs_fixed_value() {
+    // chunk 15 holds 0b0100, chunk 1 holds 0b0001, all other chunks are 0
+    let key: u128 = (0b_0100 << 120) | (0b_0001 << 8);
+
+    // assemble table for testing, entries are [low, high]
+    let mut table = [[[0; 2]; 256]; 16];
+    table[0][0] = [0b_0111, 0b_0010];
+    table[1][1] = [0b_1011, 0b_0100];
+    for column in table[2..15].iter_mut() {
+        column[0] = [1 << 64, 0];
+    }
+    table[15][0b_0100] = [0, 0b_1000]; // must not be used due to the twist
+    table[15][0b_0000] = [0, 0b_0001 << 127];
+
+    // XOR of the first 15 columns:
+    //   low  = 0b0111 ^ 0b1011 ^ 13 * (1 << 64) = (1 << 64) | 0b1100
+    //   high = 0b0010 ^ 0b0100                  = 0b0110
+    // c = chunks[15] ^ (low & 0xFF) = 0b_0000_0100 ^ 0b_0000_1100 = 0b_0000_1000
+    //
+    // since c = 8 and table[15][8] is all zeros, the high word is unchanged
+    let result: u128 = 0b_0110;
+    let twisted_tabhash = Tab128Twisted::with_table(table);
+    assert_eq!(twisted_tabhash.hash(key), result);
+
+    // Make the twist land on the 0 entry of the last column
+    // c = 0b_0000_1100 ^ 0b_0000_1100 = 0
+    let key: u128 = (0b_1100 << 120) | (0b_0001 << 8);
+    //   0b0110
+    // ^ 0b0001 << 127
+    let result: u128 = (0b_0001 << 127) | 0b_0110;
+    assert_eq!(twisted_tabhash.hash(key), result);
+}
+
+/// A table generated at compile time (using a linear congruential generator)
+const fn lcg_table() -> [[u32; 256]; 4] {
+    let mut table = [[0; 256]; 4];
+    let mut state: u64 = 1;
+    let mut i = 0;
+    while i < 4 * 256 {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        table[i / 256][i % 256] = (state >> 32) as u32;
+        i += 1;
+    }
+    table
+}
+
+static SIMPLE: Tab32Simple = Tab32Simple::with_table(lcg_table());
+static EMBEDDED: Tab32Simple =
+    Tab32Simple::from_le_bytes(include_bytes!("data/tab32simple_seed42.bin"));
+
+#[test]
+#[cfg(feature = "alloc")]
+fn static_hash_functions() {
+    let table = lcg_table();
+    assert_eq!(
+        SIMPLE.hash(0x0403_0201),
+        table[0][1] ^ table[1][2] ^ table[2][3] ^ table[3][4]
+    );
+
+    // the embedded table was generated from seed 42
+    let seeded = Tab32Simple::new_with_seed(42);
+    assert_eq!(EMBEDDED.to_vec(), seeded.to_vec());
+    assert_eq!(EMBEDDED.hash(0x0403_0201), 0x208c_ea92);
+    assert_eq!(
+        &seeded.to_le_bytes()[..],
+        &include_bytes!("data/tab32simple_seed42.bin")[..]
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn to_and_from_le_bytes() {
+    let h = Tab16Simple::new();
+    assert_eq!(
+        Tab16Simple::from_le_bytes(&h.to_le_bytes()).to_vec(),
+        h.to_vec()
+    );
+    let h = Tab32Simple::new();
+    assert_eq!(
+        Tab32Simple::from_le_bytes(&h.to_le_bytes()).to_vec(),
+        h.to_vec()
+    );
+    let h = Tab64Simple::new();
+    assert_eq!(
+        Tab64Simple::from_le_bytes(&h.to_le_bytes()).to_vec(),
+        h.to_vec()
+    );
+    let h = Tab128Simple::new();
+    assert_eq!(
+        Tab128Simple::from_le_bytes(&h.to_le_bytes()).to_vec(),
+        h.to_vec()
+    );
+    let h = Tab32Twisted::new();
+    assert_eq!(
+        Tab32Twisted::from_le_bytes(&h.to_le_bytes()).to_vec(),
+        h.to_vec()
+    );
+    let h = Tab64Twisted::new();
+    assert_eq!(
+        Tab64Twisted::from_le_bytes(&h.to_le_bytes()).to_vec(),
+        h.to_vec()
+    );
+    let h = Tab128Twisted::new();
+    let bytes = h.to_le_bytes();
+    let bytes: &[u8; 131072] = bytes.as_slice().try_into().unwrap();
+    assert_eq!(Tab128Twisted::from_le_bytes(bytes).to_vec(), h.to_vec());
+
+    // entries are little endian, the low half of 256-bit entries comes first
+    let mut table = [[[0_u128; 2]; 256]; 16];
+    table[0][0] = [0x0201, 0x0403];
+    let bytes = Tab128Twisted::with_table(table).to_le_bytes();
+    assert_eq!(&bytes[..2], &[1, 2]);
+    assert_eq!(&bytes[16..18], &[3, 4]);
 }
 
 #[test]
+#[cfg(feature = "std")]
 fn simple32_to_and_from_vec() {
     for _ in 0..1000 {
         let h = Tab32Simple::new();
@@ -161,6 +429,7 @@ fn simple32_to_and_from_vec() {
 }
 
 #[test]
+#[cfg(feature = "std")]
 fn twisted32_to_and_from_vec() {
     for _ in 0..1000 {
         let h = Tab32Twisted::new();
@@ -181,6 +450,7 @@ fn twisted32_to_and_from_vec() {
 }
 
 #[test]
+#[cfg(feature = "std")]
 fn simple64_to_and_from_vec() {
     for _ in 0..1000 {
         let h = Tab64Simple::new();
@@ -201,6 +471,7 @@ fn simple64_to_and_from_vec() {
 }
 
 #[test]
+#[cfg(feature = "std")]
 fn twisted64_to_and_from_vec() {
     for _ in 0..1000 {
         let h = Tab64Twisted::new();
@@ -221,6 +492,88 @@ fn twisted64_to_and_from_vec() {
 }
 
 #[test]
+#[cfg(feature = "std")]
+fn simple16_to_and_from_vec() {
+    for _ in 0..1000 {
+        let h = Tab16Simple::new();
+        let table = h.to_vec();
+        let h2 = Tab16Simple::from_vec(table);
+
+        let t1 = h.get_table();
+        let t2 = h2.get_table();
+        for column in 0..2 {
+            assert_eq!(t1[column].to_vec(), t2[column].to_vec());
+        }
+
+        let random_keys: [u16; 100] = array_init::array_init(|_| rand::random());
+        for key in random_keys.iter() {
+            assert_eq!(h.hash(*key), h2.hash(*key));
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn simple128_to_and_from_vec() {
+    for _ in 0..100 {
+        let h = Tab128Simple::new();
+        let table = h.to_vec();
+        let h2 = Tab128Simple::from_vec(table);
+
+        let t1 = h.get_table();
+        let t2 = h2.get_table();
+        for column in 0..16 {
+            assert_eq!(t1[column].to_vec(), t2[column].to_vec());
+        }
+
+        let random_keys: [u128; 100] = array_init::array_init(|_| rand::random());
+        for key in random_keys.iter() {
+            assert_eq!(h.hash(*key), h2.hash(*key));
+        }
+    }
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn simple16_serialization() {
+    for _ in 0..1000 {
+        let hf = Tab16Simple::new();
+        let serialized_hf = bincode::serialize(&hf).unwrap();
+        let deserialized_hf: Tab16Simple = bincode::deserialize(&serialized_hf).unwrap();
+
+        let t1 = hf.get_table();
+        let t2 = deserialized_hf.get_table();
+        for column in 0..2 {
+            assert_eq!(t1[column].to_vec(), t2[column].to_vec());
+        }
+
+        let random_keys: [u16; 100] = array_init::array_init(|_| rand::random());
+        for key in random_keys.iter() {
+            assert_eq!(hf.hash(*key), deserialized_hf.hash(*key));
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn twisted128_to_and_from_vec() {
+    for _ in 0..100 {
+        let h = Tab128Twisted::new();
+        let table = h.to_vec();
+        let h2 = Tab128Twisted::from_vec(table);
+
+        // compare exported tables to keep the 128 KiB arrays off the stack
+        assert_eq!(h.to_vec(), h2.to_vec());
+
+        let random_keys: [u128; 100] = array_init::array_init(|_| rand::random());
+        for key in random_keys.iter() {
+            assert_eq!(h.hash(*key), h2.hash(*key));
+        }
+    }
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
 fn simple32_serialization() {
     for _ in 0..1000 {
         let hf = Tab32Simple::new();
@@ -241,6 +594,7 @@ fn simple32_serialization() {
 }
 
 #[test]
+#[cfg(all(feature = "std", feature = "serde"))]
 fn simple64_serialization() {
     for _ in 0..1000 {
         let hf = Tab64Simple::new();
@@ -261,6 +615,28 @@ fn simple64_serialization() {
 }
 
 #[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn simple128_serialization() {
+    for _ in 0..100 {
+        let hf = Tab128Simple::new();
+        let serialized_hf = bincode::serialize(&hf).unwrap();
+        let deserialized_hf: Tab128Simple = bincode::deserialize(&serialized_hf).unwrap();
+
+        let t1 = hf.get_table();
+        let t2 = deserialized_hf.get_table();
+        for column in 0..16 {
+            assert_eq!(t1[column].to_vec(), t2[column].to_vec());
+        }
+
+        let random_keys: [u128; 100] = array_init::array_init(|_| rand::random());
+        for key in random_keys.iter() {
+            assert_eq!(hf.hash(*key), deserialized_hf.hash(*key));
+        }
+    }
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
 fn twisted32_serialization() {
     for _ in 0..1000 {
         let hf = Tab32Twisted::new();
@@ -281,6 +657,7 @@ fn twisted32_serialization() {
 }
 
 #[test]
+#[cfg(all(feature = "std", feature = "serde"))]
 fn twisted64_serialization() {
     for _ in 0..1000 {
         let hf = Tab64Twisted::new();
@@ -299,3 +676,5361 @@ fn twisted64_serialization() {
         }
     }
 }
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn twisted128_serialization() {
+    for _ in 0..100 {
+        let hf = Tab128Twisted::new();
+        let serialized_hf = bincode::serialize(&hf).unwrap();
+        let deserialized_hf: Tab128Twisted = bincode::deserialize(&serialized_hf).unwrap();
+
+        // compare exported tables to keep the 128 KiB arrays off the stack
+        assert_eq!(hf.to_vec(), deserialized_hf.to_vec());
+
+        let random_keys: [u128; 100] = array_init::array_init(|_| rand::random());
+        for key in random_keys.iter() {
+            assert_eq!(hf.hash(*key), deserialized_hf.hash(*key));
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn seeded_known_answers() {
+    // Expected values are derived from the SplitMix64 reference
+    // implementation. They must never change between releases.
+    let h = Tab16Simple::new_with_seed(42);
+    assert_eq!(h.get_table()[0][0], 0x6e95);
+    assert_eq!(h.get_table()[1][255], 0xcdbe);
+    assert_eq!(h.hash(0x3210), 0xd4e0);
+
+    let h = Tab32Simple::new_with_seed(42);
+    assert_eq!(h.get_table()[0][0], 0x2feb_6e95);
+    assert_eq!(h.get_table()[3][255], 0x1003_9789);
+    assert_eq!(h.hash(0x7654_3210), 0x4730_d38d);
+
+    let h = Tab64Simple::new_with_seed(42);
+    assert_eq!(h.get_table()[0][0], 0xbdd7_3226_2feb_6e95);
+    assert_eq!(h.get_table()[7][255], 0x4df6_cdb7_dda1_cfc7);
+    assert_eq!(h.hash(0xfedc_ba98_7654_3210), 0xfa63_69de_8469_0aad);
+
+    let h = Tab128Simple::new_with_seed(42);
+    assert_eq!(
+        h.get_table()[0][0],
+        0x28ef_e333_b266_f103_bdd7_3226_2feb_6e95
+    );
+    assert_eq!(
+        h.get_table()[15][255],
+        0xb2de_6552_cb07_2068_5019_4847_7f57_97f2
+    );
+    assert_eq!(
+        h.hash(0x0123_4567_89ab_cdef_fedc_ba98_7654_3210),
+        0xd633_962d_1d7f_8779_8937_0b94_c343_733e
+    );
+
+    let h = Tab32Twisted::new_with_seed(42);
+    assert_eq!(h.get_table()[0][0], 0xbdd7_3226_2feb_6e95);
+    assert_eq!(h.get_table()[3][255], 0xad1f_80ae_1003_9789);
+    assert_eq!(h.hash(0x7654_3210), 0xb5dc_a1f3);
+
+    let h = Tab64Twisted::new_with_seed(42);
+    assert_eq!(
+        h.get_table()[0][0],
+        0x28ef_e333_b266_f103_bdd7_3226_2feb_6e95
+    );
+    assert_eq!(
+        h.get_table()[7][255],
+        0x81e8_5e3a_6ecb_7a9a_691e_404e_40f0_73d9
+    );
+    assert_eq!(h.hash(0xfedc_ba98_7654_3210), 0x0509_9b04_4d67_3065);
+
+    let h = Tab128Twisted::new_with_seed(42);
+    let table = h.to_vec();
+    assert_eq!(
+        table[0][0],
+        [
+            0x28ef_e333_b266_f103_bdd7_3226_2feb_6e95,
+            0x581c_e1ff_0e4a_e394_4752_6757_130f_9f52
+        ]
+    );
+    assert_eq!(
+        table[15][255],
+        [
+            0xc2b6_3a76_25fe_9c7b_466a_e71b_bcbf_f67c,
+            0x5bd1_5ba6_243a_245b_793a_f6e3_1da0_598a
+        ]
+    );
+    assert_eq!(
+        h.hash(0x0123_4567_89ab_cdef_fedc_ba98_7654_3210),
+        0xd8de_e68e_dcd8_a462_5dde_a4e4_6452_7ebb
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn seeded_reproducible() {
+    let random_seeds: [u64; 10] = array_init::array_init(|_| rand::random());
+    for seed in random_seeds.iter() {
+        let h1 = Tab32Simple::new_with_seed(*seed);
+        let h2 = Tab32Simple::with_table(Tab32Simple::new_with_seed(*seed).get_table());
+        let h3 = Tab32Simple::new_with_seed(seed.wrapping_add(1));
+        let t1 = Tab32Twisted::new_with_seed(*seed);
+        let t2 = Tab32Twisted::new_with_seed(*seed);
+
+        let random_keys: [u32; 100] = array_init::array_init(|_| rand::random());
+        for key in random_keys.iter() {
+            assert_eq!(h1.hash(*key), h2.hash(*key));
+            assert_eq!(t1.hash(*key), t2.hash(*key));
+        }
+        assert_ne!(h1.to_vec(), h3.to_vec());
+    }
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "rand"))]
+fn rng_constructor_reproducible() {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let mut rng_1 = StdRng::seed_from_u64(1337);
+    let mut rng_2 = StdRng::seed_from_u64(1337);
+    for _ in 0..10 {
+        let simple_1 = Tab32Simple::new_with_rng(&mut rng_1);
+        let simple_2 = Tab32Simple::new_with_rng(&mut rng_2);
+        let twisted_1 = Tab64Twisted::new_with_rng(&mut rng_1);
+        let twisted_2 = Tab64Twisted::new_with_rng(&mut rng_2);
+        assert_eq!(simple_1.to_vec(), simple_2.to_vec());
+        assert_eq!(twisted_1.to_vec(), twisted_2.to_vec());
+
+        let random_keys: [u32; 100] = array_init::array_init(|_| rand::random());
+        for key in random_keys.iter() {
+            assert_eq!(simple_1.hash(*key), simple_2.hash(*key));
+            assert_eq!(twisted_1.hash(*key as u64), twisted_2.hash(*key as u64));
+        }
+    }
+
+    // trait objects can be used as well
+    let mut rng: Box<dyn rand::RngCore> = Box::new(StdRng::seed_from_u64(1337));
+    let simple = Tab32Simple::new_with_rng(&mut *rng);
+    let simple_1 = Tab32Simple::new_with_rng(&mut StdRng::seed_from_u64(1337));
+    assert_eq!(simple.to_vec(), simple_1.to_vec());
+}
+
+#[test]
+#[cfg(feature = "rand")]
+fn rng_constructor_draw_order() {
+    use rand::rngs::mock::StepRng;
+
+    // StepRng yields 0, 1, 2, ... for both next_u32 and next_u64
+    let h = Tab32Simple::new_with_rng(&mut StepRng::new(0, 1));
+    for (i, column) in h.get_table().iter().enumerate() {
+        for (j, entry) in column.iter().enumerate() {
+            assert_eq!(*entry, (i * 256 + j) as u32);
+        }
+    }
+
+    let h = Tab64Simple::new_with_rng(&mut StepRng::new(0, 1));
+    for (i, column) in h.get_table().iter().enumerate() {
+        for (j, entry) in column.iter().enumerate() {
+            assert_eq!(*entry, (i * 256 + j) as u64);
+        }
+    }
+
+    // 128-bit entries are assembled from two words, low word first
+    let h = Tab64Twisted::new_with_rng(&mut StepRng::new(0, 1));
+    for (i, column) in h.get_table().iter().enumerate() {
+        for (j, entry) in column.iter().enumerate() {
+            let k = 2 * (i * 256 + j) as u128;
+            assert_eq!(*entry, ((k + 1) << 64) | k);
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn seed_bytes_known_answers() {
+    // The all-zero key yields the well-known ChaCha20 keystream
+    // 76 b8 e0 ad a0 f1 3d 90 ...
+    let h = Tab32Simple::from_seed_bytes([0; 32]);
+    assert_eq!(h.get_table()[0][0], 0xade0_b876);
+    assert_eq!(h.get_table()[0][1], 0x903d_f1a0);
+    let h = Tab64Simple::from_seed_bytes([0; 32]);
+    assert_eq!(h.get_table()[0][0], 0x903d_f1a0_ade0_b876);
+
+    // Expected values for the seed 0, 1, ..., 31 were computed with an
+    // independent ChaCha20 implementation. They must never change.
+    let seed: [u8; 32] = array_init::array_init(|i| i as u8);
+
+    let h = Tab16Simple::from_seed_bytes(seed);
+    assert_eq!(h.get_table()[0][0], 0xfd39);
+    assert_eq!(h.get_table()[1][255], 0xd61f);
+    assert_eq!(h.hash(0x3210), 0xe3a7);
+
+    let h = Tab32Simple::from_seed_bytes(seed);
+    assert_eq!(h.get_table()[0][0], 0x7d2b_fd39);
+    assert_eq!(h.get_table()[3][255], 0x324b_d02f);
+    assert_eq!(h.hash(0x7654_3210), 0x0a1c_335a);
+
+    let h = Tab64Simple::from_seed_bytes(seed);
+    assert_eq!(h.get_table()[0][0], 0x6a19_c5d9_7d2b_fd39);
+    assert_eq!(h.get_table()[7][255], 0x7096_311a_9669_e0c8);
+    assert_eq!(h.hash(0xfedc_ba98_7654_3210), 0xbec6_a388_882d_69fc);
+
+    let h = Tab128Simple::from_seed_bytes(seed);
+    assert_eq!(
+        h.get_table()[0][0],
+        0x494a_dcb8_7703_bd8d_6a19_c5d9_7d2b_fd39
+    );
+    assert_eq!(
+        h.get_table()[15][255],
+        0x0f0c_c269_19b6_0ce9_c6be_4843_1a30_9012
+    );
+    assert_eq!(
+        h.hash(0x0123_4567_89ab_cdef_fedc_ba98_7654_3210),
+        0x60bf_bf4d_0cd8_35bc_39e0_fd7f_0fd0_5977
+    );
+
+    let h = Tab32Twisted::from_seed_bytes(seed);
+    assert_eq!(h.get_table()[0][0], 0x6a19_c5d9_7d2b_fd39);
+    assert_eq!(h.get_table()[3][255], 0x9043_5970_60f9_3e91);
+    assert_eq!(h.hash(0x7654_3210), 0x7e70_62fa);
+
+    let h = Tab64Twisted::from_seed_bytes(seed);
+    assert_eq!(
+        h.get_table()[0][0],
+        0x494a_dcb8_7703_bd8d_6a19_c5d9_7d2b_fd39
+    );
+    assert_eq!(
+        h.get_table()[7][255],
+        0x720d_5d29_cf9c_0de6_ea02_0ca6_a833_f18b
+    );
+    assert_eq!(h.hash(0xfedc_ba98_7654_3210), 0x34eb_e89e_06e4_ab4a);
+
+    let h = Tab128Twisted::from_seed_bytes(seed);
+    let table = h.to_vec();
+    assert_eq!(
+        table[0][0],
+        [
+            0x494a_dcb8_7703_bd8d_6a19_c5d9_7d2b_fd39,
+            0x9224_ead8_4c7d_ccb2_cc6a_debc_6fd8_358a
+        ]
+    );
+    assert_eq!(
+        table[15][255],
+        [
+            0x848a_83de_6d8b_8738_9c67_ef35_65ff_1114,
+            0x0d4e_8e1f_d76d_4c26_2bae_8e49_35bb_e359
+        ]
+    );
+    assert_eq!(
+        h.hash(0x0123_4567_89ab_cdef_fedc_ba98_7654_3210),
+        0xf870_99ea_9cab_6685_8205_fe1f_ce26_5275
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn seed_bytes_reproducible() {
+    let seed: [u8; 32] = rand::random();
+    let mut other_seed = seed;
+    other_seed[31] ^= 1;
+
+    let h1 = Tab64Simple::from_seed_bytes(seed);
+    let h2 = Tab64Simple::with_table(Tab64Simple::from_seed_bytes(seed).get_table());
+    let h3 = Tab64Simple::from_seed_bytes(other_seed);
+    assert_eq!(h1.to_vec(), h2.to_vec());
+    assert_ne!(h1.to_vec(), h3.to_vec());
+
+    let random_keys: [u64; 100] = array_init::array_init(|_| rand::random());
+    for key in random_keys.iter() {
+        assert_eq!(h1.hash(*key), h2.hash(*key));
+    }
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "rand"))]
+fn sample_from_rng() {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    let mut rng_1 = StdRng::seed_from_u64(42);
+    let mut rng_2 = StdRng::seed_from_u64(42);
+    let simple_1: Tab32Simple = rng_1.gen();
+    let simple_2: Tab32Simple = rng_2.gen();
+    let twisted_1: Tab64Twisted = rng_1.gen();
+    let twisted_2: Tab64Twisted = rng_2.gen();
+    assert_eq!(simple_1.to_vec(), simple_2.to_vec());
+    assert_eq!(twisted_1.to_vec(), twisted_2.to_vec());
+
+    // sampling is the same as constructing from the rng
+    let simple_3 = Tab32Simple::new_with_rng(&mut StdRng::seed_from_u64(42));
+    assert_eq!(simple_1.to_vec(), simple_3.to_vec());
+
+    // consecutive draws from one rng differ
+    let simple_4: Tab32Simple = rng_1.gen();
+    let twisted_3: Tab64Twisted = rng_1.gen();
+    assert_ne!(simple_1.to_vec(), simple_4.to_vec());
+    assert_ne!(twisted_1.to_vec(), twisted_3.to_vec());
+
+    // ensembles can be sampled from the iterator interface
+    let ensemble: Vec<Tab64Simple> = rng_2
+        .sample_iter(rand::distributions::Standard)
+        .take(4)
+        .collect();
+    for (i, h1) in ensemble.iter().enumerate() {
+        for h2 in ensemble[i + 1..].iter() {
+            assert_ne!(h1.to_vec(), h2.to_vec());
+        }
+    }
+}
+
+#[test]
+fn derive_child_known_answers() {
+    // Expected values were computed with an independent implementation
+    // of the derivation scheme. They must never change.
+    let child = Tab32Simple::new_with_seed(42).derive_child(7);
+    assert_eq!(child.get_table()[0][0], 0xebf3_7594);
+    assert_eq!(child.hash(0x7654_3210), 0x6c49_a734);
+
+    let child = Tab64Twisted::new_with_seed(42).derive_child(3);
+    assert_eq!(
+        child.get_table()[0][0],
+        0x2d1c_b0af_6616_15f0_a420_cdcc_7dee_20b9
+    );
+    assert_eq!(child.hash(0xfedc_ba98_7654_3210), 0xaeb8_1720_4a9e_834e);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn derive_child_distinct_and_reproducible() {
+    let parent = Tab32Simple::new();
+    let children: Vec<Tab32Simple> = (0..100).map(|i| parent.derive_child(i)).collect();
+    for (i, c1) in children.iter().enumerate() {
+        assert_ne!(c1.to_vec(), parent.to_vec());
+        for c2 in children[i + 1..].iter() {
+            assert_ne!(c1.to_vec(), c2.to_vec());
+        }
+    }
+
+    // children can be recreated from a restored parent
+    #[cfg(feature = "serde")]
+    {
+        let serialized_parent = bincode::serialize(&parent).unwrap();
+        let restored_parent: Tab32Simple = bincode::deserialize(&serialized_parent).unwrap();
+        for (i, child) in children.iter().enumerate() {
+            let restored_child = restored_parent.derive_child(i as u64);
+            assert_eq!(child.to_vec(), restored_child.to_vec());
+        }
+
+        // children are regular hash functions and can be serialized
+        let serialized_child = bincode::serialize(&children[3]).unwrap();
+        let deserialized_child: Tab32Simple = bincode::deserialize(&serialized_child).unwrap();
+        let random_keys: [u32; 100] = array_init::array_init(|_| rand::random());
+        for key in random_keys.iter() {
+            assert_eq!(children[3].hash(*key), deserialized_child.hash(*key));
+        }
+    }
+
+    // different parents have different children
+    let other_parent = Tab32Simple::new();
+    assert_ne!(
+        parent.derive_child(0).to_vec(),
+        other_parent.derive_child(0).to_vec()
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn reseeding() {
+    let random_keys: [u64; 100] = array_init::array_init(|_| rand::random());
+
+    let mut h = Tab64Twisted::new();
+    let before: Vec<u64> = random_keys.iter().map(|k| h.hash(*k)).collect();
+    h.reseed();
+    let after: Vec<u64> = random_keys.iter().map(|k| h.hash(*k)).collect();
+    assert_ne!(before, after);
+
+    // seeded reseeds are reproducible
+    let mut h1 = Tab32Simple::new();
+    let mut h2 = Tab32Simple::new();
+    h1.reseed_from_seed(1337);
+    h2.reseed_from_seed(1337);
+    assert_eq!(h1.to_vec(), h2.to_vec());
+    assert_eq!(h1.to_vec(), Tab32Simple::new_with_seed(1337).to_vec());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn reseeding_columns() {
+    let random_keys: [u32; 100] = array_init::array_init(|_| rand::random());
+
+    for i in 0..4 {
+        let old = Tab32Simple::new_with_seed(42);
+        let mut h = old.clone();
+        h.reseed_column_from_seed(i, 1337);
+        let (old_table, new_table) = (old.get_table(), h.get_table());
+
+        // only column i changes, and it matches the first seeded column
+        for j in 0..4 {
+            if j == i {
+                assert_ne!(old_table[j][..], new_table[j][..]);
+                assert_eq!(
+                    new_table[j][..],
+                    Tab32Simple::new_with_seed(1337).get_table()[0][..]
+                );
+            } else {
+                assert_eq!(old_table[j][..], new_table[j][..]);
+            }
+        }
+
+        // the change of a hash value only depends on the i-th byte of the key
+        for key in random_keys.iter() {
+            let byte = ((key >> (8 * i)) & 0xFF) as usize;
+            assert_eq!(
+                old.hash(*key) ^ h.hash(*key),
+                old_table[i][byte] ^ new_table[i][byte]
+            );
+        }
+    }
+
+    // random column reseeds of twisted hash functions
+    let mut t = Tab64Twisted::new_with_seed(42);
+    t.reseed_column(3);
+    let (old_table, new_table) = (Tab64Twisted::new_with_seed(42).to_vec(), t.to_vec());
+    for j in 0..8 {
+        if j == 3 {
+            assert_ne!(old_table[j], new_table[j]);
+        } else {
+            assert_eq!(old_table[j], new_table[j]);
+        }
+    }
+    let mut t = Tab128Twisted::new_with_seed(42);
+    t.reseed_column(15);
+    assert_ne!(
+        t.to_vec()[15],
+        Tab128Twisted::new_with_seed(42).to_vec()[15]
+    );
+}
+
+#[test]
+#[should_panic]
+fn reseeding_column_out_of_range() {
+    Tab16Simple::new_with_seed(42).reseed_column_from_seed(2, 1337);
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "rand"))]
+fn reseeding_with_rng() {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    let mut h = Tab64Simple::new_with_seed(1);
+    let old_table = h.to_vec();
+    h.reseed_with_rng(&mut StdRng::seed_from_u64(2));
+    let expected = Tab64Simple::new_with_rng(&mut StdRng::seed_from_u64(2));
+    assert_ne!(h.to_vec(), old_table);
+    assert_eq!(h.to_vec(), expected.to_vec());
+    let random_keys: [u64; 100] = array_init::array_init(|_| rand::random());
+    for key in random_keys.iter() {
+        assert_eq!(h.hash(*key), expected.hash(*key));
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn os_entropy_constructor() {
+    let h1 = Tab32Simple::new_from_os_entropy().unwrap();
+    let h2 = Tab32Simple::new_from_os_entropy().unwrap();
+    assert_ne!(h1.to_vec(), h2.to_vec());
+
+    let t1 = Tab64Twisted::new_from_os_entropy().unwrap();
+    let t2 = Tab64Twisted::new_from_os_entropy().unwrap();
+    assert_ne!(t1.to_vec(), t2.to_vec());
+
+    let t1 = Tab128Twisted::new_from_os_entropy().unwrap();
+    let t2 = Tab128Twisted::new_from_os_entropy().unwrap();
+    assert_ne!(t1.to_vec(), t2.to_vec());
+
+    // the resulting hash functions behave like any other
+    let restored = Tab32Simple::with_table(h1.get_table());
+    let random_keys: [u32; 100] = array_init::array_init(|_| rand::random());
+    for key in random_keys.iter() {
+        assert_eq!(h1.hash(*key), restored.hash(*key));
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn hash_map_adapters() {
+    use std::collections::HashMap;
+    use std::hash::BuildHasher;
+    use tab_hash::{Tab32BuildHasher, Tab64BuildHasher};
+
+    let random_keys: Vec<u32> = (0..1000).map(|_| rand::random()).collect();
+    let simple = Tab32Simple::new();
+    let mut map: HashMap<u32, usize, Tab32BuildHasher> =
+        HashMap::with_hasher(Tab32BuildHasher::from(&simple));
+    for (i, key) in random_keys.iter().enumerate() {
+        map.insert(*key, i);
+    }
+    for (i, key) in random_keys.iter().enumerate().rev() {
+        // later inserts of duplicate keys overwrite earlier ones
+        if random_keys[i + 1..].contains(key) {
+            continue;
+        }
+        assert_eq!(map.get(key), Some(&i));
+    }
+    // the map uses the tabulation hash, widened to 64 bits
+    let build_hasher = *map.hasher();
+    for key in random_keys.iter() {
+        let h = u64::from(simple.hash(*key));
+        assert_eq!(build_hasher.hash_one(key), (h << 32) | h);
+    }
+
+    let twisted = Tab32Twisted::new();
+    let build_hasher = Tab32BuildHasher::from(&twisted);
+    assert_eq!(build_hasher.hash_one(1337_u32) as u32, twisted.hash(1337));
+    assert_eq!(build_hasher.hash_one(-1_i32) as u32, twisted.hash(u32::MAX));
+
+    let random_keys: Vec<u64> = (0..1000).map(|_| rand::random()).collect();
+    let twisted = Tab64Twisted::new();
+    let mut map = HashMap::with_hasher(Tab64BuildHasher::from(&twisted));
+    for key in random_keys.iter() {
+        map.insert(*key, *key / 2);
+    }
+    for key in random_keys.iter() {
+        assert_eq!(map[key], *key / 2);
+        assert_eq!(map.hasher().hash_one(key), twisted.hash(*key));
+    }
+    let simple = Tab64Simple::new();
+    assert_eq!(
+        Tab64BuildHasher::from(&simple).hash_one(1337_u64),
+        simple.hash(1337)
+    );
+}
+
+#[test]
+#[should_panic(expected = "single u32 key")]
+fn hash_map_adapter_rejects_other_keys() {
+    use std::hash::BuildHasher;
+    use tab_hash::Tab32BuildHasher;
+    let simple = Tab32Simple::new_with_seed(42);
+    Tab32BuildHasher::from(&simple).hash_one("not an integer");
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn shared_random_state() {
+    use std::collections::HashMap;
+    use std::hash::BuildHasher;
+    use std::sync::Arc;
+    use std::thread;
+    use tab_hash::TabRandomState;
+
+    // states created with new share the global hash function
+    let state = TabRandomState::new();
+    assert!(Arc::ptr_eq(
+        state.function(),
+        TabRandomState::new().function()
+    ));
+    assert!(Arc::ptr_eq(state.function(), state.clone().function()));
+    assert!(Arc::ptr_eq(
+        state.function(),
+        HashMap::<u64, u64, TabRandomState>::default()
+            .hasher()
+            .function()
+    ));
+
+    // maps in different threads share one explicitly provided function
+    let function = Arc::new(Tab64Simple::new_with_seed(42));
+    let state = TabRandomState::from_function(function.clone());
+    let handles: Vec<_> = (0..4)
+        .map(|t| {
+            let state = state.clone();
+            thread::spawn(move || {
+                let mut map = HashMap::with_hasher(state);
+                for key in 0..1000_u64 {
+                    map.insert(key * t, key);
+                }
+                for key in 0..1000_u64 {
+                    assert!(map.contains_key(&(key * t)));
+                }
+                map.hasher().hash_one(1337_u64)
+            })
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), function.hash(1337));
+    }
+    assert_eq!(Arc::strong_count(&function), 2);
+
+    // hashing keys borrows the function instead of cloning the `Arc`
+    #[derive(PartialEq, Eq)]
+    struct Probe<'a>(&'a Arc<Tab64Simple>, usize);
+    impl std::hash::Hash for Probe<'_> {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            assert_eq!(Arc::strong_count(self.0), self.1);
+            1337_u64.hash(state);
+        }
+    }
+    let count = Arc::strong_count(&function);
+    assert_eq!(state.hash_one(Probe(&function, count)), function.hash(1337));
+    let mut map = HashMap::with_hasher(state.clone());
+    map.insert(Probe(&function, count + 1), ());
+
+    // a persisted table reproduces the hash values
+    let restored = TabRandomState::from_function(Arc::new(Tab64Simple::with_table(
+        state.function().get_table(),
+    )));
+    for key in 0..1000_u64 {
+        assert_eq!(restored.hash_one(key), state.hash_one(key));
+    }
+}
+
+/// Exercise a hash function through the common trait only
+#[cfg(feature = "std")]
+fn generic_roundtrip<H: TabHash>()
+where
+    H::Key: From<u8>,
+{
+    let keys: Vec<H::Key> = (0..=255_u8).map(H::Key::from).collect();
+    let h = H::random();
+    let restored = H::from_vec(h.to_vec());
+    let seeded_1 = H::new_with_seed(42);
+    let seeded_2 = H::new_with_seed(42);
+    for key in keys.iter() {
+        assert_eq!(h.hash(*key), restored.hash(*key));
+        assert_eq!(seeded_1.hash(*key), seeded_2.hash(*key));
+    }
+    assert_ne!(h.to_vec(), H::random().to_vec());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn generic_tab_hash() {
+    generic_roundtrip::<Tab16Simple>();
+    generic_roundtrip::<Tab32Simple>();
+    generic_roundtrip::<Tab64Simple>();
+    generic_roundtrip::<Tab128Simple>();
+    generic_roundtrip::<Tab32Twisted>();
+    generic_roundtrip::<Tab64Twisted>();
+    generic_roundtrip::<Tab128Twisted>();
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn dynamic_dispatch() {
+    use std::thread;
+    use tab_hash::{Hash32Fn, Hash64Fn};
+
+    let simple = Tab32Simple::new();
+    let twisted = Tab32Twisted::new();
+    let functions: Vec<Box<dyn Hash32Fn>> = vec![
+        Box::new(Tab32Simple::with_table(simple.get_table())),
+        Box::new(Tab32Twisted::with_table(twisted.get_table())),
+    ];
+    let random_keys: [u32; 100] = array_init::array_init(|_| rand::random());
+    for key in random_keys.iter() {
+        assert_eq!(functions[0].hash32(*key), simple.hash(*key));
+        assert_eq!(functions[1].hash32(*key), twisted.hash(*key));
+    }
+
+    let twisted = Tab64Twisted::new_with_seed(42);
+    let function: Box<dyn Hash64Fn + Send + Sync> = Box::new(Tab64Twisted::new_with_seed(42));
+    let hash = thread::spawn(move || function.hash64(1337)).join().unwrap();
+    assert_eq!(hash, twisted.hash(1337));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn runtime_selected_family() {
+    use tab_hash::{AnyTab32, AnyTab64, Kind};
+
+    let random_keys: [u32; 100] = array_init::array_init(|_| rand::random());
+    let simple = AnyTab32::new(Kind::Simple);
+    let twisted = AnyTab32::new(Kind::Twisted);
+    assert_eq!(simple.kind(), Kind::Simple);
+    assert_eq!(twisted.kind(), Kind::Twisted);
+    let seeded = AnyTab32::new_with_seed(Kind::Twisted, 42);
+    let expected = Tab32Twisted::new_with_seed(42);
+    for key in random_keys.iter() {
+        assert_eq!(seeded.hash(*key), expected.hash(*key));
+    }
+
+    let seeded = AnyTab64::new_with_seed(Kind::Simple, 42);
+    let expected = Tab64Simple::new_with_seed(42);
+    assert_eq!(seeded.kind(), Kind::Simple);
+    assert_eq!(seeded.hash(1337), expected.hash(1337));
+    assert_eq!(AnyTab64::from(expected).kind(), Kind::Simple);
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn runtime_selected_family_serialization() {
+    use tab_hash::{AnyTab32, AnyTab64, Kind};
+
+    let random_keys: [u64; 100] = array_init::array_init(|_| rand::random());
+    for kind in [Kind::Simple, Kind::Twisted].iter() {
+        let h32 = AnyTab32::new(*kind);
+        let serialized = bincode::serialize(&h32).unwrap();
+        let deserialized: AnyTab32 = bincode::deserialize(&serialized).unwrap();
+        assert_eq!(deserialized.kind(), *kind);
+
+        let h64 = AnyTab64::new(*kind);
+        let serialized = bincode::serialize(&h64).unwrap();
+        let deserialized_64: AnyTab64 = bincode::deserialize(&serialized).unwrap();
+        assert_eq!(deserialized_64.kind(), *kind);
+
+        for key in random_keys.iter() {
+            assert_eq!(h32.hash(*key as u32), deserialized.hash(*key as u32));
+            assert_eq!(h64.hash(*key), deserialized_64.hash(*key));
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn randomized_default() {
+    #[derive(Default)]
+    struct Config {
+        simple: Tab32Simple,
+        twisted: Tab64Twisted,
+    }
+
+    let c1 = Config::default();
+    let c2 = Config::default();
+    assert_ne!(c1.simple.to_vec(), c2.simple.to_vec());
+    assert_ne!(c1.twisted.to_vec(), c2.twisted.to_vec());
+    assert_ne!(
+        Tab16Simple::default().to_vec(),
+        Tab16Simple::default().to_vec()
+    );
+    assert_ne!(
+        Tab64Simple::default().to_vec(),
+        Tab64Simple::default().to_vec()
+    );
+    assert_ne!(
+        Tab128Simple::default().to_vec(),
+        Tab128Simple::default().to_vec()
+    );
+    assert_ne!(
+        Tab32Twisted::default().to_vec(),
+        Tab32Twisted::default().to_vec()
+    );
+    assert_ne!(
+        Tab128Twisted::default().to_vec(),
+        Tab128Twisted::default().to_vec()
+    );
+    // the default table is never all zeros
+    assert!(c1.simple.to_vec().iter().flatten().any(|e| *e != 0));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn debug_shows_fingerprint() {
+    use tab_hash::{AnyTab32, Kind};
+
+    let simple = Tab32Simple::new_with_seed(42);
+    let debug = format!("{:?}", simple);
+    assert_eq!(debug, "Tab32Simple { fingerprint: 0x230007dd387e336c }");
+    assert_eq!(
+        format!("{:?}", Tab32Simple::with_table(simple.get_table())),
+        debug
+    );
+    assert_ne!(format!("{:?}", Tab32Simple::new_with_seed(43)), debug);
+    assert_eq!(
+        format!("{:?}", Tab128Twisted::new_with_seed(42)),
+        "Tab128Twisted { fingerprint: 0xbdc31526f8b98eb7 }"
+    );
+
+    // the fingerprint is short for all hash functions
+    assert!(format!("{:?}", Tab16Simple::new()).len() < 50);
+    assert!(format!("{:?}", Tab64Simple::new()).len() < 50);
+    assert!(format!("{:?}", Tab128Simple::new()).len() < 50);
+    assert!(format!("{:?}", Tab32Twisted::new()).len() < 50);
+    assert!(format!("{:?}", Tab64Twisted::new()).len() < 50);
+    assert!(
+        format!("{:#?}", AnyTab32::new(Kind::Twisted))
+            .lines()
+            .count()
+            < 6
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn equality_and_hashing() {
+    use std::collections::HashSet;
+
+    let h1 = Tab32Simple::new();
+    let h2 = Tab32Simple::with_table(h1.get_table());
+    assert_eq!(h1, h2);
+    assert_ne!(h1, Tab32Simple::new());
+    // equal hash functions hash every key identically
+    let random_keys: [u32; 100] = array_init::array_init(|_| rand::random());
+    for key in random_keys.iter() {
+        assert_eq!(h1.hash(*key), h2.hash(*key));
+    }
+
+    let t1 = Tab64Twisted::new_with_seed(1);
+    let mut table = t1.get_table();
+    table[7][255] ^= 1;
+    assert_ne!(t1, Tab64Twisted::with_table(table));
+    table[7][255] ^= 1;
+    assert_eq!(t1, Tab64Twisted::with_table(table));
+
+    // registries can deduplicate hash functions
+    let mut registry = HashSet::new();
+    registry.insert(Tab64Simple::new_with_seed(1));
+    registry.insert(Tab64Simple::new_with_seed(2));
+    registry.insert(Tab64Simple::new_with_seed(1));
+    assert_eq!(registry.len(), 2);
+    assert!(registry.contains(&Tab64Simple::new_with_seed(2)));
+
+    assert_eq!(Tab16Simple::new_with_seed(3), Tab16Simple::new_with_seed(3));
+    assert_eq!(
+        Tab128Simple::new_with_seed(3),
+        Tab128Simple::new_with_seed(3)
+    );
+    assert_eq!(
+        Tab32Twisted::new_with_seed(3),
+        Tab32Twisted::new_with_seed(3)
+    );
+    assert_eq!(
+        Tab128Twisted::new_with_seed(3),
+        Tab128Twisted::new_with_seed(3)
+    );
+    assert_ne!(
+        Tab128Twisted::new_with_seed(3),
+        Tab128Twisted::new_with_seed(4)
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn stable_ids() {
+    assert_eq!(
+        Tab32Simple::with_table([[0; 256]; 4]).id(),
+        0x2051_c6a6_a535_fe2a
+    );
+    assert_eq!(Tab32Simple::new_with_seed(42).id(), 0x2300_07dd_387e_336c);
+    assert_eq!(Tab64Twisted::new_with_seed(42).id(), 0xa398_c03b_d76a_6f6a);
+
+    // wide entries contribute their low word first
+    let mut table = [[0_u128; 256]; 8];
+    for (i, column) in table.iter_mut().enumerate() {
+        for (j, entry) in column.iter_mut().enumerate() {
+            *entry = (((i * 256 + j) as u128) << 64) | 1;
+        }
+    }
+    assert_eq!(Tab64Twisted::with_table(table).id(), 0x1fae_31b0_907f_cd80);
+
+    // ids survive round trips
+    let h = Tab64Simple::new();
+    assert_eq!(Tab64Simple::from_vec(h.to_vec()).id(), h.id());
+    assert_eq!(Tab64Simple::from_le_bytes(&h.to_le_bytes()).id(), h.id());
+    assert_ne!(Tab64Simple::new().id(), h.id());
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn stable_ids_after_serialization() {
+    let h = Tab32Twisted::new();
+    let serialized = bincode::serialize(&h).unwrap();
+    let deserialized: Tab32Twisted = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(deserialized.id(), h.id());
+}
+
+/// Apply a hash function in a separate thread
+fn hash_in_thread<F: Fn(u32) -> u32 + Send + 'static>(f: F, key: u32) -> u32 {
+    std::thread::spawn(move || f(key)).join().unwrap()
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn closure_adapters() {
+    let simple = Tab32Simple::new();
+    let keys: Vec<u32> = (0..100).collect();
+    let hashes: Vec<u32> = keys.iter().copied().map(simple.as_fn()).collect();
+    for (key, hash) in keys.iter().zip(hashes.iter()) {
+        assert_eq!(simple.hash(*key), *hash);
+    }
+    let expected = simple.hash(1337);
+    assert_eq!(hash_in_thread(simple.into_fn(), 1337), expected);
+
+    let twisted = Tab32Twisted::new();
+    let expected = twisted.hash(1337);
+    assert_eq!(twisted.as_fn()(1337), expected);
+    assert_eq!(hash_in_thread(twisted.into_fn(), 1337), expected);
+
+    let simple = Tab64Simple::new();
+    let twisted = Tab64Twisted::new();
+    let hashes: Vec<u64> = (0..100_u64)
+        .map(simple.as_fn())
+        .map(twisted.as_fn())
+        .collect();
+    assert_eq!(hashes[42], twisted.hash(simple.hash(42)));
+    let f = twisted.clone().into_fn();
+    assert_eq!(f(42), twisted.hash(42));
+}
+
+#[test]
+fn byte_chunking_16() {
+    let random_bytes: [u8; 400] = array_init::array_init(|_| rand::random());
+    for two_bytes in random_bytes.chunks(2) {
+        let mut number = 0_u16;
+        for byte in two_bytes.iter().rev() {
+            number = (number << 8) | *byte as u16;
+        }
+        assert_eq!(two_bytes, tab_hash::byte_chunks_16(number));
+    }
+}
+
+#[test]
+fn byte_chunking_32() {
+    let random_bytes: [u8; 400] = array_init::array_init(|_| rand::random());
+    for four_bytes in random_bytes.chunks(4) {
+        let mut number = 0_u32;
+        for byte in four_bytes.iter().rev() {
+            number = (number << 8) | *byte as u32;
+        }
+        assert_eq!(four_bytes, tab_hash::byte_chunks_32(number));
+    }
+}
+
+#[test]
+fn byte_chunking_64() {
+    let random_bytes: [u8; 480] = array_init::array_init(|_| rand::random());
+    for four_bytes in random_bytes.chunks(8) {
+        let mut number = 0_u64;
+        for byte in four_bytes.iter().rev() {
+            number = (number << 8) | *byte as u64;
+        }
+        assert_eq!(four_bytes, tab_hash::byte_chunks_64(number));
+    }
+}
+
+#[test]
+fn byte_chunking_128() {
+    let random_bytes: [u8; 480] = array_init::array_init(|_| rand::random());
+    for sixteen_bytes in random_bytes.chunks(16) {
+        let mut number = 0_u128;
+        for byte in sixteen_bytes.iter().rev() {
+            number = (number << 8) | *byte as u128;
+        }
+        assert_eq!(sixteen_bytes, tab_hash::byte_chunks_128(number));
+    }
+}
+
+#[test]
+fn byte_chunking_round_trip() {
+    use tab_hash::*;
+    for _ in 0..1000 {
+        let x: u16 = rand::random();
+        assert_eq!(from_byte_chunks_16(byte_chunks_16(x)), x);
+        let x: u32 = rand::random();
+        assert_eq!(from_byte_chunks_32(byte_chunks_32(x)), x);
+        let x: u64 = rand::random();
+        assert_eq!(from_byte_chunks_64(byte_chunks_64(x)), x);
+        let x: u128 = rand::random();
+        assert_eq!(from_byte_chunks_128(byte_chunks_128(x)), x);
+        let chunks: [u8; 8] = rand::random();
+        assert_eq!(byte_chunks_64(from_byte_chunks_64(chunks)), chunks);
+    }
+
+    // chunk i is looked up in column i
+    let mut table = [[0_u32; 256]; 4];
+    for (i, column) in table.iter_mut().enumerate() {
+        column[i + 1] = 1 << i;
+    }
+    let key = from_byte_chunks_32([1, 2, 3, 4]);
+    assert_eq!(key, 0x0403_0201);
+    assert_eq!(Tab32Simple::with_table(table).hash(key), 0b1111);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn custom_key_types() {
+    use tab_hash::TabKey;
+
+    struct OrderId(u32);
+    impl TabKey for OrderId {
+        type Chunks = [u8; 4];
+        fn chunks(&self) -> [u8; 4] {
+            self.0.chunks()
+        }
+    }
+
+    /// A packed key with a custom chunk order
+    struct NodeId {
+        shard: u16,
+        local: u32,
+    }
+    impl TabKey for NodeId {
+        type Chunks = [u8; 8];
+        fn chunks(&self) -> [u8; 8] {
+            let s = self.shard.to_le_bytes();
+            let l = self.local.to_le_bytes();
+            [s[0], s[1], l[0], l[1], l[2], l[3], 0, 0]
+        }
+    }
+
+    let simple = Tab32Simple::new();
+    let twisted = Tab32Twisted::new();
+    let order = OrderId(1337);
+    assert_eq!(simple.hash_key(&order), simple.hash(1337));
+    assert_eq!(twisted.hash_key(order), twisted.hash(1337));
+
+    let simple = Tab64Simple::new();
+    let twisted = Tab64Twisted::new();
+    let node = NodeId {
+        shard: 0x0201,
+        local: 0x0605_0403,
+    };
+    assert_eq!(simple.hash_key(&node), simple.hash(0x0605_0403_0201));
+    assert_eq!(twisted.hash_key(node), twisted.hash(0x0605_0403_0201));
+
+    assert_eq!(
+        Tab16Simple::new_with_seed(1).hash_key(7_u16),
+        Tab16Simple::new_with_seed(1).hash(7)
+    );
+    let h = Tab128Twisted::new_with_seed(1);
+    assert_eq!(h.hash_key(7_u128), h.hash(7));
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "bytemuck"))]
+fn pod_keys() {
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct Port {
+        number: u16,
+        protocol: u8,
+        flags: u8,
+    }
+    unsafe impl bytemuck::Zeroable for Port {}
+    unsafe impl bytemuck::Pod for Port {}
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct Edge {
+        source: u32,
+        target: u32,
+    }
+    unsafe impl bytemuck::Zeroable for Edge {}
+    unsafe impl bytemuck::Pod for Edge {}
+
+    let port = Port {
+        number: 443,
+        protocol: 6,
+        flags: 1,
+    };
+    let transmuted: u32 = bytemuck::cast(port);
+    let simple = Tab32Simple::new();
+    let twisted = Tab32Twisted::new();
+    assert_eq!(simple.hash_pod(&port), simple.hash(transmuted));
+    assert_eq!(twisted.hash_pod(&port), twisted.hash(transmuted));
+
+    let edge = Edge {
+        source: 1,
+        target: 2,
+    };
+    let transmuted: u64 = bytemuck::cast(edge);
+    let simple = Tab64Simple::new();
+    let twisted = Tab64Twisted::new();
+    assert_eq!(simple.hash_pod(&edge), simple.hash(transmuted));
+    assert_eq!(twisted.hash_pod(&edge), twisted.hash(transmuted));
+
+    // integers are plain old data as well
+    assert_eq!(simple.hash_pod(&1337_u64), simple.hash(1337));
+    assert_eq!(simple.hash_pod(&[1_u32, 2]), simple.hash_pod(&edge));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn pair_keys() {
+    // entry j of column i is j rotated by 4 * i bits, i.e. byte i of the key
+    // ends up in the i-th nibble of the hash value
+    let mut table = [[0_u32; 256]; 8];
+    for (i, column) in table.iter_mut().enumerate() {
+        for (j, entry) in column.iter_mut().enumerate() {
+            *entry = (j as u32).rotate_left(4 * i as u32);
+        }
+    }
+    let pair = Tab32Pair::with_table(table);
+    assert_eq!(pair.hash_pair(0x0403_0201, 0x0807_0605), 0x8765_4321);
+    assert_eq!(pair.hash_pair(0x0807_0605, 0x0403_0201), 0x4321_8765);
+
+    let pair = Tab32Pair::new_with_seed(42);
+    for (a, b) in [(0, 1), (1337, 42), (0xFFFF_FFFF, 0xDEAD_BEEF)].iter() {
+        assert_ne!(pair.hash_pair(*a, *b), pair.hash_pair(*b, *a));
+        assert_eq!(pair.as_fn()(*a, *b), pair.hash_pair(*a, *b));
+    }
+    assert_eq!(pair, Tab32Pair::from_vec(pair.to_vec()));
+    assert_eq!(pair, Tab32Pair::from_le_bytes(&pair.to_le_bytes()));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn pair_keys_vs_truncated_tab64() {
+    let simple = Tab64Simple::new();
+    let mut table = [[0_u32; 256]; 8];
+    for (column, simple_column) in table.iter_mut().zip(simple.get_table().iter()) {
+        for (entry, simple_entry) in column.iter_mut().zip(simple_column.iter()) {
+            *entry = *simple_entry as u32;
+        }
+    }
+    let pair = Tab32Pair::with_table(table);
+    for _ in 0..1000 {
+        let (a, b): (u32, u32) = rand::random();
+        let packed = (u64::from(b) << 32) | u64::from(a);
+        assert_eq!(pair.hash_pair(a, b), simple.hash(packed) as u32);
+    }
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn pair_keys_serialization() {
+    let pair = Tab32Pair::new();
+    let serialized = bincode::serialize(&pair).unwrap();
+    let deserialized: Tab32Pair = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(pair, deserialized);
+    assert_eq!(pair.hash_pair(1337, 42), deserialized.hash_pair(1337, 42));
+}
+
+#[test]
+fn byte_array_keys() {
+    // entry j of column i is j shifted to byte i, i.e. hashing is the identity
+    let mut table = [[0_u64; 256]; 6];
+    for (i, column) in table.iter_mut().enumerate() {
+        for (j, entry) in column.iter_mut().enumerate() {
+            *entry = (j as u64) << (8 * i);
+        }
+    }
+    let mac = TabBytes::with_table(table);
+    assert_eq!(
+        mac.hash(&[0x00, 0x1B, 0x44, 0x11, 0x3A, 0xB7]),
+        0xB73A_1144_1B00
+    );
+
+    // entry j of column i is j shifted to nibble i, so that keys with bytes
+    // below 16 are spelled out nibble by nibble
+    let mut table = [[0_u64; 256]; 16];
+    for (i, column) in table.iter_mut().enumerate() {
+        for (j, entry) in column.iter_mut().enumerate() {
+            *entry = (j as u64).rotate_left(4 * i as u32);
+        }
+    }
+    let uuid = TabBytes::with_table(table);
+    let key = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 0];
+    assert_eq!(uuid.hash(&key), 0x0FED_CBA9_8765_4321);
+
+    // the same in the high bits of a twisted table, the first column also
+    // has the key byte in its low bits, so the last byte is twisted with it
+    let mut table = [[0_u128; 256]; 16];
+    for (i, column) in table.iter_mut().enumerate() {
+        for (j, entry) in column.iter_mut().enumerate() {
+            *entry = (j as u128) << (64 + 4 * i);
+            if i == 0 {
+                *entry |= j as u128;
+            }
+        }
+    }
+    let uuid = TabBytesTwisted::with_table(table);
+    assert_eq!(uuid.hash(&key), 0x1FED_CBA9_8765_4321);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn byte_array_keys_vs_integer_keys() {
+    let simple = Tab64Simple::new();
+    let bytes = TabBytes::with_table(simple.get_table());
+    let twisted = Tab64Twisted::new();
+    let twisted_bytes = TabBytesTwisted::with_table(twisted.get_table());
+    // the low four columns of the table, with entry 0 of the others set to 0
+    let mut table = simple.get_table();
+    for column in table[4..].iter_mut() {
+        column[0] = 0;
+    }
+    let zero_extended = Tab64Simple::with_table(table);
+    let mut low_table = [[0; 256]; 4];
+    low_table.copy_from_slice(&table[..4]);
+    let bytes32 = TabBytes::with_table(low_table);
+
+    for _ in 0..1000 {
+        let key: u64 = rand::random();
+        assert_eq!(bytes.hash(&key.to_le_bytes()), simple.hash(key));
+        assert_eq!(twisted_bytes.hash(&key.to_le_bytes()), twisted.hash(key));
+        let key: u32 = rand::random();
+        assert_eq!(
+            bytes32.hash(&key.to_le_bytes()),
+            zero_extended.hash(u64::from(key))
+        );
+    }
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn byte_array_keys_serialization() {
+    let simple: TabBytes<6> = TabBytes::new();
+    let serialized = bincode::serialize(&simple).unwrap();
+    let deserialized: TabBytes<6> = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(simple, deserialized);
+
+    let twisted: TabBytesTwisted<16> = TabBytesTwisted::new();
+    let serialized = bincode::serialize(&twisted).unwrap();
+    let deserialized: TabBytesTwisted<16> = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(twisted, deserialized);
+    assert_eq!(twisted.hash(&[42; 16]), deserialized.hash(&[42; 16]));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn slice_keys() {
+    let simple = Tab32Simple::new();
+    assert_eq!(simple.hash_slice(&[]), simple.hash(0));
+    assert_eq!(
+        simple.hash_slice(&[1, 2]),
+        simple.hash(simple.hash(simple.hash(2) ^ 1) ^ 2)
+    );
+    assert_ne!(simple.hash_slice(&[1]), simple.hash_slice(&[1, 0]));
+    assert_ne!(simple.hash_slice(&[]), simple.hash_slice(&[0]));
+    assert_ne!(simple.hash_slice(&[1, 2, 3]), simple.hash_slice(&[3, 2, 1]));
+
+    let simple = Tab64Simple::new();
+    assert_eq!(simple.hash_slice(&[]), simple.hash(0));
+    assert_ne!(simple.hash_slice(&[1]), simple.hash_slice(&[1, 0]));
+    assert_ne!(simple.hash_slice(&[1, 2, 3]), simple.hash_slice(&[3, 2, 1]));
+}
+
+/// Draw `n` distinct random slices of 3 to 10 elements
+fn random_slices<T: Eq + std::hash::Hash>(n: usize) -> std::collections::HashSet<Vec<T>>
+where
+    rand::distributions::Standard: rand::distributions::Distribution<T>,
+{
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+    let mut slices = std::collections::HashSet::new();
+    while slices.len() < n {
+        let len = rng.gen_range(3, 11);
+        slices.insert((0..len).map(|_| rng.gen()).collect());
+    }
+    slices
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn slice_keys_collisions() {
+    // 10000 slices collide in 32 bits with a probability of about 1%
+    let simple = Tab32Simple::new();
+    let slices = random_slices::<u32>(10_000);
+    let hashes: std::collections::HashSet<u32> =
+        slices.iter().map(|xs| simple.hash_slice(xs)).collect();
+    assert!(hashes.len() >= slices.len() - 2);
+
+    let simple = Tab64Simple::new();
+    let slices = random_slices::<u64>(10_000);
+    let hashes: std::collections::HashSet<u64> =
+        slices.iter().map(|xs| simple.hash_slice(xs)).collect();
+    assert_eq!(hashes.len(), slices.len());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn slice_keys_permutations() {
+    let simple = Tab64Simple::new();
+    for xs in random_slices::<u64>(1000).iter() {
+        let h = simple.hash_slice(xs);
+        let mut reversed = xs.clone();
+        reversed.reverse();
+        assert_ne!(h, simple.hash_slice(&reversed));
+        let mut swapped = xs.clone();
+        swapped.swap(0, 1);
+        assert_ne!(h, simple.hash_slice(&swapped));
+        let mut rotated = xs.clone();
+        rotated.rotate_left(1);
+        assert_ne!(h, simple.hash_slice(&rotated));
+    }
+
+    // small elements, so that permutations of the same elements are common
+    let simple = Tab32Simple::new();
+    let xs = [1, 2, 3, 4];
+    let mut hashes = std::collections::HashSet::new();
+    for a in 0..4 {
+        for b in 0..4 {
+            for c in 0..4 {
+                for d in 0..4 {
+                    let perm = [xs[a], xs[b], xs[c], xs[d]];
+                    let mut sorted = perm;
+                    sorted.sort_unstable();
+                    if sorted == xs {
+                        hashes.insert(simple.hash_slice(&perm));
+                    }
+                }
+            }
+        }
+    }
+    assert_eq!(hashes.len(), 24);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn short_string_keys() {
+    // hashing is the identity, so hash values are the packed strings
+    let mut table = [[0_u64; 256]; 8];
+    for (i, column) in table.iter_mut().enumerate() {
+        for (j, entry) in column.iter_mut().enumerate() {
+            *entry = (j as u64) << (8 * i);
+        }
+    }
+    let identity = Tab64Simple::with_table(table);
+    assert_eq!(identity.hash_short_str(""), 0xF8F8_F8F8_F8F8_F8F8);
+    assert_eq!(identity.hash_short_str("US"), 0xFAFA_FAFA_FAFA_5355);
+    assert_eq!(identity.hash_short_str("EUR"), 0xFBFB_FBFB_FB52_5545);
+    assert_eq!(identity.hash_short_str("gzipgzip"), 0x7069_7A67_7069_7A67);
+    assert_eq!(identity.hash_short_bytes(b"a\0b"), 0xFBFB_FBFB_FB62_0061);
+    assert_eq!(
+        tab_hash::pack_short_bytes(b"gzip"),
+        u64::from_le_bytes(*b"gzip\xFC\xFC\xFC\xFC")
+    );
+
+    let simple = Tab64Simple::new();
+    let twisted = Tab64Twisted::new();
+    assert_eq!(
+        simple.hash_short_str("EUR"),
+        simple.hash_short_bytes(b"EUR")
+    );
+    assert_eq!(
+        twisted.hash_short_str("gzip"),
+        twisted.hash(tab_hash::pack_short_bytes(b"gzip"))
+    );
+
+    // strings differing only by trailing zero bytes do not collide
+    let mut packed = std::collections::HashSet::new();
+    let mut simple_hashes = std::collections::HashSet::new();
+    let mut twisted_hashes = std::collections::HashSet::new();
+    for prefix in ["", "ab", "\0", "gzip"].iter() {
+        for zeros in 0..=(8 - prefix.len()) {
+            let key = format!("{}{}", prefix, "\0".repeat(zeros));
+            packed.insert(tab_hash::pack_short_bytes(key.as_bytes()));
+            simple_hashes.insert(simple.hash_short_str(&key));
+            twisted_hashes.insert(twisted.hash_short_str(&key));
+        }
+    }
+    // "\0" with trailing zeros repeats the keys of "" with trailing zeros
+    assert_eq!(packed.len(), 9 + 7 + 5);
+    assert_eq!(simple_hashes.len(), packed.len());
+    assert_eq!(twisted_hashes.len(), packed.len());
+}
+
+#[test]
+#[cfg(feature = "std")]
+#[should_panic(expected = "at most 8 bytes")]
+fn short_string_keys_too_long() {
+    Tab64Simple::new().hash_short_str("deflate64");
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn packed_48bit_keys() {
+    // entry j of column i is j rotated by 8 * i + 4 bits, to pin the chunk order
+    let mut table = [[0_u64; 256]; 6];
+    for (i, column) in table.iter_mut().enumerate() {
+        for (j, entry) in column.iter_mut().enumerate() {
+            *entry = (j as u64).rotate_left(8 * i as u32 + 4);
+        }
+    }
+    let simple = Tab48Simple::with_table(table);
+    assert_eq!(simple.hash(0x0605_0403_0201), 0x6050_4030_2010);
+    assert_eq!(simple.hash(0xFF00_0000_0000), 0x000F_F000_0000_0000);
+    assert_eq!(simple.hash(0x0000_0000_00FF), 0x0FF0);
+
+    let simple = Tab64Simple::new();
+    let mut table = [[0; 256]; 6];
+    table.copy_from_slice(&simple.get_table()[..6]);
+    let packed = Tab48Simple::with_table(table);
+    let offset = simple.hash(0);
+    for _ in 0..1000 {
+        let key = rand::random::<u64>() >> 16;
+        // the last two columns only contribute their entry 0
+        assert_eq!(packed.hash(key) ^ packed.hash(0) ^ offset, simple.hash(key));
+    }
+
+    let packed = Tab48Simple::new();
+    assert_eq!(packed, Tab48Simple::from_vec(packed.to_vec()));
+    assert_eq!(packed, Tab48Simple::from_le_bytes(&packed.to_le_bytes()));
+}
+
+#[test]
+#[cfg(all(feature = "std", debug_assertions))]
+#[should_panic(expected = "48bit keys")]
+fn packed_48bit_keys_out_of_range() {
+    Tab48Simple::new().hash(1 << 48);
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn packed_48bit_keys_serialization() {
+    let packed = Tab48Simple::new();
+    let serialized = bincode::serialize(&packed).unwrap();
+    let deserialized: Tab48Simple = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(packed, deserialized);
+    assert_eq!(packed.hash(1337), deserialized.hash(1337));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn kmer_encoding() {
+    use tab_hash::kmer::{decode_kmer, encode_kmer, KmerError};
+
+    assert_eq!(encode_kmer(b""), Ok(0));
+    assert_eq!(encode_kmer(b"ACGT"), Ok(0b00_01_10_11));
+    assert_eq!(encode_kmer(b"GATTACA"), Ok(0x23C4));
+    assert_eq!(encode_kmer(b"gattaca"), Ok(0x23C4));
+    assert_eq!(encode_kmer(&[b'T'; 32]), Ok(u64::MAX));
+    assert_eq!(
+        encode_kmer(b"ACGNT"),
+        Err(KmerError::InvalidBase {
+            position: 3,
+            base: b'N'
+        })
+    );
+    assert_eq!(encode_kmer(&[b'A'; 33]), Err(KmerError::TooLong { k: 33 }));
+
+    use rand::seq::SliceRandom;
+    let mut rng = rand::thread_rng();
+    for k in 0..=32 {
+        let kmer: Vec<u8> = (0..k).map(|_| *b"ACGT".choose(&mut rng).unwrap()).collect();
+        let packed = encode_kmer(&kmer).unwrap();
+        assert_eq!(decode_kmer(packed, k), kmer);
+        assert_eq!(encode_kmer(&decode_kmer(packed, k)), Ok(packed));
+    }
+    // bits above the k-mer are ignored
+    assert_eq!(decode_kmer(0xFF00 | 0x1B, 4), b"ACGT");
+}
+
+#[test]
+fn kmer_hashing() {
+    let simple = Tab64Simple::new_with_seed(42);
+    let twisted = Tab64Twisted::new_with_seed(42);
+    assert_eq!(simple.hash_kmer(0x23C4, 7), 0x2266_7CBD_DFB5_138B);
+    assert_eq!(simple.hash_kmer(0x1B, 4), 0xAA36_21CC_9E29_86E9);
+    assert_eq!(simple.hash_kmer(u64::MAX, 32), 0xAA69_731A_26AB_9FF8);
+    assert_eq!(twisted.hash_kmer(0x23C4, 7), 0x33DB_583E_7A41_5520);
+    assert_eq!(twisted.hash_kmer(0x1B, 4), 0x5C5A_3A83_510E_C043);
+    assert_eq!(twisted.hash_kmer(u64::MAX, 32), 0xA335_889C_1EC0_95A9);
+
+    // garbage in the unused high bits does not change the hash value
+    for garbage in [1 << 14, 0xDEAD_0000_0000_0000, u64::MAX << 14].iter() {
+        assert_eq!(simple.hash_kmer(0x23C4 | garbage, 7), simple.hash(0x23C4));
+        assert_eq!(twisted.hash_kmer(0x23C4 | garbage, 7), twisted.hash(0x23C4));
+    }
+    assert_eq!(simple.hash_kmer(u64::MAX, 0), simple.hash(0));
+}
+
+#[test]
+#[cfg(feature = "std")]
+#[should_panic(expected = "at most 32 bases")]
+fn kmer_hashing_too_long() {
+    Tab64Simple::new().hash_kmer(0, 33);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn canonical_kmer_hashing() {
+    use tab_hash::kmer::{canonical_kmer, decode_kmer, encode_kmer, mask_kmer, reverse_complement};
+
+    let gattaca = encode_kmer(b"GATTACA").unwrap();
+    assert_eq!(decode_kmer(reverse_complement(gattaca, 7), 7), b"TGTAATC");
+    assert_eq!(
+        reverse_complement(encode_kmer(b"AAAC").unwrap(), 4),
+        encode_kmer(b"GTTT").unwrap()
+    );
+    assert_eq!(canonical_kmer(encode_kmer(b"GTTT").unwrap(), 4), 0b01);
+    assert_eq!(reverse_complement(u64::MAX, 32), 0);
+    assert_eq!(reverse_complement(u64::MAX, 0), 0);
+    // high bits are ignored
+    assert_eq!(
+        reverse_complement(gattaca | 1 << 40, 7),
+        reverse_complement(gattaca, 7)
+    );
+
+    // palindromic k-mers are their own reverse complement
+    for palindrome in [&b"ACGT"[..], b"GAATTC", b"AT", b"TTTTAAAA"].iter() {
+        let packed = encode_kmer(palindrome).unwrap();
+        assert_eq!(reverse_complement(packed, palindrome.len()), packed);
+        assert_eq!(canonical_kmer(packed, palindrome.len()), packed);
+    }
+
+    let simple = Tab64Simple::new();
+    let twisted = Tab64Twisted::new();
+    for k in [1, 2, 3, 4, 15, 16, 21, 31, 32].iter().copied() {
+        for _ in 0..100 {
+            let packed = rand::random::<u64>();
+            let reverse = reverse_complement(packed, k);
+            assert_eq!(reverse_complement(reverse, k), mask_kmer(packed, k));
+            assert_eq!(
+                simple.hash_canonical(packed, k),
+                simple.hash_canonical(reverse, k)
+            );
+            assert_eq!(
+                twisted.hash_canonical(packed, k),
+                twisted.hash_canonical(reverse, k)
+            );
+            assert_eq!(
+                simple.hash_canonical(packed, k),
+                simple.hash_kmer(canonical_kmer(packed, k), k)
+            );
+        }
+    }
+}
+
+/// Select minimizers by scanning every window, reporting each position once
+fn naive_minimizers(hashes: &[u64], w: usize) -> Vec<usize> {
+    let mut positions: Vec<usize> = Vec::new();
+    for start in 0..=hashes.len().saturating_sub(w) {
+        let window = &hashes[start..hashes.len().min(start + w)];
+        let min = *window.iter().min().unwrap();
+        let position = start + window.iter().position(|h| *h == min).unwrap();
+        if positions.last() != Some(&position) {
+            positions.push(position);
+        }
+    }
+    positions
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn kmer_minimizers() {
+    // hashing is the identity, so minimizers are the minimal k-mers
+    let mut table = [[0_u64; 256]; 8];
+    for (i, column) in table.iter_mut().enumerate() {
+        for (j, entry) in column.iter_mut().enumerate() {
+            *entry = (j as u64) << (8 * i);
+        }
+    }
+    let identity = Tab64Simple::with_table(table);
+    let minimizers = |kmers: &[u64], w| identity.minimizers(kmers.to_vec(), w).collect::<Vec<_>>();
+
+    assert_eq!(
+        minimizers(&[5, 3, 4, 1, 2, 6, 0], 3),
+        vec![(1, 3), (3, 1), (6, 0)]
+    );
+    // ties select the leftmost k-mer
+    assert_eq!(
+        minimizers(&[2, 1, 1, 3, 1], 2),
+        vec![(1, 1), (2, 1), (4, 1)]
+    );
+    assert_eq!(minimizers(&[7, 7, 7, 7], 3), vec![(0, 7), (1, 7)]);
+    // windows of a single k-mer select every k-mer
+    assert_eq!(minimizers(&[4, 4, 2], 1), vec![(0, 4), (1, 4), (2, 2)]);
+    // windows longer than the sequence
+    assert_eq!(minimizers(&[4, 2, 3], 5), vec![(1, 2)]);
+    assert_eq!(minimizers(&[4, 2, 3], 3), vec![(1, 2)]);
+    assert_eq!(minimizers(&[], 3), vec![]);
+
+    // random k-mers with few distinct hash values, so that ties are common
+    let simple = Tab64Simple::new();
+    let twisted = Tab64Twisted::new();
+    for w in [1, 2, 3, 5, 11, 200].iter().copied() {
+        let kmers: Vec<u64> = (0..100).map(|_| rand::random::<u64>() % 4).collect();
+        let expected = naive_minimizers(&kmers, w);
+        let positions: Vec<usize> = identity.minimizers(kmers.clone(), w).map(|m| m.0).collect();
+        assert_eq!(positions, expected);
+
+        let kmers: Vec<u64> = (0..100).map(|_| rand::random()).collect();
+        let hashes: Vec<u64> = kmers.iter().map(|k| simple.hash(*k)).collect();
+        let selected: Vec<(usize, u64)> = simple.minimizers(kmers.iter().copied(), w).collect();
+        let positions: Vec<usize> = selected.iter().map(|m| m.0).collect();
+        assert_eq!(positions, naive_minimizers(&hashes, w));
+        for (position, kmer) in selected {
+            assert_eq!(kmers[position], kmer);
+        }
+        let hashes: Vec<u64> = kmers.iter().map(|k| twisted.hash(*k)).collect();
+        let positions: Vec<usize> = twisted.minimizers(kmers, w).map(|m| m.0).collect();
+        assert_eq!(positions, naive_minimizers(&hashes, w));
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+#[should_panic(expected = "at least one k-mer")]
+fn kmer_minimizers_empty_window() {
+    Tab64Simple::new().minimizers(vec![1, 2, 3], 0).count();
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn signed_keys() {
+    let simple = Tab32Simple::new();
+    let twisted = Tab32Twisted::new();
+    assert_eq!(simple.hash_i32(-1), simple.hash(u32::MAX));
+    assert_eq!(simple.hash_i32(i32::MIN), simple.hash(0x8000_0000));
+    assert_eq!(twisted.hash_i32(-1), twisted.hash(u32::MAX));
+    assert_eq!(twisted.hash_i32(i32::MAX), twisted.hash(0x7FFF_FFFF));
+
+    let simple = Tab64Simple::new();
+    let twisted = Tab64Twisted::new();
+    assert_eq!(simple.hash_i64(-1), simple.hash(u64::MAX));
+    assert_eq!(
+        simple.hash_i64(i64::MIN),
+        simple.hash(0x8000_0000_0000_0000)
+    );
+    assert_eq!(twisted.hash_i64(-2), twisted.hash(u64::MAX - 1));
+    for _ in 0..100 {
+        let x: i64 = rand::random();
+        assert_eq!(simple.hash_i64(x), simple.hash(x as u64));
+        assert_eq!(twisted.hash_i64(x), twisted.hash(x as u64));
+    }
+}
+
+/// Hash signed and unsigned keys alike
+fn hash_keys<K: tab_hash::TabKey<Chunks = [u8; 8]>>(h: &Tab64Simple, keys: Vec<K>) -> Vec<u64> {
+    keys.into_iter().map(|k| h.hash_key(k)).collect()
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn signed_custom_keys() {
+    use tab_hash::TabKey;
+
+    assert_eq!((-1_i16).chunks(), [0xFF; 2]);
+    assert_eq!((-2_i32).chunks(), [0xFE, 0xFF, 0xFF, 0xFF]);
+    assert_eq!(i64::MIN.chunks(), [0, 0, 0, 0, 0, 0, 0, 0x80]);
+    assert_eq!((-1_i128).chunks(), [0xFF; 16]);
+
+    let simple = Tab64Simple::new();
+    assert_eq!(
+        hash_keys(&simple, vec![-1_i64, 0, 1337]),
+        hash_keys(&simple, vec![u64::MAX, 0, 1337])
+    );
+    let simple = Tab16Simple::new();
+    assert_eq!(simple.hash_key(-1_i16), simple.hash(u16::MAX));
+    let simple = Tab128Simple::new();
+    assert_eq!(simple.hash_key(-1_i128), simple.hash(u128::MAX));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn float_keys() {
+    let simple = Tab32Simple::new();
+    let twisted = Tab32Twisted::new();
+    assert_eq!(simple.hash_f32(0.0), simple.hash_f32(-0.0));
+    assert_eq!(simple.hash_f32(0.0), simple.hash(0));
+    assert_eq!(twisted.hash_f32(-0.0), twisted.hash(0));
+    let nans = [
+        f32::NAN,
+        -f32::NAN,
+        f32::from_bits(0x7F80_0001),
+        f32::from_bits(0xFFC0_1234),
+        f32::from_bits(0x7FFF_FFFF),
+    ];
+    for nan in nans.iter() {
+        assert!(nan.is_nan());
+        assert_eq!(simple.hash_f32(*nan), simple.hash(0x7FC0_0000));
+        assert_eq!(twisted.hash_f32(*nan), twisted.hash(0x7FC0_0000));
+    }
+    assert_eq!(simple.hash_f32(f32::INFINITY), simple.hash(0x7F80_0000));
+    assert_eq!(simple.hash_f32(f32::NEG_INFINITY), simple.hash(0xFF80_0000));
+    assert_eq!(simple.hash_f32(1.5), simple.hash(1.5_f32.to_bits()));
+    assert_ne!(simple.hash_f32(1.0), simple.hash_f32(-1.0));
+    assert_ne!(simple.hash_f32(0.1), simple.hash_f32(0.1 + f32::EPSILON));
+
+    let simple = Tab64Simple::new();
+    let twisted = Tab64Twisted::new();
+    assert_eq!(simple.hash_f64(0.0), simple.hash_f64(-0.0));
+    assert_eq!(twisted.hash_f64(0.0), twisted.hash_f64(-0.0));
+    let nans = [
+        f64::NAN,
+        -f64::NAN,
+        f64::from_bits(0x7FF0_0000_0000_0001),
+        f64::from_bits(0xFFF8_DEAD_BEEF_0000),
+    ];
+    for nan in nans.iter() {
+        assert!(nan.is_nan());
+        assert_eq!(simple.hash_f64(*nan), simple.hash(0x7FF8_0000_0000_0000));
+        assert_eq!(twisted.hash_f64(*nan), twisted.hash(0x7FF8_0000_0000_0000));
+    }
+    assert_eq!(
+        simple.hash_f64(f64::INFINITY),
+        simple.hash(f64::INFINITY.to_bits())
+    );
+    assert_ne!(
+        simple.hash_f64(f64::INFINITY),
+        simple.hash_f64(f64::NEG_INFINITY)
+    );
+    assert_ne!(simple.hash_f64(f64::INFINITY), simple.hash_f64(f64::NAN));
+
+    // ordinary values with different bit patterns hash differently
+    let values: Vec<f64> = (1..1000).map(|i| i as f64 * 0.001).collect();
+    let hashes: std::collections::HashSet<u64> =
+        values.iter().map(|x| simple.hash_f64(*x)).collect();
+    assert_eq!(hashes.len(), values.len());
+    let hashes: std::collections::HashSet<u64> =
+        values.iter().map(|x| simple.hash_f64(-*x)).collect();
+    assert_eq!(hashes.len(), values.len());
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "net"))]
+fn network_address_keys() {
+    use std::net::{Ipv4Addr, Ipv6Addr, SocketAddrV4};
+
+    // hashing is the identity, which pins the byte order
+    let mut table = [[0_u32; 256]; 4];
+    for (i, column) in table.iter_mut().enumerate() {
+        for (j, entry) in column.iter_mut().enumerate() {
+            *entry = (j as u32) << (8 * i);
+        }
+    }
+    let identity = Tab32Simple::with_table(table);
+    assert_eq!(
+        identity.hash_ipv4(Ipv4Addr::new(192, 168, 0, 1)),
+        0xC0A8_0001
+    );
+    let mut table = [[0_u64; 256]; 6];
+    for (i, column) in table.iter_mut().enumerate() {
+        for (j, entry) in column.iter_mut().enumerate() {
+            *entry = (j as u64) << (8 * i);
+        }
+    }
+    let identity = Tab48Simple::with_table(table);
+    let socket = SocketAddrV4::new(Ipv4Addr::new(192, 168, 0, 1), 8080);
+    assert_eq!(identity.hash_socketaddr_v4(socket), 0xC0A8_0001_1F90);
+    let mut table = [[0_u128; 256]; 16];
+    for (i, column) in table.iter_mut().enumerate() {
+        for (j, entry) in column.iter_mut().enumerate() {
+            *entry = (j as u128) << (8 * i);
+        }
+    }
+    let identity = Tab128Simple::with_table(table);
+    let ipv6 = Ipv6Addr::new(0x2001, 0x0DB8, 0, 0, 0, 0, 0, 1);
+    assert_eq!(
+        identity.hash_ipv6(ipv6),
+        0x2001_0DB8_0000_0000_0000_0000_0000_0001
+    );
+
+    let ipv4 = Ipv4Addr::new(10, 0, 0, 42);
+    let simple = Tab32Simple::new();
+    let twisted = Tab32Twisted::new();
+    assert_eq!(simple.hash_ipv4(ipv4), simple.hash(0x0A00_002A));
+    assert_eq!(twisted.hash_ipv4(ipv4), twisted.hash(0x0A00_002A));
+    let socket = SocketAddrV4::new(ipv4, 443);
+    let simple = Tab64Simple::new();
+    let twisted = Tab64Twisted::new();
+    assert_eq!(
+        simple.hash_socketaddr_v4(socket),
+        simple.hash(0x0A00_002A_01BB)
+    );
+    assert_eq!(
+        twisted.hash_socketaddr_v4(socket),
+        twisted.hash(0x0A00_002A_01BB)
+    );
+    let simple = Tab128Simple::new();
+    let twisted = Tab128Twisted::new();
+    assert_eq!(simple.hash_ipv6(ipv6), simple.hash(0x2001_0DB8 << 96 | 1));
+    assert_eq!(twisted.hash_ipv6(ipv6), twisted.hash(0x2001_0DB8 << 96 | 1));
+}
+
+#[test]
+#[cfg(feature = "uuid")]
+fn uuid_keys() {
+    let uuid = uuid::Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+
+    // entry j of column i is j shifted to byte i % 8, i.e. the hash value
+    // is the XOR of both halves of the UUID as little-endian integers
+    let mut table = [[0_u64; 256]; 16];
+    for (i, column) in table.iter_mut().enumerate() {
+        for (j, entry) in column.iter_mut().enumerate() {
+            *entry = (j as u64) << (8 * (i % 8));
+        }
+    }
+    let folded = TabBytes::with_table(table);
+    assert_eq!(
+        folded.hash_uuid(&uuid),
+        0x6F42_B110_4450_E567 ^ 0xC8E0_5F0E_68BB_4792
+    );
+
+    let simple: TabBytes<16> = TabBytes::new_with_seed(42);
+    assert_eq!(simple.hash_uuid(&uuid), 0xC631_EDE9_B383_D8CB);
+    assert_eq!(simple.hash_uuid(&uuid), simple.hash(uuid.as_bytes()));
+    let twisted: TabBytesTwisted<16> = TabBytesTwisted::new_with_seed(42);
+    assert_eq!(twisted.hash_uuid(&uuid), 0xCDD1_76E9_7D42_D68C);
+    assert_eq!(
+        twisted.hash_uuid(&uuid::Uuid::nil()),
+        twisted.hash(&[0; 16])
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn nonzero_keys() {
+    use std::num::{NonZeroU32, NonZeroU64};
+
+    let simple = Tab32Simple::new();
+    let twisted = Tab32Twisted::new();
+    let key = NonZeroU32::new(1337).unwrap();
+    assert_eq!(simple.hash_nonzero(key), simple.hash(1337));
+    assert_eq!(twisted.hash_nonzero(key), twisted.hash(1337));
+    let key = NonZeroU64::new(u64::MAX).unwrap();
+    assert_eq!(
+        Tab64Simple::new_with_seed(42).hash_nonzero(key),
+        Tab64Simple::new_with_seed(42).hash(u64::MAX)
+    );
+    assert_eq!(
+        Tab64Twisted::new_with_seed(42).hash_nonzero(key),
+        Tab64Twisted::new_with_seed(42).hash(u64::MAX)
+    );
+}
+
+#[test]
+fn nonzero_hash_values() {
+    // an all-zero table hashes every key to 0
+    let zero = Tab32Simple::with_table([[0; 256]; 4]);
+    assert_eq!(zero.hash_nonzero_out(42).get(), 1);
+    let zero = Tab64Twisted::with_table([[0; 256]; 8]);
+    assert_eq!(zero.hash_nonzero_out(42).get(), 1);
+
+    // modify a random table such that 1337 hashes to 0
+    let mut table = Tab32Simple::new_with_seed(42).get_table();
+    table[0][1337 & 0xFF] ^= Tab32Simple::with_table(table).hash(1337);
+    let simple = Tab32Simple::with_table(table);
+    assert_eq!(simple.hash(1337), 0);
+    assert_eq!(simple.hash_nonzero_out(1337).get(), 1);
+    assert_eq!(simple.hash_nonzero_out(42).get(), simple.hash(42));
+
+    let mut table = Tab64Simple::new_with_seed(42).get_table();
+    table[0][1337 & 0xFF] ^= Tab64Simple::with_table(table).hash(1337);
+    let simple = Tab64Simple::with_table(table);
+    assert_eq!(simple.hash(1337), 0);
+    assert_eq!(simple.hash_nonzero_out(1337).get(), 1);
+    for key in 0..1000 {
+        if key != 1337 {
+            assert_eq!(simple.hash_nonzero_out(key).get(), simple.hash(key));
+        }
+    }
+
+    // twisted hash values are the high bits of the XORed entries,
+    // so entries with only low bits set yield a hash value of 0
+    let mut table = [[0_u128; 256]; 8];
+    table[0][42] = 0xFF;
+    let twisted = Tab64Twisted::with_table(table);
+    assert_eq!(twisted.hash(42), 0);
+    assert_eq!(twisted.hash_nonzero_out(42).get(), 1);
+    let twisted = Tab32Twisted::with_table([[0xFF; 256]; 4]);
+    assert_eq!(twisted.hash(42), 0);
+    assert_eq!(twisted.hash_nonzero_out(42).get(), 1);
+}
+
+/// Chi-square statistic of buckets in `0..n`, grouped into 1000 ranges of equal size
+fn chi_square_of_buckets(buckets: &[u64], n: u64) -> f64 {
+    let groups = 1000;
+    let group = |b: u64| (u128::from(b) * groups as u128 / u128::from(n)) as usize;
+    let mut counts = vec![0_u64; groups];
+    for b in buckets.iter() {
+        assert!(*b < n);
+        counts[group(*b)] += 1;
+    }
+    // number of buckets per group, starting with bucket ceil(g * n / groups)
+    let start = |g: u128| (g * u128::from(n)).div_ceil(groups as u128) as f64;
+    counts
+        .iter()
+        .enumerate()
+        .map(|(g, count)| {
+            let size = start(g as u128 + 1) - start(g as u128);
+            let expected = buckets.len() as f64 * size / n as f64;
+            (*count as f64 - expected).powi(2) / expected
+        })
+        .sum()
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn range_reduction_32() {
+    let simple = Tab32Simple::new();
+    let twisted = Tab32Twisted::new();
+    for x in 0..1000 {
+        assert_eq!(simple.hash_to_range(x, 1), 0);
+        assert_eq!(twisted.hash_to_range(x, 1), 0);
+        assert!(simple.hash_to_range(x, 7) < 7);
+        assert!(simple.hash_to_range(x, u32::MAX) < u32::MAX);
+        assert!(twisted.hash_to_range(x, u32::MAX) < u32::MAX);
+    }
+    // the largest and smallest hash values map to the last and first bucket
+    let mut table = [[0; 256]; 4];
+    table[0] = [u32::MAX; 256];
+    let max = Tab32Simple::with_table(table);
+    assert_eq!(max.hash_to_range(0, u32::MAX), u32::MAX - 1);
+    assert_eq!(max.hash_to_range(0, 1000), 999);
+    let zero = Tab32Simple::with_table([[0; 256]; 4]);
+    assert_eq!(zero.hash_to_range(0, u32::MAX), 0);
+
+    // 999 degrees of freedom, the statistic exceeds 1300 with a probability below 1e-9
+    let n = 1_000_003;
+    let keys: Vec<u32> = (0..1_000_000).map(|_| rand::random()).collect();
+    let buckets: Vec<u64> = keys
+        .iter()
+        .map(|k| u64::from(simple.hash_to_range(*k, n)))
+        .collect();
+    assert!(chi_square_of_buckets(&buckets, u64::from(n)) < 1300.0);
+    let buckets: Vec<u64> = keys
+        .iter()
+        .map(|k| u64::from(twisted.hash_to_range(*k, n)))
+        .collect();
+    assert!(chi_square_of_buckets(&buckets, u64::from(n)) < 1300.0);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn range_reduction_64() {
+    let simple = Tab64Simple::new();
+    let twisted = Tab64Twisted::new();
+    for x in 0..1000 {
+        assert_eq!(simple.hash_to_range(x, 1), 0);
+        assert_eq!(twisted.hash_to_range(x, 1), 0);
+        for n in [7, u64::MAX - 1, u64::MAX].iter() {
+            assert!(simple.hash_to_range(x, *n) < *n);
+            assert!(twisted.hash_to_range(x, *n) < *n);
+        }
+    }
+    // the largest and smallest hash values map to the last and first bucket
+    let mut table = [[0; 256]; 8];
+    table[0] = [u64::MAX; 256];
+    let max = Tab64Simple::with_table(table);
+    assert_eq!(max.hash_to_range(0, u64::MAX), u64::MAX - 1);
+    assert_eq!(max.hash_to_range(0, 1000), 999);
+    let zero = Tab64Simple::with_table([[0; 256]; 8]);
+    assert_eq!(zero.hash_to_range(0, u64::MAX), 0);
+
+    // 999 degrees of freedom, the statistic exceeds 1300 with a probability below 1e-9
+    let n = 1_000_000_000_039;
+    let keys: Vec<u64> = (0..1_000_000).map(|_| rand::random()).collect();
+    let buckets: Vec<u64> = keys.iter().map(|k| simple.hash_to_range(*k, n)).collect();
+    assert!(chi_square_of_buckets(&buckets, n) < 1300.0);
+    let buckets: Vec<u64> = keys.iter().map(|k| twisted.hash_to_range(*k, n)).collect();
+    assert!(chi_square_of_buckets(&buckets, n) < 1300.0);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn two_choice_buckets() {
+    let simple = Tab64Simple::new();
+    let twisted = Tab64Twisted::new();
+    for x in 0..1000 {
+        assert_eq!(simple.two_choices(x, 1), (0, 0));
+        assert_eq!(twisted.two_choices(x, 1), (0, 0));
+        for n in [2, 7, 1000, u64::MAX].iter() {
+            let (a, b) = simple.two_choices(x, *n);
+            assert!(a < *n && b < *n);
+            let (a, b) = twisted.two_choices(x, *n);
+            assert!(a < *n && b < *n);
+        }
+    }
+    // the first candidate uses the high half, the second the low half of the hash value
+    let mut table = [[0; 256]; 8];
+    table[0] = [0xFFFF_FFFF_0000_0000; 256];
+    let high = Tab64Simple::with_table(table);
+    assert_eq!(high.two_choices(0, 1000), (999, 0));
+    table[0] = [0x0000_0000_FFFF_FFFF; 256];
+    let low = Tab64Simple::with_table(table);
+    assert_eq!(low.two_choices(0, 1000), (0, 999));
+    assert_eq!(low.choose_less_loaded(0, &[0; 1000]), 0);
+    let mut loads = [0; 1000];
+    loads[0] = 1;
+    assert_eq!(low.choose_less_loaded(0, &loads), 999);
+
+    // balls into bins: with two choices, the maximum load is O(log log n) instead of
+    // O(log n / log log n), i.e. about 4 instead of about 8 for this setup
+    let n = 100_000;
+    for seed in 0..3 {
+        let simple = Tab64Simple::new_with_seed(seed);
+        let twisted = Tab64Twisted::new_with_seed(seed);
+        let mut one_choice = vec![0; n];
+        let mut two_choices_simple = vec![0; n];
+        let mut two_choices_twisted = vec![0; n];
+        for x in 0..n as u64 {
+            one_choice[simple.hash_to_range(x, n as u64) as usize] += 1;
+            let bucket = simple.choose_less_loaded(x, &two_choices_simple);
+            two_choices_simple[bucket] += 1;
+            let bucket = twisted.choose_less_loaded(x, &two_choices_twisted);
+            two_choices_twisted[bucket] += 1;
+        }
+        let one_max = *one_choice.iter().max().unwrap();
+        let simple_max = *two_choices_simple.iter().max().unwrap();
+        let twisted_max = *two_choices_twisted.iter().max().unwrap();
+        assert!(one_max >= 6, "{}", one_max);
+        assert!(simple_max <= 4, "{}", simple_max);
+        assert!(twisted_max <= 4, "{}", twisted_max);
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn cuckoo_filter_primitives() {
+    let simple32 = Tab32Simple::new();
+    let simple64 = Tab64Simple::new();
+    for _ in 0..10_000 {
+        let x: u64 = rand::random();
+        for bits in [1, 4, 8, 12, 16].iter().copied() {
+            let fp = simple32.fingerprint(x as u32, bits);
+            assert!(fp != 0 && u32::from(fp) < 1 << bits);
+            let fp = simple64.fingerprint(x, bits);
+            assert!(fp != 0 && u32::from(fp) < 1 << bits);
+        }
+        let fp = simple64.fingerprint(x, 16);
+        for k in [0, 1, 10, 20].iter().copied() {
+            let nbuckets = 1 << k;
+            let bucket = simple64.hash_top_bits(x, k) as usize;
+            let alt = simple64.alt_bucket(bucket, fp, nbuckets);
+            assert!(alt < nbuckets);
+            assert_eq!(simple64.alt_bucket(alt, fp, nbuckets), bucket);
+            let alt = simple32.alt_bucket(bucket, fp, nbuckets);
+            assert!(alt < nbuckets);
+            assert_eq!(simple32.alt_bucket(alt, fp, nbuckets), bucket);
+        }
+    }
+    // all nonzero fingerprints occur, even for a table of zeros
+    let zero = Tab32Simple::with_table([[0; 256]; 4]);
+    assert_eq!(zero.fingerprint(0, 8), 1);
+    let mut seen = [false; 256];
+    for x in 0..100_000 {
+        seen[simple32.fingerprint(x, 8) as usize] = true;
+    }
+    assert!(!seen[0]);
+    assert!(seen[1..].iter().all(|s| *s));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn fingerprint_bit_allocation() {
+    // with this table, the hash value of a key is the key itself
+    let mut table = [[0; 256]; 8];
+    for (i, column) in table.iter_mut().enumerate() {
+        for (c, entry) in column.iter_mut().enumerate() {
+            *entry = (c as u64) << (8 * i);
+        }
+    }
+    let identity = Tab64Simple::with_table(table);
+    assert_eq!(identity.hash(0x0123_4567_89ab_cdef), 0x0123_4567_89ab_cdef);
+
+    assert_eq!(identity.fingerprint_with_zero(0xabcd, 16), 0xabcd);
+    assert_eq!(identity.fingerprint_with_zero(0xabcd, 8), 0xcd);
+    assert_eq!(identity.fingerprint_with_zero(0xabcd, 1), 1);
+    assert_eq!(identity.fingerprint_with_zero(0xffff_0000, 16), 0);
+    assert_eq!(identity.fingerprint(0, 8), 1);
+    assert_eq!(identity.fingerprint(0xffff, 8), 255);
+    assert_eq!(identity.fingerprint(0xffff, 16), 0xffff);
+    assert_eq!(identity.fingerprint(0x8000, 1), 1);
+    assert_eq!(identity.fingerprint(0x8000, 2), 2);
+
+    // fingerprints only depend on the lowest 16 bits, buckets on the top bits
+    for _ in 0..10_000 {
+        let (low, high): (u16, u64) = (rand::random(), rand::random::<u64>() << 16);
+        let x = high | u64::from(low);
+        for bits in [1, 4, 8, 12, 16].iter().copied() {
+            assert_eq!(
+                identity.fingerprint(x, bits),
+                identity.fingerprint(u64::from(low), bits)
+            );
+            assert_eq!(
+                identity.fingerprint_with_zero(x, bits),
+                identity.fingerprint_with_zero(u64::from(low), bits)
+            );
+        }
+        assert_eq!(
+            identity.hash_top_bits(x, 48),
+            identity.hash_top_bits(high, 48)
+        );
+        assert_eq!(
+            identity.hash_to_range(x, 1 << 20),
+            identity.hash_to_range(high, 1 << 20)
+        );
+    }
+
+    // the same contract holds for 32bit keys
+    let mut table = [[0; 256]; 4];
+    for (i, column) in table.iter_mut().enumerate() {
+        for (c, entry) in column.iter_mut().enumerate() {
+            *entry = (c as u32) << (8 * i);
+        }
+    }
+    let identity = Tab32Simple::with_table(table);
+    assert_eq!(identity.fingerprint(0xffff, 16), 0xffff);
+    assert_eq!(identity.fingerprint(0x8000, 2), 2);
+    for _ in 0..10_000 {
+        let (low, high): (u16, u32) = (rand::random(), rand::random::<u32>() << 16);
+        let x = high | u32::from(low);
+        for bits in [1, 4, 8, 12, 16].iter().copied() {
+            assert_eq!(
+                identity.fingerprint(x, bits),
+                identity.fingerprint(u32::from(low), bits)
+            );
+        }
+        assert_eq!(
+            identity.hash_top_bits(x, 16),
+            identity.hash_top_bits(high, 16)
+        );
+    }
+
+    let twisted = Tab64Twisted::new();
+    for x in 0..10_000 {
+        let low = twisted.hash(x) as u16;
+        assert_eq!(twisted.fingerprint_with_zero(x, 16), low);
+        assert_eq!(twisted.fingerprint_with_zero(x, 4), low & 0xf);
+        let fp = twisted.fingerprint(x, 12);
+        assert_eq!(u32::from(fp), ((u32::from(low) * 4095) >> 16) + 1);
+    }
+}
+
+#[test]
+#[should_panic(expected = "power of two")]
+fn cuckoo_buckets_power_of_two() {
+    Tab64Simple::new_with_seed(42).alt_bucket(0, 1, 1000);
+}
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+#[should_panic(expected = "at most 2^32 buckets")]
+fn cuckoo_buckets_too_many_for_32_bits() {
+    Tab32Simple::new_with_seed(42).alt_bucket(0, 1, 1 << 33);
+}
+
+#[test]
+#[should_panic(expected = "between 1 and 16 bits")]
+fn cuckoo_fingerprint_bits() {
+    Tab32Simple::new_with_seed(42).fingerprint(0, 17);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn bloom_filter_indices() {
+    let simple = Tab64Simple::new();
+    let twisted = Tab64Twisted::new();
+    for x in 0..1000 {
+        for m in [1, 2, 1000, 1 << 20].iter().copied() {
+            assert_eq!(simple.bloom_indices(x, 7, m).len(), 7);
+            assert!(simple.bloom_indices(x, 7, m).all(|i| i < m));
+            assert!(twisted.bloom_indices(x, 7, m).all(|i| i < m));
+        }
+        assert_eq!(simple.bloom_indices(x, 0, 1000).count(), 0);
+    }
+    // positions follow h1 + i * h2 with the halves of the hash value
+    let mut table = [[0; 256]; 8];
+    table[0] = [0x0000_0005_0000_0003; 256];
+    let fixed = Tab64Simple::with_table(table);
+    let indices: Vec<usize> = fixed.bloom_indices(0, 5, 7).collect();
+    assert_eq!(indices, vec![5, 1, 4, 0, 3]);
+
+    // the false positive rate is close to (1 - e^(-kn/m))^k
+    for (k, m, n) in [(7, 10_000, 1000), (3, 4096, 1000), (4, 1 << 16, 10_000)]
+        .iter()
+        .copied()
+    {
+        let expected = (1.0 - (-f64::from(k) * n as f64 / m as f64).exp()).powi(k as i32);
+        for seed in 0..2 {
+            let simple = Tab64Simple::new_with_seed(seed);
+            let twisted = Tab64Twisted::new_with_seed(seed);
+            let mut simple_filter = vec![false; m];
+            let mut twisted_filter = vec![false; m];
+            for x in 0..n as u64 {
+                simple
+                    .bloom_indices(x, k, m)
+                    .for_each(|i| simple_filter[i] = true);
+                twisted
+                    .bloom_indices(x, k, m)
+                    .for_each(|i| twisted_filter[i] = true);
+            }
+            let queries = 200_000;
+            let mut simple_false_positives = 0;
+            let mut twisted_false_positives = 0;
+            for x in n as u64..(n + queries) as u64 {
+                if simple.bloom_indices(x, k, m).all(|i| simple_filter[i]) {
+                    simple_false_positives += 1;
+                }
+                if twisted.bloom_indices(x, k, m).all(|i| twisted_filter[i]) {
+                    twisted_false_positives += 1;
+                }
+            }
+            for false_positives in [simple_false_positives, twisted_false_positives].iter() {
+                let rate = f64::from(*false_positives) / queries as f64;
+                assert!(
+                    (rate - expected).abs() < 0.2 * expected,
+                    "false positive rate {} for k = {}, m = {}, n = {}, expected {}",
+                    rate,
+                    k,
+                    m,
+                    n,
+                    expected
+                );
+            }
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn unit_interval() {
+    let mut table = [[0; 256]; 4];
+    table[0] = [u32::MAX; 256];
+    assert_eq!(
+        Tab32Simple::with_table(table).hash_unit(0),
+        1.0 - 2_f64.powi(-32)
+    );
+    assert_eq!(Tab32Simple::with_table([[0; 256]; 4]).hash_unit(0), 0.0);
+    table[0] = [0x8000_0000; 256];
+    assert_eq!(Tab32Simple::with_table(table).hash_unit(0), 0.5);
+    let mut table = [[0; 256]; 8];
+    table[0] = [u64::MAX; 256];
+    let max = Tab64Simple::with_table(table).hash_unit(0);
+    assert_eq!(max, 1.0 - 2_f64.powi(-53));
+    assert!(max < 1.0);
+    assert_eq!(Tab64Simple::with_table([[0; 256]; 8]).hash_unit(0), 0.0);
+    // bits below the mantissa are dropped
+    table[0] = [0x7FF; 256];
+    assert_eq!(Tab64Simple::with_table(table).hash_unit(0), 0.0);
+    let mut table = [[0; 256]; 8];
+    table[7] = [u128::MAX; 256];
+    assert_eq!(
+        Tab64Twisted::with_table(table).hash_unit(0),
+        1.0 - 2_f64.powi(-53)
+    );
+
+    // the standard deviation of the mean of 100000 values is below 0.001
+    let simple32 = Tab32Simple::new();
+    let twisted32 = Tab32Twisted::new();
+    let simple64 = Tab64Simple::new();
+    let twisted64 = Tab64Twisted::new();
+    let keys: Vec<u64> = (0..100_000).map(|_| rand::random()).collect();
+    let means = [
+        keys.iter()
+            .map(|k| simple32.hash_unit(*k as u32))
+            .sum::<f64>(),
+        keys.iter()
+            .map(|k| twisted32.hash_unit(*k as u32))
+            .sum::<f64>(),
+        keys.iter().map(|k| simple64.hash_unit(*k)).sum::<f64>(),
+        keys.iter().map(|k| twisted64.hash_unit(*k)).sum::<f64>(),
+    ];
+    for mean in means.iter().map(|sum| sum / keys.len() as f64) {
+        assert!((mean - 0.5).abs() < 0.01);
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn bit_extraction() {
+    let simple = Tab64Simple::new();
+    let twisted = Tab64Twisted::new();
+    for x in 0..100 {
+        let h = simple.hash(x);
+        assert_eq!(simple.hash_top_bits(x, 0), 0);
+        assert_eq!(simple.hash_top_bits(x, 1), h >> 63);
+        assert_eq!(simple.hash_top_bits(x, 14), h >> 50);
+        assert_eq!(simple.hash_top_bits(x, 64), h);
+        assert_eq!(simple.hash_split_bits(x, 0), (0, h));
+        assert_eq!(simple.hash_split_bits(x, 64), (h, 0));
+        for p in 0..=64 {
+            let (top, low) = simple.hash_split_bits(x, p);
+            assert_eq!(top, simple.hash_top_bits(x, p));
+            assert_eq!(low.checked_shr(64 - p).unwrap_or(0), 0);
+            assert_eq!(top.checked_shl(64 - p).unwrap_or(0) | low, h);
+            let (top, low) = twisted.hash_split_bits(x, p);
+            assert_eq!(top.checked_shl(64 - p).unwrap_or(0) | low, twisted.hash(x));
+        }
+    }
+
+    let simple = Tab32Simple::new();
+    let twisted = Tab32Twisted::new();
+    for x in 0..100 {
+        let h = simple.hash(x);
+        assert_eq!(simple.hash_top_bits(x, 0), 0);
+        assert_eq!(simple.hash_top_bits(x, 12), h >> 20);
+        assert_eq!(simple.hash_top_bits(x, 32), h);
+        assert_eq!(simple.hash_split_bits(x, 0), (0, h));
+        assert_eq!(simple.hash_split_bits(x, 32), (h, 0));
+        assert_eq!(simple.hash_split_bits(x, 4), (h >> 28, h & 0x0FFF_FFFF));
+        for p in 0..=32 {
+            let (top, low) = twisted.hash_split_bits(x, p);
+            assert_eq!(top, twisted.hash_top_bits(x, p));
+            assert_eq!(top.checked_shl(32 - p).unwrap_or(0) | low, twisted.hash(x));
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+#[should_panic(expected = "cannot take 65 bits")]
+fn bit_extraction_out_of_range() {
+    Tab64Simple::new().hash_split_bits(42, 65);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn twisted_hash_pairs() {
+    // column i holds the key byte in bits 8 * i and 32 + 8 * i,
+    // so both halves spell out the key unless the twist changes the last byte
+    let mut table = [[0_u64; 256]; 4];
+    for (i, column) in table.iter_mut().enumerate() {
+        for (j, entry) in column.iter_mut().enumerate() {
+            *entry = ((j as u64) << (32 + 8 * i)) | ((j as u64) << (8 * i));
+        }
+    }
+    let twisted = Tab32Twisted::with_table(table);
+    // the lowest byte of the first three entries is 0x01, so the last byte is twisted
+    assert_eq!(twisted.hash_pair(0x0000_0001), (0x0100_0001, 0x0100_0001));
+    assert_eq!(twisted.hash_pair(0x0403_0200), (0x0403_0200, 0x0403_0200));
+
+    let twisted = Tab32Twisted::new();
+    for x in 0..1000 {
+        let (high, low) = twisted.hash_pair(x);
+        assert_eq!(high, twisted.hash(x));
+        assert_eq!(twisted.hash_pair(x), (high, low));
+    }
+    let lows: std::collections::HashSet<u32> = (0..1000).map(|x| twisted.hash_pair(x).1).collect();
+    assert!(lows.len() > 990);
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn twisted_hash_pairs_serialization() {
+    let twisted = Tab32Twisted::new();
+    let serialized = bincode::serialize(&twisted).unwrap();
+    let deserialized: Tab32Twisted = bincode::deserialize(&serialized).unwrap();
+    for x in 0..1000 {
+        assert_eq!(twisted.hash_pair(x), deserialized.hash_pair(x));
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn wide_hash_values_for_32bit_keys() {
+    let simple = Tab32To64Simple::new_with_seed(42);
+    assert_eq!(simple.hash(0), 0x2F9F_30DE_10C1_BC1D);
+    assert_eq!(simple.hash(1337), 0x12D6_ACB8_2471_FA63);
+    assert_eq!(simple.hash(0xDEAD_BEEF), 0xF56E_85E7_7510_30D6);
+    let twisted = Tab32To64Twisted::new_with_seed(42);
+    assert_eq!(twisted.hash(0), 0xF2D3_FC94_2EE3_9A79);
+    assert_eq!(twisted.hash(1337), 0xDA8C_D1A9_0E8F_C5DC);
+    assert_eq!(twisted.hash(0xDEAD_BEEF), 0x6162_577C_3558_4B1F);
+
+    // the state of Tab32Twisted is simple tabulation of the key with a twisted last byte
+    let simple = Tab32To64Simple::new();
+    let table = simple.get_table();
+    let twisted = Tab32Twisted::with_table(table);
+    for x in 0..1000_u32 {
+        // lowest byte of the XOR of the first three entries
+        let twist = (simple.hash(x & 0x00FF_FFFF) ^ table[3][0]) as u32 & 0xFF;
+        let (high, low) = twisted.hash_pair(x ^ (twist << 24));
+        assert_eq!((u64::from(high) << 32) | u64::from(low), simple.hash(x));
+    }
+
+    let simple = Tab32To64Simple::new();
+    assert_eq!(simple, Tab32To64Simple::from_vec(simple.to_vec()));
+    assert_eq!(
+        simple,
+        Tab32To64Simple::from_le_bytes(&simple.to_le_bytes())
+    );
+    let twisted = Tab32To64Twisted::new();
+    assert_eq!(twisted, Tab32To64Twisted::from_vec(twisted.to_vec()));
+    assert_eq!(
+        twisted,
+        Tab32To64Twisted::from_le_bytes(&twisted.to_le_bytes())
+    );
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn wide_hash_values_for_32bit_keys_serialization() {
+    let simple = Tab32To64Simple::new();
+    let serialized = bincode::serialize(&simple).unwrap();
+    let deserialized: Tab32To64Simple = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(simple, deserialized);
+    let twisted = Tab32To64Twisted::new();
+    let serialized = bincode::serialize(&twisted).unwrap();
+    let deserialized: Tab32To64Twisted = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(twisted, deserialized);
+    assert_eq!(twisted.hash(1337), deserialized.hash(1337));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn narrow_hash_values_for_64bit_keys() {
+    // entry j of column i is j rotated by 4 * i bits, i.e. byte i of the key
+    // ends up in the i-th nibble of the hash value
+    let mut table = [[0_u32; 256]; 8];
+    for (i, column) in table.iter_mut().enumerate() {
+        for (j, entry) in column.iter_mut().enumerate() {
+            *entry = (j as u32).rotate_left(4 * i as u32);
+        }
+    }
+    let simple = Tab64To32Simple::with_table(table);
+    assert_eq!(simple.hash(0x0807_0605_0403_0201), 0x8765_4321);
+    assert_eq!(simple.hash(0x0000_0000_0000_00F0), 0x0000_00F0);
+    // entries wrap around
+    assert_eq!(simple.hash(0xF000_0000_0000_0000), 0x0000_000F);
+
+    // the same seed yields the truncated table, other constructors do not
+    let narrow = Tab64To32Simple::new_with_seed(42);
+    let wide = Tab64Simple::new_with_seed(42);
+    assert_eq!(narrow.hash(1337), wide.hash(1337) as u32);
+    let narrow = Tab64To32Simple::from_seed_bytes([42; 32]);
+    let wide = Tab64Simple::from_seed_bytes([42; 32]);
+    assert_ne!(narrow.hash(1337), wide.hash(1337) as u32);
+    let mut table = [[0_u32; 256]; 8];
+    for (column, wide_column) in table.iter_mut().zip(wide.get_table().iter()) {
+        for (entry, wide_entry) in column.iter_mut().zip(wide_column.iter()) {
+            *entry = *wide_entry as u32;
+        }
+    }
+    let truncated = Tab64To32Simple::with_table(table);
+    for x in 0..1000 {
+        assert_eq!(truncated.hash(x), wide.hash(x) as u32);
+    }
+
+    let simple = Tab64To32Simple::new();
+    assert_eq!(simple, Tab64To32Simple::from_vec(simple.to_vec()));
+    assert_eq!(
+        simple,
+        Tab64To32Simple::from_le_bytes(&simple.to_le_bytes())
+    );
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn narrow_hash_values_for_64bit_keys_serialization() {
+    let simple = Tab64To32Simple::new();
+    let serialized = bincode::serialize(&simple).unwrap();
+    let deserialized: Tab64To32Simple = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(simple, deserialized);
+    assert_eq!(simple.hash(1337), deserialized.hash(1337));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn probe_sequences() {
+    let twisted = Tab32Twisted::new();
+    for capacity in [0, 1, 2, 3, 7, 8, 12, 64, 100, 1000, 1024].iter().copied() {
+        for x in 0..100 {
+            let probes: Vec<usize> = twisted.probe_sequence(x, capacity).collect();
+            assert_eq!(probes.len(), capacity);
+            assert_eq!(twisted.probe_sequence(x, capacity).len(), capacity);
+            let mut visited = vec![false; capacity];
+            for probe in probes.iter() {
+                assert!(!visited[*probe]);
+                visited[*probe] = true;
+            }
+            if capacity > 0 {
+                let (h1, h2) = twisted.hash_pair(x);
+                assert_eq!(probes[0], h1 as usize % capacity);
+                let step = (probes[1 % capacity] + capacity - probes[0]) % capacity;
+                if capacity.is_power_of_two() && capacity > 1 {
+                    assert_eq!(step % 2, 1);
+                }
+                if capacity == 1024 {
+                    // an even step is increased by one to make it coprime to the capacity
+                    let step_from_hash = 1 + h2 as usize % (capacity - 1);
+                    assert_eq!(step, step_from_hash | 1);
+                }
+                // repeating the sequence continues with the same step
+                let cycled: Vec<usize> = twisted
+                    .probe_sequence(x, capacity)
+                    .cycle()
+                    .take(2 * capacity + 1)
+                    .collect();
+                assert_eq!(cycled[capacity], probes[0]);
+                assert_eq!(cycled[2 * capacity], probes[0]);
+            }
+        }
+    }
+    // probe sequences differ between keys
+    let a: Vec<usize> = twisted.probe_sequence(1, 1024).collect();
+    let b: Vec<usize> = twisted.probe_sequence(2, 1024).collect();
+    assert_ne!(a, b);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn count_min_sketch() {
+    // with few keys and wide rows, counts are exact
+    let mut sketch: CountMinSketch = CountMinSketch::new_with_seed(1 << 16, 4, 42);
+    assert_eq!((sketch.width(), sketch.depth()), (1 << 16, 4));
+    for key in 0..10 {
+        sketch.insert(key, key + 1);
+        sketch.insert(key, 1);
+    }
+    for key in 0..10 {
+        assert_eq!(sketch.estimate(key), key + 2);
+    }
+    assert_eq!(sketch.estimate(1000), 0);
+
+    // estimates never underestimate, even for narrow rows
+    let mut sketch: CountMinSketch<Tab64Twisted> = CountMinSketch::new(64, 3);
+    let mut counts = std::collections::HashMap::new();
+    for _ in 0..10_000 {
+        let key = rand::random::<u64>() % 1000;
+        sketch.insert(key, 1);
+        *counts.entry(key).or_insert(0) += 1;
+    }
+    for (key, count) in counts.iter() {
+        assert!(sketch.estimate(*key) >= *count);
+    }
+
+    let hash_functions = vec![Tab64Simple::new_with_seed(1), Tab64Simple::new_with_seed(2)];
+    let sketch = CountMinSketch::with_hash_functions(10, hash_functions.clone());
+    assert_eq!(sketch.hash_functions(), &hash_functions[..]);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn count_min_sketch_merge() {
+    let stream: Vec<u64> = (0..10_000).map(|_| rand::random::<u64>() % 500).collect();
+    let (first, second) = stream.split_at(4000);
+    let mut a: CountMinSketch = CountMinSketch::new_with_seed(128, 4, 7);
+    let mut b: CountMinSketch = CountMinSketch::new_with_seed(128, 4, 7);
+    let mut both: CountMinSketch = CountMinSketch::new_with_seed(128, 4, 7);
+    first.iter().for_each(|key| a.insert(*key, 1));
+    second.iter().for_each(|key| b.insert(*key, 1));
+    stream.iter().for_each(|key| both.insert(*key, 1));
+    assert_eq!(a.merge(&b), Ok(()));
+    assert_eq!(a, both);
+
+    let other_seed: CountMinSketch = CountMinSketch::new_with_seed(128, 4, 8);
+    assert_eq!(
+        a.merge(&other_seed),
+        Err(MergeError::HashFunction { row: 0 })
+    );
+    let other_width: CountMinSketch = CountMinSketch::new_with_seed(256, 4, 7);
+    assert_eq!(
+        a.merge(&other_width),
+        Err(MergeError::Dimensions {
+            expected: (128, 4),
+            actual: (256, 4)
+        })
+    );
+    // failed merges leave the sketch unchanged
+    assert_eq!(a, both);
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn count_min_sketch_serialization() {
+    let mut sketch: CountMinSketch = CountMinSketch::new(100, 3);
+    for key in 0..1000 {
+        sketch.insert(key, key % 7);
+    }
+    let serialized = bincode::serialize(&sketch).unwrap();
+    let mut deserialized: CountMinSketch = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(deserialized, sketch);
+    // a restored sketch continues counting with the same hash functions
+    deserialized.insert(5, 1);
+    sketch.insert(5, 1);
+    assert_eq!(deserialized, sketch);
+    assert_eq!(deserialized.merge(&sketch), Ok(()));
+
+    // counters have to match the dimensions
+    let invalid =
+        bincode::serialize(&(100_usize, vec![Tab64Simple::new()], vec![0_u64; 99])).unwrap();
+    assert!(bincode::deserialize::<CountMinSketch>(&invalid).is_err());
+    let empty =
+        bincode::serialize(&(0_usize, Vec::<Tab64Simple>::new(), Vec::<u64>::new())).unwrap();
+    assert!(bincode::deserialize::<CountMinSketch>(&empty).is_err());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn sign_hashing() {
+    let simple = Tab64Simple::new();
+    let twisted = Tab64Twisted::new();
+    let keys: Vec<u64> = (0..100_000).map(|_| rand::random()).collect();
+    let simple_positive = keys.iter().filter(|k| simple.hash_sign(**k) == 1).count();
+    let twisted_positive = keys.iter().filter(|k| twisted.hash_sign(**k) == 1).count();
+    // the standard deviation of the number of positive signs is about 158
+    assert!((simple_positive as i64 - 50_000).abs() < 1000);
+    assert!((twisted_positive as i64 - 50_000).abs() < 1000);
+    for k in keys.iter().take(1000) {
+        assert!(simple.hash_sign(*k) == 1 || simple.hash_sign(*k) == -1);
+        let (bucket, sign) = simple.hash_bucket_sign(*k, 1000);
+        assert!(bucket < 1000);
+        assert_eq!(sign, simple.hash_sign(*k));
+        let (bucket, sign) = twisted.hash_bucket_sign(*k, 1000);
+        assert!(bucket < 1000);
+        assert_eq!(sign, twisted.hash_sign(*k));
+    }
+
+    // the sign is the lowest bit, the bucket uses the remaining bits
+    let mut table = [[0; 256]; 8];
+    table[0] = [1; 256];
+    assert_eq!(
+        Tab64Simple::with_table(table).hash_bucket_sign(0, 1000),
+        (0, -1)
+    );
+    table[0] = [u64::MAX - 1; 256];
+    assert_eq!(
+        Tab64Simple::with_table(table).hash_bucket_sign(0, 1000),
+        (999, 1)
+    );
+
+    // a restored table yields the same buckets and signs
+    let restored_simple = Tab64Simple::from_vec(simple.to_vec());
+    let restored_twisted = Tab64Twisted::from_le_bytes(&twisted.to_le_bytes());
+    for k in keys.iter() {
+        assert_eq!(
+            restored_simple.hash_bucket_sign(*k, 4096),
+            simple.hash_bucket_sign(*k, 4096)
+        );
+        assert_eq!(
+            restored_twisted.hash_bucket_sign(*k, 4096),
+            twisted.hash_bucket_sign(*k, 4096)
+        );
+    }
+}
+
+/// Exact second frequency moment of a stream of keys with deltas
+fn exact_f2(stream: &[(u64, i64)]) -> f64 {
+    let mut counts = std::collections::HashMap::new();
+    for (key, delta) in stream.iter() {
+        *counts.entry(*key).or_insert(0) += delta;
+    }
+    counts.values().map(|c| (*c as f64).powi(2)).sum()
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn ams_sketch() {
+    // a single key is estimated exactly
+    let mut sketch: AmsSketch = AmsSketch::new_with_seed(3, 16, 42);
+    assert_eq!((sketch.rows(), sketch.columns()), (3, 16));
+    assert_eq!(sketch.estimate_f2(), 0.0);
+    sketch.update(7, 5);
+    sketch.update(7, -2);
+    assert_eq!(sketch.estimate_f2(), 9.0);
+    sketch.update(7, -3);
+    assert_eq!(sketch.estimate_f2(), 0.0);
+
+    // with 1024 columns, the relative error of a row has a standard deviation of
+    // about sqrt(2 / 1024) = 0.044, the median of 7 rows is within 0.15 with
+    // a probability of about 1 - 1e-9
+    let zipf: Vec<(u64, i64)> = (0..2000_u64)
+        .flat_map(|key| std::iter::repeat_n((key, 1), 1 + 2000 / (key as usize + 1)))
+        .collect();
+    let uniform: Vec<(u64, i64)> = (0..100_000)
+        .map(|_| {
+            (
+                rand::random::<u64>() % 10_000,
+                1 + rand::random::<i64>() % 5,
+            )
+        })
+        .collect();
+    // deleting most occurrences of the uniform keys again
+    let mut deletions = uniform.clone();
+    deletions.extend(uniform.iter().skip(1000).map(|(key, delta)| (*key, -delta)));
+    for stream in [zipf, uniform, deletions].iter() {
+        let exact = exact_f2(stream);
+        let mut simple: AmsSketch = AmsSketch::new(7, 1024);
+        let mut twisted: AmsSketch<Tab64Twisted> = AmsSketch::new(7, 1024);
+        for (key, delta) in stream.iter() {
+            simple.update(*key, *delta);
+            twisted.update(*key, *delta);
+        }
+        for estimate in [simple.estimate_f2(), twisted.estimate_f2()].iter() {
+            assert!(
+                (estimate - exact).abs() < 0.15 * exact,
+                "estimated {} instead of {}",
+                estimate,
+                exact
+            );
+        }
+    }
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn ams_sketch_serialization() {
+    let mut sketch: AmsSketch = AmsSketch::new(5, 100);
+    for key in 0..1000 {
+        sketch.update(key, key as i64 % 7 - 3);
+    }
+    let serialized = bincode::serialize(&sketch).unwrap();
+    let mut deserialized: AmsSketch = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(deserialized, sketch);
+    assert_eq!(deserialized.estimate_f2(), sketch.estimate_f2());
+    // a restored sketch continues with the same hash functions
+    deserialized.update(5, -1);
+    sketch.update(5, -1);
+    assert_eq!(deserialized, sketch);
+
+    let invalid =
+        bincode::serialize(&(100_usize, vec![Tab64Simple::new()], vec![0_i64; 99])).unwrap();
+    assert!(bincode::deserialize::<AmsSketch>(&invalid).is_err());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn hyperloglog_parts() {
+    let with_hash_value = |h: u64| {
+        let mut table = [[0; 256]; 8];
+        table[0] = [h; 256];
+        Tab64Simple::with_table(table)
+    };
+    assert_eq!(
+        with_hash_value(0xF000_0000_0000_0000).hll_parts(0, 4),
+        (15, 61)
+    );
+    assert_eq!(
+        with_hash_value(0x0800_0000_0000_0000).hll_parts(0, 4),
+        (0, 1)
+    );
+    assert_eq!(
+        with_hash_value(0x0000_0000_0000_0001).hll_parts(0, 4),
+        (0, 60)
+    );
+    assert_eq!(
+        with_hash_value(0x0000_0000_0000_0001).hll_parts(0, 18),
+        (0, 46)
+    );
+    assert_eq!(
+        with_hash_value(u64::MAX).hll_parts(0, 18),
+        ((1 << 18) - 1, 1)
+    );
+    assert_eq!(
+        with_hash_value(0xFFFF_C000_0000_0000).hll_parts(0, 18),
+        ((1 << 18) - 1, 47)
+    );
+
+    let twisted = Tab64Twisted::new();
+    for x in 0..10_000 {
+        for p in 4..=18 {
+            let (index, rank) = twisted.hll_parts(x, p);
+            assert!(index < 1 << p);
+            assert!(rank >= 1 && rank <= 65 - p);
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "4..=18")]
+fn hyperloglog_precision() {
+    Tab64Twisted::new_with_seed(42).hll_parts(0, 19);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn hyperloglog_estimates() {
+    // the relative standard error is 1.04 / sqrt(2^12) = 1.6%, allow for 5 of them
+    for cardinality in [10, 1000, 10_000, 200_000].iter().copied() {
+        let mut sketch: HyperLogLog = HyperLogLog::new(12);
+        let mut simple: HyperLogLog<Tab64Simple> = HyperLogLog::new(12);
+        let offset: u64 = rand::random();
+        for i in 0..2 * cardinality {
+            // every key occurs twice
+            let key = offset.wrapping_add(i % cardinality);
+            sketch.insert(key);
+            simple.insert(key);
+        }
+        for estimate in [sketch.estimate(), simple.estimate()].iter() {
+            let error = (estimate - cardinality as f64).abs() / cardinality as f64;
+            assert!(
+                error < 0.08,
+                "estimated {} instead of {}",
+                estimate,
+                cardinality
+            );
+        }
+    }
+    let empty: HyperLogLog = HyperLogLog::new_with_seed(4, 42);
+    assert_eq!(empty.estimate(), 0.0);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn hyperloglog_merge() {
+    let mut a: HyperLogLog = HyperLogLog::new_with_seed(10, 7);
+    let mut b: HyperLogLog = HyperLogLog::new_with_seed(10, 7);
+    let mut both: HyperLogLog = HyperLogLog::new_with_seed(10, 7);
+    for key in 0..5000 {
+        a.insert(key);
+        both.insert(key);
+    }
+    for key in 3000..9000 {
+        b.insert(key);
+        both.insert(key);
+    }
+    let mut ab = a.clone();
+    assert_eq!(ab.merge(&b), Ok(()));
+    let mut ba = b.clone();
+    assert_eq!(ba.merge(&a), Ok(()));
+    assert_eq!(ab, ba);
+    assert_eq!(ab, both);
+    let merged = ab.clone();
+    assert_eq!(ab.merge(&b), Ok(()));
+    assert_eq!(ab.merge(&merged), Ok(()));
+    assert_eq!(ab, merged);
+    assert_eq!(ab.registers().len(), 1024);
+
+    let mut other_seed: HyperLogLog = HyperLogLog::new_with_seed(10, 8);
+    assert_eq!(
+        other_seed.merge(&a),
+        Err(MergeError::HashFunction { row: 0 })
+    );
+    let other_precision: HyperLogLog = HyperLogLog::new_with_seed(11, 7);
+    assert_eq!(
+        a.merge(&other_precision),
+        Err(MergeError::Precision {
+            expected: 10,
+            actual: 11
+        })
+    );
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn hyperloglog_serialization() {
+    let mut sketch: HyperLogLog = HyperLogLog::new(8);
+    (0..1000).for_each(|key| sketch.insert(key));
+    let serialized = bincode::serialize(&sketch).unwrap();
+    let deserialized: HyperLogLog = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(deserialized, sketch);
+    assert_eq!(deserialized.precision(), 8);
+
+    let hash_function = Tab64Twisted::new();
+    let too_few = bincode::serialize(&(8_u8, &hash_function, vec![0_u8; 255])).unwrap();
+    assert!(bincode::deserialize::<HyperLogLog>(&too_few).is_err());
+    let rank_too_large = bincode::serialize(&(8_u8, &hash_function, vec![58_u8; 256])).unwrap();
+    assert!(bincode::deserialize::<HyperLogLog>(&rank_too_large).is_err());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn minhash_jaccard() {
+    for k in [64, 256].iter().copied() {
+        // overlaps of 0, 1000 (1/3), 1800 (9/11) and 2000 keys (identical sets)
+        for shift in [2000, 1000, 200, 0].iter().copied() {
+            let start: u64 = rand::random::<u64>() >> 1;
+            let mut a: MinHash = MinHash::new(k);
+            let mut b = MinHash::with_hash_functions(a.hash_functions().to_vec());
+            (start..start + 2000).for_each(|key| a.insert(key));
+            (start + shift..start + shift + 2000).for_each(|key| b.insert(key));
+            let overlap = 2000 - shift;
+            let exact = overlap as f64 / (4000 - overlap) as f64;
+            // allow for 5 standard errors, but at least 0.01 for disjoint and identical sets
+            let tolerance = (5.0 * (exact * (1.0 - exact) / k as f64).sqrt()).max(0.01);
+            let estimate = a.jaccard(&b).unwrap();
+            assert!(
+                (estimate - exact).abs() < tolerance,
+                "estimated {} instead of {} with k = {}",
+                estimate,
+                exact,
+                k
+            );
+        }
+    }
+    let empty: MinHash<Tab64Twisted> = MinHash::new_with_seed(16, 42);
+    assert_eq!(empty.signature(), &[u64::MAX; 16][..]);
+    assert_eq!(empty.jaccard(&empty), Ok(1.0));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn minhash_merge() {
+    let mut a: MinHash = MinHash::new_with_seed(64, 7);
+    let mut b: MinHash = MinHash::new_with_seed(64, 7);
+    let mut union: MinHash = MinHash::new_with_seed(64, 7);
+    for key in 0..1000 {
+        a.insert(key);
+        union.insert(key);
+    }
+    for key in 800..3000 {
+        b.insert(key * 3);
+        union.insert(key * 3);
+    }
+    assert_eq!(a.merge(&b), Ok(()));
+    assert_eq!(a, union);
+    assert_eq!(a.signature(), union.signature());
+
+    let other_seed: MinHash = MinHash::new_with_seed(64, 8);
+    assert_eq!(
+        a.jaccard(&other_seed),
+        Err(MergeError::HashFunction { row: 0 })
+    );
+    let mut other_k: MinHash = MinHash::new_with_seed(32, 7);
+    assert_eq!(
+        other_k.merge(&a),
+        Err(MergeError::Dimensions {
+            expected: (32, 1),
+            actual: (64, 1)
+        })
+    );
+    // the first 32 hash functions are shared
+    let mut functions = a.hash_functions()[..32].to_vec();
+    functions.push(Tab64Simple::new());
+    let partially_shared = MinHash::with_hash_functions(functions);
+    assert_eq!(
+        MinHash::with_hash_functions(a.hash_functions()[..33].to_vec()).jaccard(&partially_shared),
+        Err(MergeError::HashFunction { row: 32 })
+    );
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn minhash_serialization() {
+    let mut signature: MinHash = MinHash::new(16);
+    (0..1000).for_each(|key| signature.insert(key));
+    let serialized = bincode::serialize(&signature).unwrap();
+    let deserialized: MinHash = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(deserialized, signature);
+    assert_eq!(deserialized.jaccard(&signature), Ok(1.0));
+
+    let mismatched = bincode::serialize(&(vec![Tab64Simple::new()], vec![0_u64; 2])).unwrap();
+    assert!(bincode::deserialize::<MinHash>(&mismatched).is_err());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn one_permutation_minhash_accuracy() {
+    // sets of 2000 keys with an overlap of 1000, i.e. a Jaccard similarity of 1/3
+    let exact = 1.0 / 3.0;
+    let (mut squared_error, mut squared_error_one_perm) = (0.0, 0.0);
+    let trials = 30;
+    for seed in 0..trials {
+        let mut a: MinHash = MinHash::new_with_seed(128, seed);
+        let mut b = MinHash::with_hash_functions(a.hash_functions().to_vec());
+        let mut a_one_perm: OnePermMinHash = OnePermMinHash::new_with_seed(128, seed);
+        let mut b_one_perm: OnePermMinHash = OnePermMinHash::new_with_seed(128, seed);
+        for key in 0..2000 {
+            a.insert(key);
+            a_one_perm.insert(key);
+            b.insert(key + 1000);
+            b_one_perm.insert(key + 1000);
+        }
+        squared_error += (a.jaccard(&b).unwrap() - exact).powi(2);
+        squared_error_one_perm += (a_one_perm.jaccard(&b_one_perm).unwrap() - exact).powi(2);
+    }
+    // the variance of both is about J * (1 - J) / k = 0.0017
+    let mse = squared_error / trials as f64;
+    let mse_one_perm = squared_error_one_perm / trials as f64;
+    assert!(mse < 0.004, "{}", mse);
+    assert!(mse_one_perm < 0.004, "{}", mse_one_perm);
+    assert!(mse_one_perm < 2.0 * mse, "{} {}", mse_one_perm, mse);
+
+    let mut twisted: OnePermMinHash<Tab64Twisted> = OnePermMinHash::new(256);
+    let mut other = OnePermMinHash::with_hash_function(256, twisted.hash_function().clone());
+    (0..5000).for_each(|key| twisted.insert(key));
+    (0..5000).for_each(|key| other.insert(key * 2));
+    let estimate = twisted.jaccard(&other).unwrap();
+    assert!((estimate - exact).abs() < 0.15, "{}", estimate);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn one_permutation_minhash_small_sets() {
+    // most bins of small sets are empty, but densification fills all of them
+    let mut single: OnePermMinHash = OnePermMinHash::new(256);
+    single.insert(42);
+    let signature = single.signature();
+    assert!(signature
+        .iter()
+        .all(|v| *v == signature[0] && *v != u64::MAX));
+
+    let mut estimates = 0.0;
+    for seed in 0..200 {
+        let mut a: OnePermMinHash = OnePermMinHash::new_with_seed(256, seed);
+        let mut b: OnePermMinHash = OnePermMinHash::new_with_seed(256, seed);
+        (0..10).for_each(|key| a.insert(key));
+        (5..15).for_each(|key| b.insert(key));
+        assert!(a.signature().iter().all(|v| *v != u64::MAX));
+        assert_eq!(a.jaccard(&a), Ok(1.0));
+        estimates += a.jaccard(&b).unwrap();
+    }
+    // densification keeps the estimate unbiased
+    let mean = estimates / 200.0;
+    assert!((mean - 1.0 / 3.0).abs() < 0.05, "{}", mean);
+
+    let empty: OnePermMinHash = OnePermMinHash::new_with_seed(16, 42);
+    assert!(empty.is_empty());
+    assert_eq!(empty.signature(), vec![u64::MAX; 16]);
+    assert_eq!(empty.jaccard(&empty), Ok(1.0));
+    let mut one_key: OnePermMinHash = OnePermMinHash::new_with_seed(16, 42);
+    one_key.insert(1);
+    assert_eq!(one_key.jaccard(&empty), Ok(0.0));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn one_permutation_minhash_merge() {
+    let mut a: OnePermMinHash = OnePermMinHash::new_with_seed(64, 7);
+    let mut b: OnePermMinHash = OnePermMinHash::new_with_seed(64, 7);
+    let mut union: OnePermMinHash = OnePermMinHash::new_with_seed(64, 7);
+    (0..100).for_each(|key| {
+        a.insert(key);
+        union.insert(key);
+    });
+    (50..500).for_each(|key| {
+        b.insert(key * 7);
+        union.insert(key * 7);
+    });
+    assert_eq!(a.merge(&b), Ok(()));
+    assert_eq!(a, union);
+    assert_eq!(a.signature(), union.signature());
+
+    let other_seed: OnePermMinHash = OnePermMinHash::new_with_seed(64, 8);
+    assert_eq!(
+        a.merge(&other_seed),
+        Err(MergeError::HashFunction { row: 0 })
+    );
+    let other_k: OnePermMinHash = OnePermMinHash::new_with_seed(128, 7);
+    assert_eq!(
+        a.jaccard(&other_k),
+        Err(MergeError::Dimensions {
+            expected: (64, 1),
+            actual: (128, 1)
+        })
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+#[should_panic(expected = "power of two")]
+fn one_permutation_minhash_bins() {
+    let _: OnePermMinHash = OnePermMinHash::new_with_seed(100, 42);
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn one_permutation_minhash_serialization() {
+    let mut signature: OnePermMinHash = OnePermMinHash::new(32);
+    (0..10).for_each(|key| signature.insert(key));
+    let serialized = bincode::serialize(&signature).unwrap();
+    let deserialized: OnePermMinHash = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(deserialized, signature);
+    assert_eq!(deserialized.signature(), signature.signature());
+    assert_eq!(deserialized.jaccard(&signature), Ok(1.0));
+
+    let hash_function = Tab64Simple::new();
+    let not_power_of_two = bincode::serialize(&(&hash_function, vec![0_u64; 3])).unwrap();
+    assert!(bincode::deserialize::<OnePermMinHash>(&not_power_of_two).is_err());
+    let too_large = bincode::serialize(&(&hash_function, vec![u64::MAX - 1; 2])).unwrap();
+    assert!(bincode::deserialize::<OnePermMinHash>(&too_large).is_err());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn bottom_k_estimates() {
+    // the relative standard error is about 1 / sqrt(1022) = 3.1%, allow for 5 of them
+    for distinct in [10, 1000, 10_000, 200_000].iter().copied() {
+        let mut sketch: BottomK = BottomK::new(1024);
+        let offset: u64 = rand::random();
+        for i in 0..2 * distinct {
+            sketch.insert(offset.wrapping_add(i % distinct));
+        }
+        let estimate = sketch.estimate_distinct();
+        if distinct < 1024 {
+            assert_eq!(estimate, distinct as f64);
+        }
+        let error = (estimate - distinct as f64).abs() / distinct as f64;
+        assert!(
+            error < 0.16,
+            "estimated {} instead of {}",
+            estimate,
+            distinct
+        );
+    }
+    let empty: BottomK<Tab64Simple> = BottomK::new_with_seed(16, 42);
+    assert_eq!(empty.estimate_distinct(), 0.0);
+    assert_eq!(empty.k(), 16);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn bottom_k_duplicates() {
+    let mut sketch: BottomK = BottomK::new_with_seed(64, 42);
+    (0..1000).for_each(|key| sketch.insert(key));
+    let before = sketch.clone();
+    (0..1000).rev().for_each(|key| sketch.insert(key));
+    assert_eq!(sketch, before);
+    assert_eq!(sketch.values().len(), 64);
+    assert!(sketch.values().windows(2).all(|pair| pair[0] < pair[1]));
+    // the values are the smallest hash values of all keys
+    let mut hash_values: Vec<u64> = (0..1000)
+        .map(|key| sketch.hash_function().hash(key))
+        .collect();
+    hash_values.sort_unstable();
+    assert_eq!(sketch.values(), &hash_values[..64]);
+
+    let mut small: BottomK = BottomK::new_with_seed(64, 42);
+    (0..10).for_each(|_| small.insert(7));
+    assert_eq!(small.values().len(), 1);
+    assert_eq!(small.estimate_distinct(), 1.0);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn bottom_k_set_operations() {
+    let mut a: BottomK = BottomK::new_with_seed(512, 7);
+    let mut b: BottomK = BottomK::new_with_seed(512, 7);
+    let mut union: BottomK = BottomK::new_with_seed(512, 7);
+    // 20000 keys each, overlap of 10000 keys, i.e. a Jaccard similarity of 1/3
+    for key in 0..20_000 {
+        a.insert(key);
+        b.insert(key + 10_000);
+        union.insert(key);
+        union.insert(key + 10_000);
+    }
+    // the standard error is about sqrt(J * (1 - J) / k) = 0.021
+    let estimate = a.jaccard_estimate(&b).unwrap();
+    assert!((estimate - 1.0 / 3.0).abs() < 0.1, "{}", estimate);
+    assert_eq!(a.jaccard_estimate(&a), Ok(1.0));
+    assert_eq!(a.merge(&b), Ok(()));
+    assert_eq!(a, union);
+    let error = (a.estimate_distinct() - 30_000.0).abs() / 30_000.0;
+    assert!(error < 0.25, "{}", a.estimate_distinct());
+
+    let empty: BottomK = BottomK::new_with_seed(512, 7);
+    assert_eq!(empty.jaccard_estimate(&empty), Ok(1.0));
+    assert_eq!(empty.jaccard_estimate(&b), Ok(0.0));
+    let other_seed: BottomK = BottomK::new_with_seed(512, 8);
+    assert_eq!(
+        a.merge(&other_seed),
+        Err(MergeError::HashFunction { row: 0 })
+    );
+    let other_k: BottomK = BottomK::new_with_seed(256, 7);
+    assert_eq!(
+        a.jaccard_estimate(&other_k),
+        Err(MergeError::Dimensions {
+            expected: (512, 1),
+            actual: (256, 1)
+        })
+    );
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn bottom_k_serialization() {
+    let mut sketch: BottomK = BottomK::new(32);
+    (0..1000).for_each(|key| sketch.insert(key));
+    let serialized = bincode::serialize(&sketch).unwrap();
+    let mut deserialized: BottomK = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(deserialized, sketch);
+    assert_eq!(deserialized.merge(&sketch), Ok(()));
+    assert_eq!(deserialized, sketch);
+
+    let hash_function = Tab64Twisted::new();
+    let unsorted = bincode::serialize(&(32_usize, &hash_function, vec![2_u64, 1])).unwrap();
+    assert!(bincode::deserialize::<BottomK>(&unsorted).is_err());
+    let too_many = bincode::serialize(&(2_usize, &hash_function, vec![1_u64, 2, 3])).unwrap();
+    assert!(bincode::deserialize::<BottomK>(&too_many).is_err());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn lsh_banding() {
+    let band_hasher = Tab64Simple::new();
+    let signature: Vec<u64> = (0..100).map(|_| rand::random()).collect();
+    let keys = lsh_bands(&signature, 20, 5, &band_hasher);
+    assert_eq!(keys.len(), 20);
+    assert_eq!(keys, lsh_bands(&signature.clone(), 20, 5, &band_hasher));
+    assert_eq!(keys[3], band_hasher.hash_slice(&signature[15..20]));
+    // changing one entry changes the key of its band only
+    let mut changed = signature.clone();
+    changed[17] ^= 1;
+    let changed_keys = lsh_bands(&changed, 20, 5, &band_hasher);
+    for band in 0..20 {
+        assert_eq!(keys[band] == changed_keys[band], band != 3);
+    }
+
+    // 10 groups of sets, each with a similar set of Jaccard similarity 0.8
+    // and a dissimilar set of Jaccard similarity 0.1 to the first one.
+    // With 20 bands of 5 rows, the S-curve threshold is (1 / 20)^(1 / 5) = 0.55,
+    // and pairs are candidates with probabilities 0.9996 and 0.0002, respectively.
+    let minhash: MinHash = MinHash::new_with_seed(100, 42);
+    let mut signatures = Vec::new();
+    for group in 0..10_u64 {
+        let start = group << 32;
+        let mut set = MinHash::with_hash_functions(minhash.hash_functions().to_vec());
+        let mut similar = set.clone();
+        let mut dissimilar = set.clone();
+        (start..start + 1000).for_each(|key| set.insert(key));
+        (start + 111..start + 1111).for_each(|key| similar.insert(key));
+        (start + 818..start + 1818).for_each(|key| dissimilar.insert(key));
+        assert_eq!(
+            set.lsh_bands(20, 5, &band_hasher),
+            lsh_bands(set.signature(), 20, 5, &band_hasher)
+        );
+        signatures.push(set.signature().to_vec());
+        signatures.push(similar.signature().to_vec());
+        signatures.push(dissimilar.signature().to_vec());
+    }
+    let candidates = lsh_candidates(&signatures, 20, 5, &band_hasher);
+    for group in 0..10 {
+        let set = 3 * group;
+        assert!(candidates
+            .iter()
+            .any(|bucket| bucket.contains(&set) && bucket.contains(&(set + 1))));
+    }
+    for bucket in candidates.iter() {
+        assert!(bucket.len() > 1);
+        assert!(bucket.windows(2).all(|pair| pair[0] < pair[1]));
+        // no candidates across groups or with the dissimilar sets
+        assert!(bucket.iter().all(|i| i / 3 == bucket[0] / 3 && i % 3 != 2));
+    }
+
+    // identical signatures share all buckets
+    let identical = vec![signature.clone(), changed, signature];
+    let candidates = lsh_candidates(&identical, 20, 5, &band_hasher);
+    assert_eq!(candidates.len(), 20);
+    assert_eq!(
+        candidates
+            .iter()
+            .filter(|bucket| **bucket == vec![0, 2])
+            .count(),
+        1
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+#[should_panic(expected = "do not match a signature of length 100")]
+fn lsh_bands_length() {
+    lsh_bands(&[0; 100], 10, 9, &Tab64Simple::new_with_seed(42));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn simhash_fingerprints() {
+    let simple = Tab32To64Simple::new();
+    let twisted = Tab32To64Twisted::new();
+    assert_eq!(simple.simhash(vec![]), 0);
+    assert_eq!(simple.simhash(vec![(1, 0.0), (2, 0.0)]), 0);
+    assert_eq!(simple.simhash(vec![(1, 1.0), (1, -1.0)]), 0);
+    // a single feature has its hash value or its complement as fingerprint
+    assert_eq!(simple.simhash(vec![(42, 0.5)]), simple.hash(42));
+    assert_eq!(simple.simhash(vec![(42, -2.0)]), !simple.hash(42));
+    assert_eq!(
+        simple.simhash(vec![(42, 1.0), (7, f32::NAN)]),
+        simple.hash(42)
+    );
+    assert_eq!(twisted.simhash(vec![(42, 1.0)]), twisted.hash(42));
+    // weights decide between conflicting features
+    assert_eq!(simple.simhash(vec![(1, 1.0), (2, 3.0)]), simple.hash(2));
+
+    assert_eq!(hamming_distance(0, u64::MAX), 64);
+    assert_eq!(hamming_distance(0b1011, 0b0110), 3);
+
+    // 500 weighted features, of which similar sets share 475, i.e. 95% of the weight
+    let (mut similar_distance, mut disjoint_distance) = (0, 0);
+    for _ in 0..20 {
+        let features: Vec<(u32, f32)> = (0..500)
+            .map(|_| (rand::random(), 0.5 + rand::random::<f32>()))
+            .collect();
+        let mut similar = features.clone();
+        for feature in similar.iter_mut().take(25) {
+            feature.0 = rand::random();
+        }
+        let disjoint: Vec<(u32, f32)> = features.iter().map(|(x, w)| (!*x, *w)).collect();
+        let fingerprint = simple.simhash(features.iter().copied());
+        let d = hamming_distance(fingerprint, simple.simhash(similar.iter().copied()));
+        assert!(d < 20, "{}", d);
+        similar_distance += d;
+        let d = hamming_distance(fingerprint, simple.simhash(disjoint.iter().copied()));
+        assert!(d > 12, "{}", d);
+        disjoint_distance += d;
+    }
+    // disjoint sets differ in about 32 bits, similar ones in about 64 * arccos(0.95) / pi = 6.5
+    assert!(similar_distance < 20 * 10, "{}", similar_distance);
+    assert!(disjoint_distance > 20 * 26, "{}", disjoint_distance);
+}
+
+/// A set of keys with weights
+type WeightedSet = Vec<(u64, f64)>;
+
+/// Exact weighted Jaccard similarity of two sets of weighted keys
+fn weighted_jaccard(a: &[(u64, f64)], b: &[(u64, f64)]) -> f64 {
+    let weight =
+        |set: &[(u64, f64)], key: u64| set.iter().find(|(k, _)| *k == key).map_or(0.0, |(_, w)| *w);
+    let mut keys: Vec<u64> = a.iter().chain(b.iter()).map(|(k, _)| *k).collect();
+    keys.sort_unstable();
+    keys.dedup();
+    let min: f64 = keys.iter().map(|k| weight(a, *k).min(weight(b, *k))).sum();
+    let max: f64 = keys.iter().map(|k| weight(a, *k).max(weight(b, *k))).sum();
+    min / max
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn consistent_weighted_sampling() {
+    let sampler: Icws = Icws::new(512);
+    assert_eq!(sampler.k(), 512);
+    let pairs: Vec<(WeightedSet, WeightedSet)> = vec![
+        (
+            vec![(1, 1.0), (2, 2.0), (3, 0.5)],
+            vec![(1, 2.0), (2, 1.0), (4, 0.5)],
+        ),
+        // scaling all weights by 4 yields a similarity of 1/4
+        (vec![(1, 1.0), (2, 3.0)], vec![(1, 4.0), (2, 12.0)]),
+        (
+            vec![(10, 0.1), (20, 5.0), (30, 1.5), (40, 2.5)],
+            vec![(10, 0.3), (20, 4.0), (30, 1.5), (50, 0.25)],
+        ),
+        (vec![(1, 1.0)], vec![(2, 1.0)]),
+    ];
+    for (a, b) in pairs.iter() {
+        let exact = weighted_jaccard(a, b);
+        let samples_a = sampler.sample(a.iter().copied());
+        let samples_b = sampler.sample(b.iter().copied());
+        assert_eq!(samples_a.len(), 512);
+        assert!(samples_a
+            .iter()
+            .all(|(k, _)| a.iter().any(|(key, _)| key == k)));
+        // the standard error is at most 0.5 / sqrt(512) = 0.022
+        let estimate = sampler.jaccard(&samples_a, &samples_b);
+        assert!(
+            (estimate - exact).abs() < 0.11,
+            "estimated {} instead of {}",
+            estimate,
+            exact
+        );
+        // samples only depend on the tables and the set, not on the order of the keys
+        let reversed = sampler.sample(a.iter().rev().copied());
+        assert_eq!(reversed, samples_a);
+        let restored: Icws = Icws::with_hash_functions(sampler.hash_functions().to_vec());
+        assert_eq!(restored.sample(a.iter().copied()), samples_a);
+    }
+    assert_eq!(sampler.sample(vec![]), vec![]);
+    assert_eq!(sampler.jaccard(&[], &[]), 1.0);
+    let samples = sampler.sample(vec![(1, 1.0)]);
+    assert_eq!(sampler.jaccard(&samples, &[]), 0.0);
+}
+
+#[test]
+#[cfg(feature = "std")]
+#[should_panic(expected = "positive and finite")]
+fn consistent_weighted_sampling_weights() {
+    let sampler: Icws = Icws::new_with_seed(4, 42);
+    sampler.sample(vec![(1, 1.0), (2, 0.0)]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn deterministic_sampling() {
+    let simple32 = Tab32Simple::new();
+    let twisted32 = Tab32Twisted::new();
+    let simple64 = Tab64Simple::new();
+    let twisted64 = Tab64Twisted::new();
+    let keys: Vec<u64> = (0..100_000).map(|_| rand::random()).collect();
+    for p in [0.0, 0.001, 0.1, 0.5, 0.9, 1.0].iter().copied() {
+        let ppm = (p * 1_000_000.0) as u32;
+        let rates = [
+            keys.iter()
+                .filter(|k| simple32.sample(**k as u32, p))
+                .count(),
+            keys.iter()
+                .filter(|k| twisted32.sample(**k as u32, p))
+                .count(),
+            keys.iter().filter(|k| simple64.sample(**k, p)).count(),
+            keys.iter().filter(|k| twisted64.sample(**k, p)).count(),
+            keys.iter()
+                .filter(|k| simple32.sample_rate_per_million(**k as u32, ppm))
+                .count(),
+            keys.iter()
+                .filter(|k| simple64.sample_rate_per_million(**k, ppm))
+                .count(),
+            keys.iter()
+                .filter(|k| twisted64.sample_rate_per_million(**k, ppm))
+                .count(),
+        ];
+        // at most 5 standard deviations of sqrt(n * p * (1 - p)) <= 158
+        let expected = p * keys.len() as f64;
+        for sampled in rates.iter() {
+            if p == 0.0 || p == 1.0 {
+                assert_eq!(*sampled as f64, expected);
+            } else {
+                assert!(
+                    (*sampled as f64 - expected).abs() < 800.0,
+                    "{} {}",
+                    p,
+                    sampled
+                );
+            }
+        }
+    }
+    // the decision is deterministic and monotonic in p
+    for k in keys.iter().take(1000) {
+        assert_eq!(simple64.sample(*k, 0.3), simple64.hash_unit(*k) < 0.3);
+        assert_eq!(simple64.sample(*k, 0.3), simple64.sample(*k, 0.3));
+        if simple64.sample(*k, 0.3) {
+            assert!(simple64.sample(*k, 0.31));
+        }
+        assert_eq!(
+            twisted32.sample_rate_per_million(*k as u32, 1234),
+            twisted32.hash_to_range(*k as u32, 1_000_000) < 1234
+        );
+    }
+    // the threshold is exact: hash values below p * 2^32 are sampled
+    let mut table = [[0; 256]; 4];
+    table[0] = [0x7FFF_FFFF; 256];
+    assert!(Tab32Simple::with_table(table).sample(0, 0.5));
+    table[0] = [0x8000_0000; 256];
+    assert!(!Tab32Simple::with_table(table).sample(0, 0.5));
+}
+
+#[test]
+#[should_panic(expected = "[0, 1]")]
+fn deterministic_sampling_probability() {
+    Tab64Simple::new_with_seed(42).sample(0, 1.5);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn exponential_hashing() {
+    let simple = Tab64Simple::new();
+    let twisted = Tab64Twisted::new();
+    let keys: Vec<u64> = (0..1_000_000).map(|_| rand::random()).collect();
+    let samples = [
+        keys.iter()
+            .map(|k| simple.hash_exp(*k))
+            .collect::<Vec<f64>>(),
+        keys.iter().map(|k| twisted.hash_exp(*k)).collect(),
+    ];
+    for values in samples.iter() {
+        assert!(values.iter().all(|v| *v > 0.0 && v.is_finite()));
+        // the mean and the variance of Exp(1) are 1, the standard error of the mean is 0.001
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        assert!((mean - 1.0).abs() < 0.005, "{}", mean);
+        // P(X > 1) = 1 / e
+        let above_one = values.iter().filter(|v| **v > 1.0).count() as f64 / values.len() as f64;
+        assert!(
+            (above_one - (-1.0_f64).exp()).abs() < 0.005,
+            "{}",
+            above_one
+        );
+    }
+
+    // the extreme hash values map to finite values
+    let zero = Tab64Simple::with_table([[0; 256]; 8]);
+    assert_eq!(zero.hash_exp(0), 53.0 * 2_f64.ln());
+    let mut table = [[0; 256]; 8];
+    table[0] = [u64::MAX; 256];
+    let max = Tab64Simple::with_table(table);
+    assert!(max.hash_exp(0) > 0.0 && max.hash_exp(0) < 1e-15);
+
+    assert_eq!(simple.priority(42, 1.0), simple.hash_exp(42));
+    assert_eq!(simple.priority(42, 4.0), simple.hash_exp(42) / 4.0);
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn priorities_after_serialization() {
+    let simple = Tab64Simple::new();
+    let twisted = Tab64Twisted::new();
+    let restored_simple: Tab64Simple =
+        bincode::deserialize(&bincode::serialize(&simple).unwrap()).unwrap();
+    let restored_twisted: Tab64Twisted =
+        bincode::deserialize(&bincode::serialize(&twisted).unwrap()).unwrap();
+    for _ in 0..10_000 {
+        let (key, weight): (u64, f64) = (rand::random(), 0.01 + rand::random::<f64>());
+        assert_eq!(
+            restored_simple.priority(key, weight).to_bits(),
+            simple.priority(key, weight).to_bits()
+        );
+        assert_eq!(
+            restored_twisted.priority(key, weight).to_bits(),
+            twisted.priority(key, weight).to_bits()
+        );
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+#[should_panic(expected = "positive and finite")]
+fn priority_weights() {
+    Tab64Twisted::new_with_seed(42).priority(0, -1.0);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn stable_sampling() {
+    let keys: Vec<u64> = (0..200_000).map(|_| rand::random()).collect();
+    let mut sampler = StableSampler::new(1.0);
+    let mut kept: Vec<u64> = keys.iter().copied().filter(|k| sampler.keep(*k)).collect();
+    assert_eq!(kept, keys);
+
+    for rate in [0.5, 0.5, 0.2, 0.05, 0.01, 0.0].iter() {
+        sampler.set_rate(*rate).unwrap();
+        assert_eq!(sampler.rate(), *rate);
+        let sample: Vec<u64> = keys.iter().copied().filter(|k| sampler.keep(*k)).collect();
+        // subset property: thinning the previous sample yields the sample of the whole stream
+        let thinned: Vec<u64> = kept.iter().copied().filter(|k| sampler.keep(*k)).collect();
+        assert_eq!(thinned, sample);
+        let expected = rate * keys.len() as f64;
+        assert!(
+            (sample.len() as f64 - expected).abs() <= 5.0 * expected.sqrt(),
+            "{} {}",
+            rate,
+            sample.len()
+        );
+        kept = sample;
+    }
+    assert!(kept.is_empty());
+
+    // the rate cannot be increased again
+    assert_eq!(
+        sampler.set_rate(0.0625),
+        Err(RateError {
+            current: 0,
+            requested: 1 << 60,
+        })
+    );
+    assert_eq!(sampler.threshold(), 0);
+}
+
+#[test]
+fn stable_sampler_thresholds() {
+    let mut sampler = StableSampler::new_with_seed(42, 0.25);
+    assert_eq!(sampler.threshold(), 1 << 62);
+    assert!(sampler.set_rate(0.5).is_err());
+    assert_eq!(sampler.rate(), 0.25);
+
+    // a restored sampler keeps the same keys
+    let restored =
+        StableSampler::with_threshold(sampler.hash_function().clone(), sampler.threshold());
+    assert_eq!(restored, sampler);
+    sampler.set_rate(0.125).unwrap();
+    assert!((0..10_000).all(|k| !sampler.keep(k) || restored.keep(k)));
+    assert!((0..10_000).any(|k| !sampler.keep(k) && restored.keep(k)));
+
+    // keys are kept iff their hash value is below the threshold
+    let hash_function = Tab64Twisted::new_with_seed(42);
+    for key in 0..10_000 {
+        assert_eq!(sampler.keep(key), hash_function.hash(key) < 1 << 61);
+    }
+    assert_eq!(StableSampler::new_with_seed(1, 1.0).threshold(), u64::MAX);
+    assert_eq!(StableSampler::new_with_seed(1, 0.0).threshold(), 0);
+}
+
+#[test]
+#[should_panic(expected = "sampling rates have to be in [0, 1]")]
+fn stable_sampler_rates() {
+    StableSampler::new_with_seed(42, 1.0)
+        .set_rate(f64::NAN)
+        .ok();
+}
+
+#[test]
+fn partitioner_bins() {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    // seeded table and keys, so a failure can be reproduced
+    let partitioner: Partitioner = Partitioner::new_with_seed(37, 68);
+    assert_eq!(partitioner.num_bins(), 37);
+    let mut rng = StdRng::seed_from_u64(68);
+    let mut counts = vec![0_u64; 37];
+    for _ in 0..370_000 {
+        let key = rng.gen();
+        let bin = partitioner.bin(key);
+        assert_eq!(
+            bin as u64,
+            partitioner.hash_function().hash_to_range(key, 37)
+        );
+        counts[bin] += 1;
+    }
+    // chi-squared test with 36 degrees of freedom, the 99.999% quantile is about 82
+    let expected = 10_000.0;
+    let chi_squared: f64 = counts
+        .iter()
+        .map(|c| (*c as f64 - expected).powi(2) / expected)
+        .sum();
+    assert!(chi_squared < 90.0, "{:?}", counts);
+
+    let single: Partitioner<Tab64Twisted> = Partitioner::new_with_seed(1, 42);
+    assert!((0..1000).all(|key| single.bin(key) == 0));
+    let huge: Partitioner<Tab64Twisted> = Partitioner::new_with_seed(usize::MAX, 42);
+    assert!((0..1000).all(|key| huge.bin(key) < usize::MAX));
+}
+
+#[test]
+#[cfg(feature = "std")]
+#[should_panic(expected = "at least one bin")]
+fn partitioner_without_bins() {
+    let _: Partitioner = Partitioner::new(0);
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn partitioner_serialization() {
+    let partitioner: Partitioner<Tab64Twisted> = Partitioner::new(1000);
+    let serialized = bincode::serialize(&partitioner).unwrap();
+    let deserialized: Partitioner<Tab64Twisted> = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(deserialized, partitioner);
+    for _ in 0..10_000 {
+        let key = rand::random();
+        assert_eq!(deserialized.bin(key), partitioner.bin(key));
+    }
+
+    let empty = bincode::serialize(&(0_usize, Tab64Simple::new())).unwrap();
+    assert!(bincode::deserialize::<Partitioner>(&empty).is_err());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn consistent_ring_routing() {
+    let mut ring = ConsistentRing::new();
+    assert!(ring.is_empty());
+    ring.add_node(7, 1).unwrap();
+    assert!((0..1000).all(|key| ring.route(key) == 7));
+
+    for node_id in 0..5 {
+        ring.add_node(node_id, 200).unwrap();
+    }
+    assert_eq!(ring.len(), 6);
+    assert_eq!(
+        ring.add_node(3, 10),
+        Err(RingError::DuplicateNode { node_id: 3 })
+    );
+    assert_eq!(ring.nodes()[3], (3, 200));
+
+    let keys: Vec<u64> = (0..100_000).map(|_| rand::random()).collect();
+    let owners: Vec<u64> = keys.iter().map(|key| ring.route(*key)).collect();
+    // with 200 virtual nodes, every node owns a sizable share of the keys
+    for node_id in 0..5 {
+        let owned = owners.iter().filter(|owner| **owner == node_id).count();
+        assert!(owned > 10_000, "{} {}", node_id, owned);
+    }
+
+    // removing a node only remaps the keys it owned
+    ring.remove_node(2).unwrap();
+    assert_eq!(
+        ring.remove_node(2),
+        Err(RingError::UnknownNode { node_id: 2 })
+    );
+    for (key, owner) in keys.iter().zip(owners.iter()) {
+        let new_owner = ring.route(*key);
+        if *owner == 2 {
+            assert_ne!(new_owner, 2);
+        } else {
+            assert_eq!(new_owner, *owner);
+        }
+    }
+
+    // adding it again restores the original assignment
+    ring.add_node(2, 200).unwrap();
+    assert!(keys
+        .iter()
+        .zip(owners.iter())
+        .all(|(key, owner)| ring.route(*key) == *owner));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+#[should_panic(expected = "empty ring")]
+fn consistent_ring_without_nodes() {
+    let mut ring = ConsistentRing::new_with_seed(42);
+    ring.add_node(1, 10).unwrap();
+    ring.remove_node(1).unwrap();
+    ring.route(0);
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn consistent_ring_serialization() {
+    let mut ring = ConsistentRing::new();
+    for node_id in [17, 3, 99, 42].iter() {
+        ring.add_node(*node_id, 50).unwrap();
+    }
+    let serialized = bincode::serialize(&ring).unwrap();
+    let deserialized: ConsistentRing = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(deserialized, ring);
+    for _ in 0..10_000 {
+        let key = rand::random();
+        assert_eq!(deserialized.route(key), ring.route(key));
+    }
+
+    let duplicates =
+        bincode::serialize(&(Tab64Simple::new(), vec![(1_u64, 5_usize), (1, 5)])).unwrap();
+    assert!(bincode::deserialize::<ConsistentRing>(&duplicates).is_err());
+    let no_vnodes = bincode::serialize(&(Tab64Simple::new(), vec![(1_u64, 0_usize)])).unwrap();
+    assert!(bincode::deserialize::<ConsistentRing>(&no_vnodes).is_err());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn rendezvous_hashing() {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    let simple = Tab64Simple::new_with_seed(70);
+    let twisted = Tab64Twisted::new_with_seed(70);
+    let nodes = [3, 17, 42, 99, 1000];
+    let mut rng = StdRng::seed_from_u64(70);
+    let keys: Vec<u64> = (0..10_000).map(|_| rng.gen()).collect();
+    for key in keys.iter() {
+        let node = simple.rendezvous(*key, &nodes).unwrap();
+        // the order of the nodes does not matter
+        let reversed: Vec<u64> = nodes.iter().rev().copied().collect();
+        assert_eq!(simple.rendezvous(*key, &reversed), Ok(node));
+        assert_eq!(simple.rendezvous(*key, &[node]), Ok(node));
+        assert!(nodes.contains(&twisted.rendezvous(*key, &nodes).unwrap()));
+    }
+
+    // removing a node only moves the keys it was chosen for
+    let remaining = [3, 17, 99, 1000];
+    for key in keys.iter() {
+        let before = twisted.rendezvous(*key, &nodes).unwrap();
+        let after = twisted.rendezvous(*key, &remaining).unwrap();
+        if before != 42 {
+            assert_eq!(after, before);
+        }
+    }
+
+    assert_eq!(simple.rendezvous(0, &[]), Err(NoNodesError));
+    assert_eq!(twisted.rendezvous_weighted(0, &[]), Err(NoNodesError));
+    // ties are broken by the largest node id
+    assert_eq!(simple.rendezvous(0, &[5, 5]), Ok(5));
+    let zero = Tab64Simple::with_table([[0; 256]; 8]);
+    assert_eq!(zero.rendezvous(0, &[0, 1 << 16, 256]), Ok(1 << 16));
+    assert_eq!(
+        zero.rendezvous_weighted(0, &[(1, 1.0), (7, 1.0), (2, 1.0)]),
+        Ok(7)
+    );
+}
+
+#[test]
+fn rendezvous_hashing_uniformity() {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    // chi-squared test with 9 degrees of freedom, the 99.999% quantile is about 39.3
+    fn assert_uniform<F: Fn(u64, &[u64]) -> u64>(rendezvous: F, keys: &[u64]) {
+        let nodes: Vec<u64> = (0..10).collect();
+        let mut counts = [0_u64; 10];
+        for key in keys.iter() {
+            counts[rendezvous(*key, &nodes) as usize] += 1;
+        }
+        let expected = keys.len() as f64 / 10.0;
+        let chi_squared: f64 = counts
+            .iter()
+            .map(|c| (*c as f64 - expected).powi(2) / expected)
+            .sum();
+        assert!(chi_squared < 40.0, "{:?}", counts);
+    }
+
+    // sequential node ids, seeded tables and keys, so a failure can be reproduced
+    for seed in 0..4 {
+        let simple = Tab64Simple::new_with_seed(seed);
+        let twisted = Tab64Twisted::new_with_seed(seed);
+        let sequential: Vec<u64> = (0..100_000).collect();
+        let mut rng = StdRng::seed_from_u64(seed);
+        let random: Vec<u64> = (0..100_000).map(|_| rng.gen()).collect();
+        for keys in [sequential, random].iter() {
+            assert_uniform(|key, nodes| simple.rendezvous(key, nodes).unwrap(), keys);
+            assert_uniform(|key, nodes| twisted.rendezvous(key, nodes).unwrap(), keys);
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn weighted_rendezvous_hashing() {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    // chi-squared test with 3 degrees of freedom, the 99.999% quantile is about 25.9
+    fn assert_proportional<F: Fn(u64, &[(u64, f64)]) -> u64>(rendezvous_weighted: F, keys: &[u64]) {
+        let nodes = [(0, 1.0), (1, 2.0), (2, 3.0), (3, 4.0)];
+        let mut counts = [0_u64; 4];
+        for key in keys.iter() {
+            counts[rendezvous_weighted(*key, &nodes) as usize] += 1;
+        }
+        let chi_squared: f64 = counts
+            .iter()
+            .zip(nodes.iter())
+            .map(|(c, (_, weight))| {
+                let expected = keys.len() as f64 * weight / 10.0;
+                (*c as f64 - expected).powi(2) / expected
+            })
+            .sum();
+        assert!(chi_squared < 26.0, "{:?}", counts);
+
+        // removing a node only moves the keys it was chosen for
+        let remaining = [(0, 1.0), (1, 2.0), (3, 4.0)];
+        for key in keys.iter() {
+            let before = rendezvous_weighted(*key, &nodes);
+            let after = rendezvous_weighted(*key, &remaining);
+            if before != 2 {
+                assert_eq!(after, before);
+            }
+        }
+    }
+
+    // sequential node ids, seeded tables and keys, so a failure can be reproduced
+    for seed in 0..4 {
+        let simple = Tab64Simple::new_with_seed(seed);
+        let twisted = Tab64Twisted::new_with_seed(seed);
+        let sequential: Vec<u64> = (0..100_000).collect();
+        let mut rng = StdRng::seed_from_u64(seed);
+        let random: Vec<u64> = (0..100_000).map(|_| rng.gen()).collect();
+        for keys in [sequential, random].iter() {
+            assert_proportional(
+                |key, nodes| simple.rendezvous_weighted(key, nodes).unwrap(),
+                keys,
+            );
+            assert_proportional(
+                |key, nodes| twisted.rendezvous_weighted(key, nodes).unwrap(),
+                keys,
+            );
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+#[should_panic(expected = "positive and finite")]
+fn weighted_rendezvous_weights() {
+    Tab64Simple::new_with_seed(42)
+        .rendezvous_weighted(0, &[(1, 1.0), (2, 0.0)])
+        .ok();
+}
+
+#[test]
+fn jump_consistent_hashing() {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    let simple = Tab64Simple::new_with_seed(71);
+    let twisted = Tab64Twisted::new_with_seed(71);
+    let mut rng = StdRng::seed_from_u64(71);
+    let keys: Vec<u64> = (0..10_000).map(|_| rng.gen()).collect();
+    for key in keys.iter() {
+        assert_eq!(simple.jump_bucket(*key, 1), 0);
+        // adding a bucket only moves keys into it
+        let mut previous = 0;
+        for num_buckets in 2..200 {
+            let bucket = simple.jump_bucket(*key, num_buckets);
+            assert!(bucket == previous || bucket == num_buckets - 1);
+            previous = bucket;
+        }
+        let bucket = twisted.jump_bucket(*key, 1000);
+        assert!(bucket < 1000);
+        assert!(
+            twisted.jump_bucket(*key, 2000) == bucket || twisted.jump_bucket(*key, 2000) >= 1000
+        );
+        assert!(simple.jump_bucket(*key, u32::MAX) < u32::MAX);
+    }
+}
+
+#[test]
+fn jump_consistent_hashing_uniformity() {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    // chi-squared test with 49 degrees of freedom, the 99.999% quantile is about 103
+    fn assert_uniform<F: Fn(u64) -> u32>(jump_bucket: F, keys: &[u64]) {
+        let mut counts = [0_u64; 50];
+        for key in keys.iter() {
+            counts[jump_bucket(*key) as usize] += 1;
+        }
+        let expected = keys.len() as f64 / 50.0;
+        let chi_squared: f64 = counts
+            .iter()
+            .map(|c| (*c as f64 - expected).powi(2) / expected)
+            .sum();
+        assert!(chi_squared < 110.0, "{:?}", counts);
+    }
+
+    // seeded tables and keys, so a failure can be reproduced
+    for seed in 0..4 {
+        let simple = Tab64Simple::new_with_seed(seed);
+        let twisted = Tab64Twisted::new_with_seed(seed);
+        let sequential: Vec<u64> = (0..500_000).collect();
+        let mut rng = StdRng::seed_from_u64(seed);
+        let random: Vec<u64> = (0..500_000).map(|_| rng.gen()).collect();
+        for keys in [sequential, random].iter() {
+            assert_uniform(|key| simple.jump_bucket(key, 50), keys);
+            assert_uniform(|key| twisted.jump_bucket(key, 50), keys);
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+#[should_panic(expected = "zero buckets")]
+fn jump_consistent_hashing_without_buckets() {
+    Tab64Simple::new().jump_bucket(42, 0);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn multiset_fingerprints() {
+    let empty = MultisetFingerprint::new();
+    assert_eq!(empty.digest(), 0);
+    let keys: Vec<u64> = (0..1000).map(|_| rand::random::<u64>() % 100).collect();
+
+    // adding and removing all keys yields the empty digest, in any order
+    let mut fingerprint = empty.clone();
+    keys.iter().for_each(|key| fingerprint.add(*key));
+    let digest = fingerprint.digest();
+    assert_ne!(digest, 0);
+    keys.iter().rev().for_each(|key| fingerprint.remove(*key));
+    assert_eq!(fingerprint, empty);
+
+    // removing a key before adding it
+    fingerprint.remove(7);
+    assert_ne!(fingerprint.digest(), 0);
+    fingerprint.add(7);
+    assert_eq!(fingerprint.digest(), 0);
+
+    // the digest does not depend on the order
+    let mut sorted = keys.clone();
+    sorted.sort_unstable();
+    let mut reordered = empty.clone();
+    sorted.iter().for_each(|key| reordered.add(*key));
+    assert_eq!(reordered.digest(), digest);
+    // but on the multiplicities
+    reordered.add(sorted[0]);
+    assert_ne!(reordered.digest(), digest);
+
+    // combining equals adding both streams
+    let (first, second) = keys.split_at(300);
+    let mut a = empty.clone();
+    first.iter().for_each(|key| a.add(*key));
+    let mut b = empty.clone();
+    second.iter().for_each(|key| b.add(*key));
+    a.combine(&b);
+    assert_eq!(a.digest(), digest);
+    b.combine(&empty);
+    second.iter().for_each(|key| b.remove(*key));
+    assert_eq!(b.digest(), 0);
+}
+
+#[test]
+#[should_panic(expected = "different hash functions")]
+fn multiset_fingerprints_with_different_hash_functions() {
+    let mut a = MultisetFingerprint::new_with_seed(1);
+    a.combine(&MultisetFingerprint::new_with_seed(2));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn counter_mode_rng() {
+    use rand::{RngCore, SeedableRng};
+
+    let mut rng = TabRng::new();
+    let hash_function = rng.hash_function().clone();
+    for counter in 0..100 {
+        assert_eq!(rng.counter(), counter);
+        assert_eq!(rng.next_u64(), hash_function.hash(counter));
+    }
+    assert_eq!(rng.next_u32(), hash_function.hash(100) as u32);
+
+    // a saved state continues with the same stream
+    let saved = rng.clone();
+    let words: Vec<u64> = (0..1000).map(|_| rng.next_u64()).collect();
+    let mut resumed = TabRng::with_counter(hash_function.clone(), saved.counter());
+    assert!(words.iter().all(|word| *word == resumed.next_u64()));
+    let mut wrapping = TabRng::with_counter(hash_function.clone(), u64::MAX);
+    assert_eq!(wrapping.next_u64(), hash_function.hash(u64::MAX));
+    assert_eq!(wrapping.next_u64(), hash_function.hash(0));
+
+    // fill_bytes matches the word stream for any length
+    for len in [0, 1, 3, 7, 8, 9, 15, 17, 100].iter().copied() {
+        let mut words = TabRng::with_counter(hash_function.clone(), 42);
+        let mut bytes = TabRng::with_counter(hash_function.clone(), 42);
+        let mut dest = vec![0; len];
+        bytes.fill_bytes(&mut dest);
+        let expected: Vec<u8> = (0..len.div_ceil(8))
+            .flat_map(|_| words.next_u64().to_le_bytes().to_vec())
+            .take(len)
+            .collect();
+        assert_eq!(dest, expected);
+        assert_eq!(bytes, words);
+    }
+
+    // differently seeded streams do not overlap
+    let mut a = TabRng::seed_from_u64(1);
+    let mut b = TabRng::from_seed(2_u64.to_le_bytes());
+    assert_eq!(a, TabRng::new_with_seed(1));
+    assert_eq!(b, TabRng::new_with_seed(2));
+    let mut stream: Vec<u64> = (0..10_000).map(|_| a.next_u64()).collect();
+    stream.extend((0..10_000).map(|_| b.next_u64()));
+    stream.sort_unstable();
+    stream.dedup();
+    assert_eq!(stream.len(), 20_000);
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn counter_mode_rng_serialization() {
+    use rand::RngCore;
+
+    let mut rng = TabRng::new();
+    rng.next_u64();
+    let serialized = bincode::serialize(&rng).unwrap();
+    let mut deserialized: TabRng = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(deserialized, rng);
+    for _ in 0..1000 {
+        assert_eq!(deserialized.next_u64(), rng.next_u64());
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn feistel_permutation() {
+    let perm = Perm32::new();
+    for _ in 0..1_000_000 {
+        let x: u32 = rand::random();
+        assert_eq!(perm.invert(perm.permute(x)), x);
+        assert_eq!(perm.permute(perm.invert(x)), x);
+    }
+    for x in [0, 1, 0xffff, 0x1_0000, u32::MAX - 1, u32::MAX]
+        .iter()
+        .copied()
+    {
+        assert_eq!(perm.invert(perm.permute(x)), x);
+        assert_eq!(perm.permute(perm.invert(x)), x);
+    }
+
+    // no collisions on a contiguous range of keys
+    let mut images: Vec<u32> = (0..1 << 20).map(|x| perm.permute(x)).collect();
+    images.sort_unstable();
+    images.dedup();
+    assert_eq!(images.len(), 1 << 20);
+
+    // with zero round functions, the rounds only swap the halves
+    let zero = Perm32::with_tables([[[0; 256]; 2]; 4]);
+    assert_eq!(zero.permute(0x1234_5678), 0x1234_5678);
+    let seeded = Perm32::new_with_seed(42);
+    assert_eq!(seeded, Perm32::new_with_seed(42));
+    assert_ne!(seeded, Perm32::new_with_seed(43));
+    assert_eq!(
+        Perm32::with_round_functions(seeded.round_functions().clone()),
+        seeded
+    );
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn feistel_permutation_serialization() {
+    let perm = Perm32::new();
+    let serialized = bincode::serialize(&perm).unwrap();
+    let deserialized: Perm32 = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(deserialized, perm);
+    for _ in 0..10_000 {
+        let x: u32 = rand::random();
+        assert_eq!(deserialized.permute(x), perm.permute(x));
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn cycle_walking_permutation() {
+    // bijective on small domains, including powers of 4 and their neighbors
+    for n in [1, 2, 3, 4, 5, 10, 16, 17, 1000, 65535, 65536, 65537]
+        .iter()
+        .copied()
+    {
+        let perm = PermDomain::new(n, Perm32::new());
+        assert_eq!(perm.len(), n);
+        let mut images: Vec<u64> = (0..n).map(|i| perm.permute(i)).collect();
+        assert!((0..n).all(|i| perm.invert(images[i as usize]) == i));
+        images.sort_unstable();
+        assert!(images.into_iter().eq(0..n));
+    }
+
+    for n in [(1 << 32) - 1, 1 << 32, 3_000_000_019].iter().copied() {
+        let perm = PermDomain::new_with_seed(n, 42);
+        for _ in 0..10_000 {
+            let i = rand::random::<u64>() % n;
+            let image = perm.permute(i);
+            assert!(image < n);
+            assert_eq!(perm.invert(image), i);
+        }
+        assert_eq!(perm.invert(perm.permute(n - 1)), n - 1);
+    }
+
+    // the full domain is permuted like by `Perm32`
+    let perm32 = Perm32::new();
+    let perm = PermDomain::new(1 << 32, perm32.clone());
+    for x in [0, 1, 12345, u32::MAX].iter().copied() {
+        assert_eq!(perm.permute(u64::from(x)), u64::from(perm32.permute(x)));
+    }
+}
+
+#[test]
+#[should_panic(expected = "out of range")]
+fn cycle_walking_permutation_range() {
+    PermDomain::new_with_seed(10, 42).permute(10);
+}
+
+#[test]
+#[should_panic(expected = "between 1 and 2^32 numbers")]
+fn cycle_walking_permutation_empty_domain() {
+    PermDomain::new_with_seed(0, 42);
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn cycle_walking_permutation_serialization() {
+    let perm = PermDomain::new(1_000_000, Perm32::new());
+    let serialized = bincode::serialize(&perm).unwrap();
+    let deserialized: PermDomain = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(deserialized, perm);
+    assert!((0..1000).all(|i| deserialized.permute(i) == perm.permute(i)));
+
+    let too_large = bincode::serialize(&((1_u64 << 32) + 1, Perm32::new())).unwrap();
+    assert!(bincode::deserialize::<PermDomain>(&too_large).is_err());
+}
+
+#[test]
+fn tabulation_permutation_fixed_values() {
+    // simple tabulation rotates the key left by one byte,
+    // then the bytes of the result are permuted
+    let mut table = [[0_u32; 256]; 4];
+    for (i, column) in table.iter_mut().enumerate() {
+        for (c, entry) in column.iter_mut().enumerate() {
+            *entry = (c as u32) << (8 * ((i + 1) % 4));
+        }
+    }
+    let mut permutations = [[0_u8; 256]; 4];
+    for b in 0..=255_u8 {
+        permutations[0][b as usize] = b.wrapping_add(1);
+        permutations[1][b as usize] = 255 - b;
+        permutations[2][b as usize] = b;
+        permutations[3][b as usize] = b ^ 0x5a;
+    }
+    let tabperm = Tab32TabPerm::with_tables(table, permutations);
+    assert_eq!(tabperm.hash(3), 0x5a00_fc01);
+    assert_eq!(tabperm.hash(0x1234_56ff), 0x6e56_0013);
+    assert_eq!(tabperm.hash(0), 0x5a00_ff01);
+
+    let mut table = [[0_u64; 256]; 8];
+    for (i, column) in table.iter_mut().enumerate() {
+        for (c, entry) in column.iter_mut().enumerate() {
+            *entry = (c as u64) << (8 * ((i + 1) % 8));
+        }
+    }
+    let mut permutations = [[0_u8; 256]; 8];
+    for (j, permutation) in permutations.iter_mut().enumerate() {
+        for (b, entry) in permutation.iter_mut().enumerate() {
+            *entry = (b as u8).wrapping_add(j as u8 + 1);
+        }
+    }
+    let tabperm = Tab64TabPerm::with_tables(table, permutations);
+    assert_eq!(tabperm.hash(0), 0x0807_0605_0403_0201);
+    assert_eq!(tabperm.hash(1), 0x0807_0605_0403_0301);
+    assert_eq!(tabperm.hash(u64::MAX), 0x0706_0504_0302_0100);
+    assert_eq!(tabperm.get_table(), table);
+    assert_eq!(tabperm.get_permutations(), permutations);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn tabulation_permutation_tables() {
+    let tabperm = Tab64TabPerm::new_with_seed(42);
+    assert_eq!(tabperm, Tab64TabPerm::new_with_seed(42));
+    assert_eq!(
+        tabperm.get_table(),
+        Tab64Simple::new_with_seed(42).get_table()
+    );
+    assert_eq!(
+        Tab32TabPerm::new_with_seed(42).get_table(),
+        Tab32Simple::new_with_seed(42).get_table()
+    );
+    for permutation in tabperm.get_permutations().iter() {
+        let mut sorted = permutation.to_vec();
+        sorted.sort_unstable();
+        assert!(sorted.iter().enumerate().all(|(i, b)| i == *b as usize));
+    }
+    assert_ne!(tabperm.get_permutations()[0], tabperm.get_permutations()[1]);
+
+    // the id covers the permutations
+    let mut permutations = tabperm.get_permutations();
+    permutations[7].swap(0, 1);
+    let swapped = Tab64TabPerm::with_tables(tabperm.get_table(), permutations);
+    assert_ne!(swapped.id(), tabperm.id());
+    assert_ne!(
+        tabperm.id(),
+        TabHash::id(&Tab64Simple::with_table(tabperm.get_table()))
+    );
+
+    let random = Tab32TabPerm::new();
+    assert_eq!(Tab32TabPerm::from_vec(random.to_vec()), random);
+    assert_eq!(random.to_vec().len(), 8);
+    assert_eq!(Tab64TabPerm::from_vec(tabperm.to_vec()), tabperm);
+    let keys: Vec<u64> = (0..1000).map(|_| rand::random()).collect();
+    let mut hashes: Vec<u64> = keys.iter().map(|k| tabperm.hash(*k)).collect();
+    hashes.sort_unstable();
+    hashes.dedup();
+    assert_eq!(hashes.len(), keys.len());
+
+    // tabulation-permutation hash functions work wherever a `TabHash` is expected
+    let mut sketch: CountMinSketch<Tab64TabPerm> = CountMinSketch::new_with_seed(64, 2, 42);
+    sketch.insert(7, 3);
+    assert!(sketch.estimate(7) >= 3);
+}
+
+#[test]
+#[should_panic(expected = "every byte exactly once")]
+fn tabulation_permutation_invalid_permutation() {
+    let mut permutations = Tab32TabPerm::new_with_seed(1).get_permutations();
+    permutations[2][0] = permutations[2][1];
+    Tab32TabPerm::with_tables([[0; 256]; 4], permutations);
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn tabulation_permutation_serialization() {
+    let tabperm32 = Tab32TabPerm::new();
+    let tabperm64 = Tab64TabPerm::new();
+    let serialized32 = bincode::serialize(&tabperm32).unwrap();
+    let serialized64 = bincode::serialize(&tabperm64).unwrap();
+    let deserialized32: Tab32TabPerm = bincode::deserialize(&serialized32).unwrap();
+    let deserialized64: Tab64TabPerm = bincode::deserialize(&serialized64).unwrap();
+    assert_eq!(deserialized32, tabperm32);
+    assert_eq!(deserialized64, tabperm64);
+    for _ in 0..1000 {
+        let key: u64 = rand::random();
+        assert_eq!(deserialized32.hash(key as u32), tabperm32.hash(key as u32));
+        assert_eq!(deserialized64.hash(key), tabperm64.hash(key));
+    }
+
+    let not_a_permutation =
+        bincode::serialize(&(Tab32Simple::new(), vec![vec![0_u8; 256]; 4])).unwrap();
+    assert!(bincode::deserialize::<Tab32TabPerm>(&not_a_permutation).is_err());
+    let too_few =
+        bincode::serialize(&(Tab32Simple::new(), vec![(0..=255).collect::<Vec<u8>>(); 3])).unwrap();
+    assert!(bincode::deserialize::<Tab32TabPerm>(&too_few).is_err());
+}
+
+#[test]
+fn tabulation_1_permutation_fixed_values() {
+    // simple tabulation rotates the key left by one byte,
+    // then the most significant byte of the result is permuted
+    let mut table = [[0_u32; 256]; 4];
+    for (i, column) in table.iter_mut().enumerate() {
+        for (c, entry) in column.iter_mut().enumerate() {
+            *entry = (c as u32) << (8 * ((i + 1) % 4));
+        }
+    }
+    let mut permutation = [0_u8; 256];
+    for b in 0..=255_u8 {
+        permutation[b as usize] = 255 - b;
+    }
+    let tab1perm = Tab32Tab1Perm::with_tables(table, permutation);
+    assert_eq!(tab1perm.hash(3), 0xff00_0300);
+    assert_eq!(tab1perm.hash(0x1234_56ff), 0xcb56_ff12);
+    assert_eq!(tab1perm.hash(0), 0xff00_0000);
+    assert_eq!(tab1perm.get_table(), table);
+    assert_eq!(tab1perm.get_permutation(), permutation);
+
+    let mut table = [[0_u64; 256]; 8];
+    for (i, column) in table.iter_mut().enumerate() {
+        for (c, entry) in column.iter_mut().enumerate() {
+            *entry = (c as u64) << (8 * ((i + 1) % 8));
+        }
+    }
+    for b in 0..=255_u8 {
+        permutation[b as usize] = b ^ 0x5a;
+    }
+    let tab1perm = Tab64Tab1Perm::with_tables(table, permutation);
+    assert_eq!(tab1perm.hash(0), 0x5a00_0000_0000_0000);
+    assert_eq!(tab1perm.hash(1), 0x5a00_0000_0000_0100);
+    assert_eq!(tab1perm.hash(u64::MAX), 0xa5ff_ffff_ffff_ffff);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn tabulation_1_permutation_tables() {
+    let tab1perm = Tab64Tab1Perm::new_with_seed(42);
+    let simple = Tab64Simple::new_with_seed(42);
+    assert_eq!(tab1perm.get_table(), simple.get_table());
+    assert_eq!(
+        tab1perm.get_permutation(),
+        Tab64TabPerm::new_with_seed(42).get_permutations()[0]
+    );
+    for _ in 0..1000 {
+        let key: u64 = rand::random();
+        let h = simple.hash(key);
+        let top = u64::from(tab1perm.get_permutation()[(h >> 56) as usize]);
+        assert_eq!(
+            tab1perm.hash(key),
+            (top << 56) | (h & 0x00ff_ffff_ffff_ffff)
+        );
+    }
+    assert_ne!(tab1perm.id(), TabHash::id(&simple));
+
+    let random = Tab32Tab1Perm::new();
+    assert_eq!(random.to_vec().len(), 5);
+    assert_eq!(Tab32Tab1Perm::from_vec(random.to_vec()), random);
+    assert_eq!(Tab64Tab1Perm::from_vec(tab1perm.to_vec()), tab1perm);
+}
+
+#[test]
+#[should_panic(expected = "every byte exactly once")]
+fn tabulation_1_permutation_invalid_permutation() {
+    Tab64Tab1Perm::with_tables([[0; 256]; 8], [7; 256]);
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn tabulation_1_permutation_serialization() {
+    let tab1perm32 = Tab32Tab1Perm::new();
+    let tab1perm64 = Tab64Tab1Perm::new();
+    let serialized32 = bincode::serialize(&tab1perm32).unwrap();
+    let serialized64 = bincode::serialize(&tab1perm64).unwrap();
+    let deserialized32: Tab32Tab1Perm = bincode::deserialize(&serialized32).unwrap();
+    let deserialized64: Tab64Tab1Perm = bincode::deserialize(&serialized64).unwrap();
+    assert_eq!(deserialized32, tab1perm32);
+    assert_eq!(deserialized64, tab1perm64);
+    for _ in 0..1000 {
+        let key: u64 = rand::random();
+        assert_eq!(deserialized32.hash(key as u32), tab1perm32.hash(key as u32));
+        assert_eq!(deserialized64.hash(key), tab1perm64.hash(key));
+    }
+
+    let not_a_permutation = bincode::serialize(&(Tab32Simple::new(), vec![0_u8; 256])).unwrap();
+    assert!(bincode::deserialize::<Tab32Tab1Perm>(&not_a_permutation).is_err());
+}
+
+#[test]
+fn double_tabulation_fixed_values() {
+    // the first table copies the key into the low word of the intermediate value,
+    // and its lowest byte also into the highest byte of the high word
+    let mut first = [[[0_u64; 3]; 256]; 4];
+    for (i, column) in first.iter_mut().enumerate() {
+        for (c, entry) in column.iter_mut().enumerate() {
+            entry[0] = (c as u64) << (8 * i);
+        }
+    }
+    for (c, entry) in first[0].iter_mut().enumerate() {
+        entry[2] = (c as u64) << 56;
+    }
+    // the second table rotates the low bytes left by one byte, sets bit j for
+    // every byte 4 <= j < 23, and copies the last byte into the lowest byte
+    let mut second = [[0_u32; 256]; 24];
+    for (j, column) in second.iter_mut().enumerate() {
+        for (c, entry) in column.iter_mut().enumerate() {
+            *entry = match j {
+                0..=3 => (c as u32) << (8 * ((j + 1) % 4)),
+                23 => c as u32,
+                _ => 1 << j,
+            };
+        }
+    }
+    let double = Tab32Double::with_tables(first, second);
+    assert_eq!(double.derive(0x1234_56ff), [0x1234_56ff, 0, 0xff << 56]);
+    // rotl(x, 8) ^ 0x007f_fff0 ^ (x & 0xff)
+    assert_eq!(double.hash(3), 0x007f_fcf3);
+    assert_eq!(double.hash(0x1234_56ff), 0x3429_001d);
+    assert_eq!(double.hash(0), 0x007f_fff0);
+    assert_eq!(double.get_tables(), (first, second));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn double_tabulation_fixed_values_64() {
+    // the first table copies the key into the lowest word of the intermediate value,
+    // and its lowest byte also into the highest byte of the highest word
+    let mut first = [[[0_u64; 6]; 256]; 8];
+    for (i, column) in first.iter_mut().enumerate() {
+        for (c, entry) in column.iter_mut().enumerate() {
+            entry[0] = (c as u64) << (8 * i);
+        }
+    }
+    for (c, entry) in first[0].iter_mut().enumerate() {
+        entry[5] = (c as u64) << 56;
+    }
+    // the second table rotates the low bytes left by one byte, sets bit j for
+    // every byte 8 <= j < 47, and copies the last byte into the lowest byte
+    let mut second = [[0_u64; 256]; 48];
+    for (j, column) in second.iter_mut().enumerate() {
+        for (c, entry) in column.iter_mut().enumerate() {
+            *entry = match j {
+                0..=7 => (c as u64) << (8 * ((j + 1) % 8)),
+                47 => c as u64,
+                _ => 1 << j,
+            };
+        }
+    }
+    let double = Tab64Double::with_tables(first, second);
+    assert_eq!(
+        double.derive(0x0123_4567_89ab_cdff),
+        [0x0123_4567_89ab_cdff, 0, 0, 0, 0, 0xff << 56]
+    );
+    // rotl(x, 8) ^ 0x7fff_ffff_ff00 ^ (x & 0xff)
+    assert_eq!(double.hash(3), 0x7fff_ffff_fc03);
+    assert_eq!(double.hash(0x0123_4567_89ab_cdff), 0x2345_1876_5432_00fe);
+    assert_eq!(double.hash(0), 0x7fff_ffff_ff00);
+    assert!(double.get_tables() == (first, second));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn double_tabulation_seeds() {
+    let double = Tab32Double::new_with_seed(42);
+    assert_eq!(double, Tab32Double::new_with_seed(42));
+    assert_ne!(double, Tab32Double::new_with_seed(43));
+    assert_eq!(double.id(), Tab32Double::new_with_seed(42).id());
+    assert_eq!(
+        Tab32Double::from_seed_bytes([7; 32]),
+        Tab32Double::from_seed_bytes([7; 32])
+    );
+    let (first, second) = double.get_tables();
+    assert_eq!(Tab32Double::with_tables(first, second), double);
+    let double64 = Tab64Double::new_with_seed(42);
+    assert_eq!(double64, Tab64Double::new_with_seed(42));
+    assert_ne!(double64.id(), Tab64Double::new_with_seed(43).id());
+    assert_ne!(double64.hash(1), double64.hash(2));
+
+    let keys: Vec<u32> = (0..1000).map(|_| rand::random()).collect();
+    let mut hashes: Vec<u32> = keys.iter().map(|k| double.hash(*k)).collect();
+    hashes.sort_unstable();
+    hashes.dedup();
+    assert!(hashes.len() > 990);
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn double_tabulation_serialization() {
+    let double = Tab32Double::new();
+    let serialized = bincode::serialize(&double).unwrap();
+    let deserialized: Tab32Double = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(deserialized, double);
+    for _ in 0..1000 {
+        let key: u32 = rand::random();
+        assert_eq!(deserialized.hash(key), double.hash(key));
+    }
+
+    let too_few =
+        bincode::serialize(&(vec![vec![[0_u64; 3]; 256]; 4], vec![vec![0_u32; 256]; 23])).unwrap();
+    assert!(bincode::deserialize::<Tab32Double>(&too_few).is_err());
+    let short_column =
+        bincode::serialize(&(vec![vec![[0_u64; 3]; 255]; 4], vec![vec![0_u32; 256]; 24])).unwrap();
+    assert!(bincode::deserialize::<Tab32Double>(&short_column).is_err());
+
+    let double = Tab64Double::new();
+    let serialized = bincode::serialize(&double).unwrap();
+    let deserialized: Tab64Double = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(deserialized, double);
+    for _ in 0..1000 {
+        let key: u64 = rand::random();
+        assert_eq!(deserialized.hash(key), double.hash(key));
+    }
+    let too_few =
+        bincode::serialize(&(vec![vec![[0_u64; 6]; 256]; 8], vec![vec![0_u64; 256]; 24])).unwrap();
+    assert!(bincode::deserialize::<Tab64Double>(&too_few).is_err());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn polynomial_hashing_fixed_values() {
+    // with the identity table, the hash value is the low half of the polynomial
+    let mut table = [[0_u64; 256]; 8];
+    for (i, column) in table.iter_mut().enumerate() {
+        for (c, entry) in column.iter_mut().enumerate() {
+            *entry = (c as u64) << (8 * i);
+        }
+    }
+    let identity = Tab64Simple::with_table(table);
+    let prime = (1_u128 << 89) - 1;
+
+    let linear = TabPoly64::with_coefficients(vec![3, 5], identity.clone());
+    assert_eq!(linear.independence(), 2);
+    assert_eq!(linear.hash(7), 38);
+    assert_eq!(linear.polynomial(u64::MAX), 0x4_ffff_ffff_ffff_fffe);
+    assert_eq!(linear.hash(u64::MAX), 0xffff_ffff_ffff_fffe);
+
+    // 2^126 = 2^37 and 2^128 = 2^39 modulo 2^89 - 1
+    let square = TabPoly64::with_coefficients(vec![0, 0, 1], identity.clone());
+    assert_eq!(square.hash(1 << 63), 1 << 37);
+    assert_eq!(square.polynomial(u64::MAX), 0x1ff_fffe_0000_0080_0000_0000);
+    assert_eq!(square.hash(u64::MAX), 1 << 39);
+
+    let wrapping = TabPoly64::with_coefficients(vec![prime - 1, 1], identity.clone());
+    assert_eq!(wrapping.polynomial(1), 0);
+    let largest = TabPoly64::with_coefficients(vec![prime - 1; 4], identity);
+    assert_eq!(largest.polynomial(u64::MAX), 0x1ff_fffe_0000_00ff_ffff_bfff);
+
+    // the low half of the polynomial is finalized by simple tabulation
+    let simple = Tab64Simple::new_with_seed(42);
+    let finalized = TabPoly64::with_coefficients(vec![3, 5], simple.clone());
+    assert_eq!(finalized.hash(7), simple.hash(38));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn polynomial_hashing_seeds() {
+    let poly = TabPoly64::new_with_seed(5, 42);
+    assert_eq!(poly, TabPoly64::new_with_seed(5, 42));
+    assert_eq!(poly.hash_function(), &Tab64Simple::new_with_seed(42));
+    assert_eq!(poly.coefficients().len(), 5);
+    assert!(poly.coefficients().iter().all(|c| *c < (1 << 89) - 1));
+    assert_ne!(
+        poly.coefficients(),
+        TabPoly64::new_with_seed(5, 43).coefficients()
+    );
+    assert_eq!(TabPoly64::new(3).independence(), 3);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+#[should_panic(expected = "independence has to be at least 1")]
+fn polynomial_hashing_without_coefficients() {
+    TabPoly64::new_with_seed(0, 42);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn polynomial_hashing_pairwise_independence() {
+    // the top two bits of the hash values of two keys take all 16 combinations
+    // equally often over many random hash functions
+    for (x, y) in [(0, 1), (1, 1 << 32), (7, u64::MAX)].iter() {
+        let mut counts = [0_u32; 16];
+        let n = 16_000;
+        for seed in 0..n {
+            let poly = TabPoly64::new_with_seed(2, seed);
+            let cell = ((poly.hash(*x) >> 62) << 2) | (poly.hash(*y) >> 62);
+            counts[cell as usize] += 1;
+        }
+        let expected = n as f64 / 16.0;
+        let chi_squared: f64 = counts
+            .iter()
+            .map(|c| (f64::from(*c) - expected).powi(2) / expected)
+            .sum();
+        // the 99.99% quantile of the chi-squared distribution with 15 degrees of freedom is 42.6
+        assert!(chi_squared < 42.6, "{:?}", counts);
+    }
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn polynomial_hashing_serialization() {
+    let poly = TabPoly64::new(4);
+    let serialized = bincode::serialize(&poly).unwrap();
+    let deserialized: TabPoly64 = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(deserialized, poly);
+    for _ in 0..1000 {
+        let key: u64 = rand::random();
+        assert_eq!(deserialized.hash(key), poly.hash(key));
+    }
+
+    let too_large = bincode::serialize(&(vec![1_u128 << 89], Tab64Simple::new())).unwrap();
+    assert!(bincode::deserialize::<TabPoly64>(&too_large).is_err());
+    let empty = bincode::serialize(&(Vec::<u128>::new(), Tab64Simple::new())).unwrap();
+    assert!(bincode::deserialize::<TabPoly64>(&empty).is_err());
+}
+
+#[test]
+fn premixed_fixed_values() {
+    // with the identity table, the hash value is the premixed key
+    let mut table = [[0_u64; 256]; 8];
+    for (i, column) in table.iter_mut().enumerate() {
+        for (c, entry) in column.iter_mut().enumerate() {
+            *entry = (c as u64) << (8 * i);
+        }
+    }
+    let identity = Tab64Simple::with_table(table);
+    let premixed = Tab64Premixed::with_multiplier(3, identity.clone());
+    assert_eq!(premixed.hash(5), 15);
+    assert_eq!(premixed.hash(u64::MAX), 0xffff_ffff_ffff_fffd);
+    let premixed = Tab64Premixed::with_multiplier(0x9e37_79b9_7f4a_7c15, identity);
+    assert_eq!(premixed.hash(1), 0x9e37_79b9_7f4a_7c15);
+    assert_eq!(premixed.hash(2), 0x3c6e_f372_fe94_f82a);
+
+    let simple = Tab64Simple::new_with_seed(42);
+    let premixed = Tab64Premixed::with_multiplier(3, simple.clone());
+    assert_eq!(premixed.premix(5), 15);
+    assert_eq!(premixed.hash(5), simple.hash(15));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn premixed_seeds() {
+    let premixed = Tab64Premixed::new_with_seed(42);
+    assert_eq!(premixed, Tab64Premixed::new_with_seed(42));
+    assert_eq!(premixed.hash_function(), &Tab64Simple::new_with_seed(42));
+    assert_eq!(premixed.multiplier() % 2, 1);
+    assert_eq!(Tab64Premixed::new().multiplier() % 2, 1);
+
+    // the premix is a bijection, so sequential keys keep distinct premixes
+    let mut premixes: Vec<u64> = (0..10_000).map(|k| premixed.premix(k)).collect();
+    premixes.sort_unstable();
+    premixes.dedup();
+    assert_eq!(premixes.len(), 10_000);
+}
+
+#[test]
+#[should_panic(expected = "has to be odd")]
+fn premixed_even_multiplier() {
+    Tab64Premixed::with_multiplier(4, Tab64Simple::new_with_seed(1));
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn premixed_serialization() {
+    let premixed = Tab64Premixed::new();
+    let serialized = bincode::serialize(&premixed).unwrap();
+    let deserialized: Tab64Premixed = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(deserialized, premixed);
+    for key in 0..1000 {
+        assert_eq!(deserialized.hash(key), premixed.hash(key));
+    }
+
+    let even = bincode::serialize(&(2_u64, Tab64Simple::new())).unwrap();
+    assert!(bincode::deserialize::<Tab64Premixed>(&even).is_err());
+}
+
+/// A toy FNV-1a byte stream hasher with a pluggable finalizer
+struct MixedFnv<M> {
+    state: u64,
+    mixer: M,
+}
+
+impl<M: Mixer64> MixedFnv<M> {
+    fn new(mixer: M) -> Self {
+        MixedFnv {
+            state: 0xcbf2_9ce4_8422_2325,
+            mixer,
+        }
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state = (self.state ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.mixer.mix(self.state)
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn mixers_as_finalizers() {
+    let simple = Tab64Simple::new_with_seed(42);
+    let twisted = Tab64Twisted::new_with_seed(42);
+
+    let mut by_reference = MixedFnv::new(&simple);
+    by_reference.write(b"tabulation");
+    let state = by_reference.state;
+    assert_eq!(by_reference.finish(), simple.hash(state));
+
+    let mut owned = MixedFnv::new(twisted.clone());
+    owned.write(b"tabulation");
+    assert_eq!(owned.finish(), twisted.hash(state));
+
+    let boxed: Box<dyn Mixer64> = Box::new(simple.clone());
+    let mut dynamic = MixedFnv::new(boxed);
+    dynamic.write(b"tabulation");
+    assert_eq!(dynamic.finish(), by_reference.finish());
+
+    let mixers: Vec<Box<dyn Mixer32>> = vec![
+        Box::new(Tab32Simple::new_with_seed(1)),
+        Box::new(Tab32Twisted::new_with_seed(1)),
+    ];
+    assert_eq!(mixers[0].mix(7), Tab32Simple::new_with_seed(1).hash(7));
+    assert_eq!(mixers[1].mix(7), Tab32Twisted::new_with_seed(1).hash(7));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn xor_composition() {
+    let a32 = Tab32Simple::new();
+    let b32 = Tab32Simple::new();
+    let a64 = Tab64Simple::new();
+    let b64 = Tab64Simple::new();
+    let composed32 = a32.xor_compose(&b32);
+    let composed64 = a64.xor_compose(&b64);
+    for _ in 0..1000 {
+        let key: u64 = rand::random();
+        assert_eq!(
+            composed32.hash(key as u32),
+            a32.hash(key as u32) ^ b32.hash(key as u32)
+        );
+        assert_eq!(composed64.hash(key), a64.hash(key) ^ b64.hash(key));
+    }
+    assert_eq!(composed64.xor_compose(&b64), a64);
+    assert_eq!(a32.xor_compose(&a32).hash(7), 0);
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn xor_composition_serialization() {
+    let (a32, b32) = (Tab32Simple::new(), Tab32Simple::new());
+    let (a64, b64) = (Tab64Simple::new(), Tab64Simple::new());
+    let composed32 = a32.xor_compose(&b32);
+    let composed64 = a64.xor_compose(&b64);
+    let serialized32 = bincode::serialize(&composed32).unwrap();
+    let serialized64 = bincode::serialize(&composed64).unwrap();
+    let deserialized32: Tab32Simple = bincode::deserialize(&serialized32).unwrap();
+    let deserialized64: Tab64Simple = bincode::deserialize(&serialized64).unwrap();
+    assert_eq!(deserialized32, composed32);
+    assert_eq!(deserialized64, composed64);
+    assert_eq!(
+        deserialized64.hash(12345),
+        a64.hash(12345) ^ b64.hash(12345)
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn sixteen_bit_characters_fixed_values() {
+    // the low 16 bits are looked up in the first column, the high ones in the second,
+    // and this table swaps them
+    let mut table = Box::new([[0_u32; 65536]; 2]);
+    for c in 0..65536_u32 {
+        table[0][c as usize] = c << 16;
+        table[1][c as usize] = c;
+    }
+    let simple = Tab32Simple16::with_table(table);
+    assert_eq!(simple.hash(0x1234_5678), 0x5678_1234);
+    assert_eq!(simple.hash(0xffff), 0xffff_0000);
+    assert_eq!(simple.hash(0), 0);
+    assert_eq!(simple.get_table()[1][0xabcd], 0xabcd);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn sixteen_bit_characters_tables() {
+    let simple = Tab32Simple16::new_with_seed(42);
+    assert_eq!(simple, Tab32Simple16::new_with_seed(42));
+    assert_ne!(simple.id(), Tab32Simple16::new_with_seed(43).id());
+    assert_ne!(simple.hash(1), simple.hash(1 << 16));
+    let table = simple.get_table();
+    assert_eq!(simple.hash(0x0002_0001), table[0][1] ^ table[1][2]);
+
+    let random = Tab32Simple16::new();
+    assert_eq!(Tab32Simple16::from_vec(random.to_vec()), random);
+    let bytes: Box<[u8; 524288]> = random.to_le_bytes().into_boxed_slice().try_into().unwrap();
+    assert_eq!(Tab32Simple16::from_le_bytes(&bytes), random);
+    assert_eq!(&bytes[..4], &random.get_table()[0][0].to_le_bytes());
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn sixteen_bit_characters_serialization() {
+    let simple = Tab32Simple16::new();
+    let serialized = bincode::serialize(&simple).unwrap();
+    // the table is stored as a byte string with a length prefix
+    assert_eq!(serialized.len(), 8 + 524288);
+    let deserialized: Tab32Simple16 = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(deserialized, simple);
+    for _ in 0..1000 {
+        let key: u32 = rand::random();
+        assert_eq!(deserialized.hash(key), simple.hash(key));
+    }
+    let truncated = bincode::serialize(&vec![0_u8; 1000]).unwrap();
+    assert!(bincode::deserialize::<Tab32Simple16>(&truncated).is_err());
+
+    // human-readable formats use a hex string
+    use serde::de::value::{Error, StrDeserializer};
+    use serde::Deserialize;
+    let hex: String = simple
+        .to_le_bytes()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    let from_hex = Tab32Simple16::deserialize(StrDeserializer::<Error>::new(&hex)).unwrap();
+    assert_eq!(from_hex, simple);
+    let not_hex = "zz".repeat(524288);
+    assert!(Tab32Simple16::deserialize(StrDeserializer::<Error>::new(&not_hex)).is_err());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn sixteen_bit_characters_fixed_values_64() {
+    // characters are taken from the least to the most significant 16 bits, and this
+    // table reverses their order
+    let column = |shift: u64| -> Box<[u64; 65536]> {
+        let entries: Vec<u64> = (0..65536).map(|c| c << shift).collect();
+        entries.try_into().unwrap()
+    };
+    let table = [column(48), column(32), column(16), column(0)];
+    let simple = Tab64Simple16::with_table(table);
+    assert_eq!(simple.hash(0x1111_2222_3333_4444), 0x4444_3333_2222_1111);
+    assert_eq!(simple.hash(0xffff), 0xffff_0000_0000_0000);
+    assert_eq!(simple.hash(0xffff << 48), 0xffff);
+    assert_eq!(simple.hash(0), 0);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn sixteen_bit_characters_tables_64() {
+    let simple = Tab64Simple16::new_with_seed(42);
+    assert_eq!(simple, Tab64Simple16::new_with_seed(42));
+    assert_ne!(simple.id(), Tab64Simple16::new_with_seed(43).id());
+    let table = simple.get_table();
+    assert_eq!(
+        simple.hash(0x0004_0003_0002_0001),
+        table[0][1] ^ table[1][2] ^ table[2][3] ^ table[3][4]
+    );
+
+    let random = Tab64Simple16::new();
+    assert_eq!(Tab64Simple16::from_vec(random.to_vec()), random);
+    let bytes: Box<[u8; 2097152]> = random.to_le_bytes().into_boxed_slice().try_into().unwrap();
+    assert_eq!(Tab64Simple16::from_le_bytes(&bytes), random);
+    assert_eq!(&bytes[8..16], &random.get_table()[0][1].to_le_bytes());
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn sixteen_bit_characters_serialization_64() {
+    let simple = Tab64Simple16::new();
+    let serialized = bincode::serialize(&simple).unwrap();
+    // the table is stored as a byte string with a length prefix
+    assert_eq!(serialized.len(), 8 + 2097152);
+    let deserialized: Tab64Simple16 = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(deserialized, simple);
+    for _ in 0..1000 {
+        let key: u64 = rand::random();
+        assert_eq!(deserialized.hash(key), simple.hash(key));
+    }
+    // a table of a Tab32Simple16 has the wrong length
+    let short = bincode::serialize(&Tab32Simple16::new_with_seed(1)).unwrap();
+    assert!(bincode::deserialize::<Tab64Simple16>(&short).is_err());
+}
+
+#[test]
+fn nibble_fixed_values() {
+    // nibbles are taken from the least to the most significant bits, and this
+    // table reverses their order
+    let mut table = [[0_u32; 16]; 8];
+    for (i, column) in table.iter_mut().enumerate() {
+        for (c, entry) in column.iter_mut().enumerate() {
+            *entry = (c as u32) << (28 - 4 * i);
+        }
+    }
+    let nibble = Tab32Nibble::with_table(table);
+    assert_eq!(nibble.hash(0x1234_5678), 0x8765_4321);
+    assert_eq!(nibble.hash(0xf), 0xf000_0000);
+    assert_eq!(nibble.hash(0), 0);
+    assert_eq!(nibble.get_table(), table);
+
+    const CONSTANT: Tab32Nibble = Tab32Nibble::with_table([[1; 16]; 8]);
+    assert_eq!(CONSTANT.hash(12345), 0);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn nibble_determinism() {
+    let nibble = Tab32Nibble::new_with_seed(42);
+    let same = Tab32Nibble::new_with_seed(42);
+    assert_ne!(nibble.id(), Tab32Nibble::new_with_seed(43).id());
+    let table = nibble.get_table();
+    for key in (0..=u32::MAX).step_by(65521) {
+        let expected = (0..8).fold(0, |h, i| h ^ table[i][((key >> (4 * i)) & 0xf) as usize]);
+        assert_eq!(nibble.hash(key), expected);
+        assert_eq!(same.hash(key), expected);
+    }
+
+    let random = Tab32Nibble::new();
+    assert_eq!(Tab32Nibble::from_vec(random.to_vec()), random);
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "serde"))]
+fn nibble_serialization() {
+    let nibble = Tab32Nibble::new();
+    let serialized = bincode::serialize(&nibble).unwrap();
+    assert_eq!(serialized.len(), 512);
+    let deserialized: Tab32Nibble = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(deserialized, nibble);
+    for _ in 0..1000 {
+        let key: u32 = rand::random();
+        assert_eq!(deserialized.hash(key), nibble.hash(key));
+    }
+}
+
+#[test]
+fn generic_simple_aliases() {
+    let simple = Tab64Simple::new_with_seed(42);
+    let generic: TabSimple<u64, 8> = TabSimple::new_with_seed(42);
+    assert_eq!(simple, generic);
+    assert_eq!(
+        simple.hash(0x0807_0605_0403_0201),
+        generic.hash_chars([1, 2, 3, 4, 5, 6, 7, 8])
+    );
+    assert_eq!(format!("{:?}", generic), format!("{:?}", simple));
+    assert!(format!("{:?}", Tab32To64Simple::new_with_seed(1)).starts_with("Tab32To64Simple"));
+
+    let narrow = Tab64To32Simple::new_with_seed(7);
+    let table = narrow.get_table();
+    assert_eq!(
+        narrow.hash(0x0100),
+        table[0][0] ^ table[1][1] ^ (2..8).fold(0, |h, i| h ^ table[i][0])
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn generic_simple_other_widths() {
+    // a table for 24-bit keys, which has no alias
+    let simple: TabSimple<u32, 3> = TabSimple::new_with_seed(42);
+    let table = simple.get_table();
+    assert_eq!(
+        simple.hash_chars([1, 2, 3]),
+        table[0][1] ^ table[1][2] ^ table[2][3]
+    );
+    assert_eq!(TabSimple::<u32, 3>::from_vec(simple.to_vec()), simple);
+    assert!(format!("{:?}", simple).starts_with("TabSimple"));
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn generic_simple_serialization() {
+    let simple: TabSimple<u64, 3> = TabSimple::new_with_seed(42);
+    let serialized = bincode::serialize(&simple).unwrap();
+    let deserialized: TabSimple<u64, 3> = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(deserialized, simple);
+
+    // the serialized form of an alias is the same as before it became one
+    let legacy = bincode::serialize(&Tab32Simple::new_with_seed(1).to_vec()).unwrap();
+    let from_legacy: Tab32Simple = bincode::deserialize(&legacy).unwrap();
+    assert_eq!(from_legacy, Tab32Simple::new_with_seed(1));
+
+    // wrong numbers of columns are rejected
+    assert!(bincode::deserialize::<TabSimple<u64, 4>>(&serialized).is_err());
+    assert!(bincode::deserialize::<Tab16Simple>(&legacy).is_err());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn generic_twisted_aliases() {
+    let twisted = Tab64Twisted::new_with_seed(42);
+    let generic: TabTwisted<u128, 8> = TabTwisted::new_with_seed(42);
+    assert_eq!(twisted, generic);
+    assert!(format!("{:?}", generic).starts_with("Tab64Twisted"));
+    assert!(format!("{:?}", Tab32Twisted::new_with_seed(1)).starts_with("Tab32Twisted"));
+    assert!(format!("{:?}", Tab32To64Twisted::new_with_seed(1)).starts_with("Tab32To64Twisted"));
+
+    // the constructors and conversions are shared with simple tabulation
+    let mut reseeded = Tab32To64Twisted::new_with_seed(1);
+    reseeded.reseed_from_seed(2);
+    assert_eq!(reseeded, Tab32To64Twisted::new_with_seed(2));
+    assert_eq!(Tab32To64Twisted::from_vec(reseeded.to_vec()), reseeded);
+    assert_eq!(
+        Tab32Twisted::new_with_seed(3).get_table()[0],
+        Tab32To64Simple::new_with_seed(3).get_table()[0]
+    );
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn generic_twisted_serialization() {
+    let twisted = Tab32To64Twisted::new_with_seed(42);
+    let serialized = bincode::serialize(&twisted).unwrap();
+    assert_eq!(
+        serialized,
+        bincode::serialize(&twisted.to_vec()).unwrap(),
+        "a table is serialized as the nested vector of its columns"
+    );
+    let deserialized: Tab32To64Twisted = bincode::deserialize(&serialized).unwrap();
+    assert_eq!(deserialized.hash(17), twisted.hash(17));
+    assert!(bincode::deserialize::<Tab64Twisted>(&serialized).is_err());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn derive_wide_tables() {
+    let simple = Tab32Simple::new();
+    let wide = simple.derive_wide();
+    for _ in 0..100_000 {
+        let key: u32 = rand::random();
+        assert_eq!(wide.hash(key), simple.hash(key));
+    }
+    for key in [0, 0xff, 0xff00, 0xffff, 0x1_0000, u32::MAX] {
+        assert_eq!(wide.hash(key), simple.hash(key));
+    }
+
+    let simple = Tab64Simple::new();
+    let wide = simple.derive_wide();
+    for _ in 0..100_000 {
+        let key: u64 = rand::random();
+        assert_eq!(wide.hash(key), simple.hash(key));
+    }
+    for key in [0, 0xff, 0xffff_0000, 0xffff_0000_0000, u64::MAX] {
+        assert_eq!(wide.hash(key), simple.hash(key));
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn prefix_hashing() {
+    let simple = Tab32Simple::new();
+    for prefix_bytes in 0..=4_u8 {
+        let suffix_bits = 32 - 8 * u32::from(prefix_bytes);
+        for _ in 0..100 {
+            let key: u32 = rand::random();
+            let (prefix, low) = if suffix_bits == 32 {
+                (0, key)
+            } else {
+                (
+                    key >> suffix_bits << suffix_bits,
+                    key & ((1 << suffix_bits) - 1),
+                )
+            };
+            let hasher: PrefixHasher = simple.with_prefix(prefix, prefix_bytes);
+            assert_eq!(hasher.prefix(), prefix);
+            assert_eq!(hasher.suffix_bytes(), 4 - usize::from(prefix_bytes));
+            assert_eq!(hasher.hash_suffix(low), simple.hash(prefix | low));
+        }
+    }
+
+    let hasher = simple.with_prefix(0x1234_5600, 3);
+    let hashes: Vec<u32> = hasher.hash_suffixes(0..256).collect();
+    assert_eq!(hashes.len(), 256);
+    for (low, h) in hashes.iter().enumerate() {
+        assert_eq!(*h, simple.hash(0x1234_5600 | low as u32));
+    }
+    assert_eq!(hasher.hash_function(), &simple);
+}
+
+#[test]
+#[should_panic(expected = "has bits set below")]
+fn prefix_hashing_with_low_bits() {
+    Tab32Simple::new_with_seed(42).with_prefix(0x1234_5678, 2);
+}
+
+#[test]
+#[should_panic(expected = "at most 4 bytes")]
+fn prefix_hashing_too_long() {
+    Tab32Simple::new_with_seed(42).with_prefix(0, 5);
+}
+
+#[test]
+#[should_panic(expected = "has more than 2 bytes")]
+fn prefix_hashing_suffix_too_long() {
+    Tab32Simple::new_with_seed(42)
+        .with_prefix(0x1234_0000, 2)
+        .hash_suffix(0x1_0000);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn hash_many_matches_hash() {
+    // lengths that are and are not a multiple of the block size
+    for len in [0, 1, 3, 4, 7, 10_000, 10_003].iter() {
+        let keys32: Vec<u32> = (0..*len).map(|_| rand::random()).collect();
+        let keys64: Vec<u64> = (0..*len).map(|_| rand::random()).collect();
+
+        let simple32 = Tab32Simple::new();
+        let expected: Vec<u32> = keys32.iter().map(|k| simple32.hash(*k)).collect();
+        assert_eq!(simple32.hash_many(&keys32), expected);
+
+        let twisted32 = Tab32Twisted::new();
+        let expected: Vec<u32> = keys32.iter().map(|k| twisted32.hash(*k)).collect();
+        assert_eq!(twisted32.hash_many(&keys32), expected);
+
+        let simple64 = Tab64Simple::new();
+        let expected: Vec<u64> = keys64.iter().map(|k| simple64.hash(*k)).collect();
+        assert_eq!(simple64.hash_many(&keys64), expected);
+
+        let twisted64 = Tab64Twisted::new();
+        let expected: Vec<u64> = keys64.iter().map(|k| twisted64.hash(*k)).collect();
+        let mut out = vec![0; keys64.len()];
+        twisted64.hash_into(&keys64, &mut out);
+        assert_eq!(out, expected);
+        assert_eq!(twisted64.hash_many(&keys64), expected);
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn hash_into_and_in_place() {
+    // lengths around the block sizes of the batch loop and the in-place copies
+    for len in [0, 1, 5, 63, 64, 65, 1000].iter() {
+        let keys32: Vec<u32> = (0..*len).map(|_| rand::random()).collect();
+        let keys64: Vec<u64> = (0..*len).map(|_| rand::random()).collect();
+
+        let simple32 = Tab32Simple::new();
+        let expected: Vec<u32> = keys32.iter().map(|k| simple32.hash(*k)).collect();
+        let mut out = vec![0; *len];
+        simple32.hash_into(&keys32, &mut out);
+        assert_eq!(out, expected);
+        let mut in_place = keys32.clone();
+        simple32.hash_in_place(&mut in_place);
+        assert_eq!(in_place, expected);
+
+        let twisted32 = Tab32Twisted::new();
+        let expected: Vec<u32> = keys32.iter().map(|k| twisted32.hash(*k)).collect();
+        let mut out = vec![0; *len];
+        twisted32.hash_into(&keys32, &mut out);
+        assert_eq!(out, expected);
+        let mut in_place = keys32.clone();
+        twisted32.hash_in_place(&mut in_place);
+        assert_eq!(in_place, expected);
+
+        let simple64 = Tab64Simple::new();
+        let expected: Vec<u64> = keys64.iter().map(|k| simple64.hash(*k)).collect();
+        let mut out = vec![0; *len];
+        simple64.hash_into(&keys64, &mut out);
+        assert_eq!(out, expected);
+        let mut in_place = keys64.clone();
+        simple64.hash_in_place(&mut in_place);
+        assert_eq!(in_place, expected);
+
+        let twisted64 = Tab64Twisted::new();
+        let expected: Vec<u64> = keys64.iter().map(|k| twisted64.hash(*k)).collect();
+        let mut out = vec![0; *len];
+        twisted64.hash_into(&keys64, &mut out);
+        assert_eq!(out, expected);
+        let mut in_place = keys64.clone();
+        twisted64.hash_in_place(&mut in_place);
+        assert_eq!(in_place, expected);
+    }
+}
+
+#[test]
+#[should_panic(expected = "one slot for every key")]
+fn hash_into_wrong_length() {
+    Tab32Simple::new_with_seed(42).hash_into(&[1, 2, 3], &mut [0; 2]);
+}
+
+#[test]
+#[should_panic(expected = "one slot for every key")]
+fn hash_into_wrong_length_twisted() {
+    Tab64Twisted::new_with_seed(42).hash_into(&[1, 2], &mut [0; 3]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn iterator_adapters() {
+    let simple = Tab32Simple::new();
+    let keys: Vec<u32> = (0..1000).map(|_| rand::random()).collect();
+    let expected: Vec<u32> = keys.iter().map(|k| simple.hash(*k)).collect();
+
+    // owned and borrowed keys
+    assert_eq!(keys.iter().hashed(&simple).collect::<Vec<_>>(), expected);
+    assert_eq!(
+        keys.clone().into_iter().hashed(&simple).collect::<Vec<_>>(),
+        expected
+    );
+    let pairs: Vec<(&u32, u32)> = keys.iter().with_hashes(&simple).collect();
+    assert_eq!(pairs.len(), keys.len());
+    for ((key, hash), expected_key) in pairs.iter().zip(keys.iter()) {
+        assert_eq!(*key, expected_key);
+        assert_eq!(*hash, simple.hash(*expected_key));
+    }
+
+    // size hints and cloning
+    let hashed = keys.iter().hashed(&simple);
+    assert_eq!(hashed.size_hint(), (1000, Some(1000)));
+    assert_eq!(hashed.len(), 1000);
+    assert_eq!(hashed.clone().collect::<Vec<_>>(), expected);
+    assert_eq!(keys.iter().with_hashes(&simple).len(), 1000);
+    assert_eq!(hashed.clone().next_back(), expected.last().copied());
+
+    // composition with other adapters
+    let even: Vec<u32> = keys
+        .iter()
+        .hashed(&simple)
+        .filter(|h| h % 2 == 0)
+        .take(10)
+        .collect();
+    let expected_even: Vec<u32> = expected
+        .iter()
+        .copied()
+        .filter(|h| h % 2 == 0)
+        .take(10)
+        .collect();
+    assert_eq!(even, expected_even);
+    let small_keys: Vec<(u32, u32)> = keys
+        .iter()
+        .copied()
+        .filter(|k| *k < 1 << 31)
+        .with_hashes(&simple)
+        .take(5)
+        .collect();
+    for (key, hash) in small_keys {
+        assert!(key < 1 << 31);
+        assert_eq!(hash, simple.hash(key));
+    }
+
+    // 64-bit keys
+    let twisted = Tab64Twisted::new();
+    let hashes: Vec<u64> = (0..100_u64).hashed(&twisted).collect();
+    for (key, hash) in hashes.iter().enumerate() {
+        assert_eq!(*hash, twisted.hash(key as u64));
+    }
+}
+
+#[test]
+#[cfg(feature = "parallel")]
+fn parallel_hashing_matches_serial() {
+    // lengths below, at and above the minimum number of keys per thread
+    for len in [0, 1, 1000, 1 << 16, (1 << 17) + 3, 5 * (1 << 16) + 7].iter() {
+        let keys32: Vec<u32> = (0..*len).map(|_| rand::random()).collect();
+        let keys64: Vec<u64> = (0..*len).map(|_| rand::random()).collect();
+
+        let simple32 = Tab32Simple::new();
+        let twisted32 = Tab32Twisted::new();
+        let simple64 = Tab64Simple::new();
+        let twisted64 = Tab64Twisted::new();
+        assert_eq!(simple32.par_hash_many(&keys32), simple32.hash_many(&keys32));
+        assert_eq!(
+            twisted32.par_hash_many(&keys32),
+            twisted32.hash_many(&keys32)
+        );
+        assert_eq!(simple64.par_hash_many(&keys64), simple64.hash_many(&keys64));
+        assert_eq!(
+            twisted64.par_hash_many(&keys64),
+            twisted64.hash_many(&keys64)
+        );
+
+        let expected = twisted64.hash_many(&keys64);
+        for threads in [0, 1, 2, 3, 8].iter() {
+            let mut out = vec![0; *len];
+            twisted64.par_hash_into_with_threads(&keys64, &mut out, *threads);
+            assert_eq!(out, expected);
+        }
+        let expected = simple32.hash_many(&keys32);
+        let mut out = vec![0; *len];
+        simple32.par_hash_into_with_threads(&keys32, &mut out, 4);
+        assert_eq!(out, expected);
+    }
+}
+
+#[test]
+#[cfg(feature = "parallel")]
+fn parallel_hashing_without_hash_into() {
+    fn check<K: Copy, H: PartialEq + core::fmt::Debug>(
+        keys: &[K],
+        par_hashes: Vec<H>,
+        hash: impl Fn(K) -> H,
+    ) {
+        let expected: Vec<H> = keys.iter().map(|key| hash(*key)).collect();
+        assert_eq!(par_hashes, expected);
+    }
+
+    for len in [0, 1000, (1 << 17) + 3].iter() {
+        let keys16: Vec<u16> = (0..*len).map(|_| rand::random()).collect();
+        let keys32: Vec<u32> = (0..*len).map(|_| rand::random()).collect();
+        let keys64: Vec<u64> = (0..*len).map(|_| rand::random()).collect();
+        let keys128: Vec<u128> = (0..*len).map(|_| rand::random()).collect();
+
+        let tab16 = Tab16Simple::new();
+        check(&keys16, tab16.par_hash_many(&keys16), |k| tab16.hash(k));
+        let keys48: Vec<u64> = keys64.iter().map(|k| k >> 16).collect();
+        let tab48 = Tab48Simple::new();
+        check(&keys48, tab48.par_hash_many(&keys48), |k| tab48.hash(k));
+        let simple128 = Tab128Simple::new();
+        check(&keys128, simple128.par_hash_many(&keys128), |k| {
+            simple128.hash(k)
+        });
+        let twisted128 = Tab128Twisted::new();
+        check(&keys128, twisted128.par_hash_many(&keys128), |k| {
+            twisted128.hash(k)
+        });
+        let simple32to64 = Tab32To64Simple::new();
+        check(&keys32, simple32to64.par_hash_many(&keys32), |k| {
+            simple32to64.hash(k)
+        });
+        let twisted32to64 = Tab32To64Twisted::new();
+        check(&keys32, twisted32to64.par_hash_many(&keys32), |k| {
+            twisted32to64.hash(k)
+        });
+        let tab64to32 = Tab64To32Simple::new();
+        let mut out = vec![0; *len];
+        tab64to32.par_hash_into_with_threads(&keys64, &mut out, 3);
+        check(&keys64, out, |k| tab64to32.hash(k));
+    }
+}
+
+#[test]
+#[cfg(feature = "parallel")]
+#[should_panic(expected = "one slot for every key")]
+fn parallel_hashing_wrong_length() {
+    Tab64Simple::new_with_seed(42).par_hash_into(&[1, 2, 3], &mut [0; 4]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn simd_hashing_matches_scalar() {
+    // with the `simd` and `portable-simd` features, `hash_into` of `Tab32Simple` uses
+    // SIMD kernels, compare them to the scalar `hash` on all lengths around multiples
+    // of the lane count
+    for _ in 0..20 {
+        let simple = Tab32Simple::new();
+        for len in (0..100).chain(1000..1017) {
+            let keys: Vec<u32> = (0..len).map(|_| rand::random()).collect();
+            let expected: Vec<u32> = keys.iter().map(|k| simple.hash(*k)).collect();
+            assert_eq!(simple.hash_many(&keys), expected);
+            let mut in_place = keys.clone();
+            simple.hash_in_place(&mut in_place);
+            assert_eq!(in_place, expected);
+        }
+    }
+    // extreme keys, i.e. all indices 0 and 255
+    let simple = Tab32Simple::new();
+    let keys = [
+        0,
+        u32::MAX,
+        0x00FF_00FF,
+        0xFF00_FF00,
+        1,
+        1 << 31,
+        0x8080_8080,
+        0x7F7F_7F7F,
+    ];
+    let expected: Vec<u32> = keys.iter().map(|k| simple.hash(*k)).collect();
+    assert_eq!(simple.hash_many(&keys), expected);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn simd_twisted_hashing_matches_scalar() {
+    // with the `simd` feature, `hash_into` of `Tab64Twisted` uses AVX-512 if available
+    for _ in 0..20 {
+        let twisted = Tab64Twisted::new();
+        for len in (0..50).chain(1000..1017) {
+            let keys: Vec<u64> = (0..len).map(|_| rand::random()).collect();
+            let expected: Vec<u64> = keys.iter().map(|k| twisted.hash(*k)).collect();
+            assert_eq!(twisted.hash_many(&keys), expected);
+        }
+
+        // keys with every twisted character, since the last byte of a key does not
+        // change the XOR of the first seven entries
+        let mut keys = Vec::new();
+        for _ in 0..4 {
+            let low: u64 = rand::random::<u64>() & 0x00FF_FFFF_FFFF_FFFF;
+            let (_, twist) = twisted.hash_with_twist(low);
+            for c in 0..=255_u8 {
+                keys.push(low | u64::from(c ^ twist) << 56);
+            }
+        }
+        let mut twists = [0_usize; 256];
+        for k in keys.iter() {
+            twists[usize::from(twisted.hash_with_twist(*k).1)] += 1;
+        }
+        assert!(twists.iter().all(|n| *n == 4));
+        let expected: Vec<u64> = keys.iter().map(|k| twisted.hash(*k)).collect();
+        assert_eq!(twisted.hash_many(&keys), expected);
+    }
+}
+
+#[test]
+#[cfg(any(feature = "simd", feature = "portable-simd"))]
+fn batch_kernel_dispatch() {
+    use tab_hash::{batch_kernel, force_scalar, BatchKernel};
+
+    // this is the only test forcing the scalar kernel, which does not change hash values
+    // of batch methods running in other tests
+    let detected = BatchKernel::detect();
+    assert_eq!(BatchKernel::detect(), detected);
+
+    let simple = Tab32Simple::new();
+    let twisted = Tab64Twisted::new();
+    let keys32: Vec<u32> = (0..10_001).map(|_| rand::random()).collect();
+    let keys64: Vec<u64> = (0..10_001).map(|_| rand::random()).collect();
+    let expected32: Vec<u32> = keys32.iter().map(|k| simple.hash(*k)).collect();
+    let expected64: Vec<u64> = keys64.iter().map(|k| twisted.hash(*k)).collect();
+
+    force_scalar(true);
+    assert_eq!(batch_kernel(), BatchKernel::Scalar);
+    assert_eq!(simple.hash_many(&keys32), expected32);
+    assert_eq!(twisted.hash_many(&keys64), expected64);
+
+    force_scalar(false);
+    assert_eq!(batch_kernel(), detected);
+    assert_eq!(simple.hash_many(&keys32), expected32);
+    assert_eq!(twisted.hash_many(&keys64), expected64);
+}